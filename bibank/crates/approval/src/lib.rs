@@ -4,7 +4,7 @@
 //!
 //! ## Scope
 //! - `Adjustment` intent entries
-//! - Withdrawals > threshold (e.g., 100,000 USDT)
+//! - Withdrawals/transfers > the asset's threshold (e.g., 100,000 USDT)
 //! - System parameter changes
 //!
 //! ## Features
@@ -12,6 +12,7 @@
 //! - 24h expiry for pending approvals
 //! - SQLite storage for pending state
 //! - No ledger entry until approved
+//! - Approver signatures verified against registered Ed25519 keys
 
 mod pending;
 mod store;
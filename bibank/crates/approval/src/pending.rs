@@ -1,6 +1,6 @@
 //! Pending approval data structures
 
-use bibank_ledger::EntrySignature;
+use bibank_ledger::{EntrySignature, SignatureAlgorithm, SignatureScope, UnsignedEntry};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -28,6 +28,7 @@ impl ApprovalStatus {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "pending" => Some(ApprovalStatus::Pending),
@@ -42,7 +43,7 @@ impl ApprovalStatus {
 /// A pending approval awaiting signatures
 ///
 /// Note: We store the serialized JSON of the unsigned entry rather than
-/// the struct itself, since UnsignedEntry doesn't implement Serialize.
+/// the struct itself, so the hash covers exactly the bytes that were stored.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PendingApproval {
     /// Unique identifier for the approval request
@@ -94,6 +95,20 @@ impl From<&EntrySignature> for CollectedSignature {
     }
 }
 
+impl From<&CollectedSignature> for EntrySignature {
+    /// Approver signatures cover the entry content (see `ApprovalPayload`)
+    fn from(sig: &CollectedSignature) -> Self {
+        Self {
+            signer_id: sig.signer_id.clone(),
+            algorithm: SignatureAlgorithm::Ed25519,
+            scope: SignatureScope::Approval,
+            public_key: sig.public_key.clone(),
+            signature: sig.signature.clone(),
+            signed_at: sig.signed_at,
+        }
+    }
+}
+
 impl PendingApproval {
     /// Create a new pending approval from unsigned entry JSON
     pub fn new(
@@ -132,11 +147,7 @@ impl PendingApproval {
     pub fn signatures_remaining(&self) -> usize {
         let collected = self.collected_signatures.len();
         let required = self.required_signatures as usize;
-        if collected >= required {
-            0
-        } else {
-            required - collected
-        }
+        required.saturating_sub(collected)
     }
 
     /// Add a signature (returns false if already signed by this signer)
@@ -154,6 +165,21 @@ impl PendingApproval {
     pub fn signers(&self) -> Vec<&str> {
         self.collected_signatures.iter().map(|s| s.signer_id.as_str()).collect()
     }
+
+    /// Check the stored JSON still matches its hash
+    pub fn verify_hash(&self) -> bool {
+        compute_hash(&self.unsigned_entry_json) == self.unsigned_entry_hash
+    }
+
+    /// Parse the entry awaiting approval
+    pub fn unsigned_entry(&self) -> Result<UnsignedEntry, serde_json::Error> {
+        serde_json::from_str(&self.unsigned_entry_json)
+    }
+
+    /// Collected signatures, ready to attach to the committed entry
+    pub fn entry_signatures(&self) -> Vec<EntrySignature> {
+        self.collected_signatures.iter().map(EntrySignature::from).collect()
+    }
 }
 
 /// Compute SHA256 hash of a string
@@ -266,6 +292,15 @@ mod tests {
         assert_eq!(ApprovalStatus::from_str("invalid"), None);
     }
 
+    #[test]
+    fn test_verify_hash_detects_tampering() {
+        let mut approval = PendingApproval::new(create_test_entry_json(), 2, 24);
+        assert!(approval.verify_hash());
+
+        approval.unsigned_entry_json = approval.unsigned_entry_json.replace("test-adj-001", "test-adj-002");
+        assert!(!approval.verify_hash());
+    }
+
     #[test]
     fn test_compute_hash() {
         let hash1 = compute_hash("test data");
@@ -3,6 +3,7 @@
 use crate::pending::{ApprovalStatus, PendingApproval};
 use rusqlite::{Connection, params};
 use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
 use thiserror::Error;
 
 /// Errors from the approval store
@@ -19,14 +20,16 @@ pub enum StoreError {
 }
 
 /// SQLite storage for pending approvals
+///
+/// The connection sits behind a mutex so the store can be shared with async tasks.
 pub struct ApprovalStore {
-    conn: Connection,
+    conn: Mutex<Connection>,
 }
 
 impl ApprovalStore {
     /// Create a new store with the given database path
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, StoreError> {
-        let conn = Connection::open(path)?;
+        let conn = Mutex::new(Connection::open(path)?);
         let store = Self { conn };
         store.init_schema()?;
         Ok(store)
@@ -34,15 +37,20 @@ impl ApprovalStore {
 
     /// Create an in-memory store (for testing)
     pub fn in_memory() -> Result<Self, StoreError> {
-        let conn = Connection::open_in_memory()?;
+        let conn = Mutex::new(Connection::open_in_memory()?);
         let store = Self { conn };
         store.init_schema()?;
         Ok(store)
     }
 
+    fn conn(&self) -> MutexGuard<'_, Connection> {
+        // A panic mid-statement leaves nothing half-written in SQLite
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
     /// Initialize the database schema
     fn init_schema(&self) -> Result<(), StoreError> {
-        self.conn.execute(
+        self.conn().execute(
             "CREATE TABLE IF NOT EXISTS pending_approvals (
                 id TEXT PRIMARY KEY,
                 unsigned_entry_json TEXT NOT NULL,
@@ -58,7 +66,7 @@ impl ApprovalStore {
         )?;
 
         // Index for efficient status queries
-        self.conn.execute(
+        self.conn().execute(
             "CREATE INDEX IF NOT EXISTS idx_pending_approvals_status
              ON pending_approvals(status)",
             [],
@@ -71,7 +79,7 @@ impl ApprovalStore {
     pub fn save(&self, approval: &PendingApproval) -> Result<(), StoreError> {
         let collected_signatures_json = serde_json::to_string(&approval.collected_signatures)?;
 
-        self.conn.execute(
+        self.conn().execute(
             "INSERT OR REPLACE INTO pending_approvals
              (id, unsigned_entry_json, unsigned_entry_hash, required_signatures,
               collected_signatures_json, created_at, expires_at, status, rejection_reason)
@@ -94,7 +102,8 @@ impl ApprovalStore {
 
     /// Get a pending approval by ID
     pub fn get(&self, id: &str) -> Result<PendingApproval, StoreError> {
-        let mut stmt = self.conn.prepare(
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
             "SELECT id, unsigned_entry_json, unsigned_entry_hash, required_signatures,
                     collected_signatures_json, created_at, expires_at, status, rejection_reason
              FROM pending_approvals WHERE id = ?1"
@@ -148,13 +157,16 @@ impl ApprovalStore {
 
     /// List all pending approvals with a specific status
     pub fn list_by_status(&self, status: ApprovalStatus) -> Result<Vec<PendingApproval>, StoreError> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id FROM pending_approvals WHERE status = ?1 ORDER BY created_at DESC"
-        )?;
-
-        let ids: Vec<String> = stmt
-            .query_map(params![status.as_str()], |row| row.get(0))?
-            .collect::<Result<Vec<_>, _>>()?;
+        let ids: Vec<String> = {
+            let conn = self.conn();
+            let mut stmt = conn.prepare(
+                "SELECT id FROM pending_approvals WHERE status = ?1 ORDER BY created_at DESC"
+            )?;
+            let ids = stmt
+                .query_map(params![status.as_str()], |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            ids
+        };
 
         let mut approvals = Vec::new();
         for id in ids {
@@ -166,13 +178,16 @@ impl ApprovalStore {
 
     /// List all pending approvals (any status)
     pub fn list_all(&self) -> Result<Vec<PendingApproval>, StoreError> {
-        let mut stmt = self.conn.prepare(
-            "SELECT id FROM pending_approvals ORDER BY created_at DESC"
-        )?;
-
-        let ids: Vec<String> = stmt
-            .query_map([], |row| row.get(0))?
-            .collect::<Result<Vec<_>, _>>()?;
+        let ids: Vec<String> = {
+            let conn = self.conn();
+            let mut stmt = conn.prepare(
+                "SELECT id FROM pending_approvals ORDER BY created_at DESC"
+            )?;
+            let ids = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            ids
+        };
 
         let mut approvals = Vec::new();
         for id in ids {
@@ -189,7 +204,7 @@ impl ApprovalStore {
         status: ApprovalStatus,
         rejection_reason: Option<&str>,
     ) -> Result<(), StoreError> {
-        let rows = self.conn.execute(
+        let rows = self.conn().execute(
             "UPDATE pending_approvals SET status = ?1, rejection_reason = ?2 WHERE id = ?3",
             params![status.as_str(), rejection_reason, id],
         )?;
@@ -203,7 +218,7 @@ impl ApprovalStore {
 
     /// Delete an approval by ID
     pub fn delete(&self, id: &str) -> Result<(), StoreError> {
-        let rows = self.conn.execute(
+        let rows = self.conn().execute(
             "DELETE FROM pending_approvals WHERE id = ?1",
             params![id],
         )?;
@@ -217,7 +232,7 @@ impl ApprovalStore {
 
     /// Count approvals by status
     pub fn count_by_status(&self, status: ApprovalStatus) -> Result<usize, StoreError> {
-        let count: i64 = self.conn.query_row(
+        let count: i64 = self.conn().query_row(
             "SELECT COUNT(*) FROM pending_approvals WHERE status = ?1",
             params![status.as_str()],
             |row| row.get(0),
@@ -229,7 +244,7 @@ impl ApprovalStore {
     /// Mark expired approvals
     pub fn expire_old_approvals(&self) -> Result<usize, StoreError> {
         let now = chrono::Utc::now().to_rfc3339();
-        let rows = self.conn.execute(
+        let rows = self.conn().execute(
             "UPDATE pending_approvals
              SET status = 'expired'
              WHERE status = 'pending' AND expires_at < ?1",
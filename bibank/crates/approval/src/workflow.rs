@@ -2,13 +2,17 @@
 
 use crate::pending::{ApprovalStatus, CollectedSignature, PendingApproval};
 use crate::store::{ApprovalStore, StoreError};
+use bibank_ledger::{
+    ApprovalPayload, EntrySignature, RejectionPayload, SignatureScope, TransactionIntent, UnsignedEntry,
+};
 use rust_decimal::Decimal;
+use std::collections::BTreeMap;
 use thiserror::Error;
 
 /// Configuration for the approval workflow
 #[derive(Debug, Clone)]
 pub struct ApprovalConfig {
-    /// Number of signatures required (N in N-of-M)
+    /// Number of signatures required (N in N-of-M); 0 turns approvals off
     pub required_signatures: u8,
 
     /// Total possible signers (M in N-of-M)
@@ -17,8 +21,12 @@ pub struct ApprovalConfig {
     /// Hours before approval expires
     pub expiry_hours: i64,
 
-    /// Withdrawal threshold requiring multi-sig
-    pub withdrawal_threshold: Decimal,
+    /// Per-asset withdrawal/transfer thresholds requiring multi-sig
+    /// (assets not listed need approval for any amount)
+    pub thresholds: BTreeMap<String, Decimal>,
+
+    /// Approver public keys (hex-encoded Ed25519), by signer ID
+    pub approvers: BTreeMap<String, String>,
 }
 
 impl Default for ApprovalConfig {
@@ -27,8 +35,45 @@ impl Default for ApprovalConfig {
             required_signatures: 2,
             total_signers: 3,
             expiry_hours: 24,
-            withdrawal_threshold: Decimal::new(100_000, 0), // 100,000 USDT
+            thresholds: BTreeMap::from([("USDT".to_string(), Decimal::new(100_000, 0))]),
+            approvers: BTreeMap::new(),
+        }
+    }
+}
+
+impl ApprovalConfig {
+    /// Register an approver's public key
+    pub fn with_approver(mut self, signer_id: impl Into<String>, public_key: impl Into<String>) -> Self {
+        self.approvers.insert(signer_id.into(), public_key.into());
+        self.total_signers = self.approvers.len().try_into().unwrap_or(u8::MAX);
+        self
+    }
+
+    /// Set the approval threshold for an asset
+    pub fn with_threshold(mut self, asset: &str, amount: Decimal) -> Self {
+        self.thresholds.insert(asset.to_uppercase(), amount);
+        self
+    }
+
+    /// Amount above which withdrawals and transfers of `asset` need approval
+    pub fn threshold(&self, asset: &str) -> Option<Decimal> {
+        self.thresholds.get(asset).copied()
+    }
+
+    /// Whether entries are gated at all
+    pub fn is_enabled(&self) -> bool {
+        self.required_signatures > 0
+    }
+
+    /// Check the quorum can be reached by the registered approvers
+    pub fn validate(&self) -> Result<(), ApprovalError> {
+        if self.is_enabled() && self.approvers.len() < self.required_signatures as usize {
+            return Err(ApprovalError::NotEnoughApprovers {
+                required: self.required_signatures,
+                registered: self.approvers.len(),
+            });
         }
+        Ok(())
     }
 }
 
@@ -55,6 +100,21 @@ pub enum ApprovalError {
 
     #[error("Cannot sign rejected approval")]
     CannotSignRejected,
+
+    #[error("Not a registered approver: {0}")]
+    UnknownApprover(String),
+
+    #[error("{required} signatures required but only {registered} approvers registered")]
+    NotEnoughApprovers { required: u8, registered: usize },
+
+    #[error("Approval {0} does not match its hash")]
+    Tampered(String),
+
+    #[error("Invalid entry in approval: {0}")]
+    InvalidEntry(String),
+
+    #[error("correlation_id {correlation_id} is pending approval {approval_id} with a different payload")]
+    Conflict { correlation_id: String, approval_id: String },
 }
 
 /// Multi-signature approval workflow
@@ -168,6 +228,15 @@ impl ApprovalWorkflow {
         Ok(approval)
     }
 
+    /// Record that an approved entry could not be committed
+    pub fn mark_failed(&self, id: &str, reason: &str) -> Result<PendingApproval, ApprovalError> {
+        let mut approval = self.store.get(id)?;
+        approval.status = ApprovalStatus::Rejected;
+        approval.rejection_reason = Some(format!("commit failed: {}", reason));
+        self.store.save(&approval)?;
+        Ok(approval)
+    }
+
     /// List all pending approvals
     pub fn list_pending(&self) -> Result<Vec<PendingApproval>, ApprovalError> {
         // First, expire old approvals
@@ -183,8 +252,131 @@ impl ApprovalWorkflow {
 
     /// Check if an intent type requires multi-sig approval
     pub fn requires_approval_for_intent(intent: &str) -> bool {
-        // Adjustment always requires approval
-        intent == "Adjustment"
+        // Adjustments and reversals always require approval
+        intent == "Adjustment" || intent == "Reversal"
+    }
+
+    /// Check if an entry must be approved before commit
+    ///
+    /// Adjustments and reversals always are; withdrawals and transfers are
+    /// when any posting exceeds the threshold for its asset, or its asset
+    /// has none. Nothing is when approvals are off.
+    pub fn requires_approval(&self, entry: &UnsignedEntry) -> bool {
+        if !self.config.is_enabled() {
+            return false;
+        }
+        if Self::requires_approval_for_intent(&format!("{:?}", entry.intent)) {
+            return true;
+        }
+        matches!(entry.intent, TransactionIntent::Withdrawal | TransactionIntent::Transfer)
+            && entry.postings.iter().any(|p| {
+                self.config
+                    .threshold(&p.account.asset)
+                    .is_none_or(|threshold| p.amount.value() > threshold)
+            })
+    }
+
+    /// Check if a signer is a registered approver
+    pub fn is_approver(&self, signer_id: &str) -> bool {
+        self.config.approvers.contains_key(signer_id)
+    }
+
+    /// Park an entry until enough approvers sign it
+    ///
+    /// Resubmitting an entry already pending under the same correlation_id
+    /// returns the existing approval.
+    pub fn submit(&self, entry: &UnsignedEntry) -> Result<PendingApproval, ApprovalError> {
+        let value = serde_json::to_value(entry).map_err(StoreError::from)?;
+
+        if let Some(existing) = self.find_pending(&entry.correlation_id)? {
+            let pending: serde_json::Value =
+                serde_json::from_str(&existing.unsigned_entry_json).map_err(StoreError::from)?;
+            if pending != value {
+                return Err(ApprovalError::Conflict {
+                    correlation_id: entry.correlation_id.clone(),
+                    approval_id: existing.id,
+                });
+            }
+            return Ok(existing);
+        }
+
+        self.create_approval(value.to_string())
+    }
+
+    /// Pending approval parked under a correlation_id
+    pub fn find_pending(&self, correlation_id: &str) -> Result<Option<PendingApproval>, ApprovalError> {
+        Ok(self.list_pending()?.into_iter().find(|approval| {
+            approval
+                .unsigned_entry()
+                .is_ok_and(|entry| entry.correlation_id == correlation_id)
+        }))
+    }
+
+    /// Entry awaiting approval, checked against its hash
+    pub fn entry_of(&self, approval: &PendingApproval) -> Result<UnsignedEntry, ApprovalError> {
+        if !approval.verify_hash() {
+            return Err(ApprovalError::Tampered(approval.id.clone()));
+        }
+        approval
+            .unsigned_entry()
+            .map_err(|e| ApprovalError::InvalidEntry(e.to_string()))
+    }
+
+    /// Verify an approver's signature over the parked entry, then add it
+    ///
+    /// Returns the updated approval, and whether it's now fully approved.
+    pub fn approve(
+        &self,
+        id: &str,
+        signature: &EntrySignature,
+    ) -> Result<(PendingApproval, bool), ApprovalError> {
+        let approval = self.get_approval(id)?;
+        let entry = self.entry_of(&approval)?;
+        let payload = ApprovalPayload::from_unsigned(&entry, signature.signed_at);
+        self.verify_approver(signature, SignatureScope::Approval, &payload.to_bytes())?;
+
+        self.sign_approval(id, CollectedSignature::from(signature))
+    }
+
+    /// Verify an approver's signature over the rejection, then reject
+    ///
+    /// The stored reason names the approver.
+    pub fn reject(
+        &self,
+        id: &str,
+        reason: Option<&str>,
+        signature: &EntrySignature,
+    ) -> Result<PendingApproval, ApprovalError> {
+        let payload = RejectionPayload {
+            approval_id: id.to_string(),
+            reason: reason.map(str::to_string),
+            signed_at: signature.signed_at,
+        };
+        self.verify_approver(signature, SignatureScope::Rejection, &payload.to_bytes())?;
+
+        let reason = match reason {
+            Some(reason) => format!("{} (rejected by {})", reason, signature.signer_id),
+            None => format!("rejected by {}", signature.signer_id),
+        };
+        self.reject_approval(id, Some(&reason))
+    }
+
+    /// Check a signature is a registered approver's, over `payload`
+    fn verify_approver(
+        &self,
+        signature: &EntrySignature,
+        scope: SignatureScope,
+        payload: &[u8],
+    ) -> Result<(), ApprovalError> {
+        let public_key = self
+            .config
+            .approvers
+            .get(&signature.signer_id)
+            .ok_or_else(|| ApprovalError::UnknownApprover(signature.signer_id.clone()))?;
+        if *public_key != signature.public_key || signature.scope != scope {
+            return Err(ApprovalError::InvalidSignature);
+        }
+        signature.verify(payload).map_err(|_| ApprovalError::InvalidSignature)
     }
 
     /// Get statistics about pending approvals
@@ -210,6 +402,8 @@ pub struct ApprovalStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bibank_core::Amount;
+    use bibank_ledger::{AccountKey, JournalEntryBuilder, OperatorSigner, Signer};
     use chrono::Utc;
 
     fn create_test_store() -> ApprovalStore {
@@ -327,10 +521,152 @@ mod tests {
     #[test]
     fn test_requires_approval_for_intent() {
         assert!(ApprovalWorkflow::requires_approval_for_intent("Adjustment"));
+        assert!(ApprovalWorkflow::requires_approval_for_intent("Reversal"));
         assert!(!ApprovalWorkflow::requires_approval_for_intent("Deposit"));
         assert!(!ApprovalWorkflow::requires_approval_for_intent("Transfer"));
     }
 
+    fn adjustment() -> UnsignedEntry {
+        JournalEntryBuilder::new()
+            .intent(TransactionIntent::Adjustment)
+            .correlation_id("adj-1")
+            .debit(AccountKey::system_vault("USDT"), amount(50))
+            .credit(AccountKey::user_available("ALICE", "USDT"), amount(50))
+            .metadata("reason", serde_json::json!("missed deposit"))
+            .build_unsigned()
+            .unwrap()
+    }
+
+    fn withdrawal(asset: &str, value: i64) -> UnsignedEntry {
+        JournalEntryBuilder::new()
+            .intent(TransactionIntent::Withdrawal)
+            .correlation_id("wd-1")
+            .debit(AccountKey::user_available("ALICE", asset), amount(value))
+            .credit(AccountKey::system_vault(asset), amount(value))
+            .build_unsigned()
+            .unwrap()
+    }
+
+    fn amount(val: i64) -> Amount {
+        Amount::new(Decimal::new(val, 0)).unwrap()
+    }
+
+    #[test]
+    fn test_requires_approval_for_entries() {
+        let config = ApprovalConfig::default().with_threshold("btc", Decimal::from(2));
+        let workflow = ApprovalWorkflow::new(create_test_store(), config);
+
+        assert!(workflow.requires_approval(&adjustment()));
+        assert!(!workflow.requires_approval(&withdrawal("USDT", 100_000)));
+        assert!(workflow.requires_approval(&withdrawal("USDT", 100_001)));
+        assert!(!workflow.requires_approval(&withdrawal("BTC", 2)));
+        assert!(workflow.requires_approval(&withdrawal("BTC", 3)));
+
+        // No threshold of its own: any amount
+        assert!(workflow.requires_approval(&withdrawal("ETH", 1)));
+
+        let off = ApprovalConfig {
+            required_signatures: 0,
+            ..ApprovalConfig::default()
+        };
+        let workflow = ApprovalWorkflow::new(create_test_store(), off);
+        assert!(!workflow.requires_approval(&adjustment()));
+        assert!(!workflow.requires_approval(&withdrawal("ETH", 1)));
+    }
+
+    #[test]
+    fn test_validate_requires_enough_approvers() {
+        let config = ApprovalConfig::default().with_approver("ops1", "key-1");
+        assert!(matches!(
+            config.validate(),
+            Err(ApprovalError::NotEnoughApprovers { required: 2, registered: 1 })
+        ));
+        assert!(config.with_approver("ops2", "key-2").validate().is_ok());
+
+        let off = ApprovalConfig {
+            required_signatures: 0,
+            ..ApprovalConfig::default()
+        };
+        assert!(off.validate().is_ok());
+    }
+
+    #[test]
+    fn test_submit_is_idempotent_by_correlation_id() {
+        let workflow = ApprovalWorkflow::with_store(create_test_store());
+
+        let first = workflow.submit(&adjustment()).unwrap();
+        let retry = workflow.submit(&adjustment()).unwrap();
+        assert_eq!(first.id, retry.id);
+        assert_eq!(workflow.list_pending().unwrap().len(), 1);
+
+        let mut changed = adjustment();
+        changed.metadata.insert("reason".to_string(), serde_json::json!("other"));
+        let result = workflow.submit(&changed);
+        assert!(matches!(result, Err(ApprovalError::Conflict { .. })));
+
+        let entry = workflow.entry_of(&first).unwrap();
+        assert_eq!(entry.correlation_id, "adj-1");
+        assert_eq!(entry.postings, adjustment().postings);
+    }
+
+    #[test]
+    fn test_approve_verifies_approver_signatures() {
+        let ops1 = OperatorSigner::generate("ops1");
+        let ops2 = OperatorSigner::generate("ops2");
+        let outsider = OperatorSigner::generate("mallory");
+        let config = ApprovalConfig::default()
+            .with_approver("ops1", ops1.public_key_hex())
+            .with_approver("ops2", ops2.public_key_hex());
+        let workflow = ApprovalWorkflow::new(create_test_store(), config);
+
+        let approval = workflow.submit(&adjustment()).unwrap();
+        let id = approval.id.clone();
+
+        // Not registered
+        let result = workflow.approve(&id, &outsider.sign_approval(&adjustment()));
+        assert!(matches!(result, Err(ApprovalError::UnknownApprover(_))));
+
+        // Signed a different entry
+        let mut other = adjustment();
+        other.correlation_id = "adj-2".to_string();
+        let result = workflow.approve(&id, &ops1.sign_approval(&other));
+        assert!(matches!(result, Err(ApprovalError::InvalidSignature)));
+
+        let (_, done) = workflow.approve(&id, &ops1.sign_approval(&adjustment())).unwrap();
+        assert!(!done);
+        let (approval, done) = workflow.approve(&id, &ops2.sign_approval(&adjustment())).unwrap();
+        assert!(done);
+        assert_eq!(approval.status, ApprovalStatus::Approved);
+
+        let signatures = approval.entry_signatures();
+        assert_eq!(signatures.len(), 2);
+        assert!(signatures.iter().all(|s| s.scope == SignatureScope::Approval));
+    }
+
+    #[test]
+    fn test_reject_verifies_approver_signature() {
+        let ops1 = OperatorSigner::generate("ops1");
+        let outsider = OperatorSigner::generate("mallory");
+        let config = ApprovalConfig::default().with_approver("ops1", ops1.public_key_hex());
+        let workflow = ApprovalWorkflow::new(create_test_store(), config);
+        let id = workflow.submit(&adjustment()).unwrap().id;
+
+        let result = workflow.reject(&id, None, &outsider.sign_rejection(&id, None));
+        assert!(matches!(result, Err(ApprovalError::UnknownApprover(_))));
+
+        // Signed for another reason, or as an approval
+        let signature = ops1.sign_rejection(&id, Some("typo"));
+        let result = workflow.reject(&id, Some("duplicate"), &signature);
+        assert!(matches!(result, Err(ApprovalError::InvalidSignature)));
+        let result = workflow.reject(&id, None, &ops1.sign_approval(&adjustment()));
+        assert!(matches!(result, Err(ApprovalError::InvalidSignature)));
+        assert_eq!(workflow.get_approval(&id).unwrap().status, ApprovalStatus::Pending);
+
+        let approval = workflow.reject(&id, Some("typo"), &signature).unwrap();
+        assert_eq!(approval.status, ApprovalStatus::Rejected);
+        assert_eq!(approval.rejection_reason.as_deref(), Some("typo (rejected by ops1)"));
+    }
+
     #[test]
     fn test_get_stats() {
         let store = create_test_store();
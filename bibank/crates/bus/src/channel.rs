@@ -2,13 +2,15 @@
 //!
 //! Phase 2: Async broadcast channel with subscriber management
 
+use crate::durable::{CatchUp, DurableSubscriber};
 use crate::error::BusError;
 use crate::event::LedgerEvent;
 use crate::subscriber::EventSubscriber;
 use bibank_events::EventReader;
 use std::path::Path;
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
 /// Default channel capacity
@@ -18,6 +20,8 @@ const DEFAULT_CAPACITY: usize = 1024;
 ///
 /// The bus uses a broadcast channel for non-blocking event distribution.
 /// Subscribers receive events asynchronously and can fail independently.
+/// Durable subscribers read committed entries from the journal instead, so
+/// they never miss one (see [`crate::durable`]).
 pub struct EventBus {
     /// Journal path for replay
     journal_path: std::path::PathBuf,
@@ -25,17 +29,16 @@ pub struct EventBus {
     sender: broadcast::Sender<LedgerEvent>,
     /// Registered subscribers
     subscribers: Vec<Arc<dyn EventSubscriber>>,
+    /// Subscribers fed from the journal
+    durable: Vec<DurableSubscriber>,
+    /// Sequence of the last committed entry, waking durable subscribers
+    head: watch::Sender<u64>,
 }
 
 impl EventBus {
     /// Create a new event bus
     pub fn new(journal_path: impl AsRef<Path>) -> Self {
-        let (sender, _) = broadcast::channel(DEFAULT_CAPACITY);
-        Self {
-            journal_path: journal_path.as_ref().to_path_buf(),
-            sender,
-            subscribers: Vec::new(),
-        }
+        Self::with_capacity(journal_path, DEFAULT_CAPACITY)
     }
 
     /// Create with custom capacity
@@ -45,6 +48,8 @@ impl EventBus {
             journal_path: journal_path.as_ref().to_path_buf(),
             sender,
             subscribers: Vec::new(),
+            durable: Vec::new(),
+            head: watch::channel(0).0,
         }
     }
 
@@ -54,6 +59,46 @@ impl EventBus {
         self.subscribers.push(subscriber);
     }
 
+    /// Register a durable subscriber
+    ///
+    /// It isn't called from [`EventBus::publish`]; run it with
+    /// [`EventBus::catch_up_durable`] or [`EventBus::spawn_durable`].
+    pub fn subscribe_durable(&mut self, subscriber: DurableSubscriber) {
+        info!("Registering durable subscriber: {}", subscriber.name());
+        self.durable.push(subscriber);
+    }
+
+    /// Durable subscribers
+    pub fn durable_subscribers(&self) -> &[DurableSubscriber] {
+        &self.durable
+    }
+
+    /// Bring every durable subscriber up to the journal head, one pass each
+    pub async fn catch_up_durable(&self) -> Result<Vec<(String, CatchUp)>, BusError> {
+        let mut passes = Vec::new();
+        for subscriber in &self.durable {
+            let pass = subscriber.catch_up(&self.journal_path).await?;
+            passes.push((subscriber.name().to_string(), pass));
+        }
+        Ok(passes)
+    }
+
+    /// Run each durable subscriber in its own task
+    ///
+    /// Each task catches up from its checkpoint, then again whenever an
+    /// `EntryCommitted` event is published. Tasks end when the bus is dropped.
+    pub fn spawn_durable(&self) -> Vec<JoinHandle<()>> {
+        self.durable
+            .iter()
+            .map(|subscriber| {
+                let run = subscriber
+                    .clone()
+                    .run(self.journal_path.clone(), self.head.subscribe());
+                tokio::spawn(run)
+            })
+            .collect()
+    }
+
     /// Publish an event to all subscribers
     ///
     /// This is non-blocking - the event is sent to the broadcast channel
@@ -61,6 +106,11 @@ impl EventBus {
     pub async fn publish(&self, event: LedgerEvent) -> Result<(), BusError> {
         debug!("Publishing event to {} subscribers", self.subscribers.len());
 
+        // Wake durable subscribers (they read the entry from the journal)
+        if let LedgerEvent::EntryCommitted { entry, .. } = &event {
+            self.head.send_replace(entry.sequence);
+        }
+
         // Send to broadcast channel (for any channel receivers)
         let _ = self.sender.send(event.clone());
 
@@ -81,7 +131,7 @@ impl EventBus {
 
     /// Replay events from a sequence number
     ///
-    /// Reads from JSONL files and publishes to subscribers.
+    /// Streams from the JSONL segments and publishes to subscribers.
     pub async fn replay_from(&self, from_sequence: u64) -> Result<usize, BusError> {
         info!("Starting replay from sequence {}", from_sequence);
 
@@ -94,17 +144,14 @@ impl EventBus {
         }
         let _ = self.sender.send(start_event);
 
-        // Read entries from JSONL
+        // Stream entries from JSONL, seeking straight to from_sequence
         let reader = EventReader::from_directory(&self.journal_path)?;
-        let entries = reader.read_all()?;
 
         let mut count = 0;
-        for entry in entries {
-            if entry.sequence >= from_sequence {
-                let event = LedgerEvent::entry_committed(entry);
-                self.publish(event).await?;
-                count += 1;
-            }
+        for entry in reader.iter_from(from_sequence)? {
+            let event = LedgerEvent::entry_committed(entry?);
+            self.publish(event).await?;
+            count += 1;
         }
 
         // Notify subscribers of replay complete
@@ -0,0 +1,451 @@
+//! Durable subscribers - checkpointed delivery from the journal
+//!
+//! A durable subscriber doesn't rely on the broadcast channel: it reads
+//! committed entries from the JSONL journal, starting after its own
+//! checkpoint, so a slow or restarted subscriber catches up instead of
+//! losing events. Each entry is retried with exponential backoff; an entry
+//! that keeps failing is written to the subscriber's dead-letter file and
+//! skipped, so one bad entry can't stall the subscriber forever.
+//!
+//! Files in the subscriber directory, per subscriber name:
+//! - `<name>.checkpoint.json` - last sequence delivered (or dead-lettered)
+//! - `<name>.dead.jsonl` - entries the subscriber gave up on
+
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bibank_events::EventReader;
+use bibank_ledger::JournalEntry;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use tracing::{error, info, warn};
+
+use crate::error::BusError;
+use crate::event::LedgerEvent;
+use crate::subscriber::EventSubscriber;
+
+const CHECKPOINT_SUFFIX: &str = ".checkpoint.json";
+const DEAD_LETTER_SUFFIX: &str = ".dead.jsonl";
+
+/// Retries per entry before it is dead-lettered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Delivery attempts per entry (at least 1)
+    pub max_attempts: u32,
+    /// Wait after the first failure, doubled after each further one
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(10),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Wait after failed attempt `attempt` (1-based)
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Progress of one subscriber through the journal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubscriberCheckpoint {
+    pub subscriber: String,
+    /// Last sequence delivered or dead-lettered (0: nothing yet)
+    pub last_sequence: u64,
+    /// Entries given up on so far
+    pub dead_letters: u64,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SubscriberCheckpoint {
+    /// Entries behind the journal head
+    pub fn lag(&self, head: u64) -> u64 {
+        head.saturating_sub(self.last_sequence)
+    }
+}
+
+/// An entry a subscriber kept failing on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub subscriber: String,
+    pub sequence: u64,
+    pub attempts: u32,
+    /// Error from the last attempt
+    pub error: String,
+    pub failed_at: DateTime<Utc>,
+    pub entry: JournalEntry,
+}
+
+/// Checkpoint and dead-letter files of durable subscribers
+#[derive(Debug, Clone)]
+pub struct SubscriberStore {
+    dir: PathBuf,
+}
+
+impl SubscriberStore {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Saved checkpoint of a subscriber (None if it never ran)
+    pub fn checkpoint(&self, name: &str) -> Result<Option<SubscriberCheckpoint>, BusError> {
+        let path = self.dir.join(format!("{}{}", name, CHECKPOINT_SUFFIX));
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
+    /// Write a checkpoint atomically (temp file + rename)
+    pub fn save(&self, checkpoint: &SubscriberCheckpoint) -> Result<(), BusError> {
+        fs::create_dir_all(&self.dir)?;
+
+        let path = self
+            .dir
+            .join(format!("{}{}", checkpoint.subscriber, CHECKPOINT_SUFFIX));
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(checkpoint)?)?;
+        fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// All saved checkpoints, by subscriber name
+    pub fn checkpoints(&self) -> Result<Vec<SubscriberCheckpoint>, BusError> {
+        let mut checkpoints = Vec::new();
+        if !self.dir.exists() {
+            return Ok(checkpoints);
+        }
+
+        for file in fs::read_dir(&self.dir)? {
+            let name = file?.file_name().to_string_lossy().into_owned();
+            if let Some(subscriber) = name.strip_suffix(CHECKPOINT_SUFFIX) {
+                checkpoints.extend(self.checkpoint(subscriber)?);
+            }
+        }
+        checkpoints.sort_by(|a, b| a.subscriber.cmp(&b.subscriber));
+        Ok(checkpoints)
+    }
+
+    /// Append an entry to the subscriber's dead-letter file
+    pub fn dead_letter(&self, letter: &DeadLetter) -> Result<(), BusError> {
+        fs::create_dir_all(&self.dir)?;
+
+        let path = self
+            .dir
+            .join(format!("{}{}", letter.subscriber, DEAD_LETTER_SUFFIX));
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(letter)?)?;
+        file.sync_all()?;
+        Ok(())
+    }
+
+    /// Dead letters of a subscriber, oldest first
+    pub fn dead_letters(&self, name: &str) -> Result<Vec<DeadLetter>, BusError> {
+        let path = self.dir.join(format!("{}{}", name, DEAD_LETTER_SUFFIX));
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut letters = Vec::new();
+        for line in BufReader::new(fs::File::open(path)?).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                letters.push(serde_json::from_str(&line)?);
+            }
+        }
+        Ok(letters)
+    }
+
+    /// Subscriber directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+/// Outcome of one catch-up pass
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CatchUp {
+    /// Entries handled successfully
+    pub delivered: usize,
+    /// Entries given up on
+    pub dead_lettered: usize,
+    /// Checkpoint after the pass
+    pub last_sequence: u64,
+}
+
+/// A subscriber fed from the journal, with its own checkpoint
+#[derive(Clone)]
+pub struct DurableSubscriber {
+    subscriber: Arc<dyn EventSubscriber>,
+    store: SubscriberStore,
+    policy: RetryPolicy,
+}
+
+impl DurableSubscriber {
+    pub fn new(subscriber: Arc<dyn EventSubscriber>, store: SubscriberStore) -> Self {
+        Self {
+            subscriber,
+            store,
+            policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Set the retry policy
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        self.subscriber.name()
+    }
+
+    /// Current checkpoint
+    ///
+    /// Without a saved checkpoint, starts after the subscriber's own
+    /// `last_processed_sequence()` (or from the beginning).
+    pub fn checkpoint(&self) -> Result<SubscriberCheckpoint, BusError> {
+        Ok(self.store.checkpoint(self.name())?.unwrap_or_else(|| SubscriberCheckpoint {
+            subscriber: self.name().to_string(),
+            last_sequence: self.subscriber.last_processed_sequence().unwrap_or(0),
+            dead_letters: 0,
+            updated_at: Utc::now(),
+        }))
+    }
+
+    /// Deliver every journal entry after the checkpoint
+    ///
+    /// The checkpoint is saved after each entry, so a crash redelivers at
+    /// most the entry in flight (at-least-once).
+    pub async fn catch_up(&self, journal_path: &Path) -> Result<CatchUp, BusError> {
+        let mut checkpoint = self.checkpoint()?;
+        let mut result = CatchUp {
+            last_sequence: checkpoint.last_sequence,
+            ..Default::default()
+        };
+
+        let reader = EventReader::from_directory(journal_path)?;
+        for entry in reader.iter_from(checkpoint.last_sequence + 1)? {
+            let entry = entry?;
+            let sequence = entry.sequence;
+
+            match self.deliver(&entry).await {
+                Ok(()) => result.delivered += 1,
+                Err((attempts, e)) => {
+                    error!(
+                        "Subscriber '{}' gave up on sequence {} after {} attempts: {}",
+                        self.name(),
+                        sequence,
+                        attempts,
+                        e
+                    );
+                    self.store.dead_letter(&DeadLetter {
+                        subscriber: self.name().to_string(),
+                        sequence,
+                        attempts,
+                        error: e.to_string(),
+                        failed_at: Utc::now(),
+                        entry,
+                    })?;
+                    checkpoint.dead_letters += 1;
+                    result.dead_lettered += 1;
+                }
+            }
+
+            checkpoint.last_sequence = sequence;
+            checkpoint.updated_at = Utc::now();
+            self.store.save(&checkpoint)?;
+            result.last_sequence = sequence;
+        }
+
+        Ok(result)
+    }
+
+    /// Hand an entry to the subscriber, retrying with backoff
+    async fn deliver(&self, entry: &JournalEntry) -> Result<(), (u32, BusError)> {
+        let event = LedgerEvent::entry_committed(entry.clone());
+        let max_attempts = self.policy.max_attempts.max(1);
+
+        let mut attempt = 1;
+        loop {
+            match self.subscriber.handle(&event).await {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= max_attempts => return Err((attempt, e)),
+                Err(e) => {
+                    let wait = self.policy.backoff(attempt);
+                    warn!(
+                        "Subscriber '{}' failed on sequence {} (attempt {}), retrying in {:?}: {}",
+                        self.name(),
+                        entry.sequence,
+                        attempt,
+                        wait,
+                        e
+                    );
+                    tokio::time::sleep(wait).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    /// Catch up, then follow the journal head until the bus is dropped
+    ///
+    /// A pass that fails (e.g. the checkpoint can't be written) is retried
+    /// after the policy's longest backoff.
+    pub(crate) async fn run(self, journal_path: PathBuf, mut head: watch::Receiver<u64>) {
+        loop {
+            match self.catch_up(&journal_path).await {
+                Ok(pass) if pass.delivered + pass.dead_lettered > 0 => {
+                    info!("Subscriber '{}' caught up to sequence {}", self.name(), pass.last_sequence);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    error!("Subscriber '{}' catch-up failed: {}", self.name(), e);
+                    tokio::time::sleep(self.policy.max_backoff).await;
+                    continue;
+                }
+            }
+
+            if head.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use crate::testing::deposit_entry;
+    use bibank_events::{EventStore, StoreConfig};
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Records delivered sequences; fails every attempt on `poison`
+    struct Recorder {
+        seen: Mutex<Vec<u64>>,
+        poison: Option<u64>,
+    }
+
+    impl Recorder {
+        fn new(poison: Option<u64>) -> Arc<Self> {
+            Arc::new(Self {
+                seen: Mutex::new(Vec::new()),
+                poison,
+            })
+        }
+
+        fn seen(&self) -> Vec<u64> {
+            self.seen.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl EventSubscriber for Recorder {
+        fn name(&self) -> &str {
+            "recorder"
+        }
+
+        async fn handle(&self, event: &LedgerEvent) -> Result<(), BusError> {
+            let LedgerEvent::EntryCommitted { entry, .. } = event else {
+                return Ok(());
+            };
+            if Some(entry.sequence) == self.poison {
+                return Err(BusError::SubscriberFailed {
+                    name: "recorder".to_string(),
+                    reason: "poison entry".to_string(),
+                });
+            }
+            self.seen.lock().unwrap().push(entry.sequence);
+            Ok(())
+        }
+    }
+
+    fn write_journal(dir: &Path, sequences: std::ops::RangeInclusive<u64>) {
+        let mut store = EventStore::open(dir, StoreConfig::default()).unwrap();
+        for sequence in sequences {
+            store.append(&deposit_entry(sequence, "ALICE")).unwrap();
+        }
+    }
+
+    fn fast_retries() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+        }
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(20), Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_catch_up_resumes_from_checkpoint() {
+        let dir = TempDir::new().unwrap();
+        let journal = dir.path().join("journal");
+        let store = SubscriberStore::new(dir.path().join("subscribers"));
+        write_journal(&journal, 1..=3);
+
+        let first = Recorder::new(None);
+        let durable = DurableSubscriber::new(first.clone(), store.clone());
+        let pass = durable.catch_up(&journal).await.unwrap();
+        assert_eq!((pass.delivered, pass.last_sequence), (3, 3));
+        assert_eq!(first.seen(), vec![1, 2, 3]);
+
+        // A restarted subscriber only gets what it missed
+        write_journal(&journal, 4..=5);
+        let restarted = Recorder::new(None);
+        let durable = DurableSubscriber::new(restarted.clone(), store.clone());
+        durable.catch_up(&journal).await.unwrap();
+        assert_eq!(restarted.seen(), vec![4, 5]);
+
+        let checkpoints = store.checkpoints().unwrap();
+        assert_eq!(checkpoints.len(), 1);
+        assert_eq!(checkpoints[0].last_sequence, 5);
+        assert_eq!(checkpoints[0].lag(7), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failing_entry_is_dead_lettered() {
+        let dir = TempDir::new().unwrap();
+        let journal = dir.path().join("journal");
+        let store = SubscriberStore::new(dir.path().join("subscribers"));
+        write_journal(&journal, 1..=3);
+
+        let recorder = Recorder::new(Some(2));
+        let durable = DurableSubscriber::new(recorder.clone(), store.clone()).with_retry_policy(fast_retries());
+        let pass = durable.catch_up(&journal).await.unwrap();
+
+        assert_eq!((pass.delivered, pass.dead_lettered, pass.last_sequence), (2, 1, 3));
+        assert_eq!(recorder.seen(), vec![1, 3]);
+
+        let letters = store.dead_letters("recorder").unwrap();
+        assert_eq!(letters.len(), 1);
+        assert_eq!((letters[0].sequence, letters[0].attempts), (2, 3));
+        assert!(letters[0].error.contains("poison entry"));
+        assert_eq!(store.checkpoint("recorder").unwrap().unwrap().dead_letters, 1);
+    }
+}
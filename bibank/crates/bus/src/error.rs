@@ -19,4 +19,13 @@ pub enum BusError {
 
     #[error("Channel closed")]
     ChannelClosed,
+
+    #[error("Subscriber store IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Subscriber store serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Invalid config: {0}")]
+    InvalidConfig(String),
 }
@@ -1,6 +1,7 @@
 //! Ledger events for pub/sub distribution
 
 use bibank_ledger::JournalEntry;
+use bibank_matching::{DepthChange, TradePrint, TradingPair};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -34,6 +35,20 @@ pub enum LedgerEvent {
         /// Number of entries replayed
         entries_count: usize,
     },
+
+    /// L2 depth changed on a pair's order book
+    DepthUpdated {
+        pair: TradingPair,
+        /// Changed levels (zero quantity: level removed)
+        changes: Vec<DepthChange>,
+        /// Journal sequence the book reflects
+        sequence: u64,
+    },
+
+    /// A trade printed on a pair
+    TradePrinted {
+        trade: TradePrint,
+    },
 }
 
 impl LedgerEvent {
@@ -62,4 +77,27 @@ impl LedgerEvent {
     pub fn replay_completed(entries_count: usize) -> Self {
         Self::ReplayCompleted { entries_count }
     }
+
+    /// Create a DepthUpdated event
+    pub fn depth_updated(pair: TradingPair, changes: Vec<DepthChange>, sequence: u64) -> Self {
+        Self::DepthUpdated {
+            pair,
+            changes,
+            sequence,
+        }
+    }
+
+    /// Create a TradePrinted event
+    pub fn trade_printed(trade: TradePrint) -> Self {
+        Self::TradePrinted { trade }
+    }
+
+    /// Trading pair of a market data event
+    pub fn pair(&self) -> Option<&TradingPair> {
+        match self {
+            Self::DepthUpdated { pair, .. } => Some(pair),
+            Self::TradePrinted { trade } => Some(&trade.pair),
+            _ => None,
+        }
+    }
 }
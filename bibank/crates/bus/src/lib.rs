@@ -7,13 +7,27 @@
 //! - EventSubscriber trait for custom handlers
 //! - Replay from JSONL (Source of Truth)
 //! - No retention in bus - events only in JSONL
+//! - Market data: L2 depth deltas and trade prints per trading pair
+//! - Durable subscribers: per-subscriber checkpoints, catch-up from the
+//!   journal, retries with backoff and a dead-letter file
+//! - Webhooks: HMAC-signed POSTs of filtered entries, one durable
+//!   subscriber per endpoint
 
 pub mod channel;
+pub mod durable;
 pub mod error;
 pub mod event;
 pub mod subscriber;
+pub mod webhook;
+
+#[cfg(test)]
+mod testing;
 
 pub use channel::EventBus;
+pub use durable::{
+    CatchUp, DeadLetter, DurableSubscriber, RetryPolicy, SubscriberCheckpoint, SubscriberStore,
+};
 pub use error::BusError;
 pub use event::LedgerEvent;
 pub use subscriber::EventSubscriber;
+pub use webhook::{EntryFilter, WebhookConfig, WebhookEndpoint, WebhookSubscriber};
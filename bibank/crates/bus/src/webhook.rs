@@ -0,0 +1,433 @@
+//! Webhook subscriber - POSTs committed entries to HTTP endpoints
+//!
+//! Each endpoint is its own durable subscriber (`webhook-<name>`), so it has
+//! its own checkpoint, retry queue and dead-letter file: a slow or failing
+//! endpoint never holds up the others. Delivery is at-least-once; receivers
+//! should dedupe on the `X-BiBank-Sequence` header.
+//!
+//! Every request carries an HMAC-SHA256 signature over `<timestamp>.<body>`
+//! with the endpoint's secret:
+//!
+//! ```text
+//! X-BiBank-Timestamp: 1760659200
+//! X-BiBank-Signature: sha256=5d41402abc4b2a76b9719d911017c592...
+//! ```
+
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use bibank_ledger::{JournalEntry, TransactionIntent};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::Sha256;
+
+use crate::durable::{DurableSubscriber, RetryPolicy, SubscriberStore};
+use crate::error::BusError;
+use crate::event::LedgerEvent;
+use crate::subscriber::EventSubscriber;
+
+/// Signature header
+pub const SIGNATURE_HEADER: &str = "X-BiBank-Signature";
+
+/// Signing time header (Unix seconds)
+pub const TIMESTAMP_HEADER: &str = "X-BiBank-Timestamp";
+
+/// Entry sequence header, for deduplication
+pub const SEQUENCE_HEADER: &str = "X-BiBank-Sequence";
+
+/// Request timeout
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Webhook endpoints (`webhooks.json`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    #[serde(default)]
+    pub endpoints: Vec<WebhookEndpoint>,
+}
+
+impl WebhookConfig {
+    /// Load and validate a config file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, BusError> {
+        let config: Self = serde_json::from_slice(&std::fs::read(path)?)?;
+        for endpoint in &config.endpoints {
+            endpoint.validate()?;
+        }
+        Ok(config)
+    }
+}
+
+/// One endpoint and the entries it wants
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookEndpoint {
+    /// Endpoint name (letters, digits, `-` and `_`)
+    pub name: String,
+    pub url: String,
+    /// HMAC key
+    pub secret: String,
+    #[serde(default)]
+    pub filter: EntryFilter,
+    /// Attempts per entry before it is dead-lettered (default 5)
+    #[serde(default)]
+    pub max_attempts: Option<u32>,
+}
+
+impl WebhookEndpoint {
+    fn validate(&self) -> Result<(), BusError> {
+        let valid_name = !self.name.is_empty()
+            && self
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid_name {
+            return Err(BusError::InvalidConfig(format!(
+                "invalid webhook name '{}'",
+                self.name
+            )));
+        }
+        if !self.url.starts_with("http://") && !self.url.starts_with("https://") {
+            return Err(BusError::InvalidConfig(format!(
+                "webhook '{}': url must be http(s)",
+                self.name
+            )));
+        }
+        if self.secret.is_empty() {
+            return Err(BusError::InvalidConfig(format!(
+                "webhook '{}': empty secret",
+                self.name
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Which entries an endpoint receives
+///
+/// Every non-empty list must match (any of its items); empty lists match all.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryFilter {
+    #[serde(default)]
+    pub intents: Vec<TransactionIntent>,
+    /// Account key prefixes, e.g. `LIAB:USER:`
+    #[serde(default)]
+    pub account_prefixes: Vec<String>,
+    /// Users with a posting in the entry
+    #[serde(default)]
+    pub users: Vec<String>,
+}
+
+impl EntryFilter {
+    pub fn matches(&self, entry: &JournalEntry) -> bool {
+        let intent = self.intents.is_empty() || self.intents.contains(&entry.intent);
+
+        let account = self.account_prefixes.is_empty()
+            || entry.postings.iter().any(|p| {
+                let key = p.account.to_string();
+                self.account_prefixes
+                    .iter()
+                    .any(|prefix| key.starts_with(prefix.as_str()))
+            });
+
+        let user = self.users.is_empty()
+            || entry.postings.iter().any(|p| {
+                p.account.segment == "USER"
+                    && self
+                        .users
+                        .iter()
+                        .any(|u| u.eq_ignore_ascii_case(&p.account.id))
+            });
+
+        intent && account && user
+    }
+}
+
+/// `sha256=<hex>` HMAC of `<timestamp>.<body>`
+pub fn sign(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    format!(
+        "sha256={}",
+        hex::encode(hmac(secret, timestamp, body).finalize().into_bytes())
+    )
+}
+
+/// Check a signature header (constant time)
+pub fn verify_signature(secret: &str, timestamp: i64, body: &[u8], header: &str) -> bool {
+    let Some(signature) = header
+        .strip_prefix("sha256=")
+        .and_then(|h| hex::decode(h).ok())
+    else {
+        return false;
+    };
+    hmac(secret, timestamp, body)
+        .verify_slice(&signature)
+        .is_ok()
+}
+
+fn hmac(secret: &str, timestamp: i64, body: &[u8]) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    mac
+}
+
+/// Subscriber POSTing matching committed entries to one endpoint
+pub struct WebhookSubscriber {
+    name: String,
+    endpoint: WebhookEndpoint,
+    client: reqwest::Client,
+    timeout: Duration,
+}
+
+impl WebhookSubscriber {
+    pub fn new(endpoint: WebhookEndpoint) -> Self {
+        Self {
+            name: format!("webhook-{}", endpoint.name),
+            endpoint,
+            client: reqwest::Client::new(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Set the request timeout
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Wrap in a durable subscriber with the endpoint's retry policy
+    pub fn durable(self, store: SubscriberStore) -> DurableSubscriber {
+        let mut policy = RetryPolicy::default();
+        if let Some(max_attempts) = self.endpoint.max_attempts {
+            policy.max_attempts = max_attempts;
+        }
+        DurableSubscriber::new(Arc::new(self), store).with_retry_policy(policy)
+    }
+
+    async fn post(&self, entry: &JournalEntry) -> Result<(), BusError> {
+        let body = serde_json::to_vec(&json!({ "event": "entry_committed", "entry": entry }))?;
+        let timestamp = Utc::now().timestamp();
+
+        self.client
+            .post(&self.endpoint.url)
+            .timeout(self.timeout)
+            .header("Content-Type", "application/json")
+            .header(TIMESTAMP_HEADER, timestamp.to_string())
+            .header(SEQUENCE_HEADER, entry.sequence.to_string())
+            .header(
+                SIGNATURE_HEADER,
+                sign(&self.endpoint.secret, timestamp, &body),
+            )
+            .body(body)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| BusError::SubscriberFailed {
+                name: self.name.clone(),
+                reason: e.to_string(),
+            })?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl EventSubscriber for WebhookSubscriber {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn handle(&self, event: &LedgerEvent) -> Result<(), BusError> {
+        match event {
+            LedgerEvent::EntryCommitted { entry, .. } if self.endpoint.filter.matches(entry) => {
+                self.post(entry).await
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::deposit_entry;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    fn endpoint(url: String, filter: EntryFilter) -> WebhookEndpoint {
+        WebhookEndpoint {
+            name: "test".to_string(),
+            url,
+            secret: "s3cret".to_string(),
+            filter,
+            max_attempts: None,
+        }
+    }
+
+    /// Local stub: answers with `status`, forwarding each raw request
+    async fn stub_server(status: &'static str) -> (String, mpsc::UnboundedReceiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = mpsc::unbounded_channel();
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                // Read until the headers and the whole body are in
+                loop {
+                    let n = stream.read(&mut buf).await.unwrap_or(0);
+                    request.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&request);
+                    let complete = text.split_once("\r\n\r\n").is_some_and(|(head, body)| {
+                        let length = head
+                            .lines()
+                            .find_map(|l| {
+                                l.to_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().to_string())
+                            })
+                            .and_then(|v| v.parse::<usize>().ok())
+                            .unwrap_or(0);
+                        body.len() >= length
+                    });
+                    if n == 0 || complete {
+                        break;
+                    }
+                }
+                tx.send(String::from_utf8_lossy(&request).into_owned()).ok();
+
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                );
+                stream.write_all(response.as_bytes()).await.ok();
+            }
+        });
+
+        (format!("http://{}/hook", addr), rx)
+    }
+
+    fn header<'a>(request: &'a str, name: &str) -> &'a str {
+        request
+            .lines()
+            .find_map(|l| {
+                let (key, value) = l.split_once(':')?;
+                key.eq_ignore_ascii_case(name).then(|| value.trim())
+            })
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_filter() {
+        let entry = deposit_entry(2, "ALICE");
+        assert!(EntryFilter::default().matches(&entry));
+
+        let by_intent = EntryFilter {
+            intents: vec![TransactionIntent::Withdrawal],
+            ..Default::default()
+        };
+        assert!(!by_intent.matches(&entry));
+
+        let by_account_and_user = EntryFilter {
+            account_prefixes: vec!["LIAB:USER:".to_string()],
+            users: vec!["alice".to_string()],
+            ..Default::default()
+        };
+        assert!(by_account_and_user.matches(&entry));
+        assert!(!by_account_and_user.matches(&deposit_entry(3, "BOB")));
+    }
+
+    #[test]
+    fn test_signature() {
+        let header = sign("s3cret", 1_760_659_200, b"{}");
+        assert!(header.starts_with("sha256="));
+        assert!(verify_signature("s3cret", 1_760_659_200, b"{}", &header));
+        assert!(!verify_signature("s3cret", 1_760_659_201, b"{}", &header));
+        assert!(!verify_signature("other", 1_760_659_200, b"{}", &header));
+        assert!(!verify_signature(
+            "s3cret",
+            1_760_659_200,
+            b"{}",
+            "sha256=zz"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_posts_signed_matching_entries() {
+        let (url, mut requests) = stub_server("200 OK").await;
+        let filter = EntryFilter {
+            users: vec!["ALICE".to_string()],
+            ..Default::default()
+        };
+        let webhook = WebhookSubscriber::new(endpoint(url, filter));
+
+        webhook
+            .handle(&LedgerEvent::entry_committed(deposit_entry(3, "BOB")))
+            .await
+            .unwrap();
+        webhook
+            .handle(&LedgerEvent::entry_committed(deposit_entry(4, "ALICE")))
+            .await
+            .unwrap();
+
+        // Only ALICE's entry was sent
+        let request = requests.recv().await.unwrap();
+        assert!(request.starts_with("POST /hook"));
+        assert_eq!(header(&request, SEQUENCE_HEADER), "4");
+        assert!(requests.try_recv().is_err());
+
+        let body = request.split_once("\r\n\r\n").unwrap().1;
+        let timestamp: i64 = header(&request, TIMESTAMP_HEADER).parse().unwrap();
+        assert!(verify_signature(
+            "s3cret",
+            timestamp,
+            body.as_bytes(),
+            header(&request, SIGNATURE_HEADER)
+        ));
+        let payload: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(payload["entry"]["correlation_id"], json!("corr-4"));
+    }
+
+    #[tokio::test]
+    async fn test_error_status_fails_delivery() {
+        let (url, _requests) = stub_server("500 Internal Server Error").await;
+        let webhook = WebhookSubscriber::new(endpoint(url, EntryFilter::default()));
+
+        let err = webhook
+            .handle(&LedgerEvent::entry_committed(deposit_entry(2, "ALICE")))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BusError::SubscriberFailed { name, .. } if name == "webhook-test"));
+    }
+
+    #[test]
+    fn test_config_validation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("webhooks.json");
+
+        std::fs::write(
+            &path,
+            r#"{"endpoints":[{"name":"../x","url":"http://a","secret":"k"}]}"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            WebhookConfig::from_file(&path),
+            Err(BusError::InvalidConfig(_))
+        ));
+
+        std::fs::write(
+            &path,
+            r#"{"endpoints":[{"name":"ops","url":"https://a","secret":"k","filter":{"intents":["withdrawal"]}}]}"#,
+        )
+        .unwrap();
+        let config = WebhookConfig::from_file(&path).unwrap();
+        assert_eq!(
+            config.endpoints[0].filter.intents,
+            vec![TransactionIntent::Withdrawal]
+        );
+    }
+}
@@ -0,0 +1,272 @@
+//! Case management for flagged transactions
+//!
+//! Every `TransactionFlagged` event opens a case. Cases are never stored on
+//! their own: [`CaseBook`] folds them out of the Compliance Ledger, so the
+//! ledger stays the only source of decision truth.
+//!
+//! ```text
+//! TransactionFlagged ──► Open ──► CaseAssigned ──► ReviewCompleted
+//!                         │                         (approved/rejected)
+//!                         └─► CaseEscalated (expires_at passed: L1 → L2 → ...)
+//! ```
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::decision::ApprovalLevel;
+use crate::event::{ComplianceEvent, ReviewDecision};
+
+/// Case lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaseStatus {
+    /// Awaiting review
+    Open,
+    /// Reviewed: the transaction stands
+    Approved,
+    /// Reviewed: the transaction is reversed
+    Rejected,
+    /// Closed without a review decision
+    Expired,
+}
+
+/// A flagged transaction under review
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Case {
+    /// Id of the `TransactionFlagged` event
+    pub flag_id: String,
+    /// Correlation id of the flagged transaction
+    pub correlation_id: String,
+    pub user_id: String,
+    pub reason: String,
+    /// Level currently required to decide (raised by escalation)
+    pub required_approval: ApprovalLevel,
+    pub opened_at: DateTime<Utc>,
+    /// When the case escalates if still open
+    pub expires_at: DateTime<Utc>,
+    pub assigned_to: Option<String>,
+    pub escalations: u32,
+    pub status: CaseStatus,
+    pub resolved_by: Option<String>,
+    pub notes: Option<String>,
+}
+
+impl Case {
+    /// Check if the case still awaits review
+    pub fn is_open(&self) -> bool {
+        self.status == CaseStatus::Open
+    }
+
+    /// Check if an open case is past its expiry
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        self.is_open() && self.expires_at <= now
+    }
+}
+
+/// All cases, folded from the Compliance Ledger
+#[derive(Debug, Clone, Default)]
+pub struct CaseBook {
+    cases: BTreeMap<String, Case>,
+}
+
+impl CaseBook {
+    /// Create an empty case book
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold a sequence of events
+    pub fn from_events<'a>(events: impl IntoIterator<Item = &'a ComplianceEvent>) -> Self {
+        let mut book = Self::new();
+        for event in events {
+            book.apply(event);
+        }
+        book
+    }
+
+    /// Apply one event (events about unknown cases are ignored)
+    pub fn apply(&mut self, event: &ComplianceEvent) {
+        match event {
+            ComplianceEvent::TransactionFlagged {
+                id,
+                correlation_id,
+                user_id,
+                reason,
+                required_approval,
+                expires_at,
+                timestamp,
+            } => {
+                self.cases.insert(
+                    id.clone(),
+                    Case {
+                        flag_id: id.clone(),
+                        correlation_id: correlation_id.clone(),
+                        user_id: user_id.clone(),
+                        reason: reason.clone(),
+                        required_approval: *required_approval,
+                        opened_at: *timestamp,
+                        expires_at: *expires_at,
+                        assigned_to: None,
+                        escalations: 0,
+                        status: CaseStatus::Open,
+                        resolved_by: None,
+                        notes: None,
+                    },
+                );
+            }
+            ComplianceEvent::CaseAssigned { flag_id, reviewer_id, .. } => {
+                if let Some(case) = self.cases.get_mut(flag_id) {
+                    case.assigned_to = Some(reviewer_id.clone());
+                }
+            }
+            ComplianceEvent::CaseEscalated {
+                flag_id,
+                to_level,
+                expires_at,
+                ..
+            } => {
+                if let Some(case) = self.cases.get_mut(flag_id) {
+                    case.required_approval = *to_level;
+                    case.expires_at = *expires_at;
+                    case.escalations += 1;
+                    // The assignee may not hold the new level
+                    case.assigned_to = None;
+                }
+            }
+            ComplianceEvent::ReviewCompleted {
+                flag_id,
+                decision,
+                reviewer_id,
+                notes,
+                ..
+            } => {
+                if let Some(case) = self.cases.get_mut(flag_id) {
+                    case.status = match decision {
+                        ReviewDecision::Approved => CaseStatus::Approved,
+                        ReviewDecision::Rejected => CaseStatus::Rejected,
+                        ReviewDecision::Expired => CaseStatus::Expired,
+                    };
+                    case.resolved_by = Some(reviewer_id.clone());
+                    case.notes = Some(notes.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Get a case by flag id
+    pub fn get(&self, flag_id: &str) -> Option<&Case> {
+        self.cases.get(flag_id)
+    }
+
+    /// Case opened for a transaction
+    pub fn by_correlation_id(&self, correlation_id: &str) -> Option<&Case> {
+        self.cases.values().find(|c| c.correlation_id == correlation_id)
+    }
+
+    /// Open cases, optionally at one approval level, oldest expiry first
+    pub fn open(&self, level: Option<ApprovalLevel>) -> Vec<&Case> {
+        let mut cases: Vec<&Case> = self
+            .cases
+            .values()
+            .filter(|c| c.is_open() && level.is_none_or(|l| c.required_approval == l))
+            .collect();
+        cases.sort_by_key(|c| c.expires_at);
+        cases
+    }
+
+    /// Open cases past their expiry that can still escalate
+    pub fn escalation_due(&self, now: DateTime<Utc>) -> Vec<&Case> {
+        self.open(None)
+            .into_iter()
+            .filter(|c| c.is_overdue(now) && c.required_approval.next().is_some())
+            .collect()
+    }
+
+    /// All cases
+    pub fn all(&self) -> impl Iterator<Item = &Case> {
+        self.cases.values()
+    }
+
+    /// Number of cases
+    pub fn len(&self) -> usize {
+        self.cases.len()
+    }
+
+    /// Check if there are no cases
+    pub fn is_empty(&self) -> bool {
+        self.cases.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn flagged(cid: &str, level: ApprovalLevel, expires_in: Duration) -> ComplianceEvent {
+        ComplianceEvent::transaction_flagged(cid, "ALICE", "Large", level, Utc::now() + expires_in)
+    }
+
+    #[test]
+    fn test_case_lifecycle() {
+        let flag = flagged("TX-1", ApprovalLevel::L1, Duration::hours(1));
+        let flag_id = flag.id().to_string();
+        let mut book = CaseBook::from_events(&[flag]);
+
+        let case = book.get(&flag_id).unwrap();
+        assert!(case.is_open());
+        assert_eq!(case.correlation_id, "TX-1");
+        assert_eq!(book.by_correlation_id("TX-1").unwrap().flag_id, flag_id);
+
+        book.apply(&ComplianceEvent::case_assigned(&flag_id, "officer-1", "lead"));
+        assert_eq!(book.get(&flag_id).unwrap().assigned_to.as_deref(), Some("officer-1"));
+
+        book.apply(&ComplianceEvent::review_completed(
+            &flag_id,
+            ReviewDecision::Rejected,
+            "officer-1",
+            "Source of funds unclear",
+        ));
+        let case = book.get(&flag_id).unwrap();
+        assert_eq!(case.status, CaseStatus::Rejected);
+        assert_eq!(case.resolved_by.as_deref(), Some("officer-1"));
+        assert!(book.open(None).is_empty());
+    }
+
+    #[test]
+    fn test_open_cases_by_level_and_escalation() {
+        let overdue = flagged("TX-1", ApprovalLevel::L1, Duration::hours(-1));
+        let fresh = flagged("TX-2", ApprovalLevel::L2, Duration::hours(1));
+        let board = flagged("TX-3", ApprovalLevel::L4, Duration::hours(-1));
+        let overdue_id = overdue.id().to_string();
+        let mut book = CaseBook::from_events(&[overdue, fresh, board]);
+
+        assert_eq!(book.open(None).len(), 3);
+        assert_eq!(book.open(Some(ApprovalLevel::L2))[0].correlation_id, "TX-2");
+
+        // Board-level cases have nowhere to go
+        let due: Vec<&str> = book
+            .escalation_due(Utc::now())
+            .iter()
+            .map(|c| c.correlation_id.as_str())
+            .collect();
+        assert_eq!(due, vec!["TX-1"]);
+
+        book.apply(&ComplianceEvent::case_assigned(&overdue_id, "officer-1", "lead"));
+        book.apply(&ComplianceEvent::case_escalated(
+            &overdue_id,
+            ApprovalLevel::L1,
+            ApprovalLevel::L2,
+            Utc::now() + Duration::hours(1),
+        ));
+        let case = book.get(&overdue_id).unwrap();
+        assert_eq!(case.required_approval, ApprovalLevel::L2);
+        assert_eq!(case.escalations, 1);
+        assert_eq!(case.assigned_to, None);
+        assert_eq!(book.open(Some(ApprovalLevel::L2)).len(), 2);
+        assert!(book.escalation_due(Utc::now()).is_empty());
+    }
+}
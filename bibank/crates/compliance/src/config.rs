@@ -5,6 +5,7 @@
 
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 /// Configuration for the Compliance Engine
@@ -57,9 +58,40 @@ pub struct ComplianceConfig {
     pub external_fail_policy: FailPolicy,
 
     // === Review Settings ===
-    /// Hours until flagged transaction expires (auto-reject)
+    /// Hours a flagged transaction waits at one approval level before it
+    /// escalates to the next
     #[serde(default = "default_review_expiry_hours")]
     pub review_expiry_hours: u64,
+
+    // === KYC Limits ===
+    /// Rolling withdrawal limits per KYC tier (tiers not listed are unlimited)
+    #[serde(default = "default_kyc_limits")]
+    pub kyc_limits: BTreeMap<u8, KycLimits>,
+
+    /// Asset the KYC limits are expressed in; withdrawals of other assets
+    /// are valued in it at the oracle price
+    #[serde(default = "default_kyc_limit_asset")]
+    pub kyc_limit_asset: String,
+}
+
+/// Rolling withdrawal limits for one KYC tier (None = unlimited)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct KycLimits {
+    /// Value withdrawn in the last 24 hours, all assets together
+    #[serde(default)]
+    pub daily_withdrawal: Option<Decimal>,
+    /// Value withdrawn in the last 30 days, all assets together
+    #[serde(default)]
+    pub monthly_withdrawal: Option<Decimal>,
+}
+
+impl KycLimits {
+    pub fn new(daily: Decimal, monthly: Decimal) -> Self {
+        Self {
+            daily_withdrawal: Some(daily),
+            monthly_withdrawal: Some(monthly),
+        }
+    }
 }
 
 /// Policy when external service (KYC, Watchlist) fails
@@ -117,6 +149,20 @@ fn default_review_expiry_hours() -> u64 {
     72 // 3 days
 }
 
+fn default_kyc_limits() -> BTreeMap<u8, KycLimits> {
+    BTreeMap::from([
+        (0, KycLimits::new(Decimal::new(1_000, 0), Decimal::new(5_000, 0))),
+        (1, KycLimits::new(Decimal::new(10_000, 0), Decimal::new(50_000, 0))),
+        (2, KycLimits::new(Decimal::new(100_000, 0), Decimal::new(1_000_000, 0))),
+        (3, KycLimits::new(Decimal::new(1_000_000, 0), Decimal::new(10_000_000, 0))),
+        // Tier 4: unlimited
+    ])
+}
+
+fn default_kyc_limit_asset() -> String {
+    "USDT".to_string()
+}
+
 impl Default for ComplianceConfig {
     fn default() -> Self {
         Self {
@@ -131,6 +177,8 @@ impl Default for ComplianceConfig {
             external_cache_ttl_secs: default_external_cache_ttl_secs(),
             external_fail_policy: FailPolicy::default(),
             review_expiry_hours: default_review_expiry_hours(),
+            kyc_limits: default_kyc_limits(),
+            kyc_limit_asset: default_kyc_limit_asset(),
         }
     }
 }
@@ -163,6 +211,11 @@ impl ComplianceConfig {
     pub fn new_account_threshold(&self) -> chrono::Duration {
         chrono::Duration::days(self.new_account_days)
     }
+
+    /// Withdrawal limits for a KYC tier
+    pub fn kyc_limits_for(&self, tier: u8) -> KycLimits {
+        self.kyc_limits.get(&tier).copied().unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -184,6 +237,8 @@ mod tests {
         assert_eq!(config.external_cache_ttl_secs, 300);
         assert_eq!(config.external_fail_policy, FailPolicy::FailClosed);
         assert_eq!(config.review_expiry_hours, 72);
+        assert_eq!(config.kyc_limits_for(0).daily_withdrawal, Some(Decimal::new(1_000, 0)));
+        assert_eq!(config.kyc_limits_for(4), KycLimits::default());
     }
 
     #[test]
@@ -216,6 +271,17 @@ mod tests {
         assert_eq!(config.ctr_threshold, Decimal::new(10_000, 0)); // default
     }
 
+    #[test]
+    fn test_kyc_limits_json() {
+        let json = r#"{ "kyc_limits": { "1": { "daily_withdrawal": "500" } } }"#;
+        let config: ComplianceConfig = serde_json::from_str(json).unwrap();
+
+        let limits = config.kyc_limits_for(1);
+        assert_eq!(limits.daily_withdrawal, Some(Decimal::new(500, 0)));
+        assert_eq!(limits.monthly_withdrawal, None);
+        assert_eq!(config.kyc_limits_for(0), KycLimits::default());
+    }
+
     #[test]
     fn test_duration_helpers() {
         let config = ComplianceConfig::default();
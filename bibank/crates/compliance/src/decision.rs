@@ -9,9 +9,10 @@ use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
 /// Risk score levels - ordered from lowest to highest
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum RiskScore {
+    #[default]
     Low = 1,
     Medium = 2,
     High = 3,
@@ -30,17 +31,12 @@ impl Ord for RiskScore {
     }
 }
 
-impl Default for RiskScore {
-    fn default() -> Self {
-        RiskScore::Low
-    }
-}
-
 /// Approval level required for flagged transactions
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ApprovalLevel {
     /// Single compliance officer
+    #[default]
     L1 = 1,
     /// Senior compliance officer
     L2 = 2,
@@ -50,6 +46,18 @@ pub enum ApprovalLevel {
     L4 = 4,
 }
 
+impl ApprovalLevel {
+    /// Next level up (None at board level)
+    pub fn next(&self) -> Option<ApprovalLevel> {
+        match self {
+            ApprovalLevel::L1 => Some(ApprovalLevel::L2),
+            ApprovalLevel::L2 => Some(ApprovalLevel::L3),
+            ApprovalLevel::L3 => Some(ApprovalLevel::L4),
+            ApprovalLevel::L4 => None,
+        }
+    }
+}
+
 impl PartialOrd for ApprovalLevel {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
@@ -62,22 +70,17 @@ impl Ord for ApprovalLevel {
     }
 }
 
-impl Default for ApprovalLevel {
-    fn default() -> Self {
-        ApprovalLevel::L1
-    }
-}
-
 /// AML Decision - Formal Lattice
 ///
 /// Ordering: Approved < Flagged < Blocked
 /// For Flagged decisions, higher ApprovalLevel = more restrictive
 ///
 /// Aggregation uses `max()` - most restrictive decision wins.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum AmlDecision {
     /// Transaction approved, continue (lowest in lattice)
+    #[default]
     Approved,
 
     /// Transaction flagged, requires manual review
@@ -160,12 +163,6 @@ impl Ord for AmlDecision {
     }
 }
 
-impl Default for AmlDecision {
-    fn default() -> Self {
-        AmlDecision::Approved
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -184,6 +181,13 @@ mod tests {
         assert!(ApprovalLevel::L3 < ApprovalLevel::L4);
     }
 
+    #[test]
+    fn test_approval_level_next() {
+        assert_eq!(ApprovalLevel::L1.next(), Some(ApprovalLevel::L2));
+        assert_eq!(ApprovalLevel::L3.next(), Some(ApprovalLevel::L4));
+        assert_eq!(ApprovalLevel::L4.next(), None);
+    }
+
     #[test]
     fn test_aml_decision_ordering() {
         let approved = AmlDecision::Approved;
@@ -2,12 +2,13 @@
 //!
 //! Coordinates rule evaluation, decision aggregation, and ledger writes.
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 
+use crate::case::{Case, CaseBook};
 use crate::config::ComplianceConfig;
 use crate::decision::{AmlDecision, ApprovalLevel, RiskScore};
-use crate::error::ComplianceResult;
+use crate::error::{ComplianceError, ComplianceResult};
 use crate::event::{ComplianceEvent, ReviewDecision};
 use crate::ledger::ComplianceLedger;
 use crate::state::ComplianceState;
@@ -71,8 +72,15 @@ impl ComplianceEngine {
         Self::new(ComplianceConfig::default(), ComplianceLedger::in_memory())
     }
 
-    /// Check a transaction against all rules
+    /// Check a transaction against all rules and record the decision
     pub fn check_transaction(&mut self, ctx: &TransactionContext) -> ComplianceResult<CheckResult> {
+        let result = self.evaluate(ctx);
+        self.record(ctx, &result)?;
+        Ok(result)
+    }
+
+    /// Evaluate all rules without recording anything
+    pub fn evaluate(&self, ctx: &TransactionContext) -> CheckResult {
         let mut decisions = Vec::new();
         let mut rules_triggered = Vec::new();
 
@@ -151,23 +159,40 @@ impl ComplianceEngine {
             AmlDecision::Approved => None,
         };
 
-        // Write to compliance ledger
+        CheckResult {
+            decision,
+            rules_triggered,
+            risk_score,
+        }
+    }
+
+    /// Record a decision in the Compliance Ledger
+    ///
+    /// Writes `CheckPerformed`, plus `TransactionFlagged` for flagged
+    /// decisions (its id is returned). Blocked transactions never reached
+    /// the ledger, so they don't count towards velocity.
+    pub fn record(
+        &mut self,
+        ctx: &TransactionContext,
+        result: &CheckResult,
+    ) -> ComplianceResult<Option<String>> {
         let event = ComplianceEvent::CheckPerformed {
             id: uuid::Uuid::new_v4().to_string(),
             correlation_id: ctx.correlation_id.clone(),
             user_id: ctx.user_id.clone(),
-            decision: decision.clone(),
-            rules_triggered: rules_triggered.clone(),
-            risk_score,
+            decision: result.decision.clone(),
+            rules_triggered: result.rules_triggered.clone(),
+            risk_score: result.risk_score,
             timestamp: Utc::now(),
         };
         self.ledger.append(&event)?;
 
-        // Update in-memory state
-        self.state.record_transaction(&ctx.user_id, &ctx.asset, ctx.amount);
+        if !result.decision.is_blocked() {
+            self.state.record_transaction(&ctx.user_id, &ctx.asset, ctx.amount);
+        }
 
         // If flagged, also write a TransactionFlagged event
-        if let AmlDecision::Flagged { reason, required_approval, .. } = &decision {
+        if let AmlDecision::Flagged { reason, required_approval, .. } = &result.decision {
             let expires_at = Utc::now() + self.config.review_expiry();
             let flag_event = ComplianceEvent::transaction_flagged(
                 &ctx.correlation_id,
@@ -177,13 +202,10 @@ impl ComplianceEngine {
                 expires_at,
             );
             self.ledger.append(&flag_event)?;
+            return Ok(Some(flag_event.id().to_string()));
         }
 
-        Ok(CheckResult {
-            decision,
-            rules_triggered,
-            risk_score,
-        })
+        Ok(None)
     }
 
     /// Record a review decision
@@ -199,11 +221,87 @@ impl ComplianceEngine {
         Ok(())
     }
 
+    /// Cases folded from the Compliance Ledger
+    pub fn cases(&self) -> ComplianceResult<CaseBook> {
+        Ok(CaseBook::from_events(&self.ledger.read_all()?))
+    }
+
+    /// Assign an open case to a reviewer
+    pub fn assign_case(&mut self, flag_id: &str, reviewer_id: &str, assigned_by: &str) -> ComplianceResult<Case> {
+        let mut cases = self.cases()?;
+        open_case(&cases, flag_id)?;
+
+        let event = ComplianceEvent::case_assigned(flag_id, reviewer_id, assigned_by);
+        self.ledger.append(&event)?;
+        cases.apply(&event);
+        Ok(cases.get(flag_id).cloned().expect("case exists"))
+    }
+
+    /// Approve or reject an open case
+    ///
+    /// Only the assignee can decide an assigned case.
+    pub fn resolve_case(
+        &mut self,
+        flag_id: &str,
+        decision: ReviewDecision,
+        reviewer_id: &str,
+        notes: &str,
+    ) -> ComplianceResult<Case> {
+        let mut cases = self.cases()?;
+        reviewable(&cases, flag_id, reviewer_id)?;
+
+        let event = ComplianceEvent::review_completed(flag_id, decision, reviewer_id, notes);
+        self.ledger.append(&event)?;
+        cases.apply(&event);
+        Ok(cases.get(flag_id).cloned().expect("case exists"))
+    }
+
+    /// Open case the reviewer may decide
+    pub fn reviewable_case(&self, flag_id: &str, reviewer_id: &str) -> ComplianceResult<Case> {
+        reviewable(&self.cases()?, flag_id, reviewer_id).cloned()
+    }
+
+    /// Escalate open cases past their expiry to the next approval level
+    ///
+    /// Each escalation gets a fresh `review_expiry` at the new level.
+    /// Board-level (L4) cases stay where they are.
+    pub fn escalate_expired(&mut self, now: DateTime<Utc>) -> ComplianceResult<Vec<Case>> {
+        let mut cases = self.cases()?;
+        let due: Vec<Case> = cases.escalation_due(now).into_iter().cloned().collect();
+
+        let mut escalated = Vec::new();
+        for case in due {
+            let Some(next) = case.required_approval.next() else {
+                continue;
+            };
+            let event = ComplianceEvent::case_escalated(
+                &case.flag_id,
+                case.required_approval,
+                next,
+                now + self.config.review_expiry(),
+            );
+            self.ledger.append(&event)?;
+            cases.apply(&event);
+            escalated.push(cases.get(&case.flag_id).cloned().expect("case exists"));
+        }
+        Ok(escalated)
+    }
+
+    /// Record an event raised outside transaction checks (e.g. rule set changes)
+    pub fn record_event(&mut self, event: &ComplianceEvent) -> ComplianceResult<()> {
+        self.ledger.append(event)
+    }
+
     /// Get the current configuration
     pub fn config(&self) -> &ComplianceConfig {
         &self.config
     }
 
+    /// Get the Compliance Ledger
+    pub fn ledger(&self) -> &ComplianceLedger {
+        &self.ledger
+    }
+
     /// Get the in-memory state (for queries)
     pub fn state(&self) -> &ComplianceState {
         &self.state
@@ -228,6 +326,26 @@ impl ComplianceEngine {
     }
 }
 
+/// A case that can still be acted on
+fn open_case<'a>(cases: &'a CaseBook, flag_id: &str) -> ComplianceResult<&'a Case> {
+    let case = cases
+        .get(flag_id)
+        .ok_or_else(|| ComplianceError::ReviewNotFound(flag_id.to_string()))?;
+    if !case.is_open() {
+        return Err(ComplianceError::ReviewAlreadyResolved(flag_id.to_string()));
+    }
+    Ok(case)
+}
+
+/// An open case that is unassigned or assigned to the reviewer
+fn reviewable<'a>(cases: &'a CaseBook, flag_id: &str, reviewer_id: &str) -> ComplianceResult<&'a Case> {
+    let case = open_case(cases, flag_id)?;
+    if let Some(assignee) = case.assigned_to.as_deref().filter(|a| *a != reviewer_id) {
+        return Err(ComplianceError::ReviewAssigned(flag_id.to_string(), assignee.to_string()));
+    }
+    Ok(case)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -361,6 +479,80 @@ mod tests {
         assert!(result.rules_triggered.len() >= 2);
     }
 
+    #[test]
+    fn test_record_writes_decisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = ComplianceLedger::new(dir.path().join("compliance.jsonl")).unwrap();
+        let mut engine = ComplianceEngine::new(ComplianceConfig::default(), ledger);
+
+        // Evaluating alone records nothing
+        let ctx = create_ctx(dec!(15000));
+        let result = engine.evaluate(&ctx);
+        assert!(result.decision.is_flagged());
+        assert!(engine.ledger().read_all().unwrap().is_empty());
+
+        let flag_id = engine.record(&ctx, &result).unwrap().unwrap();
+        let events = engine.ledger().read_all().unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], ComplianceEvent::CheckPerformed { .. }));
+        assert_eq!(events[1].id(), flag_id);
+
+        // Blocked transactions are recorded but don't count towards velocity
+        let blocked = CheckResult {
+            decision: AmlDecision::blocked("Sanctioned", "SANCTIONS_BLOCKED"),
+            rules_triggered: vec![],
+            risk_score: Some(RiskScore::Critical),
+        };
+        let ctx = TransactionContext {
+            user_id: "BLOCKED-USER".to_string(),
+            ..create_ctx(dec!(100))
+        };
+        assert!(engine.record(&ctx, &blocked).unwrap().is_none());
+        assert_eq!(engine.ledger().read_all().unwrap().len(), 3);
+        assert!(!engine.state().has_user("BLOCKED-USER"));
+    }
+
+    #[test]
+    fn test_case_workflow() {
+        let dir = tempfile::tempdir().unwrap();
+        let ledger = ComplianceLedger::new(dir.path().join("compliance.jsonl")).unwrap();
+        let config = ComplianceConfig {
+            review_expiry_hours: 0,
+            ..ComplianceConfig::default()
+        };
+        let mut engine = ComplianceEngine::new(config, ledger);
+
+        let ctx = create_ctx(dec!(15000));
+        let result = engine.evaluate(&ctx);
+        let flag_id = engine.record(&ctx, &result).unwrap().unwrap();
+        assert_eq!(engine.cases().unwrap().open(Some(ApprovalLevel::L1)).len(), 1);
+
+        // Expired at once: escalates one level per run
+        let escalated = engine.escalate_expired(Utc::now()).unwrap();
+        assert_eq!(escalated[0].required_approval, ApprovalLevel::L2);
+        assert_eq!(engine.cases().unwrap().open(Some(ApprovalLevel::L1)).len(), 0);
+
+        engine.assign_case(&flag_id, "officer-2", "lead").unwrap();
+        let err = engine
+            .resolve_case(&flag_id, ReviewDecision::Approved, "officer-9", "")
+            .unwrap_err();
+        assert!(matches!(err, ComplianceError::ReviewAssigned(_, ref a) if a == "officer-2"));
+
+        let case = engine
+            .resolve_case(&flag_id, ReviewDecision::Approved, "officer-2", "Payroll")
+            .unwrap();
+        assert_eq!(case.status, crate::case::CaseStatus::Approved);
+        assert!(matches!(
+            engine.resolve_case(&flag_id, ReviewDecision::Rejected, "officer-2", ""),
+            Err(ComplianceError::ReviewAlreadyResolved(_))
+        ));
+        assert!(matches!(
+            engine.assign_case("missing", "officer-1", "lead"),
+            Err(ComplianceError::ReviewNotFound(_))
+        ));
+        assert!(engine.escalate_expired(Utc::now()).unwrap().is_empty());
+    }
+
     #[test]
     fn test_record_review() {
         let mut engine = ComplianceEngine::in_memory();
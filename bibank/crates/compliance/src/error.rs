@@ -26,6 +26,15 @@ pub enum ComplianceError {
     #[error("User not found: {0}")]
     UserNotFound(String),
 
+    #[error("User already registered: {0}")]
+    UserExists(String),
+
+    #[error("User {0} is closed")]
+    UserClosed(String),
+
+    #[error("Invalid KYC tier {0} (0-{max})", max = crate::user::MAX_KYC_TIER)]
+    InvalidKycTier(u8),
+
     #[error("Transaction not found: {0}")]
     TransactionNotFound(String),
 
@@ -38,6 +47,9 @@ pub enum ComplianceError {
     #[error("Review expired: {0}")]
     ReviewExpired(String),
 
+    #[error("Review {0} is assigned to {1}")]
+    ReviewAssigned(String, String),
+
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
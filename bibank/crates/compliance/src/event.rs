@@ -3,10 +3,11 @@
 //! These events form the Decision Truth - separate from financial truth.
 //! All events are append-only and immutable.
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::decision::{AmlDecision, ApprovalLevel, RiskScore};
+use crate::user::UserStatus;
 
 /// Events appended to Compliance Ledger (append-only JSONL)
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +45,25 @@ pub enum ComplianceEvent {
         timestamp: DateTime<Utc>,
     },
 
+    /// Flagged transaction assigned to a reviewer
+    CaseAssigned {
+        id: String,
+        flag_id: String,
+        reviewer_id: String,
+        assigned_by: String,
+        timestamp: DateTime<Utc>,
+    },
+
+    /// Review expired and moved up to the next approval level
+    CaseEscalated {
+        id: String,
+        flag_id: String,
+        from_level: ApprovalLevel,
+        to_level: ApprovalLevel,
+        expires_at: DateTime<Utc>,
+        timestamp: DateTime<Utc>,
+    },
+
     /// Rule set activated/deactivated
     RuleSetChanged {
         id: String,
@@ -65,6 +85,18 @@ pub enum ComplianceEvent {
         performed_by: String,
         timestamp: DateTime<Utc>,
     },
+
+    /// User registered, or KYC tier/status changed (full record)
+    UserUpdated {
+        id: String,
+        user_id: String,
+        kyc_tier: u8,
+        status: UserStatus,
+        onboarded_at: NaiveDate,
+        reason: String,
+        performed_by: String,
+        timestamp: DateTime<Utc>,
+    },
 }
 
 /// Review decision outcomes
@@ -106,8 +138,11 @@ impl ComplianceEvent {
             ComplianceEvent::CheckPerformed { id, .. } => id,
             ComplianceEvent::TransactionFlagged { id, .. } => id,
             ComplianceEvent::ReviewCompleted { id, .. } => id,
+            ComplianceEvent::CaseAssigned { id, .. } => id,
+            ComplianceEvent::CaseEscalated { id, .. } => id,
             ComplianceEvent::RuleSetChanged { id, .. } => id,
             ComplianceEvent::WatchlistUpdated { id, .. } => id,
+            ComplianceEvent::UserUpdated { id, .. } => id,
         }
     }
 
@@ -117,8 +152,11 @@ impl ComplianceEvent {
             ComplianceEvent::CheckPerformed { timestamp, .. } => *timestamp,
             ComplianceEvent::TransactionFlagged { timestamp, .. } => *timestamp,
             ComplianceEvent::ReviewCompleted { timestamp, .. } => *timestamp,
+            ComplianceEvent::CaseAssigned { timestamp, .. } => *timestamp,
+            ComplianceEvent::CaseEscalated { timestamp, .. } => *timestamp,
             ComplianceEvent::RuleSetChanged { timestamp, .. } => *timestamp,
             ComplianceEvent::WatchlistUpdated { timestamp, .. } => *timestamp,
+            ComplianceEvent::UserUpdated { timestamp, .. } => *timestamp,
         }
     }
 
@@ -128,8 +166,11 @@ impl ComplianceEvent {
             ComplianceEvent::CheckPerformed { user_id, .. } => Some(user_id),
             ComplianceEvent::TransactionFlagged { user_id, .. } => Some(user_id),
             ComplianceEvent::ReviewCompleted { .. } => None,
+            ComplianceEvent::CaseAssigned { .. } => None,
+            ComplianceEvent::CaseEscalated { .. } => None,
             ComplianceEvent::RuleSetChanged { .. } => None,
             ComplianceEvent::WatchlistUpdated { user_id, .. } => Some(user_id),
+            ComplianceEvent::UserUpdated { user_id, .. } => Some(user_id),
         }
     }
 
@@ -186,6 +227,80 @@ impl ComplianceEvent {
             timestamp: Utc::now(),
         }
     }
+
+    /// Create a new CaseAssigned event
+    pub fn case_assigned(
+        flag_id: impl Into<String>,
+        reviewer_id: impl Into<String>,
+        assigned_by: impl Into<String>,
+    ) -> Self {
+        ComplianceEvent::CaseAssigned {
+            id: uuid::Uuid::new_v4().to_string(),
+            flag_id: flag_id.into(),
+            reviewer_id: reviewer_id.into(),
+            assigned_by: assigned_by.into(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Create a new CaseEscalated event
+    pub fn case_escalated(
+        flag_id: impl Into<String>,
+        from_level: ApprovalLevel,
+        to_level: ApprovalLevel,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        ComplianceEvent::CaseEscalated {
+            id: uuid::Uuid::new_v4().to_string(),
+            flag_id: flag_id.into(),
+            from_level,
+            to_level,
+            expires_at,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Create a new RuleSetChanged event
+    pub fn rule_set_changed(
+        rule_set_name: impl Into<String>,
+        rule_set_version: impl Into<String>,
+        rule_set_hash: impl Into<String>,
+        action: RuleAction,
+        performed_by: impl Into<String>,
+        approved_by: Vec<String>,
+    ) -> Self {
+        ComplianceEvent::RuleSetChanged {
+            id: uuid::Uuid::new_v4().to_string(),
+            rule_set_name: rule_set_name.into(),
+            rule_set_version: rule_set_version.into(),
+            rule_set_hash: rule_set_hash.into(),
+            action,
+            performed_by: performed_by.into(),
+            approved_by,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Create a new UserUpdated event
+    pub fn user_updated(
+        user_id: impl Into<String>,
+        kyc_tier: u8,
+        status: UserStatus,
+        onboarded_at: NaiveDate,
+        reason: impl Into<String>,
+        performed_by: impl Into<String>,
+    ) -> Self {
+        ComplianceEvent::UserUpdated {
+            id: uuid::Uuid::new_v4().to_string(),
+            user_id: user_id.into(),
+            kyc_tier,
+            status,
+            onboarded_at,
+            reason: reason.into(),
+            performed_by: performed_by.into(),
+            timestamp: Utc::now(),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -247,6 +362,24 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_rule_set_changed_serialization() {
+        let event = ComplianceEvent::rule_set_changed(
+            "AML_BASIC",
+            "2026-10",
+            "abc123",
+            RuleAction::Activated,
+            "ops",
+            vec!["alice".to_string()],
+        );
+
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("rule_set_changed"));
+        assert!(json.contains("\"action\":\"activated\""));
+        assert!(json.contains("abc123"));
+        assert_eq!(event.user_id(), None);
+    }
+
     #[test]
     fn test_event_accessors() {
         let event = ComplianceEvent::check_performed(
@@ -23,19 +23,30 @@
 //! - [`decision::AmlDecision`] - Formal lattice with `max()` aggregation
 //! - [`ledger::ComplianceLedger`] - Append-only JSONL ledger
 //! - [`engine::ComplianceEngine`] - Main orchestrator
+//! - [`case::CaseBook`] - Review cases folded from the ledger
+//! - [`report`] - CTR and SAR reports with reproducible input hashes
+//! - [`user::UserRegistry`] - KYC tiers and account status folded from the ledger
 
+pub mod case;
 pub mod config;
 pub mod decision;
 pub mod engine;
 pub mod error;
 pub mod event;
 pub mod ledger;
+pub mod report;
 pub mod state;
+pub mod user;
 
-pub use config::{ComplianceConfig, FailPolicy};
+pub use case::{Case, CaseBook, CaseStatus};
+pub use config::{ComplianceConfig, FailPolicy, KycLimits};
 pub use decision::{AmlDecision, ApprovalLevel, RiskScore};
 pub use engine::{CheckResult, ComplianceEngine};
 pub use error::ComplianceError;
 pub use event::{ComplianceEvent, ReviewDecision, RuleAction};
 pub use ledger::ComplianceLedger;
+pub use report::{
+    CtrBuilder, CtrReport, CtrRow, ReportFormat, ReportKind, SarBuilder, SarDraft, SarReport,
+};
 pub use state::ComplianceState;
+pub use user::{check_kyc_tier, UserRecord, UserRegistry, UserStatus, MAX_KYC_TIER};
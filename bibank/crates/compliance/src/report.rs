@@ -0,0 +1,637 @@
+//! Regulatory reports
+//!
+//! Two reports are built for a date range (inclusive, UTC days):
+//!
+//! - **CTR** (Currency Transaction Report): per user, asset and day, the
+//!   customer funds moved in and out, when either side reaches the CTR
+//!   threshold.
+//! - **SAR** (Suspicious Activity Report) drafts: every case opened in the
+//!   range, with the rules that fired and the journal entries linked to the
+//!   flagged transaction (the transaction itself, its review lock and its
+//!   release or reversal).
+//!
+//! Builders are fed journal entries one at a time, so the journal can be
+//! streamed. Each report carries `input_hash`, a SHA256 over its parameters
+//! and every input it read (journal entry hashes, compliance events), so a
+//! report can be regenerated later and checked against the original.
+//! Nothing time-dependent goes into a report: the same inputs produce the
+//! same bytes.
+
+use std::collections::{BTreeMap, HashMap};
+
+use bibank_ledger::{AccountCategory, JournalEntry, Posting, Side, TransactionIntent};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::case::{Case, CaseBook};
+use crate::decision::RiskScore;
+use crate::event::ComplianceEvent;
+
+/// Intents that move customer funds
+const REPORTED_INTENTS: [TransactionIntent; 3] = [
+    TransactionIntent::Deposit,
+    TransactionIntent::Withdrawal,
+    TransactionIntent::Transfer,
+];
+
+/// Metadata keys marking entries made by the compliance workflow itself
+const COMPLIANCE_METADATA: [&str; 2] = ["compliance_lock", "compliance_case"];
+
+/// Report types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportKind {
+    /// Currency Transaction Report
+    Ctr,
+    /// Suspicious Activity Report drafts
+    Sar,
+}
+
+impl ReportKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReportKind::Ctr => "ctr",
+            ReportKind::Sar => "sar",
+        }
+    }
+}
+
+/// Output formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+/// Direction of customer funds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FundsDirection {
+    /// Credited to the user
+    In,
+    /// Debited from the user
+    Out,
+}
+
+/// One customer's side of a reported transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportTransaction {
+    pub sequence: u64,
+    pub timestamp: DateTime<Utc>,
+    pub correlation_id: String,
+    pub intent: TransactionIntent,
+    pub user_id: String,
+    pub asset: String,
+    pub amount: Decimal,
+    pub direction: FundsDirection,
+}
+
+impl ReportTransaction {
+    /// Customer fund movements of an entry
+    ///
+    /// Empty for intents that don't move customer funds and for the review
+    /// lock/release/reversal entries, which only shuffle already-reported
+    /// funds.
+    pub fn from_entry(entry: &JournalEntry) -> Vec<Self> {
+        if !is_reported(entry) {
+            return Vec::new();
+        }
+        entry
+            .postings
+            .iter()
+            .filter(|p| is_user_funds(p))
+            .map(|p| ReportTransaction {
+                sequence: entry.sequence,
+                timestamp: entry.timestamp,
+                correlation_id: entry.correlation_id.clone(),
+                intent: entry.intent,
+                user_id: p.account.id.clone(),
+                asset: p.account.asset.clone(),
+                amount: p.amount.value(),
+                direction: match p.side {
+                    Side::Credit => FundsDirection::In,
+                    Side::Debit => FundsDirection::Out,
+                },
+            })
+            .collect()
+    }
+}
+
+/// Daily totals of one user in one asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CtrRow {
+    pub date: NaiveDate,
+    pub user_id: String,
+    pub asset: String,
+    pub cash_in: Decimal,
+    pub cash_out: Decimal,
+    pub transactions: Vec<ReportTransaction>,
+}
+
+impl CtrRow {
+    /// Check if either side reaches the threshold
+    pub fn is_reportable(&self, threshold: Decimal) -> bool {
+        self.cash_in >= threshold || self.cash_out >= threshold
+    }
+}
+
+/// Currency Transaction Report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CtrReport {
+    pub kind: ReportKind,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub threshold: Decimal,
+    pub input_hash: String,
+    /// Reportable rows, by date, user and asset
+    pub rows: Vec<CtrRow>,
+}
+
+impl CtrReport {
+    /// Render as CSV (one row per user, asset and day)
+    pub fn to_csv(&self) -> String {
+        let mut out = csv_preamble(self.kind, self.from, self.to, &self.input_hash);
+        out.push_str(&format!("# threshold: {}\n", self.threshold));
+        csv_row(
+            &mut out,
+            &["date", "user_id", "asset", "cash_in", "cash_out", "transaction_count", "sequences"],
+        );
+        for row in &self.rows {
+            csv_row(
+                &mut out,
+                &[
+                    &row.date.to_string(),
+                    &row.user_id,
+                    &row.asset,
+                    &row.cash_in.to_string(),
+                    &row.cash_out.to_string(),
+                    &row.transactions.len().to_string(),
+                    &join(row.transactions.iter().map(|t| t.sequence)),
+                ],
+            );
+        }
+        out
+    }
+}
+
+/// Builds a CTR from journal entries
+pub struct CtrBuilder {
+    from: NaiveDate,
+    to: NaiveDate,
+    threshold: Decimal,
+    hasher: Sha256,
+    rows: BTreeMap<(NaiveDate, String, String), CtrRow>,
+}
+
+impl CtrBuilder {
+    /// Start a report for `from..=to`
+    pub fn new(from: NaiveDate, to: NaiveDate, threshold: Decimal) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(format!("ctr|{}|{}|{}\n", from, to, threshold));
+        Self {
+            from,
+            to,
+            threshold,
+            hasher,
+            rows: BTreeMap::new(),
+        }
+    }
+
+    /// Feed a journal entry (entries outside the range are ignored)
+    pub fn add(&mut self, entry: &JournalEntry) {
+        if !in_range(entry.timestamp, self.from, self.to) {
+            return;
+        }
+        let transactions = ReportTransaction::from_entry(entry);
+        if transactions.is_empty() {
+            return;
+        }
+        self.hasher.update(format!("{}\n", entry.hash));
+
+        let date = entry.timestamp.date_naive();
+        for tx in transactions {
+            let row = self
+                .rows
+                .entry((date, tx.user_id.clone(), tx.asset.clone()))
+                .or_insert_with(|| CtrRow {
+                    date,
+                    user_id: tx.user_id.clone(),
+                    asset: tx.asset.clone(),
+                    cash_in: Decimal::ZERO,
+                    cash_out: Decimal::ZERO,
+                    transactions: Vec::new(),
+                });
+            match tx.direction {
+                FundsDirection::In => row.cash_in += tx.amount,
+                FundsDirection::Out => row.cash_out += tx.amount,
+            }
+            row.transactions.push(tx);
+        }
+    }
+
+    /// Finish the report
+    pub fn finish(self) -> CtrReport {
+        let threshold = self.threshold;
+        CtrReport {
+            kind: ReportKind::Ctr,
+            from: self.from,
+            to: self.to,
+            threshold,
+            input_hash: hex::encode(self.hasher.finalize()),
+            rows: self
+                .rows
+                .into_values()
+                .filter(|row| row.is_reportable(threshold))
+                .collect(),
+        }
+    }
+}
+
+/// Journal entry linked to a SAR draft
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportEntry {
+    pub sequence: u64,
+    pub hash: String,
+    pub timestamp: DateTime<Utc>,
+    pub correlation_id: String,
+    pub intent: TransactionIntent,
+    pub postings: Vec<Posting>,
+}
+
+impl From<&JournalEntry> for ReportEntry {
+    fn from(entry: &JournalEntry) -> Self {
+        Self {
+            sequence: entry.sequence,
+            hash: entry.hash.clone(),
+            timestamp: entry.timestamp,
+            correlation_id: entry.correlation_id.clone(),
+            intent: entry.intent,
+            postings: entry.postings.clone(),
+        }
+    }
+}
+
+/// Draft SAR for one case
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SarDraft {
+    pub case: Case,
+    /// Rules that fired on the flagged transaction
+    pub rules_triggered: Vec<String>,
+    pub risk_score: Option<RiskScore>,
+    /// The flagged transaction and the entries it caused, by sequence
+    pub entries: Vec<ReportEntry>,
+}
+
+/// Suspicious Activity Report drafts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SarReport {
+    pub kind: ReportKind,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub input_hash: String,
+    /// Drafts, by case opening time
+    pub drafts: Vec<SarDraft>,
+}
+
+impl SarReport {
+    /// Render as CSV (one row per case)
+    pub fn to_csv(&self) -> String {
+        let mut out = csv_preamble(self.kind, self.from, self.to, &self.input_hash);
+        csv_row(
+            &mut out,
+            &[
+                "flag_id",
+                "correlation_id",
+                "user_id",
+                "opened_at",
+                "reason",
+                "rules_triggered",
+                "risk_score",
+                "required_approval",
+                "status",
+                "resolved_by",
+                "notes",
+                "sequences",
+            ],
+        );
+        for draft in &self.drafts {
+            let case = &draft.case;
+            csv_row(
+                &mut out,
+                &[
+                    &case.flag_id,
+                    &case.correlation_id,
+                    &case.user_id,
+                    &case.opened_at.to_rfc3339(),
+                    &case.reason,
+                    &draft.rules_triggered.join(";"),
+                    &draft.risk_score.map(|s| enum_str(&s)).unwrap_or_default(),
+                    &enum_str(&case.required_approval),
+                    &enum_str(&case.status),
+                    case.resolved_by.as_deref().unwrap_or_default(),
+                    case.notes.as_deref().unwrap_or_default(),
+                    &join(draft.entries.iter().map(|e| e.sequence)),
+                ],
+            );
+        }
+        out
+    }
+}
+
+/// Builds SAR drafts from the Compliance Ledger and journal entries
+pub struct SarBuilder {
+    from: NaiveDate,
+    to: NaiveDate,
+    hasher: Sha256,
+    drafts: Vec<SarDraft>,
+    /// Correlation id of each flagged transaction -> index in `drafts`
+    by_correlation_id: HashMap<String, usize>,
+}
+
+impl SarBuilder {
+    /// Start drafts for cases opened in `from..=to`
+    pub fn new(from: NaiveDate, to: NaiveDate, events: &[ComplianceEvent]) -> Self {
+        let book = CaseBook::from_events(events);
+        let mut cases: Vec<&Case> = book
+            .all()
+            .filter(|c| in_range(c.opened_at, from, to))
+            .collect();
+        cases.sort_by(|a, b| (a.opened_at, &a.flag_id).cmp(&(b.opened_at, &b.flag_id)));
+
+        let mut hasher = Sha256::new();
+        hasher.update(format!("sar|{}|{}\n", from, to));
+
+        let mut drafts = Vec::with_capacity(cases.len());
+        let mut by_correlation_id = HashMap::new();
+        for case in cases {
+            let mut draft = SarDraft {
+                case: case.clone(),
+                rules_triggered: Vec::new(),
+                risk_score: None,
+                entries: Vec::new(),
+            };
+            for event in events.iter().filter(|e| concerns(e, case)) {
+                hasher.update(serde_json::to_string(event).expect("event serializes"));
+                hasher.update("\n");
+                if let ComplianceEvent::CheckPerformed {
+                    rules_triggered,
+                    risk_score,
+                    ..
+                } = event
+                {
+                    for rule in rules_triggered {
+                        if !draft.rules_triggered.contains(rule) {
+                            draft.rules_triggered.push(rule.clone());
+                        }
+                    }
+                    draft.risk_score = draft.risk_score.max(*risk_score);
+                }
+            }
+            by_correlation_id.insert(case.correlation_id.clone(), drafts.len());
+            drafts.push(draft);
+        }
+
+        Self {
+            from,
+            to,
+            hasher,
+            drafts,
+            by_correlation_id,
+        }
+    }
+
+    /// Feed a journal entry, kept if it is (or was caused by) a flagged
+    /// transaction
+    pub fn add(&mut self, entry: &JournalEntry) {
+        let index = self
+            .by_correlation_id
+            .get(&entry.correlation_id)
+            .or_else(|| {
+                entry
+                    .causality_id
+                    .as_ref()
+                    .and_then(|cid| self.by_correlation_id.get(cid))
+            });
+        if let Some(&index) = index {
+            self.hasher.update(format!("{}\n", entry.hash));
+            self.drafts[index].entries.push(entry.into());
+        }
+    }
+
+    /// Finish the report
+    pub fn finish(self) -> SarReport {
+        SarReport {
+            kind: ReportKind::Sar,
+            from: self.from,
+            to: self.to,
+            input_hash: hex::encode(self.hasher.finalize()),
+            drafts: self.drafts,
+        }
+    }
+}
+
+fn is_reported(entry: &JournalEntry) -> bool {
+    REPORTED_INTENTS.contains(&entry.intent)
+        && !COMPLIANCE_METADATA
+            .iter()
+            .any(|key| entry.metadata.contains_key(*key))
+}
+
+fn is_user_funds(posting: &Posting) -> bool {
+    posting.account.category == AccountCategory::Liability
+        && posting.account.segment == "USER"
+        && posting.account.sub_account == "AVAILABLE"
+}
+
+fn in_range(timestamp: DateTime<Utc>, from: NaiveDate, to: NaiveDate) -> bool {
+    let date = timestamp.date_naive();
+    from <= date && date <= to
+}
+
+/// Check if a compliance event belongs to a case
+fn concerns(event: &ComplianceEvent, case: &Case) -> bool {
+    match event {
+        ComplianceEvent::CheckPerformed { correlation_id, .. }
+        | ComplianceEvent::TransactionFlagged { correlation_id, .. } => {
+            *correlation_id == case.correlation_id
+        }
+        ComplianceEvent::ReviewCompleted { flag_id, .. }
+        | ComplianceEvent::CaseAssigned { flag_id, .. }
+        | ComplianceEvent::CaseEscalated { flag_id, .. } => *flag_id == case.flag_id,
+        _ => false,
+    }
+}
+
+fn csv_preamble(kind: ReportKind, from: NaiveDate, to: NaiveDate, input_hash: &str) -> String {
+    format!(
+        "# report: {}\n# from: {}\n# to: {}\n# input_hash: {}\n",
+        kind.as_str(),
+        from,
+        to,
+        input_hash
+    )
+}
+
+/// Append one CSV record, quoting fields as RFC 4180 requires
+fn csv_row(out: &mut String, fields: &[&str]) {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect();
+    out.push_str(&fields.join(","));
+    out.push('\n');
+}
+
+fn join(sequences: impl Iterator<Item = u64>) -> String {
+    sequences.map(|s| s.to_string()).collect::<Vec<_>>().join(";")
+}
+
+/// Serde name of a unit enum variant
+fn enum_str<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decision::{AmlDecision, ApprovalLevel};
+    use crate::event::ReviewDecision;
+    use bibank_core::Amount;
+    use bibank_ledger::AccountKey;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    fn entry(
+        sequence: u64,
+        intent: TransactionIntent,
+        correlation_id: &str,
+        postings: Vec<Posting>,
+    ) -> JournalEntry {
+        JournalEntry {
+            sequence,
+            prev_hash: format!("hash-{}", sequence - 1),
+            hash: format!("hash-{}", sequence),
+            timestamp: "2026-10-01T12:00:00Z".parse().unwrap(),
+            intent,
+            correlation_id: correlation_id.to_string(),
+            causality_id: None,
+            postings,
+            metadata: HashMap::new(),
+            signatures: Vec::new(),
+        }
+    }
+
+    fn deposit(sequence: u64, user: &str, amount: Decimal) -> JournalEntry {
+        let amount = Amount::new(amount).unwrap();
+        entry(
+            sequence,
+            TransactionIntent::Deposit,
+            &format!("TX-{}", sequence),
+            vec![
+                Posting::debit(AccountKey::system_vault("USDT"), amount),
+                Posting::credit(AccountKey::user_available(user, "USDT"), amount),
+            ],
+        )
+    }
+
+    fn day(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_ctr_aggregates_per_user_and_day() {
+        let mut builder = CtrBuilder::new(day("2026-10-01"), day("2026-10-01"), dec!(10000));
+        builder.add(&deposit(1, "ALICE", dec!(6000)));
+        builder.add(&deposit(2, "ALICE", dec!(5000)));
+        builder.add(&deposit(3, "BOB", dec!(9000)));
+
+        let mut lock = deposit(4, "ALICE", dec!(5000));
+        lock.metadata.insert("compliance_lock".to_string(), json!(true));
+        builder.add(&lock);
+
+        let mut outside = deposit(5, "BOB", dec!(50000));
+        outside.timestamp = "2026-10-02T00:00:00Z".parse().unwrap();
+        builder.add(&outside);
+
+        let report = builder.finish();
+        assert_eq!(report.rows.len(), 1);
+        let row = &report.rows[0];
+        assert_eq!(row.user_id, "ALICE");
+        assert_eq!(row.cash_in, dec!(11000));
+        assert_eq!(row.cash_out, Decimal::ZERO);
+        assert_eq!(row.transactions.len(), 2);
+
+        let csv = report.to_csv();
+        assert!(csv.contains(&format!("# input_hash: {}", report.input_hash)));
+        assert!(csv.contains("2026-10-01,ALICE,USDT,11000,0,2,1;2\n"));
+    }
+
+    #[test]
+    fn test_ctr_input_hash_is_reproducible() {
+        let build = |threshold| {
+            let mut builder = CtrBuilder::new(day("2026-10-01"), day("2026-10-31"), threshold);
+            builder.add(&deposit(1, "ALICE", dec!(6000)));
+            builder.finish()
+        };
+        assert_eq!(build(dec!(1000)), build(dec!(1000)));
+        assert_ne!(build(dec!(1000)).input_hash, build(dec!(2000)).input_hash);
+    }
+
+    #[test]
+    fn test_sar_drafts_link_entries_and_rules() {
+        let flagged = deposit(1, "ALICE", dec!(50000));
+        let mut check = ComplianceEvent::check_performed(
+            "TX-1",
+            "ALICE",
+            AmlDecision::flagged("Large", RiskScore::High, ApprovalLevel::L2),
+            vec!["LARGE_TX".to_string()],
+        );
+        if let ComplianceEvent::CheckPerformed { risk_score, .. } = &mut check {
+            *risk_score = Some(RiskScore::High);
+        }
+        let flag = ComplianceEvent::transaction_flagged(
+            "TX-1",
+            "ALICE",
+            "Large, \"round\" amount",
+            ApprovalLevel::L2,
+            Utc::now(),
+        );
+        let flag_id = flag.id().to_string();
+        let review = ComplianceEvent::review_completed(&flag_id, ReviewDecision::Rejected, "officer", "No");
+        let events = vec![check, flag, review];
+
+        let today = Utc::now().date_naive();
+        let mut builder = SarBuilder::new(today, today, &events);
+        builder.add(&flagged);
+        builder.add(&deposit(2, "BOB", dec!(10)));
+        let mut reversal = deposit(3, "ALICE", dec!(50000));
+        reversal.correlation_id = "review-reversal-TX-1".to_string();
+        reversal.causality_id = Some("TX-1".to_string());
+        builder.add(&reversal);
+
+        let report = builder.finish();
+        assert_eq!(report.drafts.len(), 1);
+        let draft = &report.drafts[0];
+        assert_eq!(draft.case.status, crate::case::CaseStatus::Rejected);
+        assert_eq!(draft.rules_triggered, vec!["LARGE_TX"]);
+        assert_eq!(draft.risk_score, Some(RiskScore::High));
+        let sequences: Vec<u64> = draft.entries.iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![1, 3]);
+
+        let csv = report.to_csv();
+        assert!(csv.contains("\"Large, \"\"round\"\" amount\",LARGE_TX,high,l2,rejected,officer,No,1;3\n"));
+    }
+}
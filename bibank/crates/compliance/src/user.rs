@@ -0,0 +1,178 @@
+//! User registry folded from the Compliance Ledger
+//!
+//! Every change to a user's KYC tier or status is recorded as a
+//! `UserUpdated` event carrying the full record, so the registry is the
+//! last event per user.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::error::ComplianceError;
+use crate::event::ComplianceEvent;
+
+/// Highest KYC tier
+pub const MAX_KYC_TIER: u8 = 4;
+
+/// Reject tiers above [`MAX_KYC_TIER`]
+pub fn check_kyc_tier(tier: u8) -> Result<(), ComplianceError> {
+    if tier > MAX_KYC_TIER {
+        return Err(ComplianceError::InvalidKycTier(tier));
+    }
+    Ok(())
+}
+
+/// Account status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserStatus {
+    #[default]
+    Active,
+    /// No debits from the user's accounts
+    Frozen,
+    /// Offboarded; no debits from the user's accounts
+    Closed,
+}
+
+impl UserStatus {
+    /// Whether debits from the user's accounts are refused
+    pub fn blocks_debits(&self) -> bool {
+        !matches!(self, UserStatus::Active)
+    }
+}
+
+impl std::fmt::Display for UserStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UserStatus::Active => write!(f, "active"),
+            UserStatus::Frozen => write!(f, "frozen"),
+            UserStatus::Closed => write!(f, "closed"),
+        }
+    }
+}
+
+impl FromStr for UserStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "active" => Ok(UserStatus::Active),
+            "frozen" => Ok(UserStatus::Frozen),
+            "closed" => Ok(UserStatus::Closed),
+            _ => Err(format!("Unknown user status: {} (use active, frozen or closed)", s)),
+        }
+    }
+}
+
+/// A registered user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserRecord {
+    pub user_id: String,
+    /// KYC tier (0 = unverified, up to [`MAX_KYC_TIER`])
+    pub kyc_tier: u8,
+    pub status: UserStatus,
+    pub onboarded_at: NaiveDate,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl UserRecord {
+    /// Days since onboarding
+    pub fn account_age_days(&self, today: NaiveDate) -> i64 {
+        (today - self.onboarded_at).num_days()
+    }
+}
+
+/// Registered users by ID
+#[derive(Debug, Clone, Default)]
+pub struct UserRegistry {
+    users: BTreeMap<String, UserRecord>,
+}
+
+impl UserRegistry {
+    /// Fold the registry from Compliance Ledger events
+    pub fn from_events(events: &[ComplianceEvent]) -> Self {
+        let mut registry = Self::default();
+        for event in events {
+            registry.apply(event);
+        }
+        registry
+    }
+
+    /// Apply a single event (anything but `UserUpdated` is ignored)
+    pub fn apply(&mut self, event: &ComplianceEvent) {
+        if let ComplianceEvent::UserUpdated {
+            user_id,
+            kyc_tier,
+            status,
+            onboarded_at,
+            timestamp,
+            ..
+        } = event
+        {
+            self.users.insert(
+                user_id.clone(),
+                UserRecord {
+                    user_id: user_id.clone(),
+                    kyc_tier: *kyc_tier,
+                    status: *status,
+                    onboarded_at: *onboarded_at,
+                    updated_at: *timestamp,
+                },
+            );
+        }
+    }
+
+    /// Get a user
+    pub fn get(&self, user_id: &str) -> Option<&UserRecord> {
+        self.users.get(user_id)
+    }
+
+    /// All users, by ID
+    pub fn users(&self) -> impl Iterator<Item = &UserRecord> {
+        self.users.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.users.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.users.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_keeps_latest_record() {
+        let onboarded = NaiveDate::from_ymd_opt(2026, 1, 10).unwrap();
+        let events = vec![
+            ComplianceEvent::user_updated("ALICE", 1, UserStatus::Active, onboarded, "onboarding", "ops"),
+            ComplianceEvent::user_updated("BOB", 2, UserStatus::Active, onboarded, "onboarding", "ops"),
+            ComplianceEvent::user_updated("ALICE", 1, UserStatus::Frozen, onboarded, "fraud report", "ops"),
+        ];
+
+        let registry = UserRegistry::from_events(&events);
+        assert_eq!(registry.len(), 2);
+
+        let alice = registry.get("ALICE").unwrap();
+        assert_eq!(alice.status, UserStatus::Frozen);
+        assert!(alice.status.blocks_debits());
+        assert_eq!(alice.account_age_days(NaiveDate::from_ymd_opt(2026, 2, 9).unwrap()), 30);
+        assert!(!registry.get("BOB").unwrap().status.blocks_debits());
+        assert_eq!("closed".parse::<UserStatus>().unwrap(), UserStatus::Closed);
+    }
+
+    #[test]
+    fn test_check_kyc_tier() {
+        assert!(check_kyc_tier(0).is_ok());
+        assert!(check_kyc_tier(MAX_KYC_TIER).is_ok());
+        assert!(matches!(
+            check_kyc_tier(MAX_KYC_TIER + 1),
+            Err(ComplianceError::InvalidKycTier(5))
+        ));
+    }
+}
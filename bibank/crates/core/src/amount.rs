@@ -3,6 +3,8 @@
 //! All financial amounts in BiBank MUST be non-negative.
 //! This is enforced at the type level.
 
+use crate::currency::Currency;
+use crate::rounding::RoundingMode;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -13,6 +15,13 @@ use thiserror::Error;
 pub enum AmountError {
     #[error("Amount cannot be negative: {0}")]
     NegativeAmount(Decimal),
+
+    #[error("Amount {value} has more than {scale} decimal places allowed for {currency}")]
+    ExcessPrecision {
+        value: Decimal,
+        currency: String,
+        scale: u32,
+    },
 }
 
 /// A non-negative decimal amount for financial operations.
@@ -51,6 +60,35 @@ impl Amount {
         }
     }
 
+    /// Create an Amount of `currency`, rejecting digits below its minimum unit
+    pub fn of(value: Decimal, currency: &Currency) -> Result<Self, AmountError> {
+        let amount = Self::new(value)?;
+        amount.check_scale(currency)?;
+        Ok(amount)
+    }
+
+    /// Create an Amount of `currency`, rounding to its scale
+    pub fn rounded(
+        value: Decimal,
+        currency: &Currency,
+        mode: RoundingMode,
+    ) -> Result<Self, AmountError> {
+        Self::new(currency.round(value, mode))
+    }
+
+    /// Check the amount is a whole number of `currency`'s minimum units
+    pub fn check_scale(&self, currency: &Currency) -> Result<(), AmountError> {
+        if currency.fits(self.0) {
+            Ok(())
+        } else {
+            Err(AmountError::ExcessPrecision {
+                value: self.0,
+                currency: currency.code().to_string(),
+                scale: currency.scale(),
+            })
+        }
+    }
+
     /// Create an Amount without validation.
     ///
     /// # Safety
@@ -152,6 +190,33 @@ mod tests {
         assert_eq!(result.value(), Decimal::new(70, 0));
     }
 
+    #[test]
+    fn test_amount_of_currency() {
+        assert!(Amount::of(Decimal::new(1050, 2), &Currency::Usd).is_ok());
+        assert!(matches!(
+            Amount::of(Decimal::new(10505, 3), &Currency::Usd),
+            Err(AmountError::ExcessPrecision { scale: 2, .. })
+        ));
+        assert!(matches!(
+            Amount::of(Decimal::new(-1, 0), &Currency::Usd),
+            Err(AmountError::NegativeAmount(_))
+        ));
+    }
+
+    #[test]
+    fn test_amount_rounded() {
+        let amount =
+            Amount::rounded(Decimal::new(10509, 3), &Currency::Usd, RoundingMode::Floor).unwrap();
+        assert_eq!(amount.value(), Decimal::new(1050, 2));
+        let amount = Amount::rounded(
+            Decimal::new(123456789, 9),
+            &Currency::Btc,
+            RoundingMode::Bankers,
+        )
+        .unwrap();
+        assert_eq!(amount.value(), Decimal::new(12345679, 8));
+    }
+
     #[test]
     fn test_serde_roundtrip() {
         let amount = Amount::new(Decimal::new(12345, 2)).unwrap(); // 123.45
@@ -2,12 +2,30 @@
 //!
 //! Instead of raw strings, we use an enum for common currencies
 //! and a fallback for custom tokens.
+//!
+//! Each currency has a scale - the decimal places its amounts may carry.
+//! Built-in currencies have fixed scales; `Other` tokens are registered
+//! with [`Currency::register`] and default to [`DEFAULT_TOKEN_SCALE`].
 
+use crate::rounding::RoundingMode;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
+use std::sync::{LazyLock, RwLock};
 use thiserror::Error;
 
+/// Scale of `Other` tokens that were never registered
+pub const DEFAULT_TOKEN_SCALE: u32 = 18;
+
+/// Largest scale a `Decimal` can hold
+pub const MAX_SCALE: u32 = 28;
+
+/// Scales of registered `Other` tokens
+static TOKEN_SCALES: LazyLock<RwLock<HashMap<String, u32>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
 /// Errors that can occur when parsing currencies
 #[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum CurrencyError {
@@ -19,6 +37,12 @@ pub enum CurrencyError {
 
     #[error("Invalid currency code format: {0}")]
     InvalidFormat(String),
+
+    #[error("Scale of built-in currency {0} can't be changed")]
+    BuiltIn(String),
+
+    #[error("Scale {0} exceeds the maximum of 28 decimal places")]
+    ScaleTooLarge(u32),
 }
 
 /// Currency/Asset codes
@@ -154,6 +178,59 @@ impl Currency {
                 | Currency::Ltc
         )
     }
+
+    /// Decimal places amounts of this currency may carry
+    pub fn scale(&self) -> u32 {
+        match self {
+            Currency::Usd | Currency::Eur | Currency::Gbp => 2,
+            Currency::Jpy | Currency::Vnd => 0,
+            Currency::Btc | Currency::Ltc | Currency::Doge => 8,
+            Currency::Usdt | Currency::Usdc | Currency::Xrp | Currency::Ada => 6,
+            Currency::Sol => 9,
+            Currency::Eth | Currency::Bnb | Currency::Matic | Currency::Busd | Currency::Dai => 18,
+            Currency::Other(code) => TOKEN_SCALES
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .get(code)
+                .copied()
+                .unwrap_or(DEFAULT_TOKEN_SCALE),
+        }
+    }
+
+    /// Smallest representable amount (10^-scale)
+    pub fn min_unit(&self) -> Decimal {
+        Decimal::new(1, self.scale())
+    }
+
+    /// Check that `value` is a whole number of minimum units
+    pub fn fits(&self, value: Decimal) -> bool {
+        value.round_dp(self.scale()) == value
+    }
+
+    /// Round `value` to this currency's scale
+    pub fn round(&self, value: Decimal, mode: RoundingMode) -> Decimal {
+        mode.round(value, self.scale())
+    }
+
+    /// Register the scale of a custom token
+    ///
+    /// Re-registering replaces the previous scale; built-in currencies
+    /// are rejected.
+    pub fn register(code: &str, scale: u32) -> Result<Currency, CurrencyError> {
+        if scale > MAX_SCALE {
+            return Err(CurrencyError::ScaleTooLarge(scale));
+        }
+        match code.parse()? {
+            Currency::Other(code) => {
+                TOKEN_SCALES
+                    .write()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .insert(code.clone(), scale);
+                Ok(Currency::Other(code))
+            }
+            builtin => Err(CurrencyError::BuiltIn(builtin.code().to_string())),
+        }
+    }
 }
 
 impl fmt::Display for Currency {
@@ -316,6 +393,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_builtin_scales() {
+        assert_eq!(Currency::Usd.scale(), 2);
+        assert_eq!(Currency::Jpy.scale(), 0);
+        assert_eq!(Currency::Btc.scale(), 8);
+        assert_eq!(Currency::Eth.scale(), 18);
+        assert_eq!(Currency::Btc.min_unit(), Decimal::new(1, 8));
+        assert_eq!(Currency::Vnd.min_unit(), Decimal::ONE);
+    }
+
+    #[test]
+    fn test_fits_and_round() {
+        assert!(Currency::Usd.fits(Decimal::new(1050, 2)));
+        assert!(Currency::Usd.fits(Decimal::new(10500, 3)));
+        assert!(!Currency::Usd.fits(Decimal::new(10505, 3)));
+        assert!(!Currency::Jpy.fits(Decimal::new(15, 1)));
+
+        assert_eq!(
+            Currency::Jpy.round(Decimal::new(25, 1), RoundingMode::Bankers),
+            Decimal::new(2, 0)
+        );
+        assert_eq!(
+            Currency::Usd.round(Decimal::new(10509, 3), RoundingMode::Floor),
+            Decimal::new(1050, 2)
+        );
+    }
+
+    #[test]
+    fn test_register_token() {
+        let token = Currency::register("scaletest", 4).unwrap();
+        assert_eq!(token, Currency::Other("SCALETEST".to_string()));
+        assert_eq!(token.scale(), 4);
+        assert_eq!(Currency::from("SCALETEST").min_unit(), Decimal::new(1, 4));
+
+        assert_eq!(
+            Currency::Other("UNREGISTERED".to_string()).scale(),
+            DEFAULT_TOKEN_SCALE
+        );
+        assert!(matches!(
+            Currency::register("USD", 4),
+            Err(CurrencyError::BuiltIn(_))
+        ));
+        assert!(matches!(
+            Currency::register("BIGSCALE", 29),
+            Err(CurrencyError::ScaleTooLarge(29))
+        ));
+    }
+
     #[test]
     fn test_from_str_trait() {
         let currency: Currency = "ETH".into();
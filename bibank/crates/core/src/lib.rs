@@ -2,10 +2,13 @@
 //!
 //! This crate contains the fundamental types used across BiBank:
 //! - `Amount`: Non-negative decimal wrapper for financial amounts
-//! - `Currency`: Type-safe currency/asset codes
+//! - `Currency`: Type-safe currency/asset codes with per-currency scale
+//! - `RoundingMode`: Rounding of computed amounts to a currency's scale
 
 pub mod amount;
 pub mod currency;
+pub mod rounding;
 
-pub use amount::Amount;
-pub use currency::Currency;
+pub use amount::{Amount, AmountError};
+pub use currency::{Currency, CurrencyError};
+pub use rounding::RoundingMode;
@@ -0,0 +1,92 @@
+//! Rounding - How computed amounts are cut to a currency's scale
+//!
+//! Interest accrual rounds half-to-even so errors don't drift one way
+//! over many accruals; order locks, fills and fees round down so a user
+//! is never charged or locked a fraction more than they hold.
+
+use rust_decimal::{Decimal, RoundingStrategy};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Rounding mode for computed amounts
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoundingMode {
+    /// Round half to even (banker's rounding)
+    #[default]
+    Bankers,
+
+    /// Round toward negative infinity
+    Floor,
+}
+
+impl RoundingMode {
+    /// Round `value` to `scale` decimal places
+    pub fn round(&self, value: Decimal, scale: u32) -> Decimal {
+        let strategy = match self {
+            RoundingMode::Bankers => RoundingStrategy::MidpointNearestEven,
+            RoundingMode::Floor => RoundingStrategy::ToNegativeInfinity,
+        };
+        value.round_dp_with_strategy(scale, strategy)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RoundingMode::Bankers => "bankers",
+            RoundingMode::Floor => "floor",
+        }
+    }
+}
+
+impl fmt::Display for RoundingMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for RoundingMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bankers" => Ok(RoundingMode::Bankers),
+            "floor" => Ok(RoundingMode::Floor),
+            other => Err(format!("Unknown rounding mode: {}", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bankers_rounds_half_to_even() {
+        let mode = RoundingMode::Bankers;
+        assert_eq!(mode.round(Decimal::new(125, 3), 2), Decimal::new(12, 2));
+        assert_eq!(mode.round(Decimal::new(135, 3), 2), Decimal::new(14, 2));
+        assert_eq!(mode.round(Decimal::new(1251, 4), 2), Decimal::new(13, 2));
+    }
+
+    #[test]
+    fn test_floor_rounds_down() {
+        let mode = RoundingMode::Floor;
+        assert_eq!(mode.round(Decimal::new(1999, 3), 2), Decimal::new(199, 2));
+        assert_eq!(mode.round(Decimal::new(-1991, 3), 2), Decimal::new(-200, 2));
+        assert_eq!(mode.round(Decimal::new(5, 0), 2), Decimal::new(5, 0));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "floor".parse::<RoundingMode>().unwrap(),
+            RoundingMode::Floor
+        );
+        assert_eq!(
+            "Bankers".parse::<RoundingMode>().unwrap(),
+            RoundingMode::Bankers
+        );
+        assert!("up".parse::<RoundingMode>().is_err());
+    }
+}
@@ -1,11 +1,10 @@
 //! Rule evaluator - evaluates rules against transaction context
 
-use rust_decimal::Decimal;
-
-use bibank_compliance::{AmlDecision, CheckResult};
+use bibank_compliance::{AmlDecision, CheckResult, RiskScore};
 use bibank_hooks::HookContext;
 
-use crate::types::{Condition, RuleAction, RuleDefinition, RuleSet, RuleType};
+use crate::predicates::PredicateRegistry;
+use crate::types::{Condition, RuleAction, RuleDefinition, RuleSet};
 
 /// Result of evaluating a single rule
 #[derive(Debug, Clone)]
@@ -30,11 +29,23 @@ pub struct RuleSetEvalResult {
 }
 
 /// Rule evaluator
+///
+/// The `*_with` variants resolve `Condition::Custom` through a
+/// [`PredicateRegistry`]; the others treat custom conditions as unmatched.
 pub struct RuleEvaluator;
 
 impl RuleEvaluator {
     /// Evaluate a single condition against a context
     pub fn eval_condition(condition: &Condition, ctx: &HookContext) -> bool {
+        Self::eval_condition_with(condition, ctx, &PredicateRegistry::default())
+    }
+
+    /// Evaluate a single condition, resolving custom predicates
+    pub fn eval_condition_with(
+        condition: &Condition,
+        ctx: &HookContext,
+        predicates: &PredicateRegistry,
+    ) -> bool {
         match condition {
             Condition::AmountGte { threshold } => ctx.amount >= *threshold,
             Condition::AmountLt { threshold } => ctx.amount < *threshold,
@@ -47,30 +58,39 @@ impl RuleEvaluator {
             }
             Condition::IsWatchlisted => ctx.metadata.is_watchlisted,
             Condition::IsPep => ctx.metadata.is_pep,
-            Condition::TxCountGte { count, .. } => {
+            Condition::TxCountGte { .. } => {
                 // Would need velocity state - simplified for now
                 // In real implementation, would query ComplianceState
                 false
             }
-            Condition::VolumeGte { threshold, .. } => {
+            Condition::VolumeGte { .. } => {
                 // Would need velocity state - simplified for now
                 false
             }
-            Condition::Custom { .. } => {
-                // Custom conditions need external handler
-                false
-            }
-            Condition::All { conditions } => {
-                conditions.iter().all(|c| Self::eval_condition(c, ctx))
-            }
-            Condition::Any { conditions } => {
-                conditions.iter().any(|c| Self::eval_condition(c, ctx))
+            Condition::Custom { name } => {
+                // Unregistered predicates never match
+                predicates.eval(name, ctx).unwrap_or(false)
             }
+            Condition::All { conditions } => conditions
+                .iter()
+                .all(|c| Self::eval_condition_with(c, ctx, predicates)),
+            Condition::Any { conditions } => conditions
+                .iter()
+                .any(|c| Self::eval_condition_with(c, ctx, predicates)),
         }
     }
 
     /// Evaluate a single rule
     pub fn eval_rule(rule: &RuleDefinition, ctx: &HookContext) -> RuleEvalResult {
+        Self::eval_rule_with(rule, ctx, &PredicateRegistry::default())
+    }
+
+    /// Evaluate a single rule, resolving custom predicates
+    pub fn eval_rule_with(
+        rule: &RuleDefinition,
+        ctx: &HookContext,
+        predicates: &PredicateRegistry,
+    ) -> RuleEvalResult {
         if !rule.enabled {
             return RuleEvalResult {
                 rule_id: rule.id.clone(),
@@ -79,7 +99,7 @@ impl RuleEvaluator {
             };
         }
 
-        let triggered = Self::eval_condition(&rule.condition, ctx);
+        let triggered = Self::eval_condition_with(&rule.condition, ctx, predicates);
 
         RuleEvalResult {
             rule_id: rule.id.clone(),
@@ -90,13 +110,22 @@ impl RuleEvaluator {
 
     /// Evaluate all rules in a rule set
     pub fn eval_ruleset(ruleset: &RuleSet, ctx: &HookContext) -> RuleSetEvalResult {
+        Self::eval_ruleset_with(ruleset, ctx, &PredicateRegistry::default())
+    }
+
+    /// Evaluate all rules in a rule set, resolving custom predicates
+    pub fn eval_ruleset_with(
+        ruleset: &RuleSet,
+        ctx: &HookContext,
+        predicates: &PredicateRegistry,
+    ) -> RuleSetEvalResult {
         let mut results = Vec::new();
         let mut triggered_rules = Vec::new();
         let mut decisions = Vec::new();
 
         // Evaluate all rules
         for rule in &ruleset.rules {
-            let result = Self::eval_rule(rule, ctx);
+            let result = Self::eval_rule_with(rule, ctx, predicates);
             if result.triggered {
                 triggered_rules.push(rule.id.clone());
                 if let Some(action) = &result.action {
@@ -121,8 +150,17 @@ impl RuleEvaluator {
 
     /// Evaluate only BLOCK rules (for pre-validation)
     pub fn eval_block_rules(ruleset: &RuleSet, ctx: &HookContext) -> Option<RuleEvalResult> {
+        Self::eval_block_rules_with(ruleset, ctx, &PredicateRegistry::default())
+    }
+
+    /// Evaluate only BLOCK rules, resolving custom predicates
+    pub fn eval_block_rules_with(
+        ruleset: &RuleSet,
+        ctx: &HookContext,
+        predicates: &PredicateRegistry,
+    ) -> Option<RuleEvalResult> {
         for rule in ruleset.block_rules() {
-            let result = Self::eval_rule(rule, ctx);
+            let result = Self::eval_rule_with(rule, ctx, predicates);
             if result.triggered {
                 return Some(result);
             }
@@ -132,12 +170,21 @@ impl RuleEvaluator {
 
     /// Evaluate only FLAG rules (for post-commit)
     pub fn eval_flag_rules(ruleset: &RuleSet, ctx: &HookContext) -> RuleSetEvalResult {
+        Self::eval_flag_rules_with(ruleset, ctx, &PredicateRegistry::default())
+    }
+
+    /// Evaluate only FLAG rules, resolving custom predicates
+    pub fn eval_flag_rules_with(
+        ruleset: &RuleSet,
+        ctx: &HookContext,
+        predicates: &PredicateRegistry,
+    ) -> RuleSetEvalResult {
         let mut results = Vec::new();
         let mut triggered_rules = Vec::new();
         let mut decisions = Vec::new();
 
         for rule in ruleset.flag_rules() {
-            let result = Self::eval_rule(rule, ctx);
+            let result = Self::eval_rule_with(rule, ctx, predicates);
             if result.triggered {
                 triggered_rules.push(rule.id.clone());
                 if let Some(action) = &result.action {
@@ -161,10 +208,16 @@ impl RuleEvaluator {
 
     /// Convert rule set evaluation to CheckResult
     pub fn to_check_result(eval_result: &RuleSetEvalResult) -> CheckResult {
+        let risk_score = match &eval_result.decision {
+            AmlDecision::Flagged { risk_score, .. } => Some(*risk_score),
+            AmlDecision::Blocked { .. } => Some(RiskScore::Critical),
+            AmlDecision::Approved => None,
+        };
+
         CheckResult {
             decision: eval_result.decision.clone(),
             rules_triggered: eval_result.triggered_rules.clone(),
-            risk_score: None, // Would extract from flagged decision
+            risk_score,
         }
     }
 }
@@ -177,8 +230,9 @@ impl RuleEvaluator {
 mod tests {
     use super::*;
     use crate::types::RuleAction;
-    use crate::{account_age_lt, all_of, amount_gte, is_watchlisted, rule, rule_set};
-    use bibank_compliance::{ApprovalLevel, RiskScore};
+    use crate::{amount_gte, is_watchlisted, rule, rule_set};
+    use bibank_compliance::ApprovalLevel;
+    use rust_decimal::Decimal;
     use rust_decimal_macros::dec;
 
     fn create_test_context(amount: Decimal, watchlisted: bool, age_days: i64) -> HookContext {
@@ -367,6 +421,26 @@ mod tests {
         assert_eq!(result2.unwrap().rule_id, "SANCTIONS");
     }
 
+    #[test]
+    fn test_eval_custom_predicate() {
+        let predicates = PredicateRegistry::new().with("is_withdrawal", |ctx| ctx.intent == "WITHDRAWAL");
+        let cond = Condition::all(vec![
+            Condition::Custom { name: "is_withdrawal".to_string() },
+            Condition::amount_gte(dec!(1000)),
+        ]);
+
+        let deposit = create_test_context(dec!(5000), false, 30);
+        let withdrawal = HookContext::new("corr-2", "user-1", "WITHDRAWAL", dec!(5000), "USDT");
+
+        assert!(!RuleEvaluator::eval_condition_with(&cond, &deposit, &predicates));
+        assert!(RuleEvaluator::eval_condition_with(&cond, &withdrawal, &predicates));
+
+        // Without the registry custom conditions never match
+        assert!(!RuleEvaluator::eval_condition(&cond, &withdrawal));
+        let unknown = Condition::Custom { name: "unknown".to_string() };
+        assert!(!RuleEvaluator::eval_condition_with(&unknown, &withdrawal, &predicates));
+    }
+
     #[test]
     fn test_decision_aggregation() {
         let ruleset = rule_set! {
@@ -0,0 +1,140 @@
+//! Rule sets as transaction hooks
+//!
+//! [`RuleSetHook`] runs a rule set in both hook stages: BLOCK rules during
+//! pre-validation and FLAG rules after commit. The rule set can be replaced
+//! while the hook is registered, so rule files reload without a restart.
+
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+
+use bibank_compliance::CheckResult;
+use bibank_hooks::{HookContext, HookDecision, HookResult, PostCommitHook, PreValidationHook};
+
+use crate::evaluator::RuleEvaluator;
+use crate::predicates::PredicateRegistry;
+use crate::types::{RuleAction, RuleSet};
+
+/// Hook evaluating a (replaceable) rule set
+pub struct RuleSetHook {
+    priority: u32,
+    rule_set: RwLock<Arc<RuleSet>>,
+    predicates: PredicateRegistry,
+}
+
+impl RuleSetHook {
+    /// Create a hook for a rule set
+    pub fn new(priority: u32, rule_set: RuleSet, predicates: PredicateRegistry) -> Self {
+        Self {
+            priority,
+            rule_set: RwLock::new(Arc::new(rule_set)),
+            predicates,
+        }
+    }
+
+    /// Rule set currently in force
+    pub fn rule_set(&self) -> Arc<RuleSet> {
+        self.rule_set.read().unwrap().clone()
+    }
+
+    /// Replace the rule set, returning the previous one
+    pub fn replace(&self, rule_set: RuleSet) -> Arc<RuleSet> {
+        std::mem::replace(&mut *self.rule_set.write().unwrap(), Arc::new(rule_set))
+    }
+
+    /// Predicates custom conditions resolve through
+    pub fn predicates(&self) -> &PredicateRegistry {
+        &self.predicates
+    }
+}
+
+#[async_trait]
+impl PreValidationHook for RuleSetHook {
+    fn name(&self) -> &str {
+        "rule_set_hook"
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    async fn on_pre_validation(&self, ctx: &HookContext) -> HookResult<HookDecision> {
+        let rule_set = self.rule_set();
+        let blocked = RuleEvaluator::eval_block_rules_with(&rule_set, ctx, &self.predicates)
+            .and_then(|result| result.action);
+        match blocked {
+            Some(RuleAction::Block { code, reason }) => Ok(HookDecision::block(reason, code)),
+            _ => Ok(HookDecision::Allow),
+        }
+    }
+}
+
+#[async_trait]
+impl PostCommitHook for RuleSetHook {
+    fn name(&self) -> &str {
+        "rule_set_hook"
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    async fn on_post_commit(&self, ctx: &HookContext) -> HookResult<CheckResult> {
+        let rule_set = self.rule_set();
+        let result = RuleEvaluator::eval_flag_rules_with(&rule_set, ctx, &self.predicates);
+        Ok(RuleEvaluator::to_check_result(&result))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_rule_set;
+    use rust_decimal_macros::dec;
+
+    const RULES: &str = r#"
+ruleset "TEST"
+rule NO_WITHDRAWALS {
+    type block
+    when custom("is_withdrawal")
+    then block("NO_WITHDRAWALS", "Withdrawals suspended")
+}
+rule LARGE {
+    type flag
+    when amount >= 1000
+    then flag(high, L2, "Large")
+}
+"#;
+
+    fn hook() -> RuleSetHook {
+        let predicates = PredicateRegistry::new().with("is_withdrawal", |ctx| ctx.intent == "Withdrawal");
+        RuleSetHook::new(50, parse_rule_set(RULES).unwrap(), predicates)
+    }
+
+    #[tokio::test]
+    async fn test_rule_set_hook_blocks_and_flags() {
+        let hook = hook();
+
+        let withdrawal = HookContext::new("TX-1", "ALICE", "Withdrawal", dec!(10), "USDT");
+        let decision = hook.on_pre_validation(&withdrawal).await.unwrap();
+        assert_eq!(decision, HookDecision::block("Withdrawals suspended", "NO_WITHDRAWALS"));
+
+        let deposit = HookContext::new("TX-2", "ALICE", "Deposit", dec!(5000), "USDT");
+        assert!(hook.on_pre_validation(&deposit).await.unwrap().is_allowed());
+        let result = hook.on_post_commit(&deposit).await.unwrap();
+        assert!(result.decision.is_flagged());
+        assert_eq!(result.rules_triggered, vec!["LARGE"]);
+    }
+
+    #[tokio::test]
+    async fn test_rule_set_hook_replace() {
+        let hook = hook();
+        let previous = hook.replace(RuleSet::new("EMPTY"));
+        assert_eq!(previous.name, "TEST");
+        assert_eq!(hook.rule_set().name, "EMPTY");
+
+        let withdrawal = HookContext::new("TX-1", "ALICE", "Withdrawal", dec!(5000), "USDT");
+        assert!(hook.on_pre_validation(&withdrawal).await.unwrap().is_allowed());
+        assert!(hook.on_post_commit(&withdrawal).await.unwrap().decision.is_approved());
+    }
+}
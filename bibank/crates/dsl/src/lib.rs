@@ -58,12 +58,25 @@
 //! - `RuleAction::block(code, reason)` - Block transaction
 //! - `RuleAction::flag(risk, level, reason)` - Flag for review
 //! - `RuleAction::Approve` - Approve (no action)
+//!
+//! # Rule Files
+//!
+//! Rule sets can also be written as text and compiled with
+//! [`parse_rule_set`] (see [`parser`] for the grammar). `custom("name")`
+//! conditions resolve through a [`PredicateRegistry`], and a [`RuleSetHook`]
+//! runs a rule set in the hook pipeline, swapping it on reload.
 
 pub mod evaluator;
+pub mod hook;
 pub mod macros;
+pub mod parser;
+pub mod predicates;
 pub mod types;
 
 // Re-export commonly used types
 pub use bibank_compliance::{AmlDecision, ApprovalLevel, RiskScore};
 pub use evaluator::{RuleEvalResult, RuleEvaluator, RuleSetEvalResult};
+pub use hook::RuleSetHook;
+pub use parser::{parse_rule_set, ParseError, RuleParser};
+pub use predicates::{Predicate, PredicateRegistry};
 pub use types::{Condition, RuleAction, RuleBuilder, RuleDefinition, RuleSet, RuleType};
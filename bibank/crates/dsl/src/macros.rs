@@ -221,7 +221,6 @@ macro_rules! any_of {
 
 #[cfg(test)]
 mod tests {
-    use super::*;
     use crate::types::{Condition, RuleAction, RuleType};
     use bibank_compliance::{ApprovalLevel, RiskScore};
     use rust_decimal_macros::dec;
@@ -0,0 +1,772 @@
+//! Text rule language
+//!
+//! Compliance officers write rule sets as plain text files, which compile
+//! to the same [`RuleSet`] the macros build:
+//!
+//! ```text
+//! ruleset "AML_BASIC"
+//! description "Basic AML rules"
+//! version "2026-10"
+//! approved_by "alice", "bob"
+//!
+//! # Sanctions
+//! rule SANCTIONS {
+//!     type block
+//!     priority 10
+//!     when is_watchlisted
+//!     then block("SANCTIONS", "User on sanctions watchlist")
+//! }
+//!
+//! rule LARGE_WITHDRAWAL {
+//!     name "Large withdrawal"
+//!     type flag
+//!     when all(custom("is_withdrawal"), amount >= 10_000)
+//!     then flag(medium, L1, "Large withdrawal")
+//! }
+//! ```
+//!
+//! Conditions: `amount >= N`, `amount < N`, `amount in N..M`,
+//! `account_age < N`, `account_age >= N`, `is_watchlisted`, `is_pep`,
+//! `tx_count >= N in 60m`, `volume >= N in 1h`, `custom("name")`,
+//! `all(...)` and `any(...)`. Actions: `block(code, reason)`,
+//! `flag(risk, level, reason)` and `approve`. `#` starts a comment.
+//!
+//! Errors carry the line and column they were found at.
+
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use rust_decimal::Decimal;
+use thiserror::Error;
+
+use bibank_compliance::{ApprovalLevel, RiskScore};
+
+use crate::predicates::PredicateRegistry;
+use crate::types::{Condition, RuleAction, RuleDefinition, RuleSet, RuleType};
+
+/// Error in a rule file, with its position (1-based)
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{line}:{column}: {message}")]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(pos: Pos, message: impl Into<String>) -> Self {
+        Self {
+            line: pos.line,
+            column: pos.column,
+            message: message.into(),
+        }
+    }
+}
+
+/// Parse a rule file, accepting any custom predicate name
+pub fn parse_rule_set(source: &str) -> Result<RuleSet, ParseError> {
+    RuleParser::new(source).parse()
+}
+
+/// Rule file parser
+///
+/// With a predicate registry, `custom("name")` conditions must name a
+/// registered predicate.
+pub struct RuleParser<'a> {
+    source: &'a str,
+    predicates: Option<&'a PredicateRegistry>,
+}
+
+impl<'a> RuleParser<'a> {
+    /// Create a parser over a rule file
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            predicates: None,
+        }
+    }
+
+    /// Reject custom conditions not in the registry
+    pub fn with_predicates(mut self, predicates: &'a PredicateRegistry) -> Self {
+        self.predicates = Some(predicates);
+        self
+    }
+
+    /// Compile the file to a rule set
+    pub fn parse(self) -> Result<RuleSet, ParseError> {
+        let tokens = tokenize(self.source)?;
+        Parser {
+            tokens,
+            index: 0,
+            predicates: self.predicates,
+        }
+        .rule_set()
+    }
+}
+
+// =============================================================================
+// Lexer
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pos {
+    line: usize,
+    column: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(String),
+    LBrace,
+    RBrace,
+    LParen,
+    RParen,
+    Comma,
+    Gte,
+    Lt,
+    Range,
+    Eof,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::Ident(s) => format!("'{}'", s),
+            Token::Str(s) => format!("\"{}\"", s),
+            Token::Number(s) => s.clone(),
+            Token::LBrace => "'{'".to_string(),
+            Token::RBrace => "'}'".to_string(),
+            Token::LParen => "'('".to_string(),
+            Token::RParen => "')'".to_string(),
+            Token::Comma => "','".to_string(),
+            Token::Gte => "'>='".to_string(),
+            Token::Lt => "'<'".to_string(),
+            Token::Range => "'..'".to_string(),
+            Token::Eof => "end of file".to_string(),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(Token, Pos)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = source.chars().peekable();
+    let mut pos = Pos { line: 1, column: 1 };
+
+    let advance = |c: char, pos: &mut Pos| {
+        if c == '\n' {
+            pos.line += 1;
+            pos.column = 1;
+        } else {
+            pos.column += 1;
+        }
+    };
+
+    while let Some(&c) = chars.peek() {
+        let start = pos;
+        if c.is_whitespace() {
+            chars.next();
+            advance(c, &mut pos);
+            continue;
+        }
+        if c == '#' {
+            while let Some(&c) = chars.peek() {
+                if c == '\n' {
+                    break;
+                }
+                chars.next();
+                advance(c, &mut pos);
+            }
+            continue;
+        }
+
+        chars.next();
+        advance(c, &mut pos);
+        let token = match c {
+            '{' => Token::LBrace,
+            '}' => Token::RBrace,
+            '(' => Token::LParen,
+            ')' => Token::RParen,
+            ',' => Token::Comma,
+            '<' => Token::Lt,
+            '>' => {
+                if chars.peek() != Some(&'=') {
+                    return Err(ParseError::new(start, "expected '>='"));
+                }
+                chars.next();
+                advance('=', &mut pos);
+                Token::Gte
+            }
+            '.' => {
+                if chars.peek() != Some(&'.') {
+                    return Err(ParseError::new(start, "expected '..'"));
+                }
+                chars.next();
+                advance('.', &mut pos);
+                Token::Range
+            }
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => {
+                            advance('"', &mut pos);
+                            break;
+                        }
+                        Some('\n') | None => {
+                            return Err(ParseError::new(start, "unterminated string"));
+                        }
+                        Some('\\') => {
+                            advance('\\', &mut pos);
+                            match chars.next() {
+                                Some(e @ ('"' | '\\')) => {
+                                    advance(e, &mut pos);
+                                    value.push(e);
+                                }
+                                _ => return Err(ParseError::new(pos, "invalid escape")),
+                            }
+                        }
+                        Some(c) => {
+                            advance(c, &mut pos);
+                            value.push(c);
+                        }
+                    }
+                }
+                Token::Str(value)
+            }
+            c if c.is_ascii_digit() => {
+                let mut value = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    // A '.' followed by another '.' is a range, not a decimal point
+                    if c == '.' {
+                        let mut lookahead = chars.clone();
+                        lookahead.next();
+                        if lookahead.peek() == Some(&'.') {
+                            break;
+                        }
+                    } else if !(c.is_ascii_digit() || c == '_') {
+                        break;
+                    }
+                    chars.next();
+                    advance(c, &mut pos);
+                    if c != '_' {
+                        value.push(c);
+                    }
+                }
+                Token::Number(value)
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut value = c.to_string();
+                while let Some(&c) = chars.peek() {
+                    if !(c.is_ascii_alphanumeric() || c == '_') {
+                        break;
+                    }
+                    chars.next();
+                    advance(c, &mut pos);
+                    value.push(c);
+                }
+                Token::Ident(value)
+            }
+            c => return Err(ParseError::new(start, format!("unexpected character '{}'", c))),
+        };
+        tokens.push((token, start));
+    }
+
+    tokens.push((Token::Eof, pos));
+    Ok(tokens)
+}
+
+// =============================================================================
+// Parser
+// =============================================================================
+
+struct Parser<'a> {
+    tokens: Vec<(Token, Pos)>,
+    index: usize,
+    predicates: Option<&'a PredicateRegistry>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index].0
+    }
+
+    fn pos(&self) -> Pos {
+        self.tokens[self.index].1
+    }
+
+    fn next(&mut self) -> (Token, Pos) {
+        let token = self.tokens[self.index].clone();
+        if self.index < self.tokens.len() - 1 {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn unexpected(&self, expected: &str) -> ParseError {
+        ParseError::new(
+            self.pos(),
+            format!("expected {}, found {}", expected, self.peek().describe()),
+        )
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), ParseError> {
+        if *self.peek() == token {
+            self.next();
+            Ok(())
+        } else {
+            Err(self.unexpected(&token.describe()))
+        }
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == token {
+            self.next();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn ident(&mut self) -> Result<(String, Pos), ParseError> {
+        match self.peek().clone() {
+            Token::Ident(s) => Ok((s, self.next().1)),
+            _ => Err(self.unexpected("an identifier")),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str) -> Result<(), ParseError> {
+        match self.peek() {
+            Token::Ident(s) if s == keyword => {
+                self.next();
+                Ok(())
+            }
+            _ => Err(self.unexpected(&format!("'{}'", keyword))),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        match self.peek().clone() {
+            Token::Str(s) => {
+                self.next();
+                Ok(s)
+            }
+            _ => Err(self.unexpected("a string")),
+        }
+    }
+
+    fn decimal(&mut self) -> Result<Decimal, ParseError> {
+        match self.peek().clone() {
+            Token::Number(s) => {
+                let pos = self.next().1;
+                Decimal::from_str(&s).map_err(|_| ParseError::new(pos, format!("invalid number {}", s)))
+            }
+            _ => Err(self.unexpected("a number")),
+        }
+    }
+
+    fn integer<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        match self.peek().clone() {
+            Token::Number(s) => {
+                let pos = self.next().1;
+                s.parse()
+                    .map_err(|_| ParseError::new(pos, format!("expected a whole number, found {}", s)))
+            }
+            _ => Err(self.unexpected("a whole number")),
+        }
+    }
+
+    fn rule_set(&mut self) -> Result<RuleSet, ParseError> {
+        self.keyword("ruleset")?;
+        let mut rule_set = RuleSet::new(self.string()?);
+        let mut seen = HashSet::new();
+        let mut ids = HashSet::new();
+
+        loop {
+            let pos = self.pos();
+            let (keyword, _) = match self.peek() {
+                Token::Eof => break,
+                Token::Ident(_) => self.ident()?,
+                _ => return Err(self.unexpected("'rule' or a ruleset field")),
+            };
+            if keyword != "rule" && !seen.insert(keyword.clone()) {
+                return Err(ParseError::new(pos, format!("duplicate field '{}'", keyword)));
+            }
+            match keyword.as_str() {
+                "description" => rule_set.description = self.string()?,
+                "version" => rule_set.version = self.string()?,
+                "approved_by" => loop {
+                    rule_set.approved_by.push(self.string()?);
+                    if !self.eat(&Token::Comma) {
+                        break;
+                    }
+                },
+                "rule" => {
+                    let rule = self.rule()?;
+                    if !ids.insert(rule.id.clone()) {
+                        return Err(ParseError::new(pos, format!("duplicate rule '{}'", rule.id)));
+                    }
+                    rule_set.rules.push(rule);
+                }
+                other => {
+                    return Err(ParseError::new(pos, format!("unknown ruleset field '{}'", other)));
+                }
+            }
+        }
+
+        Ok(rule_set)
+    }
+
+    fn rule(&mut self) -> Result<RuleDefinition, ParseError> {
+        let (id, start) = self.ident()?;
+        self.expect(Token::LBrace)?;
+
+        let mut seen = HashSet::new();
+        let mut name = None;
+        let mut description = String::new();
+        let mut rule_type = None;
+        let mut priority = 100;
+        let mut enabled = true;
+        let mut condition = None;
+        let mut action: Option<(RuleAction, Pos)> = None;
+
+        while !self.eat(&Token::RBrace) {
+            let (field, pos) = self.ident()?;
+            if !seen.insert(field.clone()) {
+                return Err(ParseError::new(pos, format!("duplicate field '{}'", field)));
+            }
+            match field.as_str() {
+                "name" => name = Some(self.string()?),
+                "description" => description = self.string()?,
+                "type" => {
+                    rule_type = Some(match self.ident()? {
+                        (t, _) if t == "block" => RuleType::Block,
+                        (t, _) if t == "flag" => RuleType::Flag,
+                        (t, pos) => {
+                            return Err(ParseError::new(
+                                pos,
+                                format!("expected 'block' or 'flag', found '{}'", t),
+                            ))
+                        }
+                    })
+                }
+                "priority" => priority = self.integer()?,
+                "enabled" => {
+                    enabled = match self.ident()? {
+                        (b, _) if b == "true" => true,
+                        (b, _) if b == "false" => false,
+                        (b, pos) => {
+                            return Err(ParseError::new(
+                                pos,
+                                format!("expected 'true' or 'false', found '{}'", b),
+                            ))
+                        }
+                    }
+                }
+                "when" => condition = Some(self.condition()?),
+                "then" => {
+                    let pos = self.pos();
+                    action = Some((self.action()?, pos));
+                }
+                other => {
+                    return Err(ParseError::new(pos, format!("unknown rule field '{}'", other)));
+                }
+            }
+        }
+
+        let missing = |field: &str| ParseError::new(start, format!("rule '{}' has no '{}'", id, field));
+        let rule_type = rule_type.ok_or_else(|| missing("type"))?;
+        let condition = condition.ok_or_else(|| missing("when"))?;
+        let (action, action_pos) = action.ok_or_else(|| missing("then"))?;
+
+        match (rule_type, &action) {
+            (RuleType::Flag, RuleAction::Block { .. }) => {
+                return Err(ParseError::new(action_pos, "a flag rule cannot block"));
+            }
+            (RuleType::Block, RuleAction::Flag { .. }) => {
+                return Err(ParseError::new(action_pos, "a block rule cannot flag"));
+            }
+            _ => {}
+        }
+
+        Ok(RuleDefinition {
+            name: name.unwrap_or_else(|| id.clone()),
+            id,
+            description,
+            rule_type,
+            condition,
+            action,
+            priority,
+            enabled,
+        })
+    }
+
+    fn condition(&mut self) -> Result<Condition, ParseError> {
+        let (name, pos) = self.ident()?;
+        match name.as_str() {
+            "amount" => match self.next() {
+                (Token::Gte, _) => Ok(Condition::amount_gte(self.decimal()?)),
+                (Token::Lt, _) => Ok(Condition::amount_lt(self.decimal()?)),
+                (Token::Ident(op), _) if op == "in" => {
+                    let min = self.decimal()?;
+                    self.expect(Token::Range)?;
+                    let max = self.decimal()?;
+                    Ok(Condition::amount_in_range(min, max))
+                }
+                (token, pos) => Err(ParseError::new(
+                    pos,
+                    format!("expected '>=', '<' or 'in', found {}", token.describe()),
+                )),
+            },
+            "account_age" => match self.next() {
+                (Token::Gte, _) => Ok(Condition::AccountAgeGte { days: self.integer()? }),
+                (Token::Lt, _) => Ok(Condition::account_age_lt(self.integer()?)),
+                (token, pos) => Err(ParseError::new(
+                    pos,
+                    format!("expected '>=' or '<', found {}", token.describe()),
+                )),
+            },
+            "tx_count" => {
+                self.expect(Token::Gte)?;
+                let count = self.integer()?;
+                Ok(Condition::tx_count_gte(count, self.window()?))
+            }
+            "volume" => {
+                self.expect(Token::Gte)?;
+                let threshold = self.decimal()?;
+                Ok(Condition::volume_gte(threshold, self.window()?))
+            }
+            "is_watchlisted" => Ok(Condition::is_watchlisted()),
+            "is_pep" => Ok(Condition::is_pep()),
+            "custom" => {
+                self.expect(Token::LParen)?;
+                let name_pos = self.pos();
+                let name = self.string()?;
+                self.expect(Token::RParen)?;
+                if let Some(predicates) = self.predicates {
+                    if !predicates.contains(&name) {
+                        return Err(ParseError::new(
+                            name_pos,
+                            format!("unknown predicate \"{}\"", name),
+                        ));
+                    }
+                }
+                Ok(Condition::Custom { name })
+            }
+            "all" | "any" => {
+                self.expect(Token::LParen)?;
+                let mut conditions = vec![self.condition()?];
+                while self.eat(&Token::Comma) {
+                    conditions.push(self.condition()?);
+                }
+                self.expect(Token::RParen)?;
+                Ok(if name == "all" {
+                    Condition::all(conditions)
+                } else {
+                    Condition::any(conditions)
+                })
+            }
+            other => Err(ParseError::new(pos, format!("unknown condition '{}'", other))),
+        }
+    }
+
+    /// `in 60m` / `in 24h`, in minutes
+    fn window(&mut self) -> Result<u32, ParseError> {
+        self.keyword("in")?;
+        let value: u32 = self.integer()?;
+        let (unit, pos) = self.ident()?;
+        match unit.as_str() {
+            "m" => Ok(value),
+            "h" => Ok(value * 60),
+            other => Err(ParseError::new(
+                pos,
+                format!("expected window unit 'm' or 'h', found '{}'", other),
+            )),
+        }
+    }
+
+    fn action(&mut self) -> Result<RuleAction, ParseError> {
+        let (name, pos) = self.ident()?;
+        match name.as_str() {
+            "approve" => Ok(RuleAction::Approve),
+            "block" => {
+                self.expect(Token::LParen)?;
+                let code = self.string()?;
+                self.expect(Token::Comma)?;
+                let reason = self.string()?;
+                self.expect(Token::RParen)?;
+                Ok(RuleAction::block(code, reason))
+            }
+            "flag" => {
+                self.expect(Token::LParen)?;
+                let risk_score = self.risk_score()?;
+                self.expect(Token::Comma)?;
+                let approval_level = self.approval_level()?;
+                self.expect(Token::Comma)?;
+                let reason = self.string()?;
+                self.expect(Token::RParen)?;
+                Ok(RuleAction::flag(risk_score, approval_level, reason))
+            }
+            other => Err(ParseError::new(
+                pos,
+                format!("expected 'block', 'flag' or 'approve', found '{}'", other),
+            )),
+        }
+    }
+
+    fn risk_score(&mut self) -> Result<RiskScore, ParseError> {
+        let (name, pos) = self.ident()?;
+        match name.as_str() {
+            "low" => Ok(RiskScore::Low),
+            "medium" => Ok(RiskScore::Medium),
+            "high" => Ok(RiskScore::High),
+            "critical" => Ok(RiskScore::Critical),
+            other => Err(ParseError::new(pos, format!("unknown risk score '{}'", other))),
+        }
+    }
+
+    fn approval_level(&mut self) -> Result<ApprovalLevel, ParseError> {
+        let (name, pos) = self.ident()?;
+        match name.as_str() {
+            "L1" => Ok(ApprovalLevel::L1),
+            "L2" => Ok(ApprovalLevel::L2),
+            "L3" => Ok(ApprovalLevel::L3),
+            "L4" => Ok(ApprovalLevel::L4),
+            other => Err(ParseError::new(pos, format!("unknown approval level '{}'", other))),
+        }
+    }
+}
+
+// =============================================================================
+// Tests
+// =============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const RULES: &str = r#"
+ruleset "AML_BASIC"
+description "Basic AML rules"
+version "2026-10"
+approved_by "alice", "bob"
+
+# Sanctions first
+rule SANCTIONS {
+    type block
+    priority 10
+    when is_watchlisted
+    then block("SANCTIONS", "User on sanctions watchlist")
+}
+
+rule LARGE_WITHDRAWAL {
+    name "Large withdrawal"
+    description "Withdrawals of 10k or more"
+    type flag
+    when all(custom("is_withdrawal"), amount >= 10_000)
+    then flag(medium, L1, "Large withdrawal")
+}
+
+rule STRUCTURING {
+    type flag
+    enabled false
+    when any(amount in 9000..10000, tx_count >= 5 in 1h, volume >= 25000.50 in 30m)
+    then flag(high, L2, "Possible structuring")
+}
+"#;
+
+    #[test]
+    fn test_parse_rule_set() {
+        let rule_set = parse_rule_set(RULES).unwrap();
+        assert_eq!(rule_set.name, "AML_BASIC");
+        assert_eq!(rule_set.description, "Basic AML rules");
+        assert_eq!(rule_set.version, "2026-10");
+        assert_eq!(rule_set.approved_by, vec!["alice", "bob"]);
+        assert_eq!(rule_set.rules.len(), 3);
+
+        let sanctions = rule_set.get_rule("SANCTIONS").unwrap();
+        assert_eq!(sanctions.name, "SANCTIONS");
+        assert_eq!(sanctions.rule_type, RuleType::Block);
+        assert_eq!(sanctions.priority, 10);
+        assert_eq!(sanctions.condition, Condition::IsWatchlisted);
+
+        let large = rule_set.get_rule("LARGE_WITHDRAWAL").unwrap();
+        assert_eq!(large.name, "Large withdrawal");
+        assert_eq!(large.priority, 100);
+        assert_eq!(
+            large.condition,
+            Condition::all(vec![
+                Condition::Custom { name: "is_withdrawal".to_string() },
+                Condition::amount_gte(dec!(10000)),
+            ])
+        );
+        assert_eq!(
+            large.action,
+            RuleAction::flag(RiskScore::Medium, ApprovalLevel::L1, "Large withdrawal")
+        );
+
+        let structuring = rule_set.get_rule("STRUCTURING").unwrap();
+        assert!(!structuring.enabled);
+        assert_eq!(
+            structuring.condition,
+            Condition::any(vec![
+                Condition::amount_in_range(dec!(9000), dec!(10000)),
+                Condition::tx_count_gte(5, 60),
+                Condition::volume_gte(dec!(25000.50), 30),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_hash_ignores_formatting() {
+        let compact = RULES.replace("    ", " ").replace("# Sanctions first\n", "");
+        assert_eq!(
+            parse_rule_set(RULES).unwrap().hash(),
+            parse_rule_set(&compact).unwrap().hash()
+        );
+    }
+
+    #[test]
+    fn test_parse_error_position() {
+        let err = parse_rule_set("ruleset \"X\"\nrule A {\n    type block\n    when amount > 5\n}").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 17));
+        assert_eq!(err.to_string(), "4:17: expected '>='");
+
+        let err = parse_rule_set("ruleset \"X\"\nrule A {\n  type flag\n  when is_pep\n  then block(\"C\", \"r\")\n}")
+            .unwrap_err();
+        assert_eq!((err.line, err.column), (5, 8));
+        assert_eq!(err.message, "a flag rule cannot block");
+
+        let err = parse_rule_set("ruleset \"X\"\nrule A {\n  type flag\n  then approve\n}").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 6));
+        assert_eq!(err.message, "rule 'A' has no 'when'");
+
+        let err = parse_rule_set("ruleset \"X\"\ndescription \"unterminated").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 13));
+    }
+
+    #[test]
+    fn test_parse_rejects_duplicates() {
+        let rule = "rule A {\n type flag\n when is_pep\n then approve\n}\n";
+        let err = parse_rule_set(&format!("ruleset \"X\"\n{}{}", rule, rule)).unwrap_err();
+        assert_eq!(err.line, 7);
+        assert_eq!(err.message, "duplicate rule 'A'");
+
+        let err = parse_rule_set("ruleset \"X\"\nrule A {\n type flag\n type block\n}").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 2));
+        assert_eq!(err.message, "duplicate field 'type'");
+    }
+
+    #[test]
+    fn test_parse_checks_predicates() {
+        let predicates = PredicateRegistry::new().with("is_withdrawal", |_| true);
+        assert!(RuleParser::new(RULES).with_predicates(&predicates).parse().is_ok());
+
+        let src = RULES.replace("is_withdrawal", "is_refund");
+        let err = RuleParser::new(&src).with_predicates(&predicates).parse().unwrap_err();
+        assert_eq!(err.message, "unknown predicate \"is_refund\"");
+        assert_eq!(err.line, 19);
+    }
+}
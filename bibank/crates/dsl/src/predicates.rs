@@ -0,0 +1,91 @@
+//! Named predicates for `Condition::Custom`
+//!
+//! Rules refer to custom checks by name (`custom("high_risk_country")`);
+//! the registry maps each name to a function over the hook context.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use bibank_hooks::HookContext;
+
+/// A named check over a transaction
+pub type Predicate = Arc<dyn Fn(&HookContext) -> bool + Send + Sync>;
+
+/// Registry of named predicates
+#[derive(Clone, Default)]
+pub struct PredicateRegistry {
+    predicates: HashMap<String, Predicate>,
+}
+
+impl PredicateRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a predicate (replaces any predicate with the same name)
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        predicate: impl Fn(&HookContext) -> bool + Send + Sync + 'static,
+    ) {
+        self.predicates.insert(name.into(), Arc::new(predicate));
+    }
+
+    /// Register a predicate, builder style
+    pub fn with(
+        mut self,
+        name: impl Into<String>,
+        predicate: impl Fn(&HookContext) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.register(name, predicate);
+        self
+    }
+
+    /// Check if a predicate is registered
+    pub fn contains(&self, name: &str) -> bool {
+        self.predicates.contains_key(name)
+    }
+
+    /// Evaluate a predicate (None if it isn't registered)
+    pub fn eval(&self, name: &str, ctx: &HookContext) -> Option<bool> {
+        self.predicates.get(name).map(|predicate| predicate(ctx))
+    }
+
+    /// Registered names, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.predicates.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+impl fmt::Debug for PredicateRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PredicateRegistry")
+            .field("predicates", &self.names())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_registry_eval() {
+        let registry = PredicateRegistry::new()
+            .with("is_withdrawal", |ctx| ctx.intent == "Withdrawal")
+            .with("has_destination", |ctx| ctx.destination.is_some());
+
+        let ctx = HookContext::new("TX-001", "USER-001", "Withdrawal", dec!(100), "USDT");
+        assert_eq!(registry.eval("is_withdrawal", &ctx), Some(true));
+        assert_eq!(registry.eval("has_destination", &ctx), Some(false));
+        assert_eq!(registry.eval("unknown", &ctx), None);
+
+        assert!(registry.contains("is_withdrawal"));
+        assert_eq!(registry.names(), vec!["has_destination", "is_withdrawal"]);
+    }
+}
@@ -4,6 +4,7 @@
 
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use bibank_compliance::{AmlDecision, ApprovalLevel, RiskScore};
 
@@ -300,6 +301,12 @@ pub struct RuleSet {
     pub name: String,
     /// Description
     pub description: String,
+    /// Version label
+    #[serde(default)]
+    pub version: String,
+    /// Compliance officers who approved this rule set
+    #[serde(default)]
+    pub approved_by: Vec<String>,
     /// Rules in this set
     pub rules: Vec<RuleDefinition>,
 }
@@ -310,6 +317,8 @@ impl RuleSet {
         Self {
             name: name.into(),
             description: String::new(),
+            version: String::new(),
+            approved_by: Vec::new(),
             rules: Vec::new(),
         }
     }
@@ -320,6 +329,18 @@ impl RuleSet {
         self
     }
 
+    /// Set the version label
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = version.into();
+        self
+    }
+
+    /// Add an approver
+    pub fn approved_by(mut self, approver: impl Into<String>) -> Self {
+        self.approved_by.push(approver.into());
+        self
+    }
+
     /// Add a rule
     pub fn add_rule(mut self, rule: RuleDefinition) -> Self {
         self.rules.push(rule);
@@ -358,6 +379,15 @@ impl RuleSet {
     pub fn get_rule(&self, id: &str) -> Option<&RuleDefinition> {
         self.rules.iter().find(|r| r.id == id)
     }
+
+    /// SHA-256 of the compiled rule set (hex)
+    ///
+    /// Computed over the serialized rules, so formatting and comments in a
+    /// rule file don't change it.
+    pub fn hash(&self) -> String {
+        let json = serde_json::to_vec(self).expect("rule set serializes");
+        hex::encode(Sha256::digest(&json))
+    }
 }
 
 // =============================================================================
@@ -477,6 +507,23 @@ mod tests {
         assert!(ruleset.get_rule("RULE_2").is_none());
     }
 
+    #[test]
+    fn test_rule_set_hash() {
+        let rule = || {
+            RuleDefinition::builder("LARGE_TX")
+                .when(Condition::amount_gte(dec!(10000)))
+                .then(RuleAction::Approve)
+                .build()
+        };
+        let a = RuleSet::new("AML").add_rule(rule());
+        let b = RuleSet::new("AML").add_rule(rule());
+        assert_eq!(a.hash(), b.hash());
+        assert_eq!(a.hash().len(), 64);
+
+        let c = RuleSet::new("AML").with_version("2").add_rule(rule());
+        assert_ne!(a.hash(), c.hash());
+    }
+
     #[test]
     fn test_rule_serialization() {
         let rule = RuleDefinition::builder("TEST")
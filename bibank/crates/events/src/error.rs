@@ -15,4 +15,14 @@ pub enum EventError {
 
     #[error("Invalid event file: {0}")]
     InvalidFile(String),
+
+    #[error("Checksum mismatch: expected {expected:08x}, got {actual:08x}")]
+    ChecksumMismatch { expected: u32, actual: u32 },
+
+    #[error("Corrupted record in {file} at byte {offset}: {reason}")]
+    Corrupted {
+        file: String,
+        offset: u64,
+        reason: String,
+    },
 }
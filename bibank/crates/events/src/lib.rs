@@ -1,12 +1,19 @@
-//! BiBank Events - JSONL event store
+//! BiBank Events - segmented JSONL event store
 //!
-//! This crate handles persistence of journal entries to JSONL files.
+//! This crate handles persistence of journal entries to JSONL segments.
+//! Each record carries a CRC and each segment an offset index, so readers
+//! can stream from any sequence and a torn tail is repaired on open.
 //! JSONL is the Source of Truth - SQLite projections are disposable.
 
 pub mod error;
 pub mod reader;
+pub mod record;
+pub mod segment;
 pub mod store;
 
+#[cfg(test)]
+mod testing;
+
 pub use error::EventError;
-pub use reader::EventReader;
-pub use store::EventStore;
+pub use reader::{EntryIter, EventReader};
+pub use store::{EventStore, StoreConfig};
@@ -1,104 +1,272 @@
-//! JSONL event reader - sequential reader for replay
+//! JSONL event reader - streaming reader for replay
 
 use crate::error::EventError;
+use crate::record;
+use crate::segment;
 use bibank_ledger::JournalEntry;
+use std::collections::VecDeque;
 use std::fs::File;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
 
 /// Sequential event reader for replay
 pub struct EventReader {
-    files: Vec<std::path::PathBuf>,
+    files: Vec<PathBuf>,
 }
 
 impl EventReader {
     /// Create a new reader from a directory
     pub fn from_directory(path: impl AsRef<Path>) -> Result<Self, EventError> {
-        let path = path.as_ref();
-        let mut files = Vec::new();
-
-        if path.exists() {
-            for entry in std::fs::read_dir(path)? {
-                let entry = entry?;
-                let file_path = entry.path();
-                if file_path.extension().map_or(false, |ext| ext == "jsonl") {
-                    files.push(file_path);
-                }
+        let files = segment::list_segments(path.as_ref())?;
+        Ok(Self { files })
+    }
+
+    /// Stream all entries in order
+    pub fn iter(&self) -> EntryIter {
+        EntryIter::new(self.files.iter().cloned().collect(), 0, 0)
+    }
+
+    /// Stream entries starting at `sequence`
+    ///
+    /// Seeks through the segment indexes instead of reading earlier entries.
+    pub fn iter_from(&self, sequence: u64) -> Result<EntryIter, EventError> {
+        // Segment holding `sequence`: the last one starting at or before it
+        let mut start = 0;
+        for (i, file) in self.files.iter().enumerate() {
+            let first = match segment::first_sequence(file) {
+                Some(first) => Some(first),
+                None => segment::load_index(file)?.first().map(|e| e.sequence),
+            };
+            match first {
+                Some(first) if first <= sequence => start = i,
+                Some(_) => break,
+                None => {}
             }
         }
 
-        files.sort();
+        let mut files: VecDeque<PathBuf> = self.files[start..].iter().cloned().collect();
+        let offset = match files.front() {
+            Some(file) => {
+                let index = segment::load_index(file)?;
+                let pos = index.partition_point(|e| e.sequence < sequence);
+                match index.get(pos) {
+                    Some(entry) => entry.offset,
+                    None => {
+                        // Everything in this segment is before `sequence`
+                        files.pop_front();
+                        0
+                    }
+                }
+            }
+            None => 0,
+        };
 
-        Ok(Self { files })
+        Ok(EntryIter::new(files, offset, sequence))
     }
 
     /// Read all entries from all files in order
     pub fn read_all(&self) -> Result<Vec<JournalEntry>, EventError> {
-        let mut entries = Vec::new();
+        self.iter().collect()
+    }
 
-        for file_path in &self.files {
-            let file = File::open(file_path)?;
-            let reader = BufReader::new(file);
+    /// Read all entries from `sequence` onwards
+    pub fn read_from(&self, sequence: u64) -> Result<Vec<JournalEntry>, EventError> {
+        self.iter_from(sequence)?.collect()
+    }
 
-            for line in reader.lines() {
-                let line = line?;
-                if line.trim().is_empty() {
-                    continue;
-                }
-                let entry: JournalEntry = serde_json::from_str(&line)?;
-                entries.push(entry);
+    /// Get the last sequence number from all files
+    pub fn last_sequence(&self) -> Result<Option<u64>, EventError> {
+        Ok(self.last_entry()?.map(|entry| entry.sequence))
+    }
+
+    /// Get the last entry (for prev_hash)
+    ///
+    /// Reads a single record located through the last segment's index.
+    pub fn last_entry(&self) -> Result<Option<JournalEntry>, EventError> {
+        for file in self.files.iter().rev() {
+            if let Some(last) = segment::load_index(file)?.last() {
+                return EntryIter::new(VecDeque::from([file.clone()]), last.offset, 0)
+                    .next()
+                    .transpose();
             }
         }
+        Ok(None)
+    }
 
-        Ok(entries)
+    /// Count total entries across all files
+    pub fn count(&self) -> Result<usize, EventError> {
+        let mut count = 0;
+        for file in &self.files {
+            count += segment::load_index(file)?.len();
+        }
+        Ok(count)
     }
+}
 
-    /// Get the last sequence number from all files
-    pub fn last_sequence(&self) -> Result<Option<u64>, EventError> {
-        if self.files.is_empty() {
-            return Ok(None);
+/// Streaming iterator over journal entries across segments
+///
+/// Stops after the first error.
+pub struct EntryIter {
+    files: VecDeque<PathBuf>,
+    current: Option<(PathBuf, BufReader<File>)>,
+    start_offset: u64,
+    offset: u64,
+    min_sequence: u64,
+    line: String,
+    failed: bool,
+}
+
+impl EntryIter {
+    fn new(files: VecDeque<PathBuf>, start_offset: u64, min_sequence: u64) -> Self {
+        Self {
+            files,
+            current: None,
+            start_offset,
+            offset: 0,
+            min_sequence,
+            line: String::new(),
+            failed: false,
         }
+    }
+
+    /// Open the next segment, seeking the first one to the start offset
+    fn open_next(&mut self) -> Result<bool, EventError> {
+        let Some(path) = self.files.pop_front() else {
+            return Ok(false);
+        };
+
+        let mut file = File::open(&path)?;
+        self.offset = std::mem::take(&mut self.start_offset);
+        file.seek(SeekFrom::Start(self.offset))?;
+        self.current = Some((path, BufReader::new(file)));
+        Ok(true)
+    }
+
+    fn next_entry(&mut self) -> Result<Option<JournalEntry>, EventError> {
+        loop {
+            let Some((path, reader)) = self.current.as_mut() else {
+                if !self.open_next()? {
+                    return Ok(None);
+                }
+                continue;
+            };
 
-        // Read from the last file
-        let last_file = &self.files[self.files.len() - 1];
-        let file = File::open(last_file)?;
-        let reader = BufReader::new(file);
+            self.line.clear();
+            let read = reader.read_line(&mut self.line)? as u64;
+            if read == 0 {
+                self.current = None;
+                continue;
+            }
 
-        let mut last_seq = None;
-        for line in reader.lines() {
-            let line = line?;
-            if line.trim().is_empty() {
+            let offset = self.offset;
+            self.offset += read;
+
+            let content = self.line.trim_end();
+            if content.is_empty() {
                 continue;
             }
-            let entry: JournalEntry = serde_json::from_str(&line)?;
-            last_seq = Some(entry.sequence);
+
+            let entry = record::decode(content).map_err(|e| EventError::Corrupted {
+                file: path.display().to_string(),
+                offset,
+                reason: e.to_string(),
+            })?;
+
+            if entry.sequence >= self.min_sequence {
+                return Ok(Some(entry));
+            }
+        }
+    }
+}
+
+impl Iterator for EntryIter {
+    type Item = Result<JournalEntry, EventError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
         }
 
-        Ok(last_seq)
+        let result = self.next_entry().transpose();
+        if matches!(result, Some(Err(_))) {
+            self.failed = true;
+        }
+        result
     }
+}
 
-    /// Get the last entry (for prev_hash)
-    pub fn last_entry(&self) -> Result<Option<JournalEntry>, EventError> {
-        let entries = self.read_all()?;
-        Ok(entries.into_iter().last())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::{EventStore, StoreConfig};
+    use crate::testing::deposit_entry;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn write_journal(dir: &Path, count: u64, max_segment_bytes: u64) {
+        let config = StoreConfig::new().max_segment_bytes(max_segment_bytes);
+        let mut store = EventStore::open(dir, config).unwrap();
+        for seq in 1..=count {
+            store.append(&deposit_entry(seq)).unwrap();
+        }
     }
 
-    /// Count total entries across all files
-    pub fn count(&self) -> Result<usize, EventError> {
-        let mut count = 0;
+    fn sequences(iter: EntryIter) -> Vec<u64> {
+        iter.map(|e| e.unwrap().sequence).collect()
+    }
 
-        for file_path in &self.files {
-            let file = File::open(file_path)?;
-            let reader = BufReader::new(file);
+    #[test]
+    fn test_iter_from_seeks_across_segments() {
+        let dir = TempDir::new().unwrap();
+        // Roughly three records per segment
+        let record_len = record::encode(&deposit_entry(1)).unwrap().len() as u64;
+        write_journal(dir.path(), 10, record_len * 3);
 
-            for line in reader.lines() {
-                let line = line?;
-                if !line.trim().is_empty() {
-                    count += 1;
-                }
-            }
+        let reader = EventReader::from_directory(dir.path()).unwrap();
+        assert!(reader.files.len() > 1);
+
+        assert_eq!(sequences(reader.iter()), (1..=10).collect::<Vec<_>>());
+        assert_eq!(sequences(reader.iter_from(5).unwrap()), (5..=10).collect::<Vec<_>>());
+        assert_eq!(sequences(reader.iter_from(1).unwrap()), (1..=10).collect::<Vec<_>>());
+        assert!(sequences(reader.iter_from(11).unwrap()).is_empty());
+
+        assert_eq!(reader.count().unwrap(), 10);
+        assert_eq!(reader.last_sequence().unwrap(), Some(10));
+        assert_eq!(reader.last_entry().unwrap().unwrap().hash, "hash-10");
+    }
+
+    #[test]
+    fn test_stale_index_rebuilt_on_read() {
+        let dir = TempDir::new().unwrap();
+        write_journal(dir.path(), 3, u64::MAX);
+
+        // Drop the index entirely
+        let segment = segment::list_segments(dir.path()).unwrap().pop().unwrap();
+        std::fs::remove_file(segment::index_path(&segment)).unwrap();
+
+        let reader = EventReader::from_directory(dir.path()).unwrap();
+        assert_eq!(sequences(reader.iter_from(2).unwrap()), vec![2, 3]);
+        assert_eq!(reader.last_sequence().unwrap(), Some(3));
+    }
+
+    #[test]
+    fn test_reads_legacy_daily_files() {
+        let dir = TempDir::new().unwrap();
+
+        // Unframed daily file from before segmentation
+        let mut legacy = File::create(dir.path().join("2026-01-25.jsonl")).unwrap();
+        for seq in 1..=2 {
+            writeln!(legacy, "{}", serde_json::to_string(&deposit_entry(seq)).unwrap()).unwrap();
         }
+        drop(legacy);
 
-        Ok(count)
+        let mut store = EventStore::new(dir.path()).unwrap();
+        store.append(&deposit_entry(3)).unwrap();
+        drop(store);
+
+        let reader = EventReader::from_directory(dir.path()).unwrap();
+        assert_eq!(sequences(reader.iter()), vec![1, 2, 3]);
+        assert_eq!(sequences(reader.iter_from(2).unwrap()), vec![2, 3]);
+        assert_eq!(reader.last_sequence().unwrap(), Some(3));
     }
 }
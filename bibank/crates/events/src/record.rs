@@ -0,0 +1,95 @@
+//! Record framing - one checksummed JSON line per journal entry
+//!
+//! Format: `<crc32 as 8 hex digits> <json>\n`, the CRC covering the JSON bytes.
+//! Lines starting with `{` are legacy unframed records and are read as-is.
+
+use crate::error::EventError;
+use bibank_ledger::JournalEntry;
+use crc::{Crc, CRC_32_ISO_HDLC};
+
+const CRC32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+/// Width of the hex checksum prefix
+const CRC_WIDTH: usize = 8;
+
+/// CRC-32 of a record payload
+pub fn checksum(payload: &[u8]) -> u32 {
+    CRC32.checksum(payload)
+}
+
+/// Encode an entry as a framed line (including the trailing newline)
+pub fn encode(entry: &JournalEntry) -> Result<String, EventError> {
+    let json = serde_json::to_string(entry)?;
+    Ok(format!("{:08x} {}\n", checksum(json.as_bytes()), json))
+}
+
+/// Decode a line (without its trailing newline)
+pub fn decode(line: &str) -> Result<JournalEntry, EventError> {
+    // Legacy record written before framing
+    if line.starts_with('{') {
+        return Ok(serde_json::from_str(line)?);
+    }
+
+    let (crc, json) = line
+        .split_at_checked(CRC_WIDTH)
+        .filter(|(_, rest)| rest.starts_with(' '))
+        .ok_or_else(|| EventError::InvalidFile("malformed record frame".to_string()))?;
+
+    let expected = u32::from_str_radix(crc, 16)
+        .map_err(|_| EventError::InvalidFile(format!("invalid checksum '{}'", crc)))?;
+    let json = &json[1..];
+    let actual = checksum(json.as_bytes());
+    if expected != actual {
+        return Err(EventError::ChecksumMismatch { expected, actual });
+    }
+
+    Ok(serde_json::from_str(json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bibank_ledger::TransactionIntent;
+    use chrono::Utc;
+
+    fn entry() -> JournalEntry {
+        JournalEntry {
+            sequence: 1,
+            prev_hash: "GENESIS".to_string(),
+            hash: "abc".to_string(),
+            timestamp: Utc::now(),
+            intent: TransactionIntent::Genesis,
+            correlation_id: "init".to_string(),
+            causality_id: None,
+            postings: Vec::new(),
+            metadata: Default::default(),
+            signatures: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let line = encode(&entry()).unwrap();
+        assert!(line.ends_with('\n'));
+
+        let decoded = decode(line.trim_end_matches('\n')).unwrap();
+        assert_eq!(decoded.sequence, 1);
+        assert_eq!(decoded.hash, "abc");
+    }
+
+    #[test]
+    fn test_detects_bit_flip() {
+        let line = encode(&entry()).unwrap().replace("abc", "abd");
+        assert!(matches!(
+            decode(line.trim_end_matches('\n')),
+            Err(EventError::ChecksumMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_reads_legacy_line() {
+        let json = serde_json::to_string(&entry()).unwrap();
+        assert_eq!(decode(&json).unwrap().sequence, 1);
+        assert!(decode("0000").is_err());
+    }
+}
@@ -0,0 +1,253 @@
+//! Journal segments and their offset indexes
+//!
+//! A segment is a `.jsonl` file of framed records named after the sequence
+//! of its first entry (`seg-00000000000000000001.jsonl`). Next to it, a
+//! `.idx` file holds one fixed-size `(sequence, byte offset)` pair per record
+//! so readers can seek without scanning. The index is derived data: it is
+//! rebuilt from the segment whenever it is missing or stale.
+//!
+//! Daily files from before segmentation (`2026-01-25.jsonl`) sort ahead of
+//! all segments and are read the same way, without an index on disk.
+
+use crate::error::EventError;
+use crate::record;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// File name prefix of segments
+pub const SEGMENT_PREFIX: &str = "seg-";
+
+/// Size of one index record in bytes
+pub(crate) const INDEX_RECORD_SIZE: usize = 16;
+
+/// One index record: where the entry with `sequence` starts in its segment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub sequence: u64,
+    pub offset: u64,
+}
+
+impl IndexEntry {
+    fn to_bytes(self) -> [u8; INDEX_RECORD_SIZE] {
+        let mut buf = [0u8; INDEX_RECORD_SIZE];
+        buf[..8].copy_from_slice(&self.sequence.to_le_bytes());
+        buf[8..].copy_from_slice(&self.offset.to_le_bytes());
+        buf
+    }
+
+    fn from_bytes(buf: &[u8]) -> Self {
+        let mut sequence = [0u8; 8];
+        let mut offset = [0u8; 8];
+        sequence.copy_from_slice(&buf[..8]);
+        offset.copy_from_slice(&buf[8..INDEX_RECORD_SIZE]);
+        Self {
+            sequence: u64::from_le_bytes(sequence),
+            offset: u64::from_le_bytes(offset),
+        }
+    }
+}
+
+/// Result of scanning a segment
+#[derive(Debug, Default)]
+pub struct ScanResult {
+    /// Index of every valid record
+    pub index: Vec<IndexEntry>,
+    /// Length of the valid prefix in bytes
+    pub valid_len: u64,
+    /// Length of the file in bytes
+    pub file_len: u64,
+}
+
+impl ScanResult {
+    /// Whether the segment ends with a torn (partially written) record
+    pub fn is_torn(&self) -> bool {
+        self.valid_len < self.file_len
+    }
+}
+
+/// File name for a segment starting at `first_sequence`
+pub fn segment_file_name(first_sequence: u64) -> String {
+    format!("{}{:020}.jsonl", SEGMENT_PREFIX, first_sequence)
+}
+
+/// First sequence encoded in a segment's file name (None for legacy files)
+pub fn first_sequence(path: &Path) -> Option<u64> {
+    path.file_stem()?
+        .to_str()?
+        .strip_prefix(SEGMENT_PREFIX)?
+        .parse()
+        .ok()
+}
+
+/// Index file path for a segment
+pub fn index_path(segment: &Path) -> PathBuf {
+    segment.with_extension("idx")
+}
+
+/// List all segment files (legacy daily files first), sorted
+pub fn list_segments(dir: &Path) -> Result<Vec<PathBuf>, EventError> {
+    let mut files = Vec::new();
+
+    if dir.exists() {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "jsonl") {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+/// Scan a segment, validating every record
+///
+/// Stops at a torn final record: one missing its newline or failing its
+/// checksum with nothing after it. A bad record followed by more data is
+/// not a torn write and is reported as corruption.
+pub fn scan(segment: &Path) -> Result<ScanResult, EventError> {
+    let file = File::open(segment)?;
+    let file_len = file.metadata()?.len();
+    let mut reader = BufReader::new(file);
+
+    let mut result = ScanResult {
+        file_len,
+        ..Default::default()
+    };
+    let mut offset = 0u64;
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        let read = reader.read_until(b'\n', &mut line)? as u64;
+        if read == 0 {
+            break;
+        }
+        if line.last() != Some(&b'\n') {
+            break; // Torn write: the record never got its newline
+        }
+
+        let decoded = std::str::from_utf8(&line)
+            .map_err(|e| EventError::InvalidFile(e.to_string()))
+            .and_then(|text| match text.trim_end() {
+                "" => Ok(None),
+                content => record::decode(content).map(Some),
+            });
+
+        match decoded {
+            Ok(Some(entry)) => result.index.push(IndexEntry {
+                sequence: entry.sequence,
+                offset,
+            }),
+            Ok(None) => {}
+            Err(e) => {
+                if reader.fill_buf()?.is_empty() {
+                    break; // Torn final record
+                }
+                return Err(corrupted(segment, offset, e.to_string()));
+            }
+        }
+
+        offset += read;
+        result.valid_len = offset;
+    }
+
+    Ok(result)
+}
+
+/// Recover a segment after a crash
+///
+/// Truncates a torn final record and rewrites the index to match.
+/// Returns the index of the surviving records.
+pub fn recover(segment: &Path) -> Result<ScanResult, EventError> {
+    let scan = scan(segment)?;
+
+    if scan.is_torn() {
+        tracing::warn!(
+            "Truncating torn record in {} ({} bytes at offset {})",
+            segment.display(),
+            scan.file_len - scan.valid_len,
+            scan.valid_len
+        );
+        let file = OpenOptions::new().write(true).open(segment)?;
+        file.set_len(scan.valid_len)?;
+        file.sync_all()?;
+    }
+
+    if first_sequence(segment).is_some() {
+        write_index(&index_path(segment), &scan.index)?;
+    }
+
+    Ok(scan)
+}
+
+/// Load a segment's index, rebuilding it in memory if missing or stale
+pub fn load_index(segment: &Path) -> Result<Vec<IndexEntry>, EventError> {
+    let idx_path = index_path(segment);
+
+    if idx_path.exists() {
+        let index = read_index(&idx_path)?;
+        let segment_len = fs::metadata(segment)?.len();
+        let complete = match index.last() {
+            // The last indexed record must end exactly at the end of the segment
+            Some(last) => record_end(segment, last.offset)? == Some(segment_len),
+            None => segment_len == 0,
+        };
+        if complete {
+            return Ok(index);
+        }
+    }
+
+    Ok(scan(segment)?.index)
+}
+
+/// Byte offset just past the record starting at `offset`
+fn record_end(segment: &Path, offset: u64) -> Result<Option<u64>, EventError> {
+    let mut file = File::open(segment)?;
+    if offset >= file.metadata()?.len() {
+        return Ok(None);
+    }
+    file.seek(SeekFrom::Start(offset))?;
+
+    let mut line = Vec::new();
+    let read = BufReader::new(file).read_until(b'\n', &mut line)? as u64;
+    Ok(Some(offset + read))
+}
+
+/// Read an index file
+pub fn read_index(path: &Path) -> Result<Vec<IndexEntry>, EventError> {
+    let mut bytes = Vec::new();
+    File::open(path)?.read_to_end(&mut bytes)?;
+
+    // A partial trailing record is ignored; the index gets rebuilt on recovery
+    Ok(bytes
+        .chunks_exact(INDEX_RECORD_SIZE)
+        .map(IndexEntry::from_bytes)
+        .collect())
+}
+
+/// Write a complete index file
+pub fn write_index(path: &Path, index: &[IndexEntry]) -> Result<(), EventError> {
+    let mut bytes = Vec::with_capacity(index.len() * INDEX_RECORD_SIZE);
+    for entry in index {
+        bytes.extend_from_slice(&entry.to_bytes());
+    }
+    fs::write(path, bytes)?;
+    Ok(())
+}
+
+/// Append one record to an open index file
+pub fn append_index(file: &mut File, entry: IndexEntry) -> Result<(), EventError> {
+    file.write_all(&entry.to_bytes())?;
+    Ok(())
+}
+
+fn corrupted(segment: &Path, offset: u64, reason: String) -> EventError {
+    EventError::Corrupted {
+        file: segment.display().to_string(),
+        offset,
+        reason,
+    }
+}
@@ -1,100 +1,207 @@
-//! JSONL event store - append-only writer
+//! Segmented JSONL event store - append-only writer
 
 use crate::error::EventError;
+use crate::record;
+use crate::segment::{self, IndexEntry};
 use bibank_ledger::JournalEntry;
-use chrono::Utc;
 use std::fs::{self, File, OpenOptions};
-use std::io::{BufWriter, Write};
+use std::io::Write;
 use std::path::{Path, PathBuf};
 
-/// Append-only JSONL event store
+/// Default segment size before rolling to a new file (64 MiB)
+pub const DEFAULT_MAX_SEGMENT_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Event store configuration
+#[derive(Debug, Clone)]
+pub struct StoreConfig {
+    /// Roll to a new segment once the current one reaches this size
+    pub max_segment_bytes: u64,
+    /// fsync the segment after every append
+    pub fsync: bool,
+}
+
+impl StoreConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_segment_bytes(mut self, bytes: u64) -> Self {
+        self.max_segment_bytes = bytes;
+        self
+    }
+
+    pub fn fsync(mut self, fsync: bool) -> Self {
+        self.fsync = fsync;
+        self
+    }
+}
+
+impl Default for StoreConfig {
+    fn default() -> Self {
+        Self {
+            max_segment_bytes: DEFAULT_MAX_SEGMENT_BYTES,
+            fsync: false,
+        }
+    }
+}
+
+/// Segment currently open for appends
+struct ActiveSegment {
+    path: PathBuf,
+    file: File,
+    index: File,
+    len: u64,
+    index_len: u64,
+}
+
+impl ActiveSegment {
+    fn open(path: PathBuf, len: u64) -> Result<Self, EventError> {
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let index = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(segment::index_path(&path))?;
+        let index_len = index.metadata()?.len();
+
+        Ok(Self {
+            path,
+            file,
+            index,
+            len,
+            index_len,
+        })
+    }
+
+    /// Write one encoded record and its index entry
+    fn write_record(&mut self, sequence: u64, line: &str, fsync: bool) -> Result<(), EventError> {
+        // Single write per record so a crash leaves at most one torn line
+        self.file.write_all(line.as_bytes())?;
+        if fsync {
+            self.file.sync_data()?;
+        }
+
+        segment::append_index(
+            &mut self.index,
+            IndexEntry {
+                sequence,
+                offset: self.len,
+            },
+        )
+    }
+
+    /// Cut the segment and its index back to the last complete record
+    fn truncate(&self) -> Result<(), EventError> {
+        self.file.set_len(self.len)?;
+        self.index.set_len(self.index_len)?;
+        Ok(())
+    }
+}
+
+/// Append-only segmented event store
+///
+/// Each record is a checksummed JSON line. Segments roll over by size and
+/// carry an offset index (see [`crate::segment`]). Opening the store
+/// truncates a torn record left at the end of the journal by a crash.
 pub struct EventStore {
     base_path: PathBuf,
-    current_file: Option<BufWriter<File>>,
-    current_date: Option<String>,
+    config: StoreConfig,
+    current: Option<ActiveSegment>,
 }
 
 impl EventStore {
-    /// Create a new event store at the given path
+    /// Create a new event store at the given path with default configuration
     pub fn new(base_path: impl AsRef<Path>) -> Result<Self, EventError> {
+        Self::open(base_path, StoreConfig::default())
+    }
+
+    /// Open the event store, recovering the tail of the journal
+    pub fn open(base_path: impl AsRef<Path>, config: StoreConfig) -> Result<Self, EventError> {
         let base_path = base_path.as_ref().to_path_buf();
         fs::create_dir_all(&base_path)?;
 
+        let mut current = None;
+        if let Some(last) = segment::list_segments(&base_path)?.pop() {
+            let scan = segment::recover(&last)?;
+
+            // Keep appending to the last segment; legacy daily files are left sealed
+            if segment::first_sequence(&last).is_some() {
+                current = Some(ActiveSegment::open(last, scan.valid_len)?);
+            }
+        }
+
         Ok(Self {
             base_path,
-            current_file: None,
-            current_date: None,
+            config,
+            current,
         })
     }
 
+    /// Get the store configuration
+    pub fn config(&self) -> &StoreConfig {
+        &self.config
+    }
+
     /// Append a journal entry to the store
     pub fn append(&mut self, entry: &JournalEntry) -> Result<(), EventError> {
-        let date = entry.timestamp.format("%Y-%m-%d").to_string();
-
-        // Rotate file if date changed
-        if self.current_date.as_ref() != Some(&date) {
-            self.rotate_file(&date)?;
+        let needs_roll = self
+            .current
+            .as_ref()
+            .is_none_or(|active| active.len >= self.config.max_segment_bytes);
+        if needs_roll {
+            self.roll_segment(entry.sequence)?;
         }
 
-        // Write entry as JSON line
-        if let Some(ref mut writer) = self.current_file {
-            let json = serde_json::to_string(entry)?;
-            writeln!(writer, "{}", json)?;
-            writer.flush()?;
+        let fsync = self.config.fsync;
+        let active = self.current.as_mut().ok_or(EventError::NotInitialized)?;
+        let line = record::encode(entry)?;
+
+        // A failed write must not leave a partial record for the next append
+        // to land behind
+        if let Err(e) = active.write_record(entry.sequence, &line, fsync) {
+            if let Err(truncate_err) = active.truncate() {
+                tracing::warn!(
+                    "Failed to truncate {} after append error: {}",
+                    active.path.display(),
+                    truncate_err
+                );
+            }
+            return Err(e);
         }
 
+        active.len += line.len() as u64;
+        active.index_len += segment::INDEX_RECORD_SIZE as u64;
+
         Ok(())
     }
 
-    /// Rotate to a new file for the given date
-    fn rotate_file(&mut self, date: &str) -> Result<(), EventError> {
-        // Flush current file
-        if let Some(ref mut writer) = self.current_file {
-            writer.flush()?;
-        }
+    /// Start a new segment whose first entry is `first_sequence`
+    fn roll_segment(&mut self, first_sequence: u64) -> Result<(), EventError> {
+        self.close()?;
 
-        // Open new file
-        let file_path = self.base_path.join(format!("{}.jsonl", date));
-        let file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(&file_path)?;
-
-        self.current_file = Some(BufWriter::new(file));
-        self.current_date = Some(date.to_string());
+        let path = self
+            .base_path
+            .join(segment::segment_file_name(first_sequence));
+        self.current = Some(ActiveSegment::open(path, 0)?);
 
         Ok(())
     }
 
-    /// Get the path to today's file
-    pub fn today_file_path(&self) -> PathBuf {
-        let date = Utc::now().format("%Y-%m-%d").to_string();
-        self.base_path.join(format!("{}.jsonl", date))
+    /// Path of the segment currently being appended to
+    pub fn current_segment_path(&self) -> Option<&Path> {
+        self.current.as_ref().map(|active| active.path.as_path())
     }
 
-    /// List all JSONL files in the store
+    /// List all segment files in the store
     pub fn list_files(&self) -> Result<Vec<PathBuf>, EventError> {
-        let mut files = Vec::new();
-
-        for entry in fs::read_dir(&self.base_path)? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.extension().map_or(false, |ext| ext == "jsonl") {
-                files.push(path);
-            }
-        }
-
-        files.sort();
-        Ok(files)
+        segment::list_segments(&self.base_path)
     }
 
-    /// Flush and close the current file
+    /// Sync and close the current segment
     pub fn close(&mut self) -> Result<(), EventError> {
-        if let Some(ref mut writer) = self.current_file {
-            writer.flush()?;
+        if let Some(active) = self.current.take() {
+            active.file.sync_all()?;
+            active.index.sync_all()?;
         }
-        self.current_file = None;
-        self.current_date = None;
         Ok(())
     }
 }
@@ -104,3 +211,119 @@ impl Drop for EventStore {
         let _ = self.close();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::EventReader;
+    use crate::testing::deposit_entry;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_segments_roll_by_size() {
+        let dir = TempDir::new().unwrap();
+        let config = StoreConfig::new().max_segment_bytes(1);
+        let mut store = EventStore::open(dir.path(), config).unwrap();
+
+        for seq in 1..=3 {
+            store.append(&deposit_entry(seq)).unwrap();
+        }
+
+        let files = store.list_files().unwrap();
+        assert_eq!(files.len(), 3);
+        assert_eq!(segment::first_sequence(&files[2]), Some(3));
+        assert!(segment::index_path(&files[2]).exists());
+    }
+
+    #[test]
+    fn test_torn_tail_truncated_on_open() {
+        let dir = TempDir::new().unwrap();
+        let path = {
+            let mut store = EventStore::new(dir.path()).unwrap();
+            store.append(&deposit_entry(1)).unwrap();
+            store.append(&deposit_entry(2)).unwrap();
+            store.current_segment_path().unwrap().to_path_buf()
+        };
+        let good_len = fs::metadata(&path).unwrap().len();
+
+        // Crash mid-write: half a record without its newline
+        let torn = record::encode(&deposit_entry(3)).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(&torn.as_bytes()[..torn.len() / 2]).unwrap();
+        drop(file);
+
+        let mut store = EventStore::new(dir.path()).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), good_len);
+
+        // Appends continue in the same segment and the index stays in step
+        store.append(&deposit_entry(3)).unwrap();
+        let reader = EventReader::from_directory(dir.path()).unwrap();
+        let sequences: Vec<u64> = reader.read_all().unwrap().iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![1, 2, 3]);
+        assert_eq!(segment::load_index(&path).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_failed_append_truncates_partial_write() {
+        let dir = TempDir::new().unwrap();
+        let mut store = EventStore::new(dir.path()).unwrap();
+        store.append(&deposit_entry(1)).unwrap();
+        let path = store.current_segment_path().unwrap().to_path_buf();
+        let good_len = fs::metadata(&path).unwrap().len();
+
+        // Segment write succeeds, index write fails on a read-only handle
+        let index_path = segment::index_path(&path);
+        let active = store.current.as_mut().unwrap();
+        let writable = std::mem::replace(&mut active.index, File::open(&index_path).unwrap());
+        assert!(store.append(&deposit_entry(2)).is_err());
+        assert_eq!(fs::metadata(&path).unwrap().len(), good_len);
+
+        // The retried append lands right after the last good record
+        store.current.as_mut().unwrap().index = writable;
+        store.append(&deposit_entry(2)).unwrap();
+        let reader = EventReader::from_directory(dir.path()).unwrap();
+        let sequences: Vec<u64> = reader.read_all().unwrap().iter().map(|e| e.sequence).collect();
+        assert_eq!(sequences, vec![1, 2]);
+        assert_eq!(segment::load_index(&path).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_bad_checksum_on_last_line_truncated() {
+        let dir = TempDir::new().unwrap();
+        let path = {
+            let mut store = EventStore::new(dir.path()).unwrap();
+            store.append(&deposit_entry(1)).unwrap();
+            store.current_segment_path().unwrap().to_path_buf()
+        };
+
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"00000000 {\"sequence\":2}\n").unwrap();
+        drop(file);
+
+        EventStore::new(dir.path()).unwrap();
+        let reader = EventReader::from_directory(dir.path()).unwrap();
+        assert_eq!(reader.last_sequence().unwrap(), Some(1));
+    }
+
+    #[test]
+    fn test_mid_segment_corruption_is_an_error() {
+        let dir = TempDir::new().unwrap();
+        let path = {
+            let mut store = EventStore::new(dir.path()).unwrap();
+            store.append(&deposit_entry(1)).unwrap();
+            store.append(&deposit_entry(2)).unwrap();
+            store.current_segment_path().unwrap().to_path_buf()
+        };
+
+        // Flip a byte inside the first record
+        let mut bytes = fs::read(&path).unwrap();
+        let pos = bytes.iter().position(|b| *b == b'{').unwrap() + 2;
+        bytes[pos] ^= 0x01;
+        fs::write(&path, bytes).unwrap();
+
+        assert!(matches!(
+            EventStore::new(dir.path()),
+            Err(EventError::Corrupted { offset: 0, .. })
+        ));
+    }
+}
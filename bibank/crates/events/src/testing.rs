@@ -0,0 +1,27 @@
+//! Shared test fixtures
+
+use bibank_core::Amount;
+use bibank_ledger::{AccountKey, JournalEntry, Posting, TransactionIntent};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+/// Deposit of 100 USDT to ALICE at `sequence`, chained to `sequence - 1`
+pub(crate) fn deposit_entry(sequence: u64) -> JournalEntry {
+    let amount = Amount::new(Decimal::new(100, 0)).unwrap();
+    JournalEntry {
+        sequence,
+        prev_hash: format!("hash-{}", sequence - 1),
+        hash: format!("hash-{}", sequence),
+        timestamp: Utc::now(),
+        intent: TransactionIntent::Deposit,
+        correlation_id: format!("corr-{}", sequence),
+        causality_id: None,
+        postings: vec![
+            Posting::debit(AccountKey::system_vault("USDT"), amount),
+            Posting::credit(AccountKey::user_available("ALICE", "USDT"), amount),
+        ],
+        metadata: HashMap::new(),
+        signatures: Vec::new(),
+    }
+}
@@ -317,8 +317,10 @@ mod tests {
     #[tokio::test]
     async fn test_sanctions_hook_metadata_watchlist() {
         let hook = SanctionsHook::new(10);
-        let mut metadata = HookMetadata::default();
-        metadata.is_watchlisted = true;
+        let metadata = HookMetadata {
+            is_watchlisted: true,
+            ..Default::default()
+        };
 
         let ctx = HookContext::new(
             "corr-1",
@@ -353,8 +355,10 @@ mod tests {
     #[tokio::test]
     async fn test_pep_hook_pep_below_threshold() {
         let hook = PepCheckHook::new(20, dec!(10000));
-        let mut metadata = HookMetadata::default();
-        metadata.is_pep = true;
+        let metadata = HookMetadata {
+            is_pep: true,
+            ..Default::default()
+        };
 
         let ctx = HookContext::new(
             "corr-1",
@@ -371,8 +375,10 @@ mod tests {
     #[tokio::test]
     async fn test_pep_hook_pep_above_threshold() {
         let hook = PepCheckHook::new(20, dec!(10000));
-        let mut metadata = HookMetadata::default();
-        metadata.is_pep = true;
+        let metadata = HookMetadata {
+            is_pep: true,
+            ..Default::default()
+        };
 
         let ctx = HookContext::new(
             "corr-1",
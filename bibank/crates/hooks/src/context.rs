@@ -1,5 +1,6 @@
 //! Hook context - data passed to hooks
 
+use bibank_compliance::engine::TransactionContext;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -104,6 +105,18 @@ impl HookContext {
         self.metadata.is_watchlisted = watchlisted;
         self
     }
+
+    /// Context for the Compliance Engine rules
+    pub fn to_transaction_context(&self) -> TransactionContext {
+        TransactionContext {
+            correlation_id: self.correlation_id.clone(),
+            user_id: self.user_id.clone(),
+            intent: self.intent.clone(),
+            amount: self.amount,
+            asset: self.asset.clone(),
+            account_age_days: self.metadata.account_age_days,
+        }
+    }
 }
 
 #[cfg(test)]
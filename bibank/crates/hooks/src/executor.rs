@@ -33,11 +33,11 @@
 
 use std::sync::Arc;
 
-use bibank_compliance::{AmlDecision, ComplianceEngine};
+use bibank_compliance::{AmlDecision, CheckResult, ComplianceEngine};
 use tokio::sync::RwLock;
 
 use crate::context::HookContext;
-use crate::error::HookResult;
+use crate::error::{HookError, HookResult};
 use crate::registry::HookRegistry;
 use crate::traits::HookDecision;
 
@@ -56,6 +56,8 @@ pub struct ExecutionResult {
     pub lock_applied: bool,
     /// Block reason (if blocked)
     pub block_reason: Option<String>,
+    /// TransactionFlagged event id (if flagged and recorded)
+    pub flag_id: Option<String>,
 }
 
 impl ExecutionResult {
@@ -68,6 +70,7 @@ impl ExecutionResult {
             rules_triggered: vec![],
             lock_applied: false,
             block_reason: Some(reason),
+            flag_id: None,
         }
     }
 
@@ -80,6 +83,7 @@ impl ExecutionResult {
             rules_triggered: vec![],
             lock_applied: false,
             block_reason: None,
+            flag_id: None,
         }
     }
 
@@ -92,6 +96,7 @@ impl ExecutionResult {
             rules_triggered: rules,
             lock_applied: true,
             block_reason: None,
+            flag_id: None,
         }
     }
 }
@@ -100,14 +105,14 @@ impl ExecutionResult {
 ///
 /// This is the main orchestrator for processing transactions with:
 /// - Pre-validation hooks (BLOCK rules)
-/// - Ledger commit simulation
+/// - Ledger commit (done by the caller between [`Self::pre_validate`]
+///   and [`Self::post_commit`])
 /// - Post-commit hooks (FLAG rules)
 /// - Lock application for flagged transactions
 pub struct TransactionExecutor {
     /// Hook registry
     registry: Arc<HookRegistry>,
     /// Compliance engine (optional - for full rule evaluation)
-    #[allow(dead_code)]
     compliance_engine: Option<Arc<RwLock<ComplianceEngine>>>,
 }
 
@@ -137,48 +142,87 @@ impl TransactionExecutor {
     /// - `Ok(ExecutionResult)` with the outcome (committed, blocked, flagged)
     /// - `Err(HookError)` if hooks fail and FailPolicy is FailClosed
     pub async fn execute(&self, ctx: &HookContext) -> HookResult<ExecutionResult> {
-        let correlation_id = ctx.correlation_id.clone();
-
         // === Phase 1: Pre-validation hooks ===
-        let pre_decision = self.registry.run_pre_validation(ctx).await?;
-
-        match pre_decision {
-            HookDecision::Block { reason, .. } => {
-                // Transaction blocked - never committed
-                return Ok(ExecutionResult::blocked(correlation_id, reason));
-            }
-            HookDecision::Allow => {
-                // Continue to commit
-            }
+        if let HookDecision::Block { reason, .. } = self.pre_validate(ctx).await? {
+            // Transaction blocked - never committed
+            return Ok(ExecutionResult::blocked(ctx.correlation_id.clone(), reason));
         }
 
-        // === Phase 2: Ledger Commit (simulated) ===
-        // In real implementation, this would call bibank-ledger
-        // to append the transaction to the journal
+        // === Phase 2: Ledger Commit ===
+        // Done by the caller between pre_validate and post_commit
 
         // === Phase 3: Post-commit hooks ===
-        let post_result = self.registry.run_post_commit(ctx).await?;
+        self.post_commit(ctx).await
+    }
+
+    /// Run pre-validation hooks
+    ///
+    /// Blocks are written to the Compliance Ledger (if an engine is set);
+    /// allowed transactions are recorded once, by [`Self::post_commit`].
+    pub async fn pre_validate(&self, ctx: &HookContext) -> HookResult<HookDecision> {
+        let decision = self.registry.run_pre_validation(ctx).await?;
+
+        if let HookDecision::Block { reason, code } = &decision {
+            let result = CheckResult {
+                decision: AmlDecision::blocked(reason, code),
+                rules_triggered: vec![code.clone()],
+                risk_score: None,
+            };
+            self.record(ctx, &result).await?;
+        }
+
+        Ok(decision)
+    }
+
+    /// Run post-commit hooks and the compliance engine rules
+    ///
+    /// Decisions are aggregated (most restrictive wins) and written to the
+    /// Compliance Ledger. A flagged result has `lock_applied` set: the
+    /// caller is expected to move the funds into review.
+    pub async fn post_commit(&self, ctx: &HookContext) -> HookResult<ExecutionResult> {
+        let mut result = self.registry.run_post_commit(ctx).await?;
+
+        if let Some(engine) = &self.compliance_engine {
+            let engine_result = engine.read().await.evaluate(&ctx.to_transaction_context());
+            result = merge(result, engine_result);
+        }
 
-        // Aggregate decision (using max lattice)
-        let final_decision = post_result.decision;
-        let rules = post_result.rules_triggered;
+        let flag_id = self.record(ctx, &result).await?;
 
         // === Phase 4: Apply lock if flagged ===
-        if final_decision.is_flagged() {
-            // In real implementation, this would:
-            // 1. Write LockApplied event to main journal
-            // 2. Write TransactionFlagged event to compliance ledger
-
-            Ok(ExecutionResult::flagged(
-                correlation_id,
-                final_decision,
-                rules,
-            ))
+        if result.decision.is_flagged() {
+            let mut flagged = ExecutionResult::flagged(
+                ctx.correlation_id.clone(),
+                result.decision,
+                result.rules_triggered,
+            );
+            flagged.flag_id = flag_id;
+            Ok(flagged)
         } else {
-            Ok(ExecutionResult::approved(correlation_id))
+            Ok(ExecutionResult {
+                rules_triggered: result.rules_triggered,
+                ..ExecutionResult::approved(ctx.correlation_id.clone())
+            })
         }
     }
 
+    /// Write a decision to the Compliance Ledger (no-op without an engine)
+    async fn record(&self, ctx: &HookContext, result: &CheckResult) -> HookResult<Option<String>> {
+        let Some(engine) = &self.compliance_engine else {
+            return Ok(None);
+        };
+        engine
+            .write()
+            .await
+            .record(&ctx.to_transaction_context(), result)
+            .map_err(|e| HookError::Internal(format!("compliance ledger: {}", e)))
+    }
+
+    /// The compliance engine, if one is set
+    pub fn compliance_engine(&self) -> Option<&Arc<RwLock<ComplianceEngine>>> {
+        self.compliance_engine.as_ref()
+    }
+
     /// Execute a batch of transactions
     pub async fn execute_batch(
         &self,
@@ -194,6 +238,22 @@ impl TransactionExecutor {
     }
 }
 
+/// Combine two check results: most restrictive decision, all rules
+fn merge(a: CheckResult, b: CheckResult) -> CheckResult {
+    let mut rules_triggered = a.rules_triggered;
+    for rule in b.rules_triggered {
+        if !rules_triggered.contains(&rule) {
+            rules_triggered.push(rule);
+        }
+    }
+
+    CheckResult {
+        decision: AmlDecision::aggregate([a.decision, b.decision]),
+        rules_triggered,
+        risk_score: a.risk_score.max(b.risk_score),
+    }
+}
+
 /// Builder for TransactionExecutor
 pub struct ExecutorBuilder {
     registry: HookRegistry,
@@ -309,8 +369,10 @@ mod tests {
     async fn test_executor_blocked_by_pep() {
         let executor = TransactionExecutor::new(Arc::new(create_registry_with_hooks()));
 
-        let mut metadata = HookMetadata::default();
-        metadata.is_pep = true;
+        let metadata = HookMetadata {
+            is_pep: true,
+            ..Default::default()
+        };
 
         let ctx = HookContext::new(
             "tx-003",
@@ -395,6 +457,37 @@ mod tests {
         assert!(results[2].as_ref().unwrap().decision.is_flagged());
     }
 
+    #[tokio::test]
+    async fn test_executor_records_decisions_with_engine() {
+        let executor = ExecutorBuilder::new()
+            .with_registry(create_registry_with_hooks())
+            .with_compliance_engine(ComplianceEngine::in_memory())
+            .build();
+
+        // Blocked before commit: recorded, never counted
+        let ctx = HookContext::new("tx-006", "blocked-user", "DEPOSIT", dec!(100), "USDT");
+        assert!(executor.pre_validate(&ctx).await.unwrap().is_blocked());
+
+        // Engine rules are merged with the hook results
+        let ctx = HookContext::new("tx-007", "normal-user", "DEPOSIT", dec!(15000), "USDT")
+            .with_account_age(30);
+        assert!(executor.pre_validate(&ctx).await.unwrap().is_allowed());
+        let result = executor.post_commit(&ctx).await.unwrap();
+
+        assert!(result.decision.is_flagged());
+        assert!(result.lock_applied);
+        assert!(result.flag_id.is_some());
+        assert!(result.rules_triggered.contains(&"CTR_THRESHOLD".to_string()));
+        assert_eq!(
+            result.rules_triggered.iter().filter(|r| *r == "LARGE_TX_ALERT").count(),
+            1
+        );
+
+        let engine = executor.compliance_engine().unwrap().read().await;
+        assert!(engine.state().has_user("normal-user"));
+        assert!(!engine.state().has_user("blocked-user"));
+    }
+
     #[tokio::test]
     async fn test_builder() {
         let registry = create_registry_with_hooks();
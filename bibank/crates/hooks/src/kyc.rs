@@ -0,0 +1,270 @@
+//! KYC tier limits
+//!
+//! [`KycLimitHook`] blocks withdrawals that would take a user past the
+//! rolling daily (24h) or monthly (30 day) limit of their KYC tier. Users
+//! without a tier in the context (not registered) get tier 0's limits.
+//! Limits are expressed in one asset; withdrawals of every asset count
+//! towards them at the oracle's last price, and a missing price fails the
+//! hook.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+
+use bibank_compliance::KycLimits;
+use bibank_oracle::{PriceOracle, TradingPair};
+
+use crate::context::HookContext;
+use crate::error::{HookError, HookResult};
+use crate::traits::{HookDecision, PreValidationHook};
+
+/// Source of committed withdrawal totals
+#[async_trait]
+pub trait WithdrawalVolume: Send + Sync {
+    /// Totals per asset the user withdrew at or after `since`
+    async fn withdrawn_since(&self, user_id: &str, since: DateTime<Utc>) -> HookResult<BTreeMap<String, Decimal>>;
+}
+
+/// Pre-validation hook enforcing rolling withdrawal limits per KYC tier
+pub struct KycLimitHook {
+    priority: u32,
+    /// Limits per tier, in `limit_asset` (tiers not listed are unlimited)
+    limits: BTreeMap<u8, KycLimits>,
+    limit_asset: String,
+    volume: Arc<dyn WithdrawalVolume>,
+    oracle: Arc<dyn PriceOracle>,
+}
+
+impl KycLimitHook {
+    /// Create a new KYC limit hook
+    pub fn new(
+        priority: u32,
+        limits: BTreeMap<u8, KycLimits>,
+        limit_asset: &str,
+        volume: Arc<dyn WithdrawalVolume>,
+        oracle: Arc<dyn PriceOracle>,
+    ) -> Self {
+        Self {
+            priority,
+            limits,
+            limit_asset: limit_asset.to_uppercase(),
+            volume,
+            oracle,
+        }
+    }
+
+    /// Value of `amount` of `asset` in the limit asset
+    async fn value(&self, asset: &str, amount: Decimal) -> HookResult<Decimal> {
+        if asset.eq_ignore_ascii_case(&self.limit_asset) {
+            return Ok(amount);
+        }
+        let price = self
+            .oracle
+            .get_price(&TradingPair::new(asset, &self.limit_asset))
+            .await
+            .map_err(|e| HookError::ExternalService(e.to_string()))?;
+        Ok(amount * price.last)
+    }
+
+    async fn check_window(
+        &self,
+        ctx: &HookContext,
+        value: Decimal,
+        limit: Option<Decimal>,
+        window: Duration,
+        name: &str,
+        code: &str,
+    ) -> HookResult<HookDecision> {
+        let Some(limit) = limit else {
+            return Ok(HookDecision::Allow);
+        };
+        let mut withdrawn = Decimal::ZERO;
+        for (asset, amount) in self.volume.withdrawn_since(&ctx.user_id, ctx.timestamp - window).await? {
+            withdrawn += self.value(&asset, amount).await?;
+        }
+
+        if withdrawn + value > limit {
+            let amount = if ctx.asset.eq_ignore_ascii_case(&self.limit_asset) {
+                format!("{} {}", ctx.amount, ctx.asset)
+            } else {
+                format!("{} {} ({} {})", ctx.amount, ctx.asset, value.normalize(), self.limit_asset)
+            };
+            return Ok(HookDecision::block(
+                format!(
+                    "Withdrawal of {} exceeds {} limit {} {} (already withdrawn {} {})",
+                    amount,
+                    name,
+                    limit,
+                    self.limit_asset,
+                    withdrawn.normalize(),
+                    self.limit_asset
+                ),
+                code,
+            ));
+        }
+        Ok(HookDecision::Allow)
+    }
+}
+
+#[async_trait]
+impl PreValidationHook for KycLimitHook {
+    fn name(&self) -> &str {
+        "kyc_limit_hook"
+    }
+
+    fn priority(&self) -> u32 {
+        self.priority
+    }
+
+    async fn on_pre_validation(&self, ctx: &HookContext) -> HookResult<HookDecision> {
+        if ctx.intent != "Withdrawal" {
+            return Ok(HookDecision::Allow);
+        }
+        let tier = ctx.metadata.kyc_level.unwrap_or(0);
+        let limits = self.limits.get(&tier).copied().unwrap_or_default();
+        if limits == KycLimits::default() {
+            return Ok(HookDecision::Allow);
+        }
+        let value = self.value(&ctx.asset, ctx.amount).await?;
+
+        let daily = self
+            .check_window(ctx, value, limits.daily_withdrawal, Duration::hours(24), "daily", "KYC_DAILY_LIMIT")
+            .await?;
+        if daily.is_blocked() {
+            return Ok(daily);
+        }
+        self.check_window(ctx, value, limits.monthly_withdrawal, Duration::days(30), "monthly", "KYC_MONTHLY_LIMIT")
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bibank_oracle::MockOracle;
+    use rust_decimal_macros::dec;
+    use std::sync::Mutex;
+
+    /// Withdrawal held in memory: (user, asset, amount, time)
+    type Withdrawal = (String, String, Decimal, DateTime<Utc>);
+
+    #[derive(Default)]
+    struct History(Mutex<Vec<Withdrawal>>);
+
+    impl History {
+        fn add(&self, user: &str, amount: Decimal, days_ago: i64) {
+            self.add_asset(user, "USDT", amount, days_ago);
+        }
+
+        fn add_asset(&self, user: &str, asset: &str, amount: Decimal, days_ago: i64) {
+            let at = Utc::now() - Duration::days(days_ago);
+            self.0.lock().unwrap().push((user.to_string(), asset.to_string(), amount, at));
+        }
+    }
+
+    #[async_trait]
+    impl WithdrawalVolume for History {
+        async fn withdrawn_since(&self, user_id: &str, since: DateTime<Utc>) -> HookResult<BTreeMap<String, Decimal>> {
+            let mut totals: BTreeMap<String, Decimal> = BTreeMap::new();
+            for (_, asset, amount, _) in self.0.lock().unwrap().iter().filter(|(u, _, _, at)| u == user_id && *at >= since) {
+                *totals.entry(asset.clone()).or_default() += *amount;
+            }
+            Ok(totals)
+        }
+    }
+
+    fn hook(history: Arc<History>) -> KycLimitHook {
+        let limits = BTreeMap::from([
+            (0, KycLimits::new(dec!(100), dec!(500))),
+            (1, KycLimits::new(dec!(1000), dec!(5000))),
+        ]);
+        let oracle = MockOracle::new();
+        oracle.set_price(TradingPair::btc_usdt(), dec!(10000));
+        KycLimitHook::new(15, limits, "USDT", history, Arc::new(oracle))
+    }
+
+    fn withdrawal(user: &str, amount: Decimal) -> HookContext {
+        HookContext::new("TX-001", user, "Withdrawal", amount, "USDT").with_kyc_level(1)
+    }
+
+    #[tokio::test]
+    async fn test_daily_limit() {
+        let history = Arc::new(History::default());
+        history.add("ALICE", dec!(600), 0);
+        let hook = hook(history);
+
+        assert!(hook.on_pre_validation(&withdrawal("ALICE", dec!(400))).await.unwrap().is_allowed());
+        let decision = hook.on_pre_validation(&withdrawal("ALICE", dec!(401))).await.unwrap();
+        assert_eq!(
+            decision,
+            HookDecision::block(
+                "Withdrawal of 401 USDT exceeds daily limit 1000 USDT (already withdrawn 600 USDT)",
+                "KYC_DAILY_LIMIT"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_monthly_limit() {
+        let history = Arc::new(History::default());
+        for days_ago in [2, 5, 10, 20] {
+            history.add("ALICE", dec!(1000), days_ago);
+        }
+        history.add("ALICE", dec!(500), 25);
+        history.add("ALICE", dec!(1000), 31); // outside the window
+        let hook = hook(history);
+
+        assert!(hook.on_pre_validation(&withdrawal("ALICE", dec!(500))).await.unwrap().is_allowed());
+        let decision = hook.on_pre_validation(&withdrawal("ALICE", dec!(501))).await.unwrap();
+        assert!(matches!(decision, HookDecision::Block { code, .. } if code == "KYC_MONTHLY_LIMIT"));
+    }
+
+    #[tokio::test]
+    async fn test_limits_count_every_asset_at_oracle_price() {
+        let history = Arc::new(History::default());
+        history.add_asset("ALICE", "BTC", dec!(0.06), 0);
+        let hook = hook(history);
+
+        // 0.06 BTC already counts 600 USDT towards the daily 1000
+        assert!(hook.on_pre_validation(&withdrawal("ALICE", dec!(400))).await.unwrap().is_allowed());
+        let btc = HookContext::new("TX-002", "ALICE", "Withdrawal", dec!(0.05), "BTC").with_kyc_level(1);
+        assert_eq!(
+            hook.on_pre_validation(&btc).await.unwrap(),
+            HookDecision::block(
+                "Withdrawal of 0.05 BTC (500 USDT) exceeds daily limit 1000 USDT (already withdrawn 600 USDT)",
+                "KYC_DAILY_LIMIT"
+            )
+        );
+
+        // Without a price the withdrawal can't be valued
+        let eth = HookContext::new("TX-003", "ALICE", "Withdrawal", dec!(1), "ETH").with_kyc_level(1);
+        assert!(hook.on_pre_validation(&eth).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_unlimited_cases() {
+        let hook = hook(Arc::new(History::default()));
+
+        // Deposits and tiers without limits pass
+        let deposit = HookContext::new("TX-001", "ALICE", "Deposit", dec!(1_000_000), "USDT").with_kyc_level(1);
+        let tier_4 = HookContext::new("TX-003", "CAROL", "Withdrawal", dec!(1_000_000), "USDT").with_kyc_level(4);
+
+        for ctx in [deposit, tier_4] {
+            assert!(hook.on_pre_validation(&ctx).await.unwrap().is_allowed());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unregistered_user_gets_tier_0_limits() {
+        let hook = hook(Arc::new(History::default()));
+
+        let within = HookContext::new("TX-001", "BOB", "Withdrawal", dec!(100), "USDT");
+        assert!(hook.on_pre_validation(&within).await.unwrap().is_allowed());
+        let over = HookContext::new("TX-002", "BOB", "Withdrawal", dec!(101), "USDT");
+        let decision = hook.on_pre_validation(&over).await.unwrap();
+        assert!(matches!(decision, HookDecision::Block { code, .. } if code == "KYC_DAILY_LIMIT"));
+    }
+}
@@ -27,6 +27,7 @@ pub mod aml;
 pub mod context;
 pub mod error;
 pub mod executor;
+pub mod kyc;
 pub mod registry;
 pub mod traits;
 
@@ -34,5 +35,6 @@ pub use aml::{LargeTxHook, NewAccountHook, PepCheckHook, SanctionsHook};
 pub use context::{HookContext, HookMetadata};
 pub use error::{HookError, HookResult};
 pub use executor::{ExecutionResult, ExecutorBuilder, TransactionExecutor};
+pub use kyc::{KycLimitHook, WithdrawalVolume};
 pub use registry::HookRegistry;
 pub use traits::{HookDecision, PostCommitHook, PreValidationHook};
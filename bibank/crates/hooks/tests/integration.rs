@@ -12,7 +12,7 @@ use bibank_dsl::{
     RuleEvaluator, RuleSet,
 };
 use bibank_hooks::{
-    HookContext, HookMetadata, HookRegistry, LargeTxHook, NewAccountHook, PepCheckHook,
+    HookContext, HookRegistry, LargeTxHook, NewAccountHook, PepCheckHook,
     SanctionsHook, TransactionExecutor,
 };
 
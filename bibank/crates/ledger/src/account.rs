@@ -128,6 +128,17 @@ impl AccountKey {
         )
     }
 
+    /// Create a user review account (funds held for compliance review)
+    pub fn user_review(user_id: impl Into<String>, asset: impl Into<String>) -> Self {
+        Self::new(
+            AccountCategory::Liability,
+            "USER",
+            user_id,
+            asset,
+            "REVIEW",
+        )
+    }
+
     /// Create a system vault account
     pub fn system_vault(asset: impl Into<String>) -> Self {
         Self::new(AccountCategory::Asset, "SYSTEM", "VAULT", asset, "MAIN")
@@ -137,6 +148,16 @@ impl AccountKey {
     pub fn fee_revenue(asset: impl Into<String>) -> Self {
         Self::new(AccountCategory::Revenue, "SYSTEM", "FEE", asset, "REVENUE")
     }
+
+    /// Create a trading fee account (maker/taker fees on fills)
+    pub fn trading_fee(asset: impl Into<String>) -> Self {
+        Self::new(AccountCategory::Revenue, "SYSTEM", "FEE", asset, "TRADING")
+    }
+
+    /// Create a rounding dust account (residue swept from locked balances)
+    pub fn dust(asset: impl Into<String>) -> Self {
+        Self::new(AccountCategory::Revenue, "SYSTEM", "DUST", asset, "ROUNDING")
+    }
 }
 
 impl fmt::Display for AccountKey {
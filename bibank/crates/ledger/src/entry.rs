@@ -3,12 +3,15 @@
 use crate::account::AccountKey;
 use crate::error::LedgerError;
 use crate::signature::EntrySignature;
-use bibank_core::Amount;
+use bibank_core::{Amount, Currency};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Correlation id prefix of reversals (followed by the reversed sequence)
+pub const REVERSAL_PREFIX: &str = "reversal-";
+
 /// Transaction intent - Financial primitive (NOT workflow)
 ///
 /// Each intent represents a specific type of financial operation.
@@ -56,6 +59,27 @@ pub enum TransactionIntent {
 
     /// Order cancellation (unlock funds)
     OrderCancel,
+
+    // === Corrections ===
+
+    /// Compensating entry mirroring a committed one (requires approval)
+    Reversal,
+}
+
+impl TransactionIntent {
+    /// Check if committed entries of this intent can be reversed
+    ///
+    /// Genesis and reversals can't; order placements and cancellations
+    /// are undone by cancelling, so the order book stays in step.
+    pub fn is_reversible(&self) -> bool {
+        !matches!(
+            self,
+            TransactionIntent::Genesis
+                | TransactionIntent::Reversal
+                | TransactionIntent::OrderPlace
+                | TransactionIntent::OrderCancel
+        )
+    }
 }
 
 /// Posting side - Debit or Credit
@@ -69,6 +93,16 @@ pub enum Side {
     Credit,
 }
 
+impl Side {
+    /// The other side
+    pub fn opposite(&self) -> Side {
+        match self {
+            Side::Debit => Side::Credit,
+            Side::Credit => Side::Debit,
+        }
+    }
+}
+
 /// A single posting within a journal entry
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Posting {
@@ -92,6 +126,27 @@ impl Posting {
         }
     }
 
+    /// Create a posting, rejecting amounts finer than the asset's minimum unit
+    pub fn checked(account: AccountKey, amount: Amount, side: Side) -> Result<Self, LedgerError> {
+        let posting = Self::new(account, amount, side);
+        posting.check_scale()?;
+        Ok(posting)
+    }
+
+    /// Check the amount is a whole number of the asset's minimum units
+    pub fn check_scale(&self) -> Result<(), LedgerError> {
+        let currency = Currency::from(self.account.asset.as_str());
+        if currency.fits(self.amount.value()) {
+            Ok(())
+        } else {
+            Err(LedgerError::ExcessPrecision {
+                account: self.account.to_string(),
+                amount: self.amount.value(),
+                scale: currency.scale(),
+            })
+        }
+    }
+
     /// Create a debit posting
     pub fn debit(account: AccountKey, amount: Amount) -> Self {
         Self::new(account, amount, Side::Debit)
@@ -204,6 +259,46 @@ impl JournalEntry {
         Ok(())
     }
 
+    /// Compensating entry: every posting mirrored, caused by this entry
+    pub fn reversal(&self, reason: &str) -> UnsignedEntry {
+        let mut metadata = HashMap::new();
+        metadata.insert("reversed_sequence".to_string(), serde_json::json!(self.sequence));
+        metadata.insert("reversed_hash".to_string(), serde_json::json!(self.hash));
+        metadata.insert("reason".to_string(), serde_json::json!(reason));
+
+        UnsignedEntry {
+            intent: TransactionIntent::Reversal,
+            correlation_id: format!("{}{}", REVERSAL_PREFIX, self.sequence),
+            causality_id: Some(self.correlation_id.clone()),
+            postings: self
+                .postings
+                .iter()
+                .map(|p| Posting::new(p.account.clone(), p.amount, p.side.opposite()))
+                .collect(),
+            metadata,
+        }
+    }
+
+    /// Check if this committed entry can be reversed
+    ///
+    /// On top of the intent's rule, order-book fills can't: the book keeps
+    /// the fill applied, so mirrored funds would back no order.
+    pub fn is_reversible(&self) -> bool {
+        let order_fill = self.intent == TransactionIntent::Trade
+            && ["maker_order_id", "taker_order_id"]
+                .iter()
+                .any(|key| self.metadata.contains_key(*key));
+        self.intent.is_reversible() && !order_fill
+    }
+
+    /// Sequence this entry reverses (reversals only)
+    pub fn reversed_sequence(&self) -> Option<u64> {
+        if self.intent != TransactionIntent::Reversal {
+            return None;
+        }
+        self.metadata.get("reversed_sequence").and_then(|v| v.as_u64())
+    }
+
     /// Get all unique assets in this entry
     pub fn assets(&self) -> Vec<String> {
         let mut assets: Vec<_> = self
@@ -225,6 +320,7 @@ pub struct JournalEntryBuilder {
     causality_id: Option<String>,
     postings: Vec<Posting>,
     metadata: HashMap<String, serde_json::Value>,
+    error: Option<LedgerError>,
 }
 
 impl JournalEntryBuilder {
@@ -247,18 +343,26 @@ impl JournalEntryBuilder {
         self
     }
 
-    pub fn posting(mut self, posting: Posting) -> Self {
-        self.postings.push(posting);
-        self
+    pub fn posting(self, posting: Posting) -> Self {
+        self.push(Posting::checked(posting.account, posting.amount, posting.side))
     }
 
-    pub fn debit(mut self, account: AccountKey, amount: Amount) -> Self {
-        self.postings.push(Posting::debit(account, amount));
-        self
+    pub fn debit(self, account: AccountKey, amount: Amount) -> Self {
+        self.push(Posting::checked(account, amount, Side::Debit))
+    }
+
+    pub fn credit(self, account: AccountKey, amount: Amount) -> Self {
+        self.push(Posting::checked(account, amount, Side::Credit))
     }
 
-    pub fn credit(mut self, account: AccountKey, amount: Amount) -> Self {
-        self.postings.push(Posting::credit(account, amount));
+    /// Keep the first invalid posting's error for build_unsigned
+    fn push(mut self, posting: Result<Posting, LedgerError>) -> Self {
+        match posting {
+            Ok(posting) => self.postings.push(posting),
+            Err(e) => {
+                self.error.get_or_insert(e);
+            }
+        }
         self
     }
 
@@ -269,6 +373,10 @@ impl JournalEntryBuilder {
 
     /// Build the entry (sequence, prev_hash, hash, timestamp will be set by ledger)
     pub fn build_unsigned(self) -> Result<UnsignedEntry, LedgerError> {
+        if let Some(e) = self.error {
+            return Err(e);
+        }
+
         let intent = self.intent.unwrap_or(TransactionIntent::Transfer);
         let correlation_id = self
             .correlation_id
@@ -298,7 +406,7 @@ impl JournalEntryBuilder {
 }
 
 /// An entry that hasn't been signed with sequence/hash yet
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UnsignedEntry {
     pub intent: TransactionIntent,
     pub correlation_id: String,
@@ -327,6 +435,14 @@ impl UnsignedEntry {
 
         Ok(())
     }
+
+    /// Validate every amount against its asset's scale
+    ///
+    /// Checked on commit rather than in `JournalEntry::validate`, so
+    /// journals written before scales were enforced still replay.
+    pub fn validate_scale(&self) -> Result<(), LedgerError> {
+        self.postings.iter().try_for_each(Posting::check_scale)
+    }
 }
 
 #[cfg(test)]
@@ -463,4 +579,59 @@ mod tests {
         assert_eq!(unsigned.postings.len(), 2);
         assert!(unsigned.validate_balance().is_ok());
     }
+
+    #[test]
+    fn test_builder_rejects_excess_precision() {
+        // USD has 2 decimal places
+        let cents = Amount::new(Decimal::new(1005, 3)).unwrap();
+        let result = JournalEntryBuilder::new()
+            .intent(TransactionIntent::Deposit)
+            .correlation_id("req-124")
+            .debit(AccountKey::system_vault("USD"), cents)
+            .credit(AccountKey::user_available("ALICE", "USD"), cents)
+            .build_unsigned();
+        assert!(matches!(
+            result,
+            Err(LedgerError::ExcessPrecision { scale: 2, .. })
+        ));
+
+        let posting = Posting::debit(AccountKey::system_vault("USD"), cents);
+        assert!(posting.check_scale().is_err());
+        assert!(Posting::checked(AccountKey::system_vault("BTC"), cents, Side::Debit).is_ok());
+    }
+
+    #[test]
+    fn test_reversal_mirrors_postings() {
+        let original = JournalEntry {
+            sequence: 7,
+            prev_hash: "prev".to_string(),
+            hash: "hash-7".to_string(),
+            timestamp: Utc::now(),
+            intent: TransactionIntent::Deposit,
+            correlation_id: "dep-1".to_string(),
+            causality_id: None,
+            postings: vec![
+                Posting::debit(AccountKey::system_vault("USDT"), amount(100)),
+                Posting::credit(AccountKey::user_available("ALICE", "USDT"), amount(100)),
+            ],
+            metadata: HashMap::new(),
+            signatures: Vec::new(),
+        };
+
+        let reversal = original.reversal("Duplicate deposit");
+        assert_eq!(reversal.intent, TransactionIntent::Reversal);
+        assert_eq!(reversal.correlation_id, "reversal-7");
+        assert_eq!(reversal.causality_id.as_deref(), Some("dep-1"));
+        assert_eq!(reversal.postings[0].side, Side::Credit);
+        assert_eq!(reversal.postings[1].side, Side::Debit);
+        assert!(reversal.validate_balance().is_ok());
+        assert!(!TransactionIntent::Reversal.is_reversible());
+        assert!(original.is_reversible());
+
+        let mut fill = original.clone();
+        fill.intent = TransactionIntent::Trade;
+        assert!(fill.is_reversible());
+        fill.metadata.insert("maker_order_id".to_string(), serde_json::json!("ORD-1"));
+        assert!(!fill.is_reversible());
+    }
 }
@@ -18,6 +18,20 @@ pub enum LedgerError {
     #[error("Entry unbalanced for asset {asset}: imbalance {imbalance}")]
     UnbalancedEntry { asset: String, imbalance: Decimal },
 
+    #[error("Books don't balance for {asset}: {left} != {right}")]
+    UnbalancedBooks {
+        asset: String,
+        left: Decimal,
+        right: Decimal,
+    },
+
+    #[error("Amount {amount} on {account} is finer than the asset's {scale} decimal places")]
+    ExcessPrecision {
+        account: String,
+        amount: Decimal,
+        scale: u32,
+    },
+
     #[error("correlation_id cannot be empty")]
     EmptyCorrelationId,
 
@@ -73,4 +87,14 @@ pub enum LedgerError {
 
     #[error("Signature verification failed: {0}")]
     SignatureVerificationFailed(String),
+
+    #[error("Key {public_key} of {signer} is not valid at sequence {sequence}")]
+    UntrustedKey {
+        signer: String,
+        public_key: String,
+        sequence: u64,
+    },
+
+    #[error("Key registry: {0}")]
+    KeyRegistry(String),
 }
@@ -40,11 +40,19 @@ pub fn calculate_entry_hash(entry: &JournalEntry) -> String {
 
 /// Verify hash chain integrity
 pub fn verify_chain(entries: &[JournalEntry]) -> Result<(), ChainError> {
+    verify_chain_from(entries, "GENESIS")
+}
+
+/// Verify the hash chain of entries following an entry already trusted
+///
+/// `prev_hash` is the hash of the entry before the first one (e.g. the last
+/// entry of a checkpoint).
+pub fn verify_chain_from(entries: &[JournalEntry], prev_hash: &str) -> Result<(), ChainError> {
     if entries.is_empty() {
         return Ok(());
     }
 
-    let mut prev_hash = "GENESIS".to_string();
+    let mut prev_hash = prev_hash.to_string();
 
     for (i, entry) in entries.iter().enumerate() {
         // Verify prev_hash links correctly
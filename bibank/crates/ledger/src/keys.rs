@@ -0,0 +1,338 @@
+//! Key registry - which signer keys were valid for which entries
+//!
+//! Keys are activated and revoked by sequence number: a key is valid for
+//! entries from its activation sequence up to, not including, its
+//! revocation sequence. Rotating a signer's key is a single activation that
+//! replaces the previous key and carries its hand-off signature
+//! ([`HandoffPayload`]), so a rotation can't be forged without the old key.
+
+use crate::entry::JournalEntry;
+use crate::error::LedgerError;
+use crate::signature::{EntrySignature, HandoffPayload, SignatureScope};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Key registry event (one per line of the registry journal)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum KeyEvent {
+    /// A key becomes valid
+    Activated {
+        signer_id: String,
+        public_key: String,
+        /// First sequence signed with this key
+        from_sequence: u64,
+        /// Key this one rotates out
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        replaces: Option<String>,
+        /// Hand-off signature by the replaced key
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        handoff: Option<EntrySignature>,
+        timestamp: DateTime<Utc>,
+    },
+    /// A key stops being valid
+    Revoked {
+        signer_id: String,
+        public_key: String,
+        /// First sequence the key is no longer valid for
+        from_sequence: u64,
+        reason: String,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+impl KeyEvent {
+    /// Activate a new key
+    pub fn activated(signer_id: impl Into<String>, public_key: impl Into<String>, from_sequence: u64) -> Self {
+        KeyEvent::Activated {
+            signer_id: signer_id.into(),
+            public_key: public_key.into(),
+            from_sequence,
+            replaces: None,
+            handoff: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Rotate to a new key, endorsed by the outgoing key's `handoff` signature
+    pub fn rotated(public_key: impl Into<String>, from_sequence: u64, handoff: EntrySignature) -> Self {
+        KeyEvent::Activated {
+            signer_id: handoff.signer_id.clone(),
+            public_key: public_key.into(),
+            from_sequence,
+            replaces: Some(handoff.public_key.clone()),
+            handoff: Some(handoff),
+            timestamp: Utc::now(),
+        }
+    }
+
+    /// Revoke a key
+    pub fn revoked(
+        signer_id: impl Into<String>,
+        public_key: impl Into<String>,
+        from_sequence: u64,
+        reason: impl Into<String>,
+    ) -> Self {
+        KeyEvent::Revoked {
+            signer_id: signer_id.into(),
+            public_key: public_key.into(),
+            from_sequence,
+            reason: reason.into(),
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// A key and the sequences it may sign
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyValidity {
+    pub signer_id: String,
+    pub public_key: String,
+    /// First valid sequence
+    pub valid_from: u64,
+    /// First invalid sequence (None while active)
+    pub valid_until: Option<u64>,
+    pub activated_at: DateTime<Utc>,
+    /// Revocation reason, or the rotation that ended the key
+    pub ended_by: Option<String>,
+}
+
+impl KeyValidity {
+    /// Whether the key may sign entry `sequence`
+    pub fn covers(&self, sequence: u64) -> bool {
+        sequence >= self.valid_from && self.valid_until.is_none_or(|until| sequence < until)
+    }
+
+    /// Whether the key has no end yet
+    pub fn is_active(&self) -> bool {
+        self.valid_until.is_none()
+    }
+}
+
+/// Signer keys folded from the registry journal
+#[derive(Debug, Clone, Default)]
+pub struct KeyRegistry {
+    keys: Vec<KeyValidity>,
+}
+
+impl KeyRegistry {
+    /// Fold the registry, rejecting inconsistent events
+    pub fn from_events(events: &[KeyEvent]) -> Result<Self, LedgerError> {
+        let mut registry = Self::default();
+        for event in events {
+            registry.apply(event)?;
+        }
+        Ok(registry)
+    }
+
+    /// Apply a single event
+    pub fn apply(&mut self, event: &KeyEvent) -> Result<(), LedgerError> {
+        match event {
+            KeyEvent::Activated {
+                signer_id,
+                public_key,
+                from_sequence,
+                replaces,
+                handoff,
+                timestamp,
+            } => {
+                if self.keys.iter().any(|k| k.public_key == *public_key) {
+                    return Err(registry_error(format!("key {} is already registered", public_key)));
+                }
+                if replaces.is_none() && self.active(signer_id).is_some() {
+                    return Err(registry_error(format!("{} already has an active key; rotate it", signer_id)));
+                }
+
+                if let Some(old_key) = replaces {
+                    let handoff = handoff
+                        .as_ref()
+                        .ok_or_else(|| registry_error(format!("rotation of {} has no hand-off signature", signer_id)))?;
+                    if handoff.scope != SignatureScope::Handoff || handoff.public_key != *old_key {
+                        return Err(registry_error(format!("hand-off for {} not signed by {}", signer_id, old_key)));
+                    }
+                    let payload = HandoffPayload {
+                        signer_id: signer_id.clone(),
+                        old_public_key: old_key.clone(),
+                        new_public_key: public_key.clone(),
+                        from_sequence: *from_sequence,
+                        signed_at: handoff.signed_at,
+                    };
+                    handoff.verify(&payload.to_bytes())?;
+
+                    let old = self
+                        .keys
+                        .iter_mut()
+                        .find(|k| k.signer_id == *signer_id && k.public_key == *old_key && k.is_active())
+                        .ok_or_else(|| registry_error(format!("{} has no active key {}", signer_id, old_key)))?;
+                    if *from_sequence < old.valid_from {
+                        return Err(registry_error(format!(
+                            "rotation of {} at sequence {} precedes the key it replaces",
+                            signer_id, from_sequence
+                        )));
+                    }
+                    old.valid_until = Some(*from_sequence);
+                    old.ended_by = Some(format!("rotated to {}", public_key));
+                }
+
+                self.keys.push(KeyValidity {
+                    signer_id: signer_id.clone(),
+                    public_key: public_key.clone(),
+                    valid_from: *from_sequence,
+                    valid_until: None,
+                    activated_at: *timestamp,
+                    ended_by: None,
+                });
+            }
+            KeyEvent::Revoked {
+                signer_id,
+                public_key,
+                from_sequence,
+                reason,
+                ..
+            } => {
+                let key = self
+                    .keys
+                    .iter_mut()
+                    .find(|k| k.signer_id == *signer_id && k.public_key == *public_key)
+                    .ok_or_else(|| registry_error(format!("{} has no key {}", signer_id, public_key)))?;
+                // Revoking can only shorten a key's validity
+                let until = key.valid_until.map_or(*from_sequence, |until| until.min(*from_sequence));
+                key.valid_until = Some(until.max(key.valid_from));
+                key.ended_by = Some(reason.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// All keys, in activation order
+    pub fn keys(&self) -> &[KeyValidity] {
+        &self.keys
+    }
+
+    /// The signer's latest active key
+    pub fn active(&self, signer_id: &str) -> Option<&KeyValidity> {
+        self.keys
+            .iter()
+            .rev()
+            .find(|k| k.signer_id == signer_id && k.is_active())
+    }
+
+    /// Whether any key was ever registered for the signer
+    pub fn knows(&self, signer_id: &str) -> bool {
+        self.keys.iter().any(|k| k.signer_id == signer_id)
+    }
+
+    /// Whether `public_key` may sign for `signer_id` at `sequence`
+    pub fn is_valid(&self, signer_id: &str, public_key: &str, sequence: u64) -> bool {
+        self.keys
+            .iter()
+            .any(|k| k.signer_id == signer_id && k.public_key == public_key && k.covers(sequence))
+    }
+
+    /// Verify an entry's signatures, each from a key valid at its sequence
+    pub fn verify_entry(&self, entry: &JournalEntry) -> Result<(), LedgerError> {
+        entry.verify_signatures()?;
+        for signature in &entry.signatures {
+            if !self.is_valid(&signature.signer_id, &signature.public_key, entry.sequence) {
+                return Err(LedgerError::UntrustedKey {
+                    signer: signature.signer_id.clone(),
+                    public_key: signature.public_key.clone(),
+                    sequence: entry.sequence,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+fn registry_error(reason: String) -> LedgerError {
+    LedgerError::KeyRegistry(reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signature::{Signer, SystemSigner};
+    use crate::testing::deposit_entry;
+
+    fn signed_entry(signer: &SystemSigner, sequence: u64) -> JournalEntry {
+        let mut entry = deposit_entry(sequence);
+        entry.signatures.push(signer.sign(&entry));
+        entry
+    }
+
+    #[test]
+    fn test_rotation_splits_validity() {
+        let old = SystemSigner::generate();
+        let new = SystemSigner::generate();
+        let events = vec![
+            KeyEvent::activated("SYSTEM", old.public_key_hex(), 1),
+            KeyEvent::rotated(new.public_key_hex(), 10, old.sign_handoff(&new.public_key_hex(), 10)),
+        ];
+        let registry = KeyRegistry::from_events(&events).unwrap();
+
+        assert_eq!(registry.active("SYSTEM").unwrap().public_key, new.public_key_hex());
+        assert!(registry.verify_entry(&signed_entry(&old, 9)).is_ok());
+        assert!(registry.verify_entry(&signed_entry(&new, 10)).is_ok());
+        assert_eq!(
+            registry.verify_entry(&signed_entry(&old, 10)),
+            Err(LedgerError::UntrustedKey {
+                signer: "SYSTEM".to_string(),
+                public_key: old.public_key_hex(),
+                sequence: 10,
+            })
+        );
+        assert!(registry.verify_entry(&signed_entry(&new, 9)).is_err());
+
+        // A second key alongside the active one must come in as a rotation
+        let other = KeyEvent::activated("SYSTEM", SystemSigner::generate().public_key_hex(), 20);
+        assert!(registry.clone().apply(&other).is_err());
+    }
+
+    #[test]
+    fn test_rotation_requires_old_key_handoff() {
+        let old = SystemSigner::generate();
+        let new = SystemSigner::generate();
+        let intruder = SystemSigner::generate();
+        let activated = KeyEvent::activated("SYSTEM", old.public_key_hex(), 1);
+
+        // Signed by a key that isn't the one being replaced
+        let forged = KeyEvent::rotated(new.public_key_hex(), 10, intruder.sign_handoff(&new.public_key_hex(), 10));
+        assert!(matches!(
+            KeyRegistry::from_events(&[activated.clone(), forged]),
+            Err(LedgerError::KeyRegistry(_))
+        ));
+
+        // Hand-off for a different sequence than the one recorded
+        let mut moved = KeyEvent::rotated(new.public_key_hex(), 10, old.sign_handoff(&new.public_key_hex(), 10));
+        if let KeyEvent::Activated { from_sequence, .. } = &mut moved {
+            *from_sequence = 5;
+        }
+        assert!(KeyRegistry::from_events(&[activated, moved]).is_err());
+    }
+
+    #[test]
+    fn test_revocation_ends_validity() {
+        let key = SystemSigner::generate();
+        let events = vec![
+            KeyEvent::activated("SYSTEM", key.public_key_hex(), 1),
+            KeyEvent::revoked("SYSTEM", key.public_key_hex(), 5, "compromised"),
+        ];
+        let registry = KeyRegistry::from_events(&events).unwrap();
+
+        assert!(registry.active("SYSTEM").is_none());
+        assert!(registry.knows("SYSTEM"));
+        assert!(registry.is_valid("SYSTEM", &key.public_key_hex(), 4));
+        assert!(!registry.is_valid("SYSTEM", &key.public_key_hex(), 5));
+        assert_eq!(registry.keys()[0].ended_by.as_deref(), Some("compromised"));
+        assert!(registry.verify_entry(&signed_entry(&key, 6)).is_err());
+    }
+}
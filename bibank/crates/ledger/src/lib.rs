@@ -7,16 +7,34 @@
 //! - `JournalEntry`: Atomic unit of financial state change
 //! - `Posting`: Single debit/credit in an entry
 //! - `Side`: Debit or Credit
+//! - `Checkpoint`: Signed Merkle root over a range of entries
+//! - `KeyRegistry`: Signer keys and the sequences they were valid for
+//! - `LedgerBalances`: Trial balance and balance sheet as of a cutoff
 
 pub mod account;
 pub mod entry;
 pub mod error;
 pub mod hash;
+pub mod keys;
+pub mod merkle;
 pub mod signature;
+pub mod statement;
 pub mod validation;
 
+#[cfg(test)]
+mod testing;
+
 pub use account::{AccountCategory, AccountKey};
-pub use entry::{JournalEntry, JournalEntryBuilder, Posting, Side, TransactionIntent, UnsignedEntry};
+pub use entry::{JournalEntry, JournalEntryBuilder, Posting, Side, TransactionIntent, UnsignedEntry, REVERSAL_PREFIX};
 pub use error::LedgerError;
-pub use signature::{EntrySignature, SignatureAlgorithm, SignablePayload, Signer, SystemSigner};
+pub use keys::{KeyEvent, KeyRegistry, KeyValidity};
+pub use merkle::{Checkpoint, CheckpointPayload, MerkleProof, MerkleTree, NodeSide, ProofStep};
+pub use signature::{
+    ApprovalPayload, EntrySignature, HandoffPayload, OperatorSigner, RejectionPayload, SignatureAlgorithm, SignatureScope,
+    SignablePayload, Signer, SystemSigner,
+};
+pub use statement::{
+    AsOf, AssetBalanceSheet, AssetIncomeStatement, AssetTrialBalance, BalanceSheet, IncomeStatement,
+    IncomeStatementBuilder, LedgerBalances, StatementLine, TrialBalance, TrialBalanceLine,
+};
 pub use validation::validate_intent;
@@ -0,0 +1,329 @@
+//! Merkle checkpoints over ranges of journal entries
+//!
+//! The hash chain proves the journal is intact, but only by reading every
+//! entry before the one in question. A [`Checkpoint`] commits to a range of
+//! entries with a Merkle root over their hashes, so one entry can be proven
+//! with a [`MerkleProof`] of `log2(n)` sibling hashes, and auditing can start
+//! from the last trusted checkpoint instead of genesis.
+//!
+//! Leaves and inner nodes are hashed with distinct prefixes (`0x00` / `0x01`)
+//! so a leaf can never pass for a node. An odd node at the end of a level is
+//! carried up unchanged rather than paired with itself.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::entry::JournalEntry;
+use crate::error::LedgerError;
+use crate::hash::calculate_entry_hash;
+use crate::signature::{EntrySignature, SignatureScope};
+
+type Node = [u8; 32];
+
+fn leaf(entry_hash: &str) -> Node {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00]);
+    hasher.update(entry_hash.as_bytes());
+    hasher.finalize().into()
+}
+
+fn node(left: &Node, right: &Node) -> Node {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Merkle tree over the hashes of consecutive entries
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    from_sequence: u64,
+    hashes: Vec<String>,
+    /// Levels from the leaves (0) up to the root
+    levels: Vec<Vec<Node>>,
+}
+
+impl MerkleTree {
+    /// Build a tree over entry hashes, the first being entry `from_sequence`
+    ///
+    /// Returns None for an empty range.
+    pub fn new(from_sequence: u64, hashes: Vec<String>) -> Option<Self> {
+        if hashes.is_empty() {
+            return None;
+        }
+
+        let mut levels = vec![hashes.iter().map(|h| leaf(h)).collect::<Vec<_>>()];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let level = levels.last().expect("levels is never empty");
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node(left, right),
+                    [odd] => *odd,
+                    _ => unreachable!("chunks(2) yields one or two nodes"),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Some(Self {
+            from_sequence,
+            hashes,
+            levels,
+        })
+    }
+
+    /// Build a tree over consecutive entries
+    pub fn from_entries(entries: &[JournalEntry]) -> Option<Self> {
+        let from_sequence = entries.first()?.sequence;
+        Self::new(from_sequence, entries.iter().map(|e| e.hash.clone()).collect())
+    }
+
+    /// Root hash (hex)
+    pub fn root(&self) -> String {
+        hex::encode(self.levels.last().expect("tree is never empty")[0])
+    }
+
+    /// First sequence covered
+    pub fn from_sequence(&self) -> u64 {
+        self.from_sequence
+    }
+
+    /// Last sequence covered
+    pub fn to_sequence(&self) -> u64 {
+        self.from_sequence + self.hashes.len() as u64 - 1
+    }
+
+    /// Inclusion proof for entry `sequence` (None if not covered)
+    pub fn proof(&self, sequence: u64) -> Option<MerkleProof> {
+        if sequence < self.from_sequence || sequence > self.to_sequence() {
+            return None;
+        }
+
+        let mut index = (sequence - self.from_sequence) as usize;
+        let mut path = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = index ^ 1;
+            if sibling < level.len() {
+                path.push(ProofStep {
+                    hash: hex::encode(level[sibling]),
+                    side: if sibling < index { NodeSide::Left } else { NodeSide::Right },
+                });
+            }
+            index /= 2;
+        }
+
+        Some(MerkleProof {
+            sequence,
+            entry_hash: self.hashes[(sequence - self.from_sequence) as usize].clone(),
+            from_sequence: self.from_sequence,
+            to_sequence: self.to_sequence(),
+            path,
+        })
+    }
+
+    /// Unsigned checkpoint committing to this tree
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            from_sequence: self.from_sequence,
+            to_sequence: self.to_sequence(),
+            root: self.root(),
+            last_hash: self.hashes.last().expect("tree is never empty").clone(),
+            created_at: Utc::now(),
+            signature: None,
+        }
+    }
+}
+
+/// Which side of the path a sibling hash sits on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeSide {
+    Left,
+    Right,
+}
+
+/// One sibling on the path from a leaf to the root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofStep {
+    /// Sibling node hash (hex)
+    pub hash: String,
+    pub side: NodeSide,
+}
+
+/// Proof that an entry is covered by a checkpoint's root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub sequence: u64,
+    /// Hash of the proven entry
+    pub entry_hash: String,
+    /// Range of the checkpoint the proof is against
+    pub from_sequence: u64,
+    pub to_sequence: u64,
+    /// Siblings from the leaf up
+    pub path: Vec<ProofStep>,
+}
+
+impl MerkleProof {
+    /// Root the proof leads to (None if a sibling hash is malformed)
+    pub fn compute_root(&self) -> Option<String> {
+        let mut current = leaf(&self.entry_hash);
+        for step in &self.path {
+            let sibling: Node = hex::decode(&step.hash).ok()?.try_into().ok()?;
+            current = match step.side {
+                NodeSide::Left => node(&sibling, &current),
+                NodeSide::Right => node(&current, &sibling),
+            };
+        }
+        Some(hex::encode(current))
+    }
+
+    /// Check the proof against a root
+    pub fn verify(&self, root: &str) -> bool {
+        self.compute_root().is_some_and(|computed| computed == root)
+    }
+
+    /// Check that the proof is for this entry (by recomputing its hash)
+    pub fn proves(&self, entry: &JournalEntry) -> bool {
+        entry.sequence == self.sequence
+            && entry.hash == self.entry_hash
+            && calculate_entry_hash(entry) == self.entry_hash
+    }
+}
+
+/// Signed commitment to a range of entries
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub from_sequence: u64,
+    pub to_sequence: u64,
+    /// Merkle root over the entry hashes in the range
+    pub root: String,
+    /// Hash of the entry at `to_sequence`, anchoring the checkpoint in the chain
+    pub last_hash: String,
+    pub created_at: DateTime<Utc>,
+    /// System signature over the [`CheckpointPayload`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<EntrySignature>,
+}
+
+impl Checkpoint {
+    /// Check if the checkpoint covers entry `sequence`
+    pub fn covers(&self, sequence: u64) -> bool {
+        self.from_sequence <= sequence && sequence <= self.to_sequence
+    }
+
+    /// Verify the signature (a checkpoint without one fails)
+    pub fn verify_signature(&self) -> Result<(), LedgerError> {
+        let signature = self.signature.as_ref().ok_or(LedgerError::MissingSystemSignature)?;
+        if signature.scope != SignatureScope::Checkpoint {
+            return Err(LedgerError::InvalidSignature {
+                signer: signature.signer_id.clone(),
+                reason: "Not a checkpoint signature".to_string(),
+            });
+        }
+        signature.verify(&CheckpointPayload::from_checkpoint(self, signature.signed_at).to_bytes())
+    }
+
+    /// Check a proof against this checkpoint
+    pub fn verify_proof(&self, proof: &MerkleProof) -> bool {
+        proof.from_sequence == self.from_sequence
+            && proof.to_sequence == self.to_sequence
+            && self.covers(proof.sequence)
+            && proof.verify(&self.root)
+    }
+}
+
+/// Checkpoint payload - what the system key signs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointPayload {
+    pub from_sequence: u64,
+    pub to_sequence: u64,
+    pub root: String,
+    pub last_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub signed_at: DateTime<Utc>,
+}
+
+impl CheckpointPayload {
+    /// Create from a checkpoint and signing timestamp
+    pub fn from_checkpoint(checkpoint: &Checkpoint, signed_at: DateTime<Utc>) -> Self {
+        Self {
+            from_sequence: checkpoint.from_sequence,
+            to_sequence: checkpoint.to_sequence,
+            root: checkpoint.root.clone(),
+            last_hash: checkpoint.last_hash.clone(),
+            created_at: checkpoint.created_at,
+            signed_at,
+        }
+    }
+
+    /// Serialize to canonical JSON bytes for signing
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("CheckpointPayload serialization should never fail")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signature::{Signer, SystemSigner};
+
+    fn hashes(n: usize) -> Vec<String> {
+        (1..=n).map(|i| format!("hash-{}", i)).collect()
+    }
+
+    #[test]
+    fn test_proofs_verify_for_every_leaf() {
+        for n in [1, 2, 3, 5, 8, 13] {
+            let tree = MerkleTree::new(10, hashes(n)).unwrap();
+            let root = tree.root();
+            for sequence in 10..10 + n as u64 {
+                let proof = tree.proof(sequence).unwrap();
+                assert!(proof.verify(&root), "n={} sequence={}", n, sequence);
+            }
+            assert!(tree.proof(9).is_none());
+            assert!(tree.proof(10 + n as u64).is_none());
+        }
+    }
+
+    #[test]
+    fn test_tampered_proof_fails() {
+        let tree = MerkleTree::new(1, hashes(5)).unwrap();
+        let root = tree.root();
+
+        let mut proof = tree.proof(3).unwrap();
+        proof.entry_hash = "forged".to_string();
+        assert!(!proof.verify(&root));
+
+        let mut proof = tree.proof(3).unwrap();
+        proof.path[0].side = NodeSide::Left;
+        assert!(!proof.verify(&root));
+
+        // A different range gives a different root
+        assert_ne!(MerkleTree::new(1, hashes(4)).unwrap().root(), root);
+        assert!(MerkleTree::new(1, Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_signed_checkpoint() {
+        let tree = MerkleTree::new(1, hashes(4)).unwrap();
+        let mut checkpoint = tree.checkpoint();
+        assert_eq!(checkpoint.to_sequence, 4);
+        assert_eq!(checkpoint.last_hash, "hash-4");
+        assert!(matches!(
+            checkpoint.verify_signature(),
+            Err(LedgerError::MissingSystemSignature)
+        ));
+
+        let signer = SystemSigner::generate();
+        checkpoint.signature = Some(signer.sign_checkpoint(&checkpoint));
+        assert!(checkpoint.verify_signature().is_ok());
+        assert!(checkpoint.verify_proof(&tree.proof(2).unwrap()));
+
+        let mut forged = checkpoint.clone();
+        forged.root = MerkleTree::new(1, hashes(3)).unwrap().root();
+        assert!(forged.verify_signature().is_err());
+    }
+}
@@ -2,32 +2,61 @@
 //!
 //! Each entry is signed by the system key, and optionally by operator keys
 //! for Adjustment entries requiring human approval.
+//!
+//! Operators approve an entry before it is committed, so their signatures
+//! cover the [`ApprovalPayload`] (the entry content) rather than the
+//! sequence and hash, which only exist once the entry is in the journal.
+//!
+//! Rejecting a parked entry is signed too, over the [`RejectionPayload`].
+//!
+//! Merkle checkpoints are signed with the same keys, over the
+//! [`CheckpointPayload`]. A key being rotated out signs the hand-off to its
+//! successor ([`HandoffPayload`]).
 
-use crate::entry::{JournalEntry, Posting, TransactionIntent};
+use crate::entry::{JournalEntry, Posting, TransactionIntent, UnsignedEntry};
 use crate::error::LedgerError;
+use crate::merkle::{Checkpoint, CheckpointPayload};
 use chrono::{DateTime, Utc};
 use ed25519_dalek::{Signature, Signer as DalekSigner, SigningKey, Verifier, VerifyingKey};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Signature algorithm
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum SignatureAlgorithm {
     /// Ed25519 (default)
+    #[default]
     Ed25519,
     /// Secp256k1 (for future blockchain compatibility)
     Secp256k1,
 }
 
-impl Default for SignatureAlgorithm {
-    fn default() -> Self {
-        Self::Ed25519
+/// What a signature covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureScope {
+    /// The committed entry ([`SignablePayload`])
+    #[default]
+    Entry,
+    /// The entry content, signed before commit ([`ApprovalPayload`])
+    Approval,
+    /// The rejection of an entry awaiting approval ([`RejectionPayload`])
+    Rejection,
+    /// A Merkle checkpoint ([`CheckpointPayload`])
+    Checkpoint,
+    /// A key rotation ([`HandoffPayload`])
+    Handoff,
+}
+
+impl SignatureScope {
+    fn is_entry(&self) -> bool {
+        *self == SignatureScope::Entry
     }
 }
 
 /// Digital signature attached to a journal entry
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EntrySignature {
     /// Signer identifier ("SYSTEM" or operator ID)
     pub signer_id: String,
@@ -35,6 +64,10 @@ pub struct EntrySignature {
     /// Signature algorithm used
     pub algorithm: SignatureAlgorithm,
 
+    /// Payload the signature covers
+    #[serde(default, skip_serializing_if = "SignatureScope::is_entry")]
+    pub scope: SignatureScope,
+
     /// Public key (hex-encoded)
     pub public_key: String,
 
@@ -143,6 +176,85 @@ impl SignablePayload {
     }
 }
 
+/// Approval payload - the entry content operators sign before commit
+///
+/// Everything the ledger will record except sequence, hash and timestamp.
+/// Serialized through `serde_json::Value` so metadata keys are sorted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalPayload {
+    pub intent: TransactionIntent,
+    pub correlation_id: String,
+    pub causality_id: Option<String>,
+    pub postings: Vec<Posting>,
+    pub metadata: HashMap<String, serde_json::Value>,
+    pub signed_at: DateTime<Utc>,
+}
+
+impl ApprovalPayload {
+    /// Create from an entry awaiting approval
+    pub fn from_unsigned(entry: &UnsignedEntry, signed_at: DateTime<Utc>) -> Self {
+        Self {
+            intent: entry.intent,
+            correlation_id: entry.correlation_id.clone(),
+            causality_id: entry.causality_id.clone(),
+            postings: entry.postings.clone(),
+            metadata: entry.metadata.clone(),
+            signed_at,
+        }
+    }
+
+    /// Create from the committed entry
+    pub fn from_entry(entry: &JournalEntry, signed_at: DateTime<Utc>) -> Self {
+        Self {
+            intent: entry.intent,
+            correlation_id: entry.correlation_id.clone(),
+            causality_id: entry.causality_id.clone(),
+            postings: entry.postings.clone(),
+            metadata: entry.metadata.clone(),
+            signed_at,
+        }
+    }
+
+    /// Serialize to canonical JSON bytes for signing
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let value = serde_json::to_value(self).expect("ApprovalPayload serialization should never fail");
+        serde_json::to_vec(&value).expect("ApprovalPayload serialization should never fail")
+    }
+}
+
+/// Rejection payload - an operator turning down an entry awaiting approval
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectionPayload {
+    pub approval_id: String,
+    pub reason: Option<String>,
+    pub signed_at: DateTime<Utc>,
+}
+
+impl RejectionPayload {
+    /// Serialize to canonical JSON bytes for signing
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("RejectionPayload serialization should never fail")
+    }
+}
+
+/// Hand-off payload - the outgoing key endorsing its successor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HandoffPayload {
+    pub signer_id: String,
+    pub old_public_key: String,
+    pub new_public_key: String,
+    /// First sequence signed with the new key
+    pub from_sequence: u64,
+    pub signed_at: DateTime<Utc>,
+}
+
+impl HandoffPayload {
+    /// Serialize to canonical JSON bytes for signing
+    pub fn to_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("HandoffPayload serialization should never fail")
+    }
+}
+
 /// Trait for signers
 pub trait Signer: Send + Sync {
     /// Get the signer ID
@@ -153,6 +265,45 @@ pub trait Signer: Send + Sync {
 
     /// Sign a payload and return the signature
     fn sign(&self, entry: &JournalEntry) -> EntrySignature;
+
+    /// Sign raw payload bytes, returning the hex-encoded signature
+    fn sign_bytes(&self, bytes: &[u8]) -> String;
+
+    /// Sign a Merkle checkpoint
+    fn sign_checkpoint(&self, checkpoint: &Checkpoint) -> EntrySignature {
+        let signed_at = Utc::now();
+        let payload = CheckpointPayload::from_checkpoint(checkpoint, signed_at);
+
+        EntrySignature {
+            signer_id: self.signer_id().to_string(),
+            algorithm: SignatureAlgorithm::Ed25519,
+            scope: SignatureScope::Checkpoint,
+            public_key: self.public_key_hex(),
+            signature: self.sign_bytes(&payload.to_bytes()),
+            signed_at,
+        }
+    }
+
+    /// Sign the hand-off to `new_public_key` from `from_sequence` on
+    fn sign_handoff(&self, new_public_key: &str, from_sequence: u64) -> EntrySignature {
+        let signed_at = Utc::now();
+        let payload = HandoffPayload {
+            signer_id: self.signer_id().to_string(),
+            old_public_key: self.public_key_hex(),
+            new_public_key: new_public_key.to_string(),
+            from_sequence,
+            signed_at,
+        };
+
+        EntrySignature {
+            signer_id: self.signer_id().to_string(),
+            algorithm: SignatureAlgorithm::Ed25519,
+            scope: SignatureScope::Handoff,
+            public_key: self.public_key_hex(),
+            signature: self.sign_bytes(&payload.to_bytes()),
+            signed_at,
+        }
+    }
 }
 
 /// System signer using Ed25519
@@ -163,22 +314,8 @@ pub struct SystemSigner {
 impl SystemSigner {
     /// Create from a 32-byte seed (hex-encoded in env var)
     pub fn from_hex(hex_seed: &str) -> Result<Self, LedgerError> {
-        let bytes = hex::decode(hex_seed).map_err(|e| {
-            LedgerError::InvalidSignature {
-                signer: "SYSTEM".to_string(),
-                reason: format!("Invalid key hex: {}", e),
-            }
-        })?;
-
-        let seed: [u8; 32] = bytes.try_into().map_err(|_| {
-            LedgerError::InvalidSignature {
-                signer: "SYSTEM".to_string(),
-                reason: "Key must be 32 bytes".to_string(),
-            }
-        })?;
-
         Ok(Self {
-            signing_key: SigningKey::from_bytes(&seed),
+            signing_key: signing_key_from_hex("SYSTEM", hex_seed)?,
         })
     }
 
@@ -215,11 +352,129 @@ impl Signer for SystemSigner {
         EntrySignature {
             signer_id: self.signer_id().to_string(),
             algorithm: SignatureAlgorithm::Ed25519,
+            scope: SignatureScope::Entry,
             public_key: self.public_key_hex(),
             signature: hex::encode(signature.to_bytes()),
             signed_at,
         }
     }
+
+    fn sign_bytes(&self, bytes: &[u8]) -> String {
+        hex::encode(self.signing_key.sign(bytes).to_bytes())
+    }
+}
+
+/// Operator signer using Ed25519
+///
+/// Signs entries awaiting approval; see [`ApprovalPayload`].
+pub struct OperatorSigner {
+    signer_id: String,
+    signing_key: SigningKey,
+}
+
+impl OperatorSigner {
+    /// Create from a 32-byte seed (hex-encoded)
+    pub fn from_hex(signer_id: impl Into<String>, hex_seed: &str) -> Result<Self, LedgerError> {
+        let signer_id = signer_id.into();
+        let signing_key = signing_key_from_hex(&signer_id, hex_seed)?;
+        Ok(Self {
+            signer_id,
+            signing_key,
+        })
+    }
+
+    /// Generate a new random signing key
+    pub fn generate(signer_id: impl Into<String>) -> Self {
+        let mut rng = rand::thread_rng();
+        Self {
+            signer_id: signer_id.into(),
+            signing_key: SigningKey::generate(&mut rng),
+        }
+    }
+
+    /// Export the seed as hex (for storage)
+    pub fn seed_hex(&self) -> String {
+        hex::encode(self.signing_key.to_bytes())
+    }
+
+    /// Sign an entry awaiting approval
+    pub fn sign_approval(&self, entry: &UnsignedEntry) -> EntrySignature {
+        let signed_at = Utc::now();
+        let payload = ApprovalPayload::from_unsigned(entry, signed_at);
+        let signature = self.signing_key.sign(&payload.to_bytes());
+
+        EntrySignature {
+            signer_id: self.signer_id.clone(),
+            algorithm: SignatureAlgorithm::Ed25519,
+            scope: SignatureScope::Approval,
+            public_key: self.public_key_hex(),
+            signature: hex::encode(signature.to_bytes()),
+            signed_at,
+        }
+    }
+
+    /// Sign the rejection of approval `approval_id`
+    pub fn sign_rejection(&self, approval_id: &str, reason: Option<&str>) -> EntrySignature {
+        let signed_at = Utc::now();
+        let payload = RejectionPayload {
+            approval_id: approval_id.to_string(),
+            reason: reason.map(str::to_string),
+            signed_at,
+        };
+
+        EntrySignature {
+            signer_id: self.signer_id.clone(),
+            algorithm: SignatureAlgorithm::Ed25519,
+            scope: SignatureScope::Rejection,
+            public_key: self.public_key_hex(),
+            signature: self.sign_bytes(&payload.to_bytes()),
+            signed_at,
+        }
+    }
+}
+
+impl Signer for OperatorSigner {
+    fn signer_id(&self) -> &str {
+        &self.signer_id
+    }
+
+    fn public_key_hex(&self) -> String {
+        hex::encode(self.signing_key.verifying_key().to_bytes())
+    }
+
+    fn sign(&self, entry: &JournalEntry) -> EntrySignature {
+        let signed_at = Utc::now();
+        let payload = SignablePayload::from_entry(entry, signed_at);
+        let signature = self.signing_key.sign(&payload.to_bytes());
+
+        EntrySignature {
+            signer_id: self.signer_id.clone(),
+            algorithm: SignatureAlgorithm::Ed25519,
+            scope: SignatureScope::Entry,
+            public_key: self.public_key_hex(),
+            signature: hex::encode(signature.to_bytes()),
+            signed_at,
+        }
+    }
+
+    fn sign_bytes(&self, bytes: &[u8]) -> String {
+        hex::encode(self.signing_key.sign(bytes).to_bytes())
+    }
+}
+
+/// Parse a hex-encoded 32-byte Ed25519 seed
+fn signing_key_from_hex(signer: &str, hex_seed: &str) -> Result<SigningKey, LedgerError> {
+    let bytes = hex::decode(hex_seed.trim()).map_err(|e| LedgerError::InvalidSignature {
+        signer: signer.to_string(),
+        reason: format!("Invalid key hex: {}", e),
+    })?;
+
+    let seed: [u8; 32] = bytes.try_into().map_err(|_| LedgerError::InvalidSignature {
+        signer: signer.to_string(),
+        reason: "Key must be 32 bytes".to_string(),
+    })?;
+
+    Ok(SigningKey::from_bytes(&seed))
 }
 
 impl JournalEntry {
@@ -231,8 +486,17 @@ impl JournalEntry {
         }
 
         for sig in &self.signatures {
-            let payload = SignablePayload::from_entry(self, sig.signed_at);
-            sig.verify(&payload.to_bytes())?;
+            let payload = match sig.scope {
+                SignatureScope::Entry => SignablePayload::from_entry(self, sig.signed_at).to_bytes(),
+                SignatureScope::Approval => ApprovalPayload::from_entry(self, sig.signed_at).to_bytes(),
+                SignatureScope::Rejection | SignatureScope::Checkpoint | SignatureScope::Handoff => {
+                    return Err(LedgerError::InvalidSignature {
+                        signer: sig.signer_id.clone(),
+                        reason: format!("{:?} signature on an entry", sig.scope),
+                    })
+                }
+            };
+            sig.verify(&payload)?;
         }
 
         Ok(())
@@ -242,6 +506,15 @@ impl JournalEntry {
     pub fn has_system_signature(&self) -> bool {
         self.signatures.iter().any(|s| s.signer_id == "SYSTEM")
     }
+
+    /// Operators who approved this entry before commit
+    pub fn approvers(&self) -> Vec<&str> {
+        self.signatures
+            .iter()
+            .filter(|s| s.scope == SignatureScope::Approval)
+            .map(|s| s.signer_id.as_str())
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -324,4 +597,57 @@ mod tests {
         // Verification should fail
         assert!(entry.verify_signatures().is_err());
     }
+
+    #[test]
+    fn test_approval_signature_survives_commit() {
+        let operator = OperatorSigner::generate("ops1");
+        let system = SystemSigner::generate();
+        let committed = make_test_entry();
+        let unsigned = UnsignedEntry {
+            intent: committed.intent,
+            correlation_id: committed.correlation_id.clone(),
+            causality_id: None,
+            postings: committed.postings.clone(),
+            metadata: HashMap::from([
+                ("reason".to_string(), serde_json::json!("correction")),
+                ("ticket".to_string(), serde_json::json!(42)),
+            ]),
+        };
+
+        // Signed before sequence and hash exist
+        let approval = operator.sign_approval(&unsigned);
+        assert_eq!(approval.scope, SignatureScope::Approval);
+        let payload = ApprovalPayload::from_unsigned(&unsigned, approval.signed_at);
+        assert!(approval.verify(&payload.to_bytes()).is_ok());
+
+        let mut entry = committed;
+        entry.metadata = unsigned.metadata.clone();
+        entry.signatures.push(approval);
+        entry.signatures.push(system.sign(&entry));
+        assert!(entry.verify_signatures().is_ok());
+        assert_eq!(entry.approvers(), vec!["ops1"]);
+
+        // The approval covers the postings
+        entry.postings.swap(0, 1);
+        assert!(entry.verify_signatures().is_err());
+    }
+
+    #[test]
+    fn test_signature_scope_defaults_to_entry() {
+        let signature = SystemSigner::generate().sign(&make_test_entry());
+        let json = serde_json::to_value(&signature).unwrap();
+        assert!(json.get("scope").is_none());
+
+        let parsed: EntrySignature = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.scope, SignatureScope::Entry);
+    }
+
+    #[test]
+    fn test_operator_signer_roundtrip() {
+        let signer = OperatorSigner::generate("ops1");
+        let restored = OperatorSigner::from_hex("ops1", &signer.seed_hex()).unwrap();
+        assert_eq!(signer.public_key_hex(), restored.public_key_hex());
+        assert_eq!(restored.signer_id(), "ops1");
+        assert!(OperatorSigner::from_hex("ops1", "abcd").is_err());
+    }
 }
@@ -0,0 +1,619 @@
+//! Financial statements
+//!
+//! Statements are folded from journal entries and kept per asset: nothing
+//! is converted between currencies.
+//!
+//! - **Trial balance**: every account's balance as of a cutoff, in the debit
+//!   or credit column. Debits equal credits.
+//! - **Balance sheet**: assets, liabilities and equity as of a cutoff, with
+//!   revenue less expenses as retained earnings.
+//!   `assets = liabilities + equity + retained earnings` must hold.
+//! - **Income statement**: revenue and expenses booked in a date range.
+//!   Liquidation penalties are booked straight to the insurance fund (equity),
+//!   so they are shown as their own line.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::account::{AccountCategory, AccountKey};
+use crate::entry::{JournalEntry, Side, TransactionIntent};
+use crate::error::LedgerError;
+
+/// Cutoff for point-in-time statements
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AsOf {
+    /// Up to and including this entry
+    Sequence(u64),
+    /// Entries committed at or before this time
+    Time(DateTime<Utc>),
+}
+
+impl AsOf {
+    /// Check if an entry falls before the cutoff
+    pub fn includes(&self, entry: &JournalEntry) -> bool {
+        match self {
+            AsOf::Sequence(sequence) => entry.sequence <= *sequence,
+            AsOf::Time(time) => entry.timestamp <= *time,
+        }
+    }
+}
+
+/// Account balances as of a cutoff, in normal-balance terms
+#[derive(Debug, Clone, Default)]
+pub struct LedgerBalances {
+    as_of: Option<AsOf>,
+    /// Last entry applied
+    sequence: u64,
+    /// (asset, account) -> (category, balance)
+    balances: BTreeMap<(String, String), (AccountCategory, Decimal)>,
+}
+
+impl LedgerBalances {
+    /// Start folding entries up to `as_of` (None: all of them)
+    pub fn new(as_of: Option<AsOf>) -> Self {
+        Self {
+            as_of,
+            ..Self::default()
+        }
+    }
+
+    /// Fold one entry (entries past the cutoff are ignored)
+    pub fn apply(&mut self, entry: &JournalEntry) {
+        if self.as_of.is_some_and(|as_of| !as_of.includes(entry)) {
+            return;
+        }
+        for posting in &entry.postings {
+            let account = &posting.account;
+            let (_, balance) = self
+                .balances
+                .entry((account.asset.clone(), account.to_string()))
+                .or_insert((account.category, Decimal::ZERO));
+            *balance += normal_amount(account, posting.side, posting.amount.value());
+        }
+        self.sequence = self.sequence.max(entry.sequence);
+    }
+
+    /// Last entry applied
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Non-zero balances: (asset, account, category, balance)
+    fn iter(&self) -> impl Iterator<Item = (&str, &str, AccountCategory, Decimal)> {
+        self.balances
+            .iter()
+            .filter(|(_, (_, balance))| !balance.is_zero())
+            .map(|((asset, account), (category, balance))| (asset.as_str(), account.as_str(), *category, *balance))
+    }
+
+    /// Trial balance
+    pub fn trial_balance(&self) -> TrialBalance {
+        let mut assets: BTreeMap<&str, AssetTrialBalance> = BTreeMap::new();
+        for (asset, account, category, balance) in self.iter() {
+            let tb = assets.entry(asset).or_insert_with(|| AssetTrialBalance {
+                asset: asset.to_string(),
+                lines: Vec::new(),
+                total_debit: Decimal::ZERO,
+                total_credit: Decimal::ZERO,
+            });
+            // Balances are in normal terms; a negative one sits on the other side
+            let debit_side = (category.normal_balance() == Side::Debit) == (balance > Decimal::ZERO);
+            let (debit, credit) = if debit_side {
+                (balance.abs(), Decimal::ZERO)
+            } else {
+                (Decimal::ZERO, balance.abs())
+            };
+            tb.total_debit += debit;
+            tb.total_credit += credit;
+            tb.lines.push(TrialBalanceLine {
+                account: account.to_string(),
+                category,
+                debit,
+                credit,
+            });
+        }
+        TrialBalance {
+            sequence: self.sequence,
+            assets: assets.into_values().collect(),
+        }
+    }
+
+    /// Balance sheet
+    pub fn balance_sheet(&self) -> BalanceSheet {
+        let mut sheets: BTreeMap<&str, AssetBalanceSheet> = BTreeMap::new();
+        for (asset, account, category, balance) in self.iter() {
+            let sheet = sheets.entry(asset).or_insert_with(|| AssetBalanceSheet::new(asset));
+            let line = StatementLine {
+                account: account.to_string(),
+                amount: balance,
+            };
+            match category {
+                AccountCategory::Asset => {
+                    sheet.total_assets += balance;
+                    sheet.assets.push(line);
+                }
+                AccountCategory::Liability => {
+                    sheet.total_liabilities += balance;
+                    sheet.liabilities.push(line);
+                }
+                AccountCategory::Equity => {
+                    sheet.total_equity += balance;
+                    sheet.equity.push(line);
+                }
+                AccountCategory::Revenue => sheet.retained_earnings += balance,
+                AccountCategory::Expense => sheet.retained_earnings -= balance,
+            }
+        }
+        BalanceSheet {
+            sequence: self.sequence,
+            sheets: sheets.into_values().collect(),
+        }
+    }
+}
+
+/// One account in a trial balance
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrialBalanceLine {
+    pub account: String,
+    pub category: AccountCategory,
+    pub debit: Decimal,
+    pub credit: Decimal,
+}
+
+/// Trial balance of one asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetTrialBalance {
+    pub asset: String,
+    pub lines: Vec<TrialBalanceLine>,
+    pub total_debit: Decimal,
+    pub total_credit: Decimal,
+}
+
+impl AssetTrialBalance {
+    /// Check if debits equal credits
+    pub fn is_balanced(&self) -> bool {
+        self.total_debit == self.total_credit
+    }
+}
+
+/// Trial balance per asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrialBalance {
+    /// Last entry included
+    pub sequence: u64,
+    pub assets: Vec<AssetTrialBalance>,
+}
+
+impl TrialBalance {
+    /// Fail on the first asset whose debits and credits differ
+    pub fn check(&self) -> Result<(), LedgerError> {
+        match self.assets.iter().find(|tb| !tb.is_balanced()) {
+            Some(tb) => Err(LedgerError::UnbalancedBooks {
+                asset: tb.asset.clone(),
+                left: tb.total_debit,
+                right: tb.total_credit,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// CSV rendering
+    pub fn to_csv(&self) -> String {
+        let mut out = format!("# statement: trial_balance\n# sequence: {}\n", self.sequence);
+        csv_row(&mut out, &["asset", "account", "category", "debit", "credit"]);
+        for tb in &self.assets {
+            for line in &tb.lines {
+                csv_row(
+                    &mut out,
+                    &[
+                        &tb.asset,
+                        &line.account,
+                        line.category.code(),
+                        &line.debit.to_string(),
+                        &line.credit.to_string(),
+                    ],
+                );
+            }
+            csv_row(
+                &mut out,
+                &[&tb.asset, "TOTAL", "", &tb.total_debit.to_string(), &tb.total_credit.to_string()],
+            );
+        }
+        out
+    }
+}
+
+/// One account on a statement, in normal-balance terms
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatementLine {
+    pub account: String,
+    pub amount: Decimal,
+}
+
+/// Balance sheet of one asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetBalanceSheet {
+    pub asset: String,
+    pub assets: Vec<StatementLine>,
+    pub liabilities: Vec<StatementLine>,
+    pub equity: Vec<StatementLine>,
+    pub total_assets: Decimal,
+    pub total_liabilities: Decimal,
+    pub total_equity: Decimal,
+    /// Revenue less expenses, not yet closed into equity
+    pub retained_earnings: Decimal,
+}
+
+impl AssetBalanceSheet {
+    fn new(asset: &str) -> Self {
+        Self {
+            asset: asset.to_string(),
+            assets: Vec::new(),
+            liabilities: Vec::new(),
+            equity: Vec::new(),
+            total_assets: Decimal::ZERO,
+            total_liabilities: Decimal::ZERO,
+            total_equity: Decimal::ZERO,
+            retained_earnings: Decimal::ZERO,
+        }
+    }
+
+    /// Liabilities + equity + retained earnings
+    pub fn total_liabilities_and_equity(&self) -> Decimal {
+        self.total_liabilities + self.total_equity + self.retained_earnings
+    }
+
+    /// Check if assets equal liabilities plus equity
+    pub fn is_balanced(&self) -> bool {
+        self.total_assets == self.total_liabilities_and_equity()
+    }
+}
+
+/// Balance sheet per asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceSheet {
+    /// Last entry included
+    pub sequence: u64,
+    pub sheets: Vec<AssetBalanceSheet>,
+}
+
+impl BalanceSheet {
+    /// Fail on the first asset whose assets differ from liabilities plus equity
+    pub fn check(&self) -> Result<(), LedgerError> {
+        match self.sheets.iter().find(|sheet| !sheet.is_balanced()) {
+            Some(sheet) => Err(LedgerError::UnbalancedBooks {
+                asset: sheet.asset.clone(),
+                left: sheet.total_assets,
+                right: sheet.total_liabilities_and_equity(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// CSV rendering
+    pub fn to_csv(&self) -> String {
+        let mut out = format!("# statement: balance_sheet\n# sequence: {}\n", self.sequence);
+        csv_row(&mut out, &["asset", "section", "account", "amount"]);
+        for sheet in &self.sheets {
+            for (section, lines) in [
+                ("assets", &sheet.assets),
+                ("liabilities", &sheet.liabilities),
+                ("equity", &sheet.equity),
+            ] {
+                for line in lines {
+                    csv_row(&mut out, &[&sheet.asset, section, &line.account, &line.amount.to_string()]);
+                }
+            }
+            for (section, amount) in [
+                ("retained_earnings", sheet.retained_earnings),
+                ("total_assets", sheet.total_assets),
+                ("total_liabilities_and_equity", sheet.total_liabilities_and_equity()),
+            ] {
+                csv_row(&mut out, &[&sheet.asset, section, "", &amount.to_string()]);
+            }
+        }
+        out
+    }
+}
+
+/// Income statement of one asset
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetIncomeStatement {
+    pub asset: String,
+    /// `REV:SYSTEM:FEE:*`
+    pub fee_revenue: Decimal,
+    /// `REV:SYSTEM:INTEREST:*`
+    pub interest_revenue: Decimal,
+    /// Net penalties paid into the insurance fund by liquidations
+    pub liquidation_fees: Decimal,
+    /// Any other revenue account
+    pub other_revenue: Decimal,
+    pub expenses: Decimal,
+}
+
+impl AssetIncomeStatement {
+    /// All income lines
+    pub fn total_revenue(&self) -> Decimal {
+        self.fee_revenue + self.interest_revenue + self.liquidation_fees + self.other_revenue
+    }
+
+    /// Revenue less expenses
+    pub fn net_income(&self) -> Decimal {
+        self.total_revenue() - self.expenses
+    }
+}
+
+/// Income statement per asset for `from..=to` (UTC days)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncomeStatement {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub statements: Vec<AssetIncomeStatement>,
+}
+
+impl IncomeStatement {
+    /// CSV rendering
+    pub fn to_csv(&self) -> String {
+        let mut out = format!("# statement: income_statement\n# from: {}\n# to: {}\n", self.from, self.to);
+        csv_row(
+            &mut out,
+            &[
+                "asset",
+                "fee_revenue",
+                "interest_revenue",
+                "liquidation_fees",
+                "other_revenue",
+                "total_revenue",
+                "expenses",
+                "net_income",
+            ],
+        );
+        for s in &self.statements {
+            csv_row(
+                &mut out,
+                &[
+                    &s.asset,
+                    &s.fee_revenue.to_string(),
+                    &s.interest_revenue.to_string(),
+                    &s.liquidation_fees.to_string(),
+                    &s.other_revenue.to_string(),
+                    &s.total_revenue().to_string(),
+                    &s.expenses.to_string(),
+                    &s.net_income().to_string(),
+                ],
+            );
+        }
+        out
+    }
+}
+
+/// Builds an income statement from journal entries
+pub struct IncomeStatementBuilder {
+    from: NaiveDate,
+    to: NaiveDate,
+    statements: BTreeMap<String, AssetIncomeStatement>,
+}
+
+impl IncomeStatementBuilder {
+    /// Start a statement for `from..=to`
+    pub fn new(from: NaiveDate, to: NaiveDate) -> Self {
+        Self {
+            from,
+            to,
+            statements: BTreeMap::new(),
+        }
+    }
+
+    /// Add one entry (entries outside the period are ignored)
+    pub fn add(&mut self, entry: &JournalEntry) {
+        let date = entry.timestamp.date_naive();
+        if date < self.from || date > self.to {
+            return;
+        }
+        for posting in &entry.postings {
+            let account = &posting.account;
+            let amount = normal_amount(account, posting.side, posting.amount.value());
+            let liquidation_fee = account.category == AccountCategory::Equity
+                && account.id == "INSURANCE"
+                && entry.intent == TransactionIntent::Liquidation;
+            if !matches!(account.category, AccountCategory::Revenue | AccountCategory::Expense) && !liquidation_fee {
+                continue;
+            }
+
+            let statement = self
+                .statements
+                .entry(account.asset.clone())
+                .or_insert_with(|| AssetIncomeStatement {
+                    asset: account.asset.clone(),
+                    ..AssetIncomeStatement::default()
+                });
+            let line = match account.category {
+                AccountCategory::Revenue if account.id == "FEE" => &mut statement.fee_revenue,
+                AccountCategory::Revenue if account.id == "INTEREST" => &mut statement.interest_revenue,
+                AccountCategory::Revenue => &mut statement.other_revenue,
+                AccountCategory::Expense => &mut statement.expenses,
+                _ => &mut statement.liquidation_fees,
+            };
+            *line += amount;
+        }
+    }
+
+    /// Finish the statement
+    pub fn finish(self) -> IncomeStatement {
+        IncomeStatement {
+            from: self.from,
+            to: self.to,
+            statements: self.statements.into_values().collect(),
+        }
+    }
+}
+
+/// Posting amount in the account's normal-balance terms
+fn normal_amount(account: &AccountKey, side: Side, amount: Decimal) -> Decimal {
+    if side == account.category.normal_balance() {
+        amount
+    } else {
+        -amount
+    }
+}
+
+/// Append one CSV record, quoting fields as RFC 4180 requires
+fn csv_row(out: &mut String, fields: &[&str]) {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.to_string()
+            }
+        })
+        .collect();
+    out.push_str(&fields.join(","));
+    out.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry::Posting;
+    use bibank_core::Amount;
+    use chrono::{Duration, TimeZone};
+    use std::collections::HashMap;
+
+    fn amount(value: i64) -> Amount {
+        Amount::new(Decimal::new(value, 0)).unwrap()
+    }
+
+    fn entry(sequence: u64, intent: TransactionIntent, postings: Vec<Posting>) -> JournalEntry {
+        JournalEntry {
+            sequence,
+            prev_hash: "test".to_string(),
+            hash: "test".to_string(),
+            timestamp: Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap() + Duration::days(sequence as i64),
+            intent,
+            correlation_id: format!("tx-{}", sequence),
+            causality_id: None,
+            postings,
+            metadata: HashMap::new(),
+            signatures: Vec::new(),
+        }
+    }
+
+    fn journal() -> Vec<JournalEntry> {
+        let capital = AccountKey::new(AccountCategory::Equity, "SYSTEM", "CAPITAL", "USDT", "MAIN");
+        let insurance = AccountKey::new(AccountCategory::Equity, "SYSTEM", "INSURANCE", "USDT", "FUND");
+        let interest = AccountKey::new(AccountCategory::Revenue, "SYSTEM", "INTEREST", "USDT", "INCOME");
+        let loan = AccountKey::new(AccountCategory::Asset, "USER", "ALICE", "USDT", "LOAN");
+        vec![
+            entry(
+                1,
+                TransactionIntent::Genesis,
+                vec![
+                    Posting::debit(AccountKey::system_vault("USDT"), amount(1000)),
+                    Posting::credit(capital, amount(1000)),
+                ],
+            ),
+            entry(
+                2,
+                TransactionIntent::Deposit,
+                vec![
+                    Posting::debit(AccountKey::system_vault("USDT"), amount(100)),
+                    Posting::credit(AccountKey::user_available("ALICE", "USDT"), amount(100)),
+                ],
+            ),
+            entry(
+                3,
+                TransactionIntent::Fee,
+                vec![
+                    Posting::debit(AccountKey::user_available("ALICE", "USDT"), amount(2)),
+                    Posting::credit(AccountKey::fee_revenue("USDT"), amount(2)),
+                ],
+            ),
+            entry(
+                4,
+                TransactionIntent::Interest,
+                vec![Posting::debit(loan.clone(), amount(3)), Posting::credit(interest, amount(3))],
+            ),
+            entry(
+                5,
+                TransactionIntent::Liquidation,
+                vec![
+                    Posting::debit(AccountKey::user_available("ALICE", "USDT"), amount(10)),
+                    Posting::credit(loan, amount(8)),
+                    Posting::credit(insurance, amount(2)),
+                ],
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_trial_balance_as_of() {
+        let mut balances = LedgerBalances::new(Some(AsOf::Sequence(3)));
+        for entry in journal() {
+            balances.apply(&entry);
+        }
+        let tb = balances.trial_balance();
+        assert_eq!(tb.sequence, 3);
+        assert!(tb.check().is_ok());
+        let usdt = &tb.assets[0];
+        assert_eq!(usdt.total_debit, Decimal::new(1100, 0));
+        assert_eq!(usdt.total_credit, Decimal::new(1100, 0));
+
+        // A cutoff by time includes the same entries
+        let at = journal()[2].timestamp;
+        let mut by_time = LedgerBalances::new(Some(AsOf::Time(at)));
+        for entry in journal() {
+            by_time.apply(&entry);
+        }
+        assert_eq!(by_time.trial_balance(), tb);
+    }
+
+    #[test]
+    fn test_balance_sheet_check() {
+        let mut balances = LedgerBalances::new(None);
+        for entry in journal() {
+            balances.apply(&entry);
+        }
+        let sheet = balances.balance_sheet();
+        assert!(sheet.check().is_ok());
+        let usdt = &sheet.sheets[0];
+        assert_eq!(usdt.total_assets, Decimal::new(1095, 0));
+        assert_eq!(usdt.total_liabilities, Decimal::new(88, 0));
+        assert_eq!(usdt.total_equity, Decimal::new(1002, 0));
+        assert_eq!(usdt.retained_earnings, Decimal::new(5, 0));
+
+        // A posting without its counterpart must not go unnoticed
+        let mut broken = sheet.clone();
+        broken.sheets[0].total_assets += Decimal::ONE;
+        assert!(matches!(broken.check(), Err(LedgerError::UnbalancedBooks { .. })));
+        assert!(sheet.to_csv().contains("USDT,total_assets,,1095\n"));
+    }
+
+    #[test]
+    fn test_income_statement_period() {
+        let from = NaiveDate::from_ymd_opt(2026, 1, 3).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 1, 6).unwrap();
+        let mut builder = IncomeStatementBuilder::new(from, to);
+        for entry in journal() {
+            builder.add(&entry);
+        }
+        let statement = builder.finish();
+        let usdt = &statement.statements[0];
+        assert_eq!(usdt.fee_revenue, Decimal::new(2, 0));
+        assert_eq!(usdt.interest_revenue, Decimal::new(3, 0));
+        assert_eq!(usdt.liquidation_fees, Decimal::new(2, 0));
+        assert_eq!(usdt.net_income(), Decimal::new(7, 0));
+
+        // Only the deposit falls on the first day
+        let mut builder = IncomeStatementBuilder::new(from, from);
+        for entry in journal() {
+            builder.add(&entry);
+        }
+        assert!(builder.finish().statements.is_empty());
+    }
+}
@@ -0,0 +1,28 @@
+//! Shared test fixtures
+
+use crate::account::AccountKey;
+use crate::entry::{JournalEntry, Posting, TransactionIntent};
+use bibank_core::Amount;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+/// Deposit of 100 USDT to ALICE at `sequence`, chained to `sequence - 1`
+pub(crate) fn deposit_entry(sequence: u64) -> JournalEntry {
+    let amount = Amount::new(Decimal::new(100, 0)).unwrap();
+    JournalEntry {
+        sequence,
+        prev_hash: format!("hash-{}", sequence - 1),
+        hash: format!("hash-{}", sequence),
+        timestamp: Utc::now(),
+        intent: TransactionIntent::Deposit,
+        correlation_id: format!("corr-{}", sequence),
+        causality_id: None,
+        postings: vec![
+            Posting::debit(AccountKey::system_vault("USDT"), amount),
+            Posting::credit(AccountKey::user_available("ALICE", "USDT"), amount),
+        ],
+        metadata: HashMap::new(),
+        signatures: Vec::new(),
+    }
+}
@@ -26,6 +26,7 @@ pub fn validate_intent(entry: &UnsignedEntry) -> ValidationResult {
         TransactionIntent::Liquidation => validate_liquidation(entry),
         TransactionIntent::OrderPlace => validate_order_place(entry),
         TransactionIntent::OrderCancel => validate_order_cancel(entry),
+        TransactionIntent::Reversal => validate_reversal(entry),
     }
 }
 
@@ -98,7 +99,8 @@ fn validate_transfer(entry: &UnsignedEntry) -> ValidationResult {
     Ok(())
 }
 
-/// Trade: LIAB only, exactly 2 assets, min 4 postings, zero-sum per asset
+/// Trade: LIAB (plus REV credits for fees), exactly 2 assets, min 4 postings,
+/// zero-sum per asset
 pub fn validate_trade(entry: &UnsignedEntry) -> ValidationResult {
     // Rule 1: Min 4 postings (2 legs × 2 sides)
     if entry.postings.len() < 4 {
@@ -109,13 +111,18 @@ pub fn validate_trade(entry: &UnsignedEntry) -> ValidationResult {
         });
     }
 
-    // Rule 2: LIAB accounts only
+    // Rule 2: LIAB accounts, or REV credits for trading fees
     for posting in &entry.postings {
-        if posting.account.category != AccountCategory::Liability {
+        let valid = match posting.account.category {
+            AccountCategory::Liability => true,
+            AccountCategory::Revenue => posting.side == Side::Credit,
+            _ => false,
+        };
+        if !valid {
             return Err(LedgerError::InvalidIntentPosting {
                 intent: "Trade",
                 account: posting.account.to_string(),
-                reason: "Trade only allows LIAB accounts",
+                reason: "Trade only allows LIAB accounts and REV fee credits",
             });
         }
     }
@@ -155,11 +162,10 @@ pub fn validate_fee(entry: &UnsignedEntry) -> ValidationResult {
 
     // All postings must be either LIAB debit or REV credit
     for posting in &entry.postings {
-        let valid = match (&posting.account.category, &posting.side) {
-            (AccountCategory::Liability, Side::Debit) => true,
-            (AccountCategory::Revenue, Side::Credit) => true,
-            _ => false,
-        };
+        let valid = matches!(
+            (&posting.account.category, &posting.side),
+            (AccountCategory::Liability, Side::Debit) | (AccountCategory::Revenue, Side::Credit)
+        );
         if !valid {
             return Err(LedgerError::InvalidIntentPosting {
                 intent: "Fee",
@@ -216,11 +222,10 @@ fn validate_borrow(entry: &UnsignedEntry) -> ValidationResult {
 
     // All postings must be ASSET:LOAN or LIAB:AVAILABLE
     for posting in &entry.postings {
-        let valid = match (&posting.account.category, posting.account.sub_account.as_str()) {
-            (AccountCategory::Asset, "LOAN") => true,
-            (AccountCategory::Liability, "AVAILABLE") => true,
-            _ => false,
-        };
+        let valid = matches!(
+            (&posting.account.category, posting.account.sub_account.as_str()),
+            (AccountCategory::Asset, "LOAN") | (AccountCategory::Liability, "AVAILABLE")
+        );
         if !valid {
             return Err(LedgerError::InvalidIntentPosting {
                 intent: "Borrow",
@@ -413,6 +418,20 @@ fn validate_order_cancel(entry: &UnsignedEntry) -> ValidationResult {
     Ok(())
 }
 
+/// Reversal: mirrors a committed entry, so any accounts; must name the original
+fn validate_reversal(entry: &UnsignedEntry) -> ValidationResult {
+    let has_cause = entry.causality_id.as_deref().is_some_and(|c| !c.is_empty());
+    let has_sequence = entry.metadata.get("reversed_sequence").is_some_and(|v| v.is_u64());
+    if !has_cause || !has_sequence {
+        return Err(LedgerError::InvalidIntentPosting {
+            intent: "Reversal",
+            account: String::new(),
+            reason: "Reversal requires causality_id and reversed_sequence pointing at the original entry",
+        });
+    }
+    Ok(())
+}
+
 /// Collect unique assets from postings
 fn collect_assets(postings: &[Posting]) -> std::collections::HashSet<String> {
     postings.iter().map(|p| p.account.asset.clone()).collect()
@@ -506,6 +525,33 @@ mod tests {
         assert!(matches!(result, Err(LedgerError::InvalidIntentPosting { .. })));
     }
 
+    #[test]
+    fn test_validate_trade_with_fees() {
+        let mut postings = vec![
+            // USDT leg: BOB keeps 1 as fee
+            Posting::debit(AccountKey::user_available("ALICE", "USDT"), amount(100)),
+            Posting::credit(AccountKey::user_available("BOB", "USDT"), amount(99)),
+            Posting::credit(AccountKey::trading_fee("USDT"), amount(1)),
+            // BTC leg
+            Posting::debit(AccountKey::user_available("BOB", "BTC"), amount(1)),
+            Posting::credit(AccountKey::user_available("ALICE", "BTC"), amount(1)),
+        ];
+        let entry = UnsignedEntry {
+            intent: TransactionIntent::Trade,
+            correlation_id: "test-1".to_string(),
+            causality_id: None,
+            postings: postings.clone(),
+            metadata: Default::default(),
+        };
+        assert!(validate_trade(&entry).is_ok());
+
+        // Fee accounts can't pay into a trade
+        postings[2] = Posting::debit(AccountKey::trading_fee("USDT"), amount(1));
+        postings[1] = Posting::credit(AccountKey::user_available("BOB", "USDT"), amount(101));
+        let entry = UnsignedEntry { postings, ..entry };
+        assert!(matches!(validate_trade(&entry), Err(LedgerError::InvalidIntentPosting { .. })));
+    }
+
     #[test]
     fn test_validate_fee_success() {
         let entry = UnsignedEntry {
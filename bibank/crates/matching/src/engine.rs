@@ -6,7 +6,7 @@ use rust_decimal::Decimal;
 
 use crate::error::MatchingError;
 use crate::fill::MatchResult;
-use crate::order::{Order, OrderId, OrderSide, TradingPair};
+use crate::order::{Order, OrderSide, OrderType, TradingPair};
 use crate::orderbook::OrderBook;
 
 /// Central matching engine managing multiple order books
@@ -55,6 +55,13 @@ impl MatchingEngine {
         self.books.get(&pair.to_string())
     }
 
+    /// Replace a pair's book with a copy taken earlier
+    ///
+    /// Used to undo a match whose fills could not be settled.
+    pub fn restore_book(&mut self, book: OrderBook) {
+        self.books.insert(book.pair().to_string(), book);
+    }
+
     /// Get a mutable reference to an order book
     fn get_book_mut(&mut self, pair: &TradingPair) -> Option<&mut OrderBook> {
         self.books.get_mut(&pair.to_string())
@@ -74,6 +81,38 @@ impl MatchingEngine {
         book.match_order(order)
     }
 
+    /// Validate an order against its book without placing it
+    pub fn validate_order(&self, order: &Order) -> Result<(), MatchingError> {
+        self.get_book(&order.pair)
+            .ok_or_else(|| MatchingError::PairNotFound(order.pair.to_string()))?
+            .validate_order(order)
+    }
+
+    /// Restore a resting order without matching (journal replay)
+    pub fn restore_order(&mut self, order: Order) -> Result<(), MatchingError> {
+        let pair = order.pair.clone();
+
+        let book = self
+            .get_book_mut(&pair)
+            .ok_or_else(|| MatchingError::PairNotFound(pair.to_string()))?;
+
+        book.restore_order(order)
+    }
+
+    /// Apply a replayed fill to a resting order
+    pub fn apply_fill(
+        &mut self,
+        pair: &TradingPair,
+        order_id: &str,
+        quantity: Decimal,
+    ) -> Result<(), MatchingError> {
+        let book = self
+            .get_book_mut(pair)
+            .ok_or_else(|| MatchingError::PairNotFound(pair.to_string()))?;
+
+        book.apply_fill(order_id, quantity)
+    }
+
     /// Place a new limit order with parameters
     pub fn place_limit_order(
         &mut self,
@@ -89,6 +128,21 @@ impl MatchingEngine {
         Ok((order_clone, result))
     }
 
+    /// Place a market order with a slippage bound
+    pub fn place_market_order(
+        &mut self,
+        user_id: impl Into<String>,
+        pair: TradingPair,
+        side: OrderSide,
+        quantity: Decimal,
+        max_slippage: Decimal,
+    ) -> Result<(Order, MatchResult), MatchingError> {
+        let order = Order::market(user_id, pair, side, quantity, max_slippage);
+        let order_clone = order.clone();
+        let result = self.place_order(order)?;
+        Ok((order_clone, result))
+    }
+
     /// Cancel an order
     pub fn cancel_order(&mut self, pair: &TradingPair, order_id: &str) -> Result<Order, MatchingError> {
         let book = self
@@ -142,6 +196,11 @@ impl MatchingEngine {
         self.books.values().map(|b| b.pair().clone()).collect()
     }
 
+    /// All resting orders across books, in time priority per price level
+    pub fn resting_orders(&self) -> Vec<&Order> {
+        self.books.values().flat_map(|b| b.resting_orders()).collect()
+    }
+
     /// Total order count across all books
     pub fn total_order_count(&self) -> usize {
         self.books.values().map(|b| b.order_count()).sum()
@@ -185,6 +244,7 @@ pub struct OrderBuilder {
     side: Option<OrderSide>,
     price: Option<Decimal>,
     quantity: Option<Decimal>,
+    order_type: OrderType,
 }
 
 impl OrderBuilder {
@@ -195,6 +255,7 @@ impl OrderBuilder {
             side: None,
             price: None,
             quantity: None,
+            order_type: OrderType::Limit,
         }
     }
 
@@ -231,31 +292,41 @@ impl OrderBuilder {
         self
     }
 
+    pub fn order_type(mut self, order_type: OrderType) -> Self {
+        self.order_type = order_type;
+        self
+    }
+
+    /// Market order; no price is needed
+    pub fn market(self, max_slippage: Decimal) -> Self {
+        self.order_type(OrderType::Market { max_slippage })
+    }
+
     pub fn build(self) -> Result<Order, MatchingError> {
-        let user_id = self.user_id.ok_or_else(|| {
-            MatchingError::InvalidQuantity(Decimal::ZERO) // TODO: Better error
-        })?;
+        let user_id = self.user_id.ok_or(
+            MatchingError::InvalidQuantity(Decimal::ZERO), // TODO: Better error
+        )?;
         let pair = self.pair.ok_or_else(|| {
             MatchingError::PairNotFound("unspecified".to_string())
         })?;
-        let side = self.side.ok_or_else(|| {
-            MatchingError::InvalidQuantity(Decimal::ZERO) // TODO: Better error
-        })?;
-        let price = self.price.ok_or_else(|| {
-            MatchingError::InvalidPrice(Decimal::ZERO)
-        })?;
-        let quantity = self.quantity.ok_or_else(|| {
-            MatchingError::InvalidQuantity(Decimal::ZERO)
-        })?;
+        let side = self.side.ok_or(
+            MatchingError::InvalidQuantity(Decimal::ZERO), // TODO: Better error
+        )?;
+        let quantity = self.quantity.ok_or(MatchingError::InvalidQuantity(Decimal::ZERO))?;
+        if quantity <= Decimal::ZERO {
+            return Err(MatchingError::InvalidQuantity(quantity));
+        }
+
+        if let OrderType::Market { max_slippage } = self.order_type {
+            return Ok(Order::market(user_id, pair, side, quantity, max_slippage));
+        }
 
+        let price = self.price.ok_or(MatchingError::InvalidPrice(Decimal::ZERO))?;
         if price <= Decimal::ZERO {
             return Err(MatchingError::InvalidPrice(price));
         }
-        if quantity <= Decimal::ZERO {
-            return Err(MatchingError::InvalidQuantity(quantity));
-        }
 
-        Ok(Order::new(user_id, pair, side, price, quantity))
+        Ok(Order::new(user_id, pair, side, price, quantity).with_order_type(self.order_type))
     }
 }
 
@@ -268,6 +339,7 @@ impl Default for OrderBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::order::OrderStatus;
     use rust_decimal_macros::dec;
 
     fn create_engine() -> MatchingEngine {
@@ -335,6 +407,26 @@ mod tests {
         assert_eq!(engine.total_order_count(), 0);
     }
 
+    #[test]
+    fn test_restore_book_undoes_match() {
+        let mut engine = create_engine();
+        let pair = TradingPair::btc_usdt();
+
+        engine
+            .place_limit_order("BOB", pair.clone(), OrderSide::Sell, dec!(50000), dec!(1))
+            .unwrap();
+        let saved = engine.get_book(&pair).unwrap().clone();
+
+        engine
+            .place_limit_order("ALICE", pair.clone(), OrderSide::Buy, dec!(50000), dec!(1))
+            .unwrap();
+        assert_eq!(engine.total_order_count(), 0);
+
+        engine.restore_book(saved);
+        assert_eq!(engine.total_order_count(), 1);
+        assert_eq!(engine.best_ask(&pair), Some(dec!(50000)));
+    }
+
     #[test]
     fn test_cancel_order() {
         let mut engine = create_engine();
@@ -422,6 +514,48 @@ mod tests {
         assert!(matches!(result, Err(MatchingError::InvalidPrice(_))));
     }
 
+    #[test]
+    fn test_order_builder_order_types() {
+        let market = OrderBuilder::new()
+            .user_id("ALICE")
+            .pair(TradingPair::btc_usdt())
+            .buy()
+            .market(dec!(0.01))
+            .quantity(dec!(1))
+            .build()
+            .unwrap();
+        assert_eq!(market.order_type, OrderType::Market { max_slippage: dec!(0.01) });
+
+        let ioc = OrderBuilder::new()
+            .user_id("ALICE")
+            .pair(TradingPair::btc_usdt())
+            .sell()
+            .price(dec!(50000))
+            .quantity(dec!(1))
+            .order_type(OrderType::ImmediateOrCancel)
+            .build()
+            .unwrap();
+        assert_eq!(ioc.order_type, OrderType::ImmediateOrCancel);
+    }
+
+    #[test]
+    fn test_place_market_order() {
+        let mut engine = create_engine();
+
+        engine
+            .place_limit_order("BOB", TradingPair::btc_usdt(), OrderSide::Sell, dec!(50000), dec!(1))
+            .unwrap();
+
+        let (_, result) = engine
+            .place_market_order("ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(2), dec!(0.01))
+            .unwrap();
+
+        assert_eq!(result.total_filled(), dec!(1));
+        assert_eq!(result.cancelled_quantity, dec!(1));
+        assert_eq!(result.status, OrderStatus::Cancelled);
+        assert_eq!(engine.best_bid(&TradingPair::btc_usdt()), None);
+    }
+
     #[test]
     fn test_multiple_pairs() {
         let mut engine = create_engine();
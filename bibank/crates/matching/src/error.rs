@@ -37,4 +37,20 @@ pub enum MatchingError {
     /// Self-trade prevention
     #[error("Self-trade not allowed")]
     SelfTradeNotAllowed,
+
+    /// Market order slippage bound out of range
+    #[error("Invalid max slippage: {0} (must be >= 0 and < 1)")]
+    InvalidSlippage(Decimal),
+
+    /// Unknown order type
+    #[error("Invalid order type: {0}")]
+    InvalidOrderType(String),
+
+    /// Unknown candle interval
+    #[error("Invalid candle interval: {0} (use 1m, 5m, 1h or 1d)")]
+    InvalidInterval(String),
+
+    /// Malformed fee schedule
+    #[error("Invalid fee schedule: {0}")]
+    InvalidFeeSchedule(String),
 }
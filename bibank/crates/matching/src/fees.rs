@@ -0,0 +1,277 @@
+//! Fee schedules - maker/taker rates per trading pair with volume tiers
+//!
+//! A user's tier is the highest one whose `min_volume` their traded volume
+//! over the last [`FEE_VOLUME_DAYS`] days reaches, counted in the schedule's
+//! `volume_asset`. Pairs without tiers of their own use `default`; an empty
+//! schedule charges nothing.
+
+use crate::error::MatchingError;
+use crate::order::TradingPair;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Days of trading volume that count towards a fee tier
+pub const FEE_VOLUME_DAYS: i64 = 30;
+
+/// Asset volumes are counted in unless the schedule says otherwise
+pub const DEFAULT_VOLUME_ASSET: &str = "USDT";
+
+/// Rates from a volume threshold up
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeTier {
+    /// 30-day volume needed to reach the tier
+    pub min_volume: Decimal,
+    /// Rate charged on fills that rested on the book
+    pub maker_rate: Decimal,
+    /// Rate charged on fills that took liquidity
+    pub taker_rate: Decimal,
+}
+
+impl FeeTier {
+    pub fn new(min_volume: Decimal, maker_rate: Decimal, taker_rate: Decimal) -> Self {
+        Self {
+            min_volume,
+            maker_rate,
+            taker_rate,
+        }
+    }
+}
+
+/// Maker/taker fee schedule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeeSchedule {
+    /// Asset trading volume is counted in
+    #[serde(default = "default_volume_asset")]
+    pub volume_asset: String,
+    /// Tiers for pairs without their own, lowest volume first
+    #[serde(default)]
+    pub default: Vec<FeeTier>,
+    /// Tiers per pair (`BASE/QUOTE`), lowest volume first
+    #[serde(default)]
+    pub pairs: BTreeMap<String, Vec<FeeTier>>,
+}
+
+fn default_volume_asset() -> String {
+    DEFAULT_VOLUME_ASSET.to_string()
+}
+
+impl Default for FeeSchedule {
+    fn default() -> Self {
+        Self {
+            volume_asset: default_volume_asset(),
+            default: Vec::new(),
+            pairs: BTreeMap::new(),
+        }
+    }
+}
+
+/// Rates a user pays on a pair
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FeeRate {
+    pub pair: TradingPair,
+    /// 30-day volume the tier was picked by
+    pub volume: Decimal,
+    pub volume_asset: String,
+    /// Index of the tier reached (None if the pair has no tiers)
+    pub tier: Option<usize>,
+    pub maker_rate: Decimal,
+    pub taker_rate: Decimal,
+    /// Volume needed for the next tier, if there is one
+    pub next_tier_volume: Option<Decimal>,
+}
+
+impl FeeRate {
+    /// Rate for the maker or taker side of a fill
+    pub fn rate(&self, is_maker: bool) -> Decimal {
+        if is_maker {
+            self.maker_rate
+        } else {
+            self.taker_rate
+        }
+    }
+}
+
+impl FeeSchedule {
+    /// Load and validate a schedule from a JSON file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, MatchingError> {
+        let content = std::fs::read_to_string(path.as_ref())
+            .map_err(|e| MatchingError::InvalidFeeSchedule(e.to_string()))?;
+        let schedule: FeeSchedule = serde_json::from_str(&content)
+            .map_err(|e| MatchingError::InvalidFeeSchedule(e.to_string()))?;
+        schedule.normalized()
+    }
+
+    /// Check tiers and upper-case asset codes
+    pub fn normalized(self) -> Result<Self, MatchingError> {
+        validate_tiers("default", &self.default)?;
+
+        let mut pairs = BTreeMap::new();
+        for (name, tiers) in self.pairs {
+            let Some((base, quote)) = name.split_once('/') else {
+                return Err(MatchingError::InvalidFeeSchedule(format!(
+                    "pair {} is not BASE/QUOTE",
+                    name
+                )));
+            };
+            validate_tiers(&name, &tiers)?;
+            pairs.insert(TradingPair::new(base, quote).to_string(), tiers);
+        }
+
+        Ok(Self {
+            volume_asset: self.volume_asset.to_uppercase(),
+            default: self.default,
+            pairs,
+        })
+    }
+
+    /// Set the tiers of a pair
+    pub fn with_pair(mut self, pair: &TradingPair, tiers: Vec<FeeTier>) -> Self {
+        self.pairs.insert(pair.to_string(), tiers);
+        self
+    }
+
+    /// Set the tiers of pairs without their own
+    pub fn with_default(mut self, tiers: Vec<FeeTier>) -> Self {
+        self.default = tiers;
+        self
+    }
+
+    /// Tiers that apply to a pair
+    pub fn tiers(&self, pair: &TradingPair) -> &[FeeTier] {
+        self.pairs.get(&pair.to_string()).unwrap_or(&self.default)
+    }
+
+    /// True if no pair is charged
+    pub fn is_empty(&self) -> bool {
+        self.default.is_empty() && self.pairs.values().all(Vec::is_empty)
+    }
+
+    /// Rates for a user with `volume` traded over the last 30 days
+    pub fn rate(&self, pair: &TradingPair, volume: Decimal) -> FeeRate {
+        let tiers = self.tiers(pair);
+        let tier = tiers.iter().rposition(|t| volume >= t.min_volume);
+        let (maker_rate, taker_rate) = tier.map_or((Decimal::ZERO, Decimal::ZERO), |i| {
+            (tiers[i].maker_rate, tiers[i].taker_rate)
+        });
+        let next = tier.map_or(0, |i| i + 1);
+
+        FeeRate {
+            pair: pair.clone(),
+            volume,
+            volume_asset: self.volume_asset.clone(),
+            tier,
+            maker_rate,
+            taker_rate,
+            next_tier_volume: tiers.get(next).map(|t| t.min_volume),
+        }
+    }
+}
+
+/// Tiers must climb in volume, with rates in [0, 1)
+fn validate_tiers(name: &str, tiers: &[FeeTier]) -> Result<(), MatchingError> {
+    let invalid = |reason: &str| MatchingError::InvalidFeeSchedule(format!("{}: {}", name, reason));
+    let rate_ok = |rate: Decimal| rate >= Decimal::ZERO && rate < Decimal::ONE;
+
+    let mut previous: Option<Decimal> = None;
+    for tier in tiers {
+        if tier.min_volume < Decimal::ZERO {
+            return Err(invalid("min_volume can't be negative"));
+        }
+        if previous.is_some_and(|p| tier.min_volume <= p) {
+            return Err(invalid("tiers must be in increasing min_volume order"));
+        }
+        if !rate_ok(tier.maker_rate) || !rate_ok(tier.taker_rate) {
+            return Err(invalid("rates must be >= 0 and < 1"));
+        }
+        previous = Some(tier.min_volume);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn schedule() -> FeeSchedule {
+        FeeSchedule::default()
+            .with_default(vec![
+                FeeTier::new(dec!(0), dec!(0.001), dec!(0.002)),
+                FeeTier::new(dec!(100000), dec!(0.0008), dec!(0.0015)),
+                FeeTier::new(dec!(1000000), dec!(0), dec!(0.001)),
+            ])
+            .with_pair(
+                &TradingPair::eth_usdt(),
+                vec![FeeTier::new(dec!(0), dec!(0.0005), dec!(0.0005))],
+            )
+    }
+
+    #[test]
+    fn test_rate_picks_tier_by_volume() {
+        let schedule = schedule();
+        let pair = TradingPair::btc_usdt();
+
+        let rate = schedule.rate(&pair, dec!(5000));
+        assert_eq!(rate.tier, Some(0));
+        assert_eq!(rate.rate(true), dec!(0.001));
+        assert_eq!(rate.rate(false), dec!(0.002));
+        assert_eq!(rate.next_tier_volume, Some(dec!(100000)));
+
+        let rate = schedule.rate(&pair, dec!(100000));
+        assert_eq!(rate.tier, Some(1));
+        assert_eq!(rate.taker_rate, dec!(0.0015));
+
+        let rate = schedule.rate(&pair, dec!(2000000));
+        assert_eq!(rate.tier, Some(2));
+        assert_eq!(rate.maker_rate, Decimal::ZERO);
+        assert_eq!(rate.next_tier_volume, None);
+    }
+
+    #[test]
+    fn test_pair_overrides_default() {
+        let schedule = schedule();
+        let rate = schedule.rate(&TradingPair::eth_usdt(), dec!(5000000));
+        assert_eq!(rate.tier, Some(0));
+        assert_eq!(rate.taker_rate, dec!(0.0005));
+
+        let empty = FeeSchedule::default();
+        assert!(empty.is_empty());
+        let rate = empty.rate(&TradingPair::btc_usdt(), dec!(5000));
+        assert_eq!(rate.tier, None);
+        assert_eq!(rate.taker_rate, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_schedule_from_json() {
+        let json = r#"{
+            "default": [{ "min_volume": "0", "maker_rate": "0.001", "taker_rate": "0.002" }],
+            "pairs": { "btc/usdt": [{ "min_volume": "0", "maker_rate": "0", "taker_rate": "0.001" }] }
+        }"#;
+        let schedule: FeeSchedule = serde_json::from_str(json).unwrap();
+        let schedule = schedule.normalized().unwrap();
+        assert_eq!(schedule.volume_asset, "USDT");
+        assert_eq!(
+            schedule.rate(&TradingPair::btc_usdt(), dec!(0)).taker_rate,
+            dec!(0.001)
+        );
+
+        let unordered = FeeSchedule::default().with_default(vec![
+            FeeTier::new(dec!(1000), dec!(0.001), dec!(0.001)),
+            FeeTier::new(dec!(0), dec!(0.001), dec!(0.001)),
+        ]);
+        assert!(matches!(
+            unordered.normalized(),
+            Err(MatchingError::InvalidFeeSchedule(_))
+        ));
+
+        let too_high =
+            FeeSchedule::default().with_default(vec![FeeTier::new(dec!(0), dec!(1), dec!(0))]);
+        assert!(too_high.normalized().is_err());
+
+        let bad_pair: FeeSchedule =
+            serde_json::from_str(r#"{ "pairs": { "BTCUSDT": [] } }"#).unwrap();
+        assert!(bad_pair.normalized().is_err());
+    }
+}
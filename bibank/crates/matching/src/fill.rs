@@ -5,7 +5,7 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::order::{OrderId, OrderSide, TradingPair};
+use crate::order::{OrderId, OrderSide, OrderStatus, TradingPair};
 
 /// Unique fill identifier
 pub type FillId = String;
@@ -37,6 +37,7 @@ pub struct Fill {
 
 impl Fill {
     /// Create a new fill
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         pair: TradingPair,
         taker_order_id: OrderId,
@@ -90,8 +91,12 @@ pub struct MatchResult {
     pub fills: Vec<Fill>,
     /// Remaining quantity of the taker order (0 if fully filled)
     pub remaining_quantity: Decimal,
+    /// Part of the remaining quantity cancelled instead of resting (IOC, FOK, market, post-only)
+    pub cancelled_quantity: Decimal,
     /// Whether the taker order was fully filled
     pub fully_filled: bool,
+    /// Final status of the taker order
+    pub status: OrderStatus,
 }
 
 impl MatchResult {
@@ -100,10 +105,22 @@ impl MatchResult {
         Self {
             fills: Vec::new(),
             remaining_quantity: remaining,
+            cancelled_quantity: Decimal::ZERO,
             fully_filled: false,
+            status: OrderStatus::Open,
         }
     }
 
+    /// Quantity left resting on the book
+    pub fn resting_quantity(&self) -> Decimal {
+        self.remaining_quantity - self.cancelled_quantity
+    }
+
+    /// Whether any quantity was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled_quantity > Decimal::ZERO
+    }
+
     /// Total quantity filled
     pub fn total_filled(&self) -> Decimal {
         self.fills.iter().map(|f| f.quantity).sum()
@@ -208,4 +225,16 @@ mod tests {
         assert_eq!(result.total_notional(), Decimal::from(50050));
         assert_eq!(result.average_price(), Some(Decimal::from(50050)));
     }
+
+    #[test]
+    fn test_match_result_resting_quantity() {
+        let mut result = MatchResult::empty(Decimal::from(3));
+        assert_eq!(result.resting_quantity(), Decimal::from(3));
+        assert!(!result.is_cancelled());
+
+        result.cancelled_quantity = Decimal::from(3);
+        result.status = OrderStatus::Cancelled;
+        assert_eq!(result.resting_quantity(), Decimal::ZERO);
+        assert!(result.is_cancelled());
+    }
 }
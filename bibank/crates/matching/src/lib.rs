@@ -1,16 +1,22 @@
 //! BiBank Order Matching Engine
 //!
 //! CLOB (Central Limit Order Book) with price-time priority.
-//! Phase 3: Limit GTC orders only.
+//! Order types: limit (GTC), market, IOC, FOK and post-only.
+//! Market data: trade prints, L2 depth deltas and OHLCV candles.
+//! Fees: maker/taker schedules per pair with 30-day volume tiers.
 
 mod engine;
 mod error;
+mod fees;
 mod fill;
+mod market_data;
 mod order;
 mod orderbook;
 
-pub use engine::{MatchingEngine, OrderBookDepth};
+pub use engine::{MatchingEngine, OrderBookDepth, OrderBuilder};
 pub use error::MatchingError;
+pub use fees::{FeeRate, FeeSchedule, FeeTier, DEFAULT_VOLUME_ASSET, FEE_VOLUME_DAYS};
 pub use fill::{Fill, MatchResult};
-pub use order::{Order, OrderId, OrderSide, OrderStatus, TradingPair};
+pub use market_data::{Candle, CandleInterval, DepthChange, TradePrint};
+pub use order::{Order, OrderId, OrderSide, OrderStatus, OrderType, TradingPair};
 pub use orderbook::OrderBook;
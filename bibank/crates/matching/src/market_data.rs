@@ -0,0 +1,297 @@
+//! Market data: trade prints, L2 depth deltas and OHLCV candles
+//!
+//! Trade prints are read back from committed Trade entries, so candles
+//! rebuild identically from the journal. Depth deltas are diffs between
+//! two [`OrderBookDepth`] snapshots of the same book.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use bibank_ledger::{JournalEntry, TransactionIntent};
+use chrono::{DateTime, TimeZone, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::OrderBookDepth;
+use crate::error::MatchingError;
+use crate::order::{OrderSide, TradingPair};
+
+/// A changed price level (quantity is the new total; zero removes the level)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DepthChange {
+    pub side: OrderSide,
+    pub price: Decimal,
+    pub quantity: Decimal,
+}
+
+impl OrderBookDepth {
+    /// Levels that differ between this snapshot and `after`
+    ///
+    /// Bids first, then asks, each by ascending price.
+    pub fn changes_to(&self, after: &OrderBookDepth) -> Vec<DepthChange> {
+        let mut changes = level_changes(OrderSide::Buy, &self.bids, &after.bids);
+        changes.extend(level_changes(OrderSide::Sell, &self.asks, &after.asks));
+        changes
+    }
+}
+
+fn level_changes(
+    side: OrderSide,
+    before: &[(Decimal, Decimal)],
+    after: &[(Decimal, Decimal)],
+) -> Vec<DepthChange> {
+    let mut levels: BTreeMap<Decimal, (Decimal, Decimal)> = BTreeMap::new();
+    for (price, quantity) in before {
+        levels.entry(*price).or_default().0 = *quantity;
+    }
+    for (price, quantity) in after {
+        levels.entry(*price).or_default().1 = *quantity;
+    }
+
+    levels
+        .into_iter()
+        .filter(|(_, (before, after))| before != after)
+        .map(|(price, (_, quantity))| DepthChange {
+            side,
+            price,
+            quantity,
+        })
+        .collect()
+}
+
+/// An executed trade, as printed on the tape
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TradePrint {
+    pub pair: TradingPair,
+    /// Sequence of the Trade entry
+    pub sequence: u64,
+    pub price: Decimal,
+    /// Base quantity
+    pub quantity: Decimal,
+    /// Aggressor side (None for direct trades)
+    pub taker_side: Option<OrderSide>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl TradePrint {
+    /// Read the print back from a Trade entry's metadata
+    pub fn from_entry(entry: &JournalEntry) -> Option<Self> {
+        if entry.intent != TransactionIntent::Trade {
+            return None;
+        }
+        let meta = |key: &str| entry.metadata.get(key).and_then(|v| v.as_str());
+
+        let taker_side = match meta("taker_side") {
+            Some("buy") => Some(OrderSide::Buy),
+            Some("sell") => Some(OrderSide::Sell),
+            _ => None,
+        };
+        Some(Self {
+            pair: TradingPair::new(meta("base_asset")?, meta("quote_asset")?),
+            sequence: entry.sequence,
+            price: meta("price")?.parse().ok()?,
+            quantity: meta("base_amount")?.parse().ok()?,
+            taker_side,
+            timestamp: entry.timestamp,
+        })
+    }
+
+    /// Quote value of the trade (price * quantity)
+    pub fn notional_value(&self) -> Decimal {
+        self.price * self.quantity
+    }
+}
+
+/// Candle width
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum CandleInterval {
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "1d")]
+    OneDay,
+}
+
+impl CandleInterval {
+    /// All intervals, narrowest first
+    pub const ALL: [CandleInterval; 4] = [
+        CandleInterval::OneMinute,
+        CandleInterval::FiveMinutes,
+        CandleInterval::OneHour,
+        CandleInterval::OneDay,
+    ];
+
+    /// Width in seconds
+    pub fn seconds(&self) -> i64 {
+        match self {
+            CandleInterval::OneMinute => 60,
+            CandleInterval::FiveMinutes => 5 * 60,
+            CandleInterval::OneHour => 60 * 60,
+            CandleInterval::OneDay => 24 * 60 * 60,
+        }
+    }
+
+    /// Start of the candle containing `time` (aligned to the Unix epoch, UTC)
+    pub fn open_time(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let secs = time.timestamp();
+        let start = secs - secs.rem_euclid(self.seconds());
+        Utc.timestamp_opt(start, 0).single().unwrap_or(time)
+    }
+}
+
+impl std::fmt::Display for CandleInterval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CandleInterval::OneMinute => write!(f, "1m"),
+            CandleInterval::FiveMinutes => write!(f, "5m"),
+            CandleInterval::OneHour => write!(f, "1h"),
+            CandleInterval::OneDay => write!(f, "1d"),
+        }
+    }
+}
+
+impl FromStr for CandleInterval {
+    type Err = MatchingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1m" => Ok(CandleInterval::OneMinute),
+            "5m" => Ok(CandleInterval::FiveMinutes),
+            "1h" => Ok(CandleInterval::OneHour),
+            "1d" => Ok(CandleInterval::OneDay),
+            _ => Err(MatchingError::InvalidInterval(s.to_string())),
+        }
+    }
+}
+
+/// OHLCV candle for one pair and interval
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Candle {
+    pub pair: TradingPair,
+    pub interval: CandleInterval,
+    pub open_time: DateTime<Utc>,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    /// Base volume
+    pub volume: Decimal,
+    /// Quote volume
+    pub quote_volume: Decimal,
+    /// Number of trades
+    pub trades: u64,
+}
+
+impl Candle {
+    /// Open a candle with its first trade
+    pub fn open(interval: CandleInterval, trade: &TradePrint) -> Self {
+        Self {
+            pair: trade.pair.clone(),
+            interval,
+            open_time: interval.open_time(trade.timestamp),
+            open: trade.price,
+            high: trade.price,
+            low: trade.price,
+            close: trade.price,
+            volume: trade.quantity,
+            quote_volume: trade.notional_value(),
+            trades: 1,
+        }
+    }
+
+    /// Add a later trade in the same interval
+    pub fn update(&mut self, trade: &TradePrint) {
+        self.high = self.high.max(trade.price);
+        self.low = self.low.min(trade.price);
+        self.close = trade.price;
+        self.volume += trade.quantity;
+        self.quote_volume += trade.notional_value();
+        self.trades += 1;
+    }
+
+    /// Check if a trade falls into this candle
+    pub fn contains(&self, trade: &TradePrint) -> bool {
+        trade.pair == self.pair && self.interval.open_time(trade.timestamp) == self.open_time
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    fn print(price: Decimal, quantity: Decimal, time: &str) -> TradePrint {
+        TradePrint {
+            pair: TradingPair::btc_usdt(),
+            sequence: 1,
+            price,
+            quantity,
+            taker_side: Some(OrderSide::Buy),
+            timestamp: time.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_depth_changes() {
+        let before = OrderBookDepth {
+            pair: TradingPair::btc_usdt(),
+            bids: vec![(dec!(100), dec!(2)), (dec!(99), dec!(1))],
+            asks: vec![(dec!(101), dec!(1))],
+        };
+        let after = OrderBookDepth {
+            pair: TradingPair::btc_usdt(),
+            bids: vec![(dec!(100), dec!(1)), (dec!(99), dec!(1))],
+            asks: vec![(dec!(102), dec!(3))],
+        };
+
+        let changes = before.changes_to(&after);
+        assert_eq!(
+            changes,
+            vec![
+                DepthChange { side: OrderSide::Buy, price: dec!(100), quantity: dec!(1) },
+                DepthChange { side: OrderSide::Sell, price: dec!(101), quantity: dec!(0) },
+                DepthChange { side: OrderSide::Sell, price: dec!(102), quantity: dec!(3) },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interval_open_time() {
+        let time: DateTime<Utc> = "2026-03-01T10:17:42Z".parse().unwrap();
+        let open = |interval: CandleInterval| interval.open_time(time).to_rfc3339();
+
+        assert_eq!(open(CandleInterval::OneMinute), "2026-03-01T10:17:00+00:00");
+        assert_eq!(open(CandleInterval::FiveMinutes), "2026-03-01T10:15:00+00:00");
+        assert_eq!(open(CandleInterval::OneHour), "2026-03-01T10:00:00+00:00");
+        assert_eq!(open(CandleInterval::OneDay), "2026-03-01T00:00:00+00:00");
+        assert_eq!("5m".parse::<CandleInterval>().unwrap(), CandleInterval::FiveMinutes);
+        assert!("2m".parse::<CandleInterval>().is_err());
+    }
+
+    #[test]
+    fn test_candle_aggregation() {
+        let first = print(dec!(100), dec!(1), "2026-03-01T10:00:05Z");
+        let mut candle = Candle::open(CandleInterval::OneMinute, &first);
+
+        for trade in [
+            print(dec!(105), dec!(2), "2026-03-01T10:00:20Z"),
+            print(dec!(98), dec!(1), "2026-03-01T10:00:40Z"),
+            print(dec!(101), dec!(1), "2026-03-01T10:00:59Z"),
+        ] {
+            assert!(candle.contains(&trade));
+            candle.update(&trade);
+        }
+
+        assert_eq!(candle.open, dec!(100));
+        assert_eq!(candle.high, dec!(105));
+        assert_eq!(candle.low, dec!(98));
+        assert_eq!(candle.close, dec!(101));
+        assert_eq!(candle.volume, dec!(5));
+        assert_eq!(candle.quote_volume, dec!(509));
+        assert_eq!(candle.trades, 4);
+        assert!(!candle.contains(&print(dec!(100), dec!(1), "2026-03-01T10:01:00Z")));
+    }
+}
@@ -5,6 +5,8 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::error::MatchingError;
+
 /// Unique order identifier
 pub type OrderId = String;
 
@@ -35,11 +37,76 @@ impl std::fmt::Display for OrderSide {
     }
 }
 
+/// Order type (execution instruction)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OrderType {
+    /// Limit order, rests on the book until filled or cancelled (GTC)
+    #[default]
+    Limit,
+    /// Market order, takes liquidity up to `max_slippage` away from the best price
+    Market {
+        /// Maximum slippage as a fraction of the best price (0.01 = 1%)
+        max_slippage: Decimal,
+    },
+    /// Immediate-or-cancel: fill what crosses now, cancel the rest
+    ImmediateOrCancel,
+    /// Fill-or-kill: fill the whole quantity now or cancel entirely
+    FillOrKill,
+    /// Post-only: rest as maker, cancelled if it would take liquidity
+    PostOnly,
+}
+
+impl OrderType {
+    /// Default slippage bound for market orders (1%)
+    pub const DEFAULT_MAX_SLIPPAGE: Decimal = Decimal::from_parts(1, 0, 0, false, 2);
+
+    /// Market order with the default slippage bound
+    pub fn market() -> Self {
+        OrderType::Market {
+            max_slippage: Self::DEFAULT_MAX_SLIPPAGE,
+        }
+    }
+
+    /// Whether an unfilled remainder rests on the book
+    pub fn rests_on_book(&self) -> bool {
+        matches!(self, OrderType::Limit | OrderType::PostOnly)
+    }
+}
+
+impl std::fmt::Display for OrderType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrderType::Limit => write!(f, "limit"),
+            OrderType::Market { .. } => write!(f, "market"),
+            OrderType::ImmediateOrCancel => write!(f, "ioc"),
+            OrderType::FillOrKill => write!(f, "fok"),
+            OrderType::PostOnly => write!(f, "post_only"),
+        }
+    }
+}
+
+impl std::str::FromStr for OrderType {
+    type Err = MatchingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "limit" | "gtc" => Ok(OrderType::Limit),
+            "market" => Ok(OrderType::market()),
+            "ioc" => Ok(OrderType::ImmediateOrCancel),
+            "fok" => Ok(OrderType::FillOrKill),
+            "post_only" | "post-only" => Ok(OrderType::PostOnly),
+            _ => Err(MatchingError::InvalidOrderType(s.to_string())),
+        }
+    }
+}
+
 /// Order status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum OrderStatus {
     /// Order is open and waiting for fills
+    #[default]
     Open,
     /// Order is partially filled
     PartiallyFilled,
@@ -92,7 +159,7 @@ impl std::fmt::Display for TradingPair {
     }
 }
 
-/// A limit order in the order book
+/// An order in the order book
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Order {
     /// Unique order ID
@@ -103,8 +170,11 @@ pub struct Order {
     pub pair: TradingPair,
     /// Buy or Sell
     pub side: OrderSide,
-    /// Limit price
+    /// Limit price (for market orders, the slippage-protected limit set at match time)
     pub price: Decimal,
+    /// Order type
+    #[serde(default)]
+    pub order_type: OrderType,
     /// Original quantity
     pub quantity: Decimal,
     /// Filled quantity
@@ -133,6 +203,7 @@ impl Order {
             pair,
             side,
             price,
+            order_type: OrderType::Limit,
             quantity,
             filled: Decimal::ZERO,
             status: OrderStatus::Open,
@@ -157,6 +228,7 @@ impl Order {
             pair,
             side,
             price,
+            order_type: OrderType::Limit,
             quantity,
             filled: Decimal::ZERO,
             status: OrderStatus::Open,
@@ -165,6 +237,26 @@ impl Order {
         }
     }
 
+    /// Create a market order
+    ///
+    /// The limit price is derived from the book when the order is matched.
+    pub fn market(
+        user_id: impl Into<String>,
+        pair: TradingPair,
+        side: OrderSide,
+        quantity: Decimal,
+        max_slippage: Decimal,
+    ) -> Self {
+        Self::new(user_id, pair, side, Decimal::ZERO, quantity)
+            .with_order_type(OrderType::Market { max_slippage })
+    }
+
+    /// Set the order type
+    pub fn with_order_type(mut self, order_type: OrderType) -> Self {
+        self.order_type = order_type;
+        self
+    }
+
     /// Remaining unfilled quantity
     pub fn remaining(&self) -> Decimal {
         self.quantity - self.filled
@@ -269,6 +361,43 @@ mod tests {
         assert!(!order.is_active());
     }
 
+    #[test]
+    fn test_order_type_defaults_to_limit() {
+        let order = Order::new(
+            "ALICE",
+            TradingPair::btc_usdt(),
+            OrderSide::Buy,
+            Decimal::from(50000),
+            Decimal::from(1),
+        );
+        assert_eq!(order.order_type, OrderType::Limit);
+
+        // Orders serialized before order types existed still load
+        let mut json = serde_json::to_value(&order).unwrap();
+        json.as_object_mut().unwrap().remove("order_type");
+        let parsed: Order = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.order_type, OrderType::Limit);
+    }
+
+    #[test]
+    fn test_order_type_parse() {
+        assert_eq!("ioc".parse::<OrderType>().unwrap(), OrderType::ImmediateOrCancel);
+        assert_eq!("FOK".parse::<OrderType>().unwrap(), OrderType::FillOrKill);
+        assert_eq!("post-only".parse::<OrderType>().unwrap(), OrderType::PostOnly);
+        assert_eq!(
+            "market".parse::<OrderType>().unwrap(),
+            OrderType::Market {
+                max_slippage: Decimal::from_str_exact("0.01").unwrap()
+            }
+        );
+        assert!("stop".parse::<OrderType>().is_err());
+
+        assert!(OrderType::Limit.rests_on_book());
+        assert!(OrderType::PostOnly.rests_on_book());
+        assert!(!OrderType::ImmediateOrCancel.rests_on_book());
+        assert!(!OrderType::market().rests_on_book());
+    }
+
     #[test]
     fn test_trading_pair_display() {
         let pair = TradingPair::btc_usdt();
@@ -6,7 +6,7 @@ use rust_decimal::Decimal;
 
 use crate::error::MatchingError;
 use crate::fill::{Fill, MatchResult};
-use crate::order::{Order, OrderId, OrderSide, OrderStatus, TradingPair};
+use crate::order::{Order, OrderId, OrderSide, OrderStatus, OrderType, TradingPair};
 
 /// An order book for a single trading pair
 ///
@@ -14,7 +14,7 @@ use crate::order::{Order, OrderId, OrderSide, OrderStatus, TradingPair};
 /// - Bids (buy orders): sorted by price descending (highest first)
 /// - Asks (sell orders): sorted by price ascending (lowest first)
 /// - At each price level: FIFO queue (time priority)
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OrderBook {
     /// Trading pair
     pair: TradingPair,
@@ -174,22 +174,179 @@ impl OrderBook {
             .find(|o| o.id == order_id)
     }
 
-    /// Match an incoming order against the book
+    /// Validate an incoming order without touching the book
     ///
-    /// Returns fills and any remaining quantity
-    pub fn match_order(&mut self, mut order: Order) -> Result<MatchResult, MatchingError> {
-        // Validate order
+    /// Checks quantity, price and pair, and walks the crossing side of the book
+    /// to detect a self-trade before any maker order is filled.
+    pub fn validate_order(&self, order: &Order) -> Result<(), MatchingError> {
         if order.quantity <= Decimal::ZERO {
             return Err(MatchingError::InvalidQuantity(order.quantity));
         }
-        if order.price <= Decimal::ZERO {
-            return Err(MatchingError::InvalidPrice(order.price));
+        if order.pair != self.pair {
+            return Err(MatchingError::PairNotFound(order.pair.to_string()));
+        }
+
+        let limit = match order.order_type {
+            OrderType::Market { max_slippage } => {
+                if max_slippage < Decimal::ZERO || max_slippage >= Decimal::ONE {
+                    return Err(MatchingError::InvalidSlippage(max_slippage));
+                }
+                match self.slippage_limit(order.side, max_slippage) {
+                    Some(limit) => limit,
+                    None => return Ok(()), // Empty book: nothing to cross
+                }
+            }
+            _ => {
+                if order.price <= Decimal::ZERO {
+                    return Err(MatchingError::InvalidPrice(order.price));
+                }
+                order.price
+            }
+        };
+
+        // Post-only orders never take, so they can never self-trade
+        if order.order_type == OrderType::PostOnly {
+            return Ok(());
+        }
+
+        // Self-trade prevention: the order would fill makers in this sequence
+        let crossing: Box<dyn Iterator<Item = (&PriceLevel, &VecDeque<Order>)>> = match order.side {
+            OrderSide::Buy => Box::new(self.asks.iter().take_while(|(p, _)| p.0 <= limit)),
+            OrderSide::Sell => Box::new(self.bids.iter().rev().take_while(|(p, _)| p.0 >= limit)),
+        };
+
+        let mut remaining = order.remaining();
+        for maker_order in crossing.flat_map(|(_, queue)| queue.iter()) {
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+            if maker_order.user_id == order.user_id {
+                return Err(MatchingError::SelfTradeNotAllowed);
+            }
+            remaining -= remaining.min(maker_order.remaining());
+        }
+
+        Ok(())
+    }
+
+    /// Protective limit price for a market order
+    ///
+    /// Best opposite price moved `max_slippage` against the taker.
+    /// Returns None when the opposite side of the book is empty.
+    pub fn slippage_limit(&self, side: OrderSide, max_slippage: Decimal) -> Option<Decimal> {
+        match side {
+            OrderSide::Buy => self
+                .get_asks(1)
+                .first()
+                .map(|(best, _)| *best * (Decimal::ONE + max_slippage)),
+            OrderSide::Sell => self
+                .get_bids(1)
+                .first()
+                .map(|(best, _)| *best * (Decimal::ONE - max_slippage)),
+        }
+    }
+
+    /// Quantity available on the opposite side at or better than `limit`
+    pub fn fillable_quantity(&self, side: OrderSide, limit: Decimal) -> Decimal {
+        match side {
+            OrderSide::Buy => self
+                .get_asks(usize::MAX)
+                .into_iter()
+                .take_while(|(price, _)| *price <= limit)
+                .map(|(_, qty)| qty)
+                .sum(),
+            OrderSide::Sell => self
+                .get_bids(usize::MAX)
+                .into_iter()
+                .take_while(|(price, _)| *price >= limit)
+                .map(|(_, qty)| qty)
+                .sum(),
+        }
+    }
+
+    /// Whether an order at `price` would take liquidity
+    pub fn would_cross(&self, side: OrderSide, price: Decimal) -> bool {
+        match side {
+            OrderSide::Buy => self.best_ask().is_some_and(|ask| ask <= price),
+            OrderSide::Sell => self.best_bid().is_some_and(|bid| bid >= price),
+        }
+    }
+
+    /// Insert a resting order without matching
+    ///
+    /// Used when rebuilding the book from the journal, where the fills
+    /// are replayed separately from their Trade entries.
+    pub fn restore_order(&mut self, order: Order) -> Result<(), MatchingError> {
+        if self.orders.contains_key(&order.id) {
+            return Err(MatchingError::OrderAlreadyExists(order.id));
         }
         if order.pair != self.pair {
             return Err(MatchingError::PairNotFound(order.pair.to_string()));
         }
+        if order.is_active() && order.remaining() > Decimal::ZERO {
+            self.add_order(order);
+        }
+        Ok(())
+    }
 
-        let mut result = MatchResult::empty(order.quantity);
+    /// Apply a fill to a resting order, removing it once fully filled
+    pub fn apply_fill(&mut self, order_id: &str, quantity: Decimal) -> Result<(), MatchingError> {
+        let location = self
+            .orders
+            .get(order_id)
+            .cloned()
+            .ok_or_else(|| MatchingError::OrderNotFound(order_id.to_string()))?;
+
+        let book = match location.side {
+            OrderSide::Buy => &mut self.bids,
+            OrderSide::Sell => &mut self.asks,
+        };
+
+        let order = book
+            .get_mut(&location.price)
+            .and_then(|queue| queue.iter_mut().find(|o| o.id == order_id))
+            .ok_or_else(|| MatchingError::OrderNotFound(order_id.to_string()))?;
+
+        if quantity <= Decimal::ZERO || quantity > order.remaining() {
+            return Err(MatchingError::InvalidQuantity(quantity));
+        }
+
+        order.fill(quantity);
+        if order.is_filled() {
+            self.remove_order(order_id);
+        }
+        Ok(())
+    }
+
+    /// Match an incoming order against the book
+    ///
+    /// Returns fills, the remaining quantity and how much of it was
+    /// cancelled rather than left resting (see [`OrderType`]).
+    pub fn match_order(&mut self, mut order: Order) -> Result<MatchResult, MatchingError> {
+        // Validate order (before any maker is touched)
+        self.validate_order(&order)?;
+
+        let mut result = MatchResult::empty(order.remaining());
+
+        // Pre-match checks per order type; a killed order touches nothing
+        let killed = match order.order_type {
+            OrderType::Market { max_slippage } => match self.slippage_limit(order.side, max_slippage) {
+                Some(limit) => {
+                    order.price = limit;
+                    false
+                }
+                None => true, // No liquidity
+            },
+            OrderType::FillOrKill => self.fillable_quantity(order.side, order.price) < order.remaining(),
+            OrderType::PostOnly => self.would_cross(order.side, order.price),
+            OrderType::Limit | OrderType::ImmediateOrCancel => false,
+        };
+        if killed {
+            order.cancel();
+            result.cancelled_quantity = order.remaining();
+            result.status = order.status;
+            return Ok(result);
+        }
 
         // Get opposite side book
         let can_match = match order.side {
@@ -243,11 +400,6 @@ impl OrderBook {
                     break;
                 }
 
-                // Self-trade prevention
-                if maker_order.user_id == order.user_id {
-                    return Err(MatchingError::SelfTradeNotAllowed);
-                }
-
                 let fill_qty = order.remaining().min(maker_order.remaining());
 
                 // Create fill
@@ -289,11 +441,18 @@ impl OrderBook {
 
         result.remaining_quantity = order.remaining();
 
-        // If order has remaining quantity, add it to the book
+        // Remaining quantity rests on the book or is cancelled, by order type
         if order.remaining() > Decimal::ZERO && order.is_active() {
-            self.add_order(order);
+            if order.order_type.rests_on_book() {
+                result.status = order.status;
+                self.add_order(order);
+                return Ok(result);
+            }
+            order.cancel();
+            result.cancelled_quantity = order.remaining();
         }
 
+        result.status = order.status;
         Ok(result)
     }
 
@@ -313,6 +472,16 @@ impl OrderBook {
         Ok(order)
     }
 
+    /// All resting orders, each price level in time priority
+    ///
+    /// Restoring them in this order rebuilds an identical book.
+    pub fn resting_orders(&self) -> impl Iterator<Item = &Order> {
+        self.bids
+            .values()
+            .chain(self.asks.values())
+            .flat_map(|queue| queue.iter())
+    }
+
     /// Get all bids as (price, total_quantity) tuples, sorted by price descending
     pub fn get_bids(&self, depth: usize) -> Vec<(Decimal, Decimal)> {
         self.bids
@@ -547,6 +716,263 @@ mod tests {
         assert!(matches!(result, Err(MatchingError::SelfTradeNotAllowed)));
     }
 
+    #[test]
+    fn test_self_trade_leaves_book_untouched() {
+        let mut book = create_test_book();
+
+        // BOB at the better price, ALICE behind him
+        let bob = Order::with_id("sell-1", "BOB", TradingPair::btc_usdt(), OrderSide::Sell, dec!(50000), dec!(1));
+        let alice = Order::with_id("sell-2", "ALICE", TradingPair::btc_usdt(), OrderSide::Sell, dec!(50100), dec!(1));
+        book.match_order(bob).unwrap();
+        book.match_order(alice).unwrap();
+
+        // ALICE's buy would fill BOB first, then hit her own order
+        let buy = Order::new("ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(50100), dec!(2));
+        let result = book.match_order(buy);
+
+        assert!(matches!(result, Err(MatchingError::SelfTradeNotAllowed)));
+        assert_eq!(book.get_order("sell-1").unwrap().filled, Decimal::ZERO);
+        assert_eq!(book.order_count(), 2);
+    }
+
+    #[test]
+    fn test_restore_and_apply_fill() {
+        let mut book = create_test_book();
+
+        // Restored orders rest even when they cross
+        let sell = Order::with_id("sell-1", "BOB", TradingPair::btc_usdt(), OrderSide::Sell, dec!(50000), dec!(2));
+        let buy = Order::with_id("buy-1", "ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(50000), dec!(1));
+        book.restore_order(sell.clone()).unwrap();
+        book.restore_order(buy).unwrap();
+        assert_eq!(book.order_count(), 2);
+        assert!(matches!(
+            book.restore_order(sell),
+            Err(MatchingError::OrderAlreadyExists(_))
+        ));
+
+        book.apply_fill("sell-1", dec!(1)).unwrap();
+        book.apply_fill("buy-1", dec!(1)).unwrap();
+
+        assert_eq!(book.order_count(), 1);
+        assert!(book.get_order("buy-1").is_none());
+        let sell = book.get_order("sell-1").unwrap();
+        assert_eq!(sell.remaining(), dec!(1));
+        assert_eq!(sell.status, OrderStatus::PartiallyFilled);
+
+        // Overfill is rejected
+        assert!(matches!(
+            book.apply_fill("sell-1", dec!(5)),
+            Err(MatchingError::InvalidQuantity(_))
+        ));
+    }
+
+    /// Asks: BOB 1 @ 50000, CAROL 1 @ 50500, DAVE 1 @ 52000
+    fn create_ask_ladder() -> OrderBook {
+        let mut book = create_test_book();
+        for (id, user, price) in [
+            ("ask-1", "BOB", dec!(50000)),
+            ("ask-2", "CAROL", dec!(50500)),
+            ("ask-3", "DAVE", dec!(52000)),
+        ] {
+            let order = Order::with_id(id, user, TradingPair::btc_usdt(), OrderSide::Sell, price, dec!(1));
+            book.match_order(order).unwrap();
+        }
+        book
+    }
+
+    #[test]
+    fn test_limit_order_status() {
+        let mut book = create_ask_ladder();
+
+        let buy = Order::new("ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(50000), dec!(2));
+        let result = book.match_order(buy).unwrap();
+
+        assert_eq!(result.status, OrderStatus::PartiallyFilled);
+        assert_eq!(result.total_filled(), dec!(1));
+        assert_eq!(result.remaining_quantity, dec!(1));
+        assert_eq!(result.cancelled_quantity, Decimal::ZERO);
+        assert_eq!(result.resting_quantity(), dec!(1));
+        assert_eq!(book.best_bid(), Some(dec!(50000)));
+    }
+
+    #[test]
+    fn test_market_order_slippage_protection() {
+        let mut book = create_ask_ladder();
+
+        // 2% above 50000 reaches 51000: takes BOB and CAROL, not DAVE
+        let buy = Order::market("ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(3), dec!(0.02));
+        let result = book.match_order(buy).unwrap();
+
+        assert_eq!(result.fills.len(), 2);
+        assert_eq!(result.total_filled(), dec!(2));
+        assert_eq!(result.average_price(), Some(dec!(50250)));
+        assert_eq!(result.cancelled_quantity, dec!(1));
+        assert_eq!(result.status, OrderStatus::Cancelled);
+        assert!(!result.fully_filled);
+
+        // Market orders never rest
+        assert_eq!(book.best_bid(), None);
+        assert_eq!(book.best_ask(), Some(dec!(52000)));
+    }
+
+    #[test]
+    fn test_market_order_full_fill() {
+        let mut book = create_ask_ladder();
+
+        let buy = Order::market("ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(1.5), dec!(0.05));
+        let result = book.match_order(buy).unwrap();
+
+        assert!(result.fully_filled);
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!(result.cancelled_quantity, Decimal::ZERO);
+        assert_eq!(book.get_order("ask-2").unwrap().remaining(), dec!(0.5));
+    }
+
+    #[test]
+    fn test_market_order_empty_book() {
+        let mut book = create_test_book();
+
+        let sell = Order::market("BOB", TradingPair::btc_usdt(), OrderSide::Sell, dec!(1), dec!(0.01));
+        let result = book.match_order(sell).unwrap();
+
+        assert!(result.fills.is_empty());
+        assert_eq!(result.cancelled_quantity, dec!(1));
+        assert_eq!(result.status, OrderStatus::Cancelled);
+        assert_eq!(book.order_count(), 0);
+    }
+
+    #[test]
+    fn test_market_order_invalid_slippage() {
+        let mut book = create_ask_ladder();
+
+        let buy = Order::market("ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(1), dec!(1));
+        assert!(matches!(
+            book.match_order(buy),
+            Err(MatchingError::InvalidSlippage(_))
+        ));
+        assert_eq!(book.order_count(), 3);
+    }
+
+    #[test]
+    fn test_slippage_limit_and_fillable_quantity() {
+        let book = create_ask_ladder();
+
+        assert_eq!(book.slippage_limit(OrderSide::Buy, dec!(0.01)), Some(dec!(50500)));
+        assert_eq!(book.slippage_limit(OrderSide::Sell, dec!(0.01)), None);
+        assert_eq!(book.fillable_quantity(OrderSide::Buy, dec!(50500)), dec!(2));
+        assert_eq!(book.fillable_quantity(OrderSide::Buy, dec!(49999)), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_ioc_cancels_remainder() {
+        let mut book = create_ask_ladder();
+
+        let buy = Order::new("ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(50500), dec!(5))
+            .with_order_type(OrderType::ImmediateOrCancel);
+        let result = book.match_order(buy).unwrap();
+
+        assert_eq!(result.total_filled(), dec!(2));
+        assert_eq!(result.remaining_quantity, dec!(3));
+        assert_eq!(result.cancelled_quantity, dec!(3));
+        assert_eq!(result.resting_quantity(), Decimal::ZERO);
+        assert_eq!(result.status, OrderStatus::Cancelled);
+        assert_eq!(book.best_bid(), None);
+    }
+
+    #[test]
+    fn test_ioc_full_fill() {
+        let mut book = create_ask_ladder();
+
+        let buy = Order::new("ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(50000), dec!(1))
+            .with_order_type(OrderType::ImmediateOrCancel);
+        let result = book.match_order(buy).unwrap();
+
+        assert!(result.fully_filled);
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert!(!result.is_cancelled());
+    }
+
+    #[test]
+    fn test_fok_killed_when_insufficient_depth() {
+        let mut book = create_ask_ladder();
+
+        let buy = Order::new("ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(50500), dec!(3))
+            .with_order_type(OrderType::FillOrKill);
+        let result = book.match_order(buy).unwrap();
+
+        assert!(result.fills.is_empty());
+        assert_eq!(result.cancelled_quantity, dec!(3));
+        assert_eq!(result.status, OrderStatus::Cancelled);
+        // Book untouched
+        assert_eq!(book.order_count(), 3);
+        assert_eq!(book.get_order("ask-1").unwrap().filled, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_fok_fills_completely() {
+        let mut book = create_ask_ladder();
+
+        let buy = Order::new("ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(52000), dec!(3))
+            .with_order_type(OrderType::FillOrKill);
+        let result = book.match_order(buy).unwrap();
+
+        assert!(result.fully_filled);
+        assert_eq!(result.fills.len(), 3);
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!(book.order_count(), 0);
+    }
+
+    #[test]
+    fn test_post_only_rejected_when_crossing() {
+        let mut book = create_ask_ladder();
+
+        let buy = Order::new("ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(50000), dec!(1))
+            .with_order_type(OrderType::PostOnly);
+        let result = book.match_order(buy).unwrap();
+
+        assert!(result.fills.is_empty());
+        assert_eq!(result.cancelled_quantity, dec!(1));
+        assert_eq!(result.status, OrderStatus::Cancelled);
+        assert_eq!(book.best_bid(), None);
+    }
+
+    #[test]
+    fn test_post_only_rests_as_maker() {
+        let mut book = create_ask_ladder();
+
+        let buy = Order::with_id("bid-1", "ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(49900), dec!(1))
+            .with_order_type(OrderType::PostOnly);
+        let result = book.match_order(buy).unwrap();
+
+        assert!(result.fills.is_empty());
+        assert_eq!(result.status, OrderStatus::Open);
+        assert_eq!(result.resting_quantity(), dec!(1));
+        assert_eq!(book.get_order("bid-1").unwrap().order_type, OrderType::PostOnly);
+    }
+
+    #[test]
+    fn test_resting_orders_rebuild_book() {
+        let mut book = create_ask_ladder();
+        let bid = Order::with_id("bid-1", "ALICE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(49000), dec!(1));
+        book.match_order(bid).unwrap();
+        let late = Order::with_id("ask-4", "ERIN", TradingPair::btc_usdt(), OrderSide::Sell, dec!(50000), dec!(1));
+        book.match_order(late).unwrap();
+
+        let mut rebuilt = create_test_book();
+        for order in book.resting_orders() {
+            rebuilt.restore_order(order.clone()).unwrap();
+        }
+
+        assert_eq!(rebuilt.order_count(), 5);
+        assert_eq!(rebuilt.get_bids(10), book.get_bids(10));
+        assert_eq!(rebuilt.get_asks(10), book.get_asks(10));
+
+        // Time priority survives: BOB still ahead of ERIN at 50000
+        let buy = Order::new("ZOE", TradingPair::btc_usdt(), OrderSide::Buy, dec!(50000), dec!(1));
+        let result = rebuilt.match_order(buy).unwrap();
+        assert_eq!(result.fills[0].maker_order_id, "ask-1");
+    }
+
     #[test]
     fn test_depth() {
         let mut book = create_test_book();
@@ -27,4 +27,27 @@ pub enum OracleError {
         #[source]
         source: Box<dyn std::error::Error + Send + Sync>,
     },
+
+    /// No quote at or before the requested time
+    #[error("No price for {pair} at or before {timestamp}")]
+    NoPriceAt { pair: String, timestamp: String },
+
+    /// Too few fresh quotes to aggregate
+    #[error("Not enough fresh sources for {pair}: {fresh} fresh, {required} required")]
+    InsufficientSources {
+        pair: String,
+        fresh: usize,
+        required: usize,
+    },
+
+    /// Malformed price feed file
+    #[error("Invalid price feed at line {line}: {reason}")]
+    InvalidFeed { line: usize, reason: String },
+
+    /// I/O error reading a price feed
+    #[error("Price feed I/O error: {source}")]
+    Io {
+        #[from]
+        source: std::io::Error,
+    },
 }
@@ -0,0 +1,276 @@
+//! HTTP Oracle - prices from any JSON endpoint
+//!
+//! The request URL is a template with `{base}` and `{quote}` placeholders
+//! (e.g. `http://feed.local/ticker?symbol={base}{quote}`). Fields are picked
+//! out of the response with JSON pointers; numbers and numeric strings are
+//! both accepted.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde_json::Value;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::error::OracleError;
+use crate::types::{Price, PriceOracle, TradingPair};
+
+/// Default request timeout
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Price Oracle backed by an HTTP JSON endpoint
+pub struct HttpOracle {
+    client: reqwest::Client,
+    url_template: String,
+    /// Pointer to the last price (required)
+    price_pointer: String,
+    /// Pointers to bid/ask (default to the last price)
+    bid_pointer: Option<String>,
+    ask_pointer: Option<String>,
+    /// Pointer to the quote time (defaults to the fetch time)
+    timestamp_pointer: Option<String>,
+    pairs: Vec<TradingPair>,
+    source: String,
+    timeout: Duration,
+}
+
+impl HttpOracle {
+    /// Create an oracle for a URL template, reading the price from `/price`
+    pub fn new(url_template: impl Into<String>) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            url_template: url_template.into(),
+            price_pointer: "/price".to_string(),
+            bid_pointer: None,
+            ask_pointer: None,
+            timestamp_pointer: None,
+            pairs: Vec::new(),
+            source: "http".to_string(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    pub fn price_pointer(mut self, pointer: impl Into<String>) -> Self {
+        self.price_pointer = pointer.into();
+        self
+    }
+
+    pub fn bid_pointer(mut self, pointer: impl Into<String>) -> Self {
+        self.bid_pointer = Some(pointer.into());
+        self
+    }
+
+    pub fn ask_pointer(mut self, pointer: impl Into<String>) -> Self {
+        self.ask_pointer = Some(pointer.into());
+        self
+    }
+
+    /// Quote time: Unix seconds/milliseconds or an RFC 3339 string
+    pub fn timestamp_pointer(mut self, pointer: impl Into<String>) -> Self {
+        self.timestamp_pointer = Some(pointer.into());
+        self
+    }
+
+    /// Declare a supported pair
+    pub fn pair(mut self, pair: TradingPair) -> Self {
+        self.pairs.push(pair);
+        self
+    }
+
+    /// Name stamped on returned prices
+    pub fn source_name(mut self, name: impl Into<String>) -> Self {
+        self.source = name.into();
+        self
+    }
+
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Request URL for a pair
+    pub fn url_for(&self, pair: &TradingPair) -> String {
+        self.url_template
+            .replace("{base}", &pair.base)
+            .replace("{quote}", &pair.quote)
+    }
+
+    /// Build a price from a response body
+    fn parse(&self, pair: &TradingPair, body: &Value) -> Result<Price, OracleError> {
+        let invalid = |reason: String| OracleError::InvalidPrice {
+            pair: pair.to_string(),
+            reason,
+        };
+        let field = |pointer: &str| -> Result<Decimal, OracleError> {
+            let value = body
+                .pointer(pointer)
+                .ok_or_else(|| invalid(format!("missing field {}", pointer)))?;
+            decimal_value(value).ok_or_else(|| invalid(format!("{} is not a number: {}", pointer, value)))
+        };
+
+        let last = field(&self.price_pointer)?;
+        let bid = match &self.bid_pointer {
+            Some(pointer) => field(pointer)?,
+            None => last,
+        };
+        let ask = match &self.ask_pointer {
+            Some(pointer) => field(pointer)?,
+            None => last,
+        };
+        if last <= Decimal::ZERO || bid <= Decimal::ZERO || ask < bid {
+            return Err(invalid(format!("bid={} ask={} last={}", bid, ask, last)));
+        }
+
+        let timestamp = match &self.timestamp_pointer {
+            Some(pointer) => body
+                .pointer(pointer)
+                .and_then(timestamp_value)
+                .ok_or_else(|| invalid(format!("missing or bad timestamp {}", pointer)))?,
+            None => Utc::now(),
+        };
+
+        Ok(Price {
+            pair: pair.clone(),
+            bid,
+            ask,
+            last,
+            timestamp,
+            source: self.source.clone(),
+        })
+    }
+}
+
+fn decimal_value(value: &Value) -> Option<Decimal> {
+    match value {
+        Value::String(s) => Decimal::from_str(s).ok(),
+        Value::Number(n) => Decimal::from_str(&n.to_string())
+            .ok()
+            .or_else(|| n.as_f64().and_then(|f| Decimal::try_from(f).ok())),
+        _ => None,
+    }
+}
+
+fn timestamp_value(value: &Value) -> Option<DateTime<Utc>> {
+    match value {
+        Value::Number(n) => {
+            let n = n.as_i64()?;
+            // Millisecond timestamps are 13 digits
+            if n > 100_000_000_000 {
+                DateTime::from_timestamp_millis(n)
+            } else {
+                DateTime::from_timestamp(n, 0)
+            }
+        }
+        Value::String(s) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|ts| ts.with_timezone(&Utc)),
+        _ => None,
+    }
+}
+
+fn connection_failed(e: reqwest::Error) -> OracleError {
+    OracleError::ConnectionFailed {
+        source: Box::new(e),
+    }
+}
+
+#[async_trait]
+impl PriceOracle for HttpOracle {
+    async fn get_price(&self, pair: &TradingPair) -> Result<Price, OracleError> {
+        let response = self
+            .client
+            .get(self.url_for(pair))
+            .timeout(self.timeout)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(connection_failed)?;
+
+        let bytes = response.bytes().await.map_err(connection_failed)?;
+        let body: Value = serde_json::from_slice(&bytes).map_err(|e| OracleError::InvalidPrice {
+            pair: pair.to_string(),
+            reason: format!("response is not JSON: {}", e),
+        })?;
+
+        self.parse(pair, &body)
+    }
+
+    async fn supported_pairs(&self) -> Vec<TradingPair> {
+        self.pairs.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Local stub: answers every request with `status` and `body`, echoing the path in a header
+    async fn stub_server(status: &'static str, body: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let n = stream.read(&mut buf).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request.split_whitespace().nth(1).unwrap_or("").to_string();
+
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nX-Path: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    path,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.ok();
+            }
+        });
+
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_http_oracle_reads_json_fields() {
+        let base = stub_server(
+            "200 OK",
+            r#"{"data":{"bid":"49990.5","ask":50010,"last":"50000"},"ts":1769335200000}"#,
+        )
+        .await;
+
+        let oracle = HttpOracle::new(format!("{}/ticker/{{base}}-{{quote}}", base))
+            .price_pointer("/data/last")
+            .bid_pointer("/data/bid")
+            .ask_pointer("/data/ask")
+            .timestamp_pointer("/ts")
+            .source_name("stub")
+            .pair(TradingPair::btc_usdt());
+
+        assert_eq!(oracle.url_for(&TradingPair::btc_usdt()), format!("{}/ticker/BTC-USDT", base));
+
+        let price = oracle.get_price(&TradingPair::btc_usdt()).await.unwrap();
+        assert_eq!(price.last, Decimal::from(50000));
+        assert_eq!(price.bid, Decimal::from_str("49990.5").unwrap());
+        assert_eq!(price.ask, Decimal::from(50010));
+        assert_eq!(price.timestamp, DateTime::from_timestamp(1_769_335_200, 0).unwrap());
+        assert_eq!(price.source, "stub");
+        assert!(oracle.is_supported(&TradingPair::btc_usdt()).await);
+    }
+
+    #[tokio::test]
+    async fn test_http_oracle_errors() {
+        let base = stub_server("200 OK", r#"{"price":"not a number"}"#).await;
+        let result = HttpOracle::new(base).get_price(&TradingPair::btc_usdt()).await;
+        assert!(matches!(result, Err(OracleError::InvalidPrice { .. })));
+
+        let base = stub_server("200 OK", r#"{"other":1}"#).await;
+        let result = HttpOracle::new(base).get_price(&TradingPair::btc_usdt()).await;
+        assert!(matches!(result, Err(OracleError::InvalidPrice { .. })));
+
+        let base = stub_server("503 Service Unavailable", "{}").await;
+        let result = HttpOracle::new(base).get_price(&TradingPair::btc_usdt()).await;
+        assert!(matches!(result, Err(OracleError::ConnectionFailed { .. })));
+    }
+}
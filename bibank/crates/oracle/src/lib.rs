@@ -1,12 +1,21 @@
 //! BiBank Price Oracle
 //!
-//! Provides price feeds for margin calculation and PnL computation.
-//! Currently implements MockOracle for testing; can be extended for external feeds.
+//! Provides price feeds for margin calculation and PnL computation:
+//! - `MockOracle`: fixed prices for testing
+//! - `ReplayOracle`: recorded prices from a CSV file, served as of a point in time
+//! - `HttpOracle`: any JSON price endpoint
+//! - `MedianOracle`: median over several oracles, ignoring stale quotes
 
 mod error;
+mod http;
+mod median;
 mod mock;
+mod replay;
 mod types;
 
 pub use error::OracleError;
+pub use http::HttpOracle;
+pub use median::{MedianOracle, DEFAULT_MAX_AGE_SECS};
 pub use mock::MockOracle;
+pub use replay::ReplayOracle;
 pub use types::{Price, PriceOracle, TradingPair};
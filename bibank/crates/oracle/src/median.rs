@@ -0,0 +1,182 @@
+//! Median Oracle - aggregates several price feeds
+//!
+//! Queries every source, drops failed and stale quotes, and serves the
+//! median of what is left. A single bad feed can't move the price as long
+//! as most sources agree.
+
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+use crate::error::OracleError;
+use crate::types::{Price, PriceOracle, TradingPair};
+
+/// Default maximum quote age (seconds)
+pub const DEFAULT_MAX_AGE_SECS: u64 = 60;
+
+/// Median over several Price Oracles
+pub struct MedianOracle {
+    sources: Vec<Arc<dyn PriceOracle>>,
+    /// Quotes older than this are ignored
+    max_age_secs: u64,
+    /// Fresh quotes required to serve a price
+    min_sources: usize,
+}
+
+impl MedianOracle {
+    /// Create an aggregator with no sources
+    pub fn new() -> Self {
+        Self {
+            sources: Vec::new(),
+            max_age_secs: DEFAULT_MAX_AGE_SECS,
+            min_sources: 1,
+        }
+    }
+
+    /// Add a price source
+    pub fn source(mut self, oracle: Arc<dyn PriceOracle>) -> Self {
+        self.sources.push(oracle);
+        self
+    }
+
+    /// Set the maximum quote age
+    pub fn max_age_secs(mut self, secs: u64) -> Self {
+        self.max_age_secs = secs;
+        self
+    }
+
+    /// Set the number of fresh quotes required
+    pub fn min_sources(mut self, count: usize) -> Self {
+        self.min_sources = count.max(1);
+        self
+    }
+
+    /// Number of configured sources
+    pub fn source_count(&self) -> usize {
+        self.sources.len()
+    }
+}
+
+impl Default for MedianOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Median of a non-empty list (mean of the middle two for even lengths)
+fn median(mut values: Vec<Decimal>) -> Decimal {
+    values.sort();
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / Decimal::from(2)
+    } else {
+        values[mid]
+    }
+}
+
+#[async_trait]
+impl PriceOracle for MedianOracle {
+    async fn get_price(&self, pair: &TradingPair) -> Result<Price, OracleError> {
+        let mut fresh = Vec::new();
+        for source in &self.sources {
+            match source.get_price(pair).await {
+                Ok(price) if !price.is_stale(self.max_age_secs) => fresh.push(price),
+                Ok(price) => tracing::debug!("Ignoring stale {} quote from {}", pair, price.source),
+                Err(e) => tracing::debug!("Ignoring failed {} quote: {}", pair, e),
+            }
+        }
+
+        if fresh.len() < self.min_sources {
+            return Err(OracleError::InsufficientSources {
+                pair: pair.to_string(),
+                fresh: fresh.len(),
+                required: self.min_sources,
+            });
+        }
+
+        // Report the age of the oldest quote that went into the median
+        let timestamp = fresh.iter().map(|p| p.timestamp).min().unwrap_or_default();
+
+        Ok(Price {
+            pair: pair.clone(),
+            bid: median(fresh.iter().map(|p| p.bid).collect()),
+            ask: median(fresh.iter().map(|p| p.ask).collect()),
+            last: median(fresh.iter().map(|p| p.last).collect()),
+            timestamp,
+            source: format!("median({})", fresh.len()),
+        })
+    }
+
+    async fn supported_pairs(&self) -> Vec<TradingPair> {
+        let mut pairs = Vec::new();
+        for source in &self.sources {
+            for pair in source.supported_pairs().await {
+                if !pairs.contains(&pair) {
+                    pairs.push(pair);
+                }
+            }
+        }
+        pairs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::MockOracle;
+    use crate::replay::ReplayOracle;
+
+    fn mock(price: i64) -> Arc<dyn PriceOracle> {
+        let oracle = MockOracle::new();
+        oracle.set_price(TradingPair::btc_usdt(), Decimal::from(price));
+        Arc::new(oracle)
+    }
+
+    #[tokio::test]
+    async fn test_median_of_fresh_sources() {
+        let oracle = MedianOracle::new()
+            .source(mock(50000))
+            .source(mock(50100))
+            .source(mock(90000)); // outlier
+
+        let price = oracle.get_price(&TradingPair::btc_usdt()).await.unwrap();
+        assert_eq!(price.last, Decimal::from(50100));
+        assert_eq!(price.source, "median(3)");
+
+        let oracle = MedianOracle::new().source(mock(50000)).source(mock(50100));
+        let price = oracle.get_price(&TradingPair::btc_usdt()).await.unwrap();
+        assert_eq!(price.last, Decimal::from(50050));
+    }
+
+    #[tokio::test]
+    async fn test_median_rejects_stale_quotes() {
+        // Recorded quote from 2026-01-25: stale against the wall clock
+        let stale = ReplayOracle::from_csv("2026-01-25T10:00:00Z,BTC/USDT,10000\n").unwrap();
+
+        let oracle = MedianOracle::new()
+            .max_age_secs(60)
+            .source(Arc::new(stale))
+            .source(mock(50000));
+        let price = oracle.get_price(&TradingPair::btc_usdt()).await.unwrap();
+        assert_eq!(price.last, Decimal::from(50000));
+        assert_eq!(price.source, "median(1)");
+
+        let oracle = oracle.min_sources(2);
+        let result = oracle.get_price(&TradingPair::btc_usdt()).await;
+        assert!(matches!(
+            result,
+            Err(OracleError::InsufficientSources { fresh: 1, required: 2, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_median_skips_failing_sources() {
+        let oracle = MedianOracle::new()
+            .source(Arc::new(MockOracle::new()))
+            .source(mock(50000));
+
+        let price = oracle.get_price(&TradingPair::btc_usdt()).await.unwrap();
+        assert_eq!(price.last, Decimal::from(50000));
+        assert_eq!(oracle.supported_pairs().await, vec![TradingPair::btc_usdt()]);
+    }
+}
@@ -0,0 +1,215 @@
+//! Replay Oracle - historical prices from a CSV file
+//!
+//! Serves the latest quote at or before a point in time, so margin checks
+//! and liquidations can be replayed against recorded market data.
+//!
+//! Format, one quote per line (`#` comments and a `timestamp` header are skipped):
+//!
+//! ```text
+//! timestamp,pair,price
+//! 2026-01-25T10:00:00Z,BTC/USDT,50000
+//! 2026-01-25T10:00:00Z,ETH/USDT,2990,3010,3000
+//! ```
+//!
+//! Either a single price or `bid,ask,last` follows the pair. Timestamps are
+//! RFC 3339 or Unix seconds.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use crate::error::OracleError;
+use crate::types::{Price, PriceOracle, TradingPair};
+
+/// Source name stamped on replayed prices
+pub const REPLAY_SOURCE: &str = "replay";
+
+/// Price Oracle replaying recorded quotes
+pub struct ReplayOracle {
+    /// Quotes per pair, sorted by timestamp
+    quotes: HashMap<String, Vec<Price>>,
+    /// Serve prices as of this time (None = as of now)
+    as_of: RwLock<Option<DateTime<Utc>>>,
+}
+
+impl ReplayOracle {
+    /// Load quotes from a CSV file
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, OracleError> {
+        Self::from_csv(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse quotes from CSV text
+    pub fn from_csv(csv: &str) -> Result<Self, OracleError> {
+        let mut quotes: HashMap<String, Vec<Price>> = HashMap::new();
+
+        for (i, line) in csv.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with("timestamp") {
+                continue;
+            }
+            let price = parse_line(line).map_err(|reason| OracleError::InvalidFeed {
+                line: i + 1,
+                reason,
+            })?;
+            quotes.entry(price.pair.to_string()).or_default().push(price);
+        }
+
+        for series in quotes.values_mut() {
+            series.sort_by_key(|p| p.timestamp);
+        }
+
+        Ok(Self {
+            quotes,
+            as_of: RwLock::new(None),
+        })
+    }
+
+    /// Serve prices as of `timestamp` (None = as of now)
+    pub fn set_as_of(&self, timestamp: Option<DateTime<Utc>>) {
+        *self.as_of.write().unwrap() = timestamp;
+    }
+
+    /// Time prices are currently served as of (None = now)
+    pub fn as_of(&self) -> Option<DateTime<Utc>> {
+        *self.as_of.read().unwrap()
+    }
+
+    /// Latest quote for a pair at or before `timestamp`
+    pub fn price_at(&self, pair: &TradingPair, timestamp: DateTime<Utc>) -> Result<Price, OracleError> {
+        let series = self
+            .quotes
+            .get(&pair.to_string())
+            .ok_or_else(|| OracleError::PairNotFound {
+                pair: pair.to_string(),
+            })?;
+
+        let idx = series.partition_point(|p| p.timestamp <= timestamp);
+        if idx == 0 {
+            return Err(OracleError::NoPriceAt {
+                pair: pair.to_string(),
+                timestamp: timestamp.to_rfc3339(),
+            });
+        }
+        Ok(series[idx - 1].clone())
+    }
+
+    /// Total number of quotes loaded
+    pub fn quote_count(&self) -> usize {
+        self.quotes.values().map(Vec::len).sum()
+    }
+}
+
+/// Parse one `timestamp,pair,price` or `timestamp,pair,bid,ask,last` line
+fn parse_line(line: &str) -> Result<Price, String> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+
+    let decimal = |s: &str| Decimal::from_str(s).map_err(|e| format!("bad price '{}': {}", s, e));
+    let (bid, ask, last) = match fields.len() {
+        3 => {
+            let price = decimal(fields[2])?;
+            (price, price, price)
+        }
+        5 => (decimal(fields[2])?, decimal(fields[3])?, decimal(fields[4])?),
+        n => return Err(format!("expected 3 or 5 fields, got {}", n)),
+    };
+
+    let (base, quote) = fields[1]
+        .split_once('/')
+        .ok_or_else(|| format!("bad pair '{}': expected BASE/QUOTE", fields[1]))?;
+
+    Ok(Price {
+        pair: TradingPair::new(base, quote),
+        bid,
+        ask,
+        last,
+        timestamp: parse_timestamp(fields[0])?,
+        source: REPLAY_SOURCE.to_string(),
+    })
+}
+
+fn parse_timestamp(s: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(secs) = s.parse::<i64>() {
+        return DateTime::from_timestamp(secs, 0).ok_or_else(|| format!("bad timestamp '{}'", s));
+    }
+    DateTime::parse_from_rfc3339(s)
+        .map(|ts| ts.with_timezone(&Utc))
+        .map_err(|e| format!("bad timestamp '{}': {}", s, e))
+}
+
+#[async_trait]
+impl PriceOracle for ReplayOracle {
+    async fn get_price(&self, pair: &TradingPair) -> Result<Price, OracleError> {
+        let as_of = self.as_of().unwrap_or_else(Utc::now);
+        self.price_at(pair, as_of)
+    }
+
+    async fn supported_pairs(&self) -> Vec<TradingPair> {
+        self.quotes
+            .values()
+            .filter_map(|series| series.first())
+            .map(|p| p.pair.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FEED: &str = "\
+timestamp,pair,price
+# BTC moves, ETH has a spread
+2026-01-25T10:00:00Z,BTC/USDT,50000
+2026-01-25T11:00:00Z,BTC/USDT,48000
+2026-01-25T10:30:00Z,ETH/USDT,2990,3010,3000
+";
+
+    fn at(s: &str) -> DateTime<Utc> {
+        parse_timestamp(s).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_replay_serves_price_as_of() {
+        let oracle = ReplayOracle::from_csv(FEED).unwrap();
+        assert_eq!(oracle.quote_count(), 3);
+
+        oracle.set_as_of(Some(at("2026-01-25T10:59:59Z")));
+        let btc = oracle.get_price(&TradingPair::btc_usdt()).await.unwrap();
+        assert_eq!(btc.last, Decimal::from(50000));
+        assert_eq!(btc.source, REPLAY_SOURCE);
+
+        oracle.set_as_of(Some(at("2026-01-25T11:00:00Z")));
+        let btc = oracle.get_price(&TradingPair::btc_usdt()).await.unwrap();
+        assert_eq!(btc.last, Decimal::from(48000));
+        assert_eq!(btc.timestamp, at("2026-01-25T11:00:00Z"));
+
+        let eth = oracle.get_price(&TradingPair::eth_usdt()).await.unwrap();
+        assert_eq!(eth.bid, Decimal::from(2990));
+        assert_eq!(eth.mid(), Decimal::from(3000));
+    }
+
+    #[tokio::test]
+    async fn test_replay_before_first_quote() {
+        let oracle = ReplayOracle::from_csv(FEED).unwrap();
+        oracle.set_as_of(Some(at("2026-01-25T10:15:00Z")));
+
+        let result = oracle.get_price(&TradingPair::eth_usdt()).await;
+        assert!(matches!(result, Err(OracleError::NoPriceAt { .. })));
+
+        let result = oracle.get_price(&TradingPair::new("SOL", "USDT")).await;
+        assert!(matches!(result, Err(OracleError::PairNotFound { .. })));
+    }
+
+    #[test]
+    fn test_replay_rejects_bad_lines() {
+        let result = ReplayOracle::from_csv("1769335200,BTC/USDT,50000\n1769335200,BTCUSDT,1\n");
+        assert!(matches!(result, Err(OracleError::InvalidFeed { line: 2, .. })));
+
+        let result = ReplayOracle::from_csv("yesterday,BTC/USDT,50000\n");
+        assert!(matches!(result, Err(OracleError::InvalidFeed { line: 1, .. })));
+    }
+}
@@ -101,10 +101,11 @@ impl Price {
 
 /// Price Oracle trait - interface for price feeds
 ///
-/// Implementations can be:
+/// Implementations:
 /// - MockOracle: For testing with fixed prices
-/// - BinanceOracle: Real-time prices from Binance API
-/// - ChainlinkOracle: On-chain prices from Chainlink
+/// - ReplayOracle: Historical prices from a CSV file
+/// - HttpOracle: Prices from a JSON HTTP endpoint (e.g. an exchange ticker)
+/// - MedianOracle: Median over other oracles, rejecting stale quotes
 #[async_trait]
 pub trait PriceOracle: Send + Sync {
     /// Get the current price for a trading pair
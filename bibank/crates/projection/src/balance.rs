@@ -2,7 +2,7 @@
 
 use bibank_ledger::JournalEntry;
 use rust_decimal::Decimal;
-use sqlx::{Row, SqlitePool};
+use sqlx::{Row, SqliteConnection, SqlitePool};
 use std::collections::HashMap;
 
 /// Balance projection - tracks account balances
@@ -48,7 +48,11 @@ impl BalanceProjection {
     }
 
     /// Apply a journal entry to update balances
-    pub async fn apply(&self, entry: &JournalEntry) -> Result<(), sqlx::Error> {
+    pub async fn apply(
+        &self,
+        conn: &mut SqliteConnection,
+        entry: &JournalEntry,
+    ) -> Result<(), sqlx::Error> {
         for posting in &entry.postings {
             let key = posting.account.to_string();
             let normal_side = posting.account.category.normal_balance();
@@ -79,7 +83,7 @@ impl BalanceProjection {
             .bind(entry.timestamp.to_rfc3339())
             .bind(delta.to_string())
             .bind(entry.timestamp.to_rfc3339())
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await?;
         }
 
@@ -0,0 +1,189 @@
+//! Candle projection - OHLCV candles aggregated from trade prints
+//!
+//! Each row remembers the last entry folded into it, so re-applying an entry
+//! (a resync from an older checkpoint) doesn't count its trade twice.
+
+use bibank_ledger::JournalEntry;
+use bibank_matching::{Candle, CandleInterval, TradePrint, TradingPair};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+/// Candle projection - one row per pair, interval and open time
+pub struct CandleProjection {
+    pool: SqlitePool,
+}
+
+impl CandleProjection {
+    /// Create a new candle projection
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Initialize the schema
+    pub async fn init(&self) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS candles (
+                pair TEXT NOT NULL,
+                interval TEXT NOT NULL,
+                open_time INTEGER NOT NULL,
+                open TEXT NOT NULL,
+                high TEXT NOT NULL,
+                low TEXT NOT NULL,
+                close TEXT NOT NULL,
+                volume TEXT NOT NULL,
+                quote_volume TEXT NOT NULL,
+                trades INTEGER NOT NULL,
+                last_sequence INTEGER NOT NULL,
+                PRIMARY KEY (pair, interval, open_time)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Apply a journal entry, folding Trade entries into every interval
+    pub async fn apply(
+        &self,
+        conn: &mut SqliteConnection,
+        entry: &JournalEntry,
+    ) -> Result<(), sqlx::Error> {
+        let Some(trade) = TradePrint::from_entry(entry) else {
+            return Ok(());
+        };
+
+        for interval in CandleInterval::ALL {
+            let open_time = interval.open_time(trade.timestamp);
+            let candle = match Self::get(conn, &trade.pair, interval, open_time).await? {
+                Some((_, last_sequence)) if last_sequence >= entry.sequence => continue,
+                Some((mut candle, _)) => {
+                    candle.update(&trade);
+                    candle
+                }
+                None => Candle::open(interval, &trade),
+            };
+            Self::upsert(conn, &candle, entry.sequence).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn upsert(
+        conn: &mut SqliteConnection,
+        candle: &Candle,
+        sequence: u64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO candles
+            (pair, interval, open_time, open, high, low, close, volume, quote_volume, trades, last_sequence)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(candle.pair.to_string())
+        .bind(candle.interval.to_string())
+        .bind(candle.open_time.timestamp())
+        .bind(candle.open.to_string())
+        .bind(candle.high.to_string())
+        .bind(candle.low.to_string())
+        .bind(candle.close.to_string())
+        .bind(candle.volume.to_string())
+        .bind(candle.quote_volume.to_string())
+        .bind(candle.trades as i64)
+        .bind(sequence as i64)
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Candle and the last sequence folded into it
+    async fn get(
+        conn: &mut SqliteConnection,
+        pair: &TradingPair,
+        interval: CandleInterval,
+        open_time: DateTime<Utc>,
+    ) -> Result<Option<(Candle, u64)>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT pair, interval, open_time, open, high, low, close, volume, quote_volume, trades, last_sequence
+            FROM candles
+            WHERE pair = ? AND interval = ? AND open_time = ?
+            "#,
+        )
+        .bind(pair.to_string())
+        .bind(interval.to_string())
+        .bind(open_time.timestamp())
+        .fetch_optional(conn)
+        .await?;
+
+        Ok(row.map(|row| {
+            let last_sequence = row.get::<i64, _>("last_sequence") as u64;
+            (candle_from_row(pair, interval, &row), last_sequence)
+        }))
+    }
+
+    /// Candles for a pair opening in `[from, to)`, oldest first
+    pub async fn get_range(
+        &self,
+        pair: &TradingPair,
+        interval: CandleInterval,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<Candle>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT pair, interval, open_time, open, high, low, close, volume, quote_volume, trades
+            FROM candles
+            WHERE pair = ? AND interval = ? AND open_time >= ? AND open_time < ?
+            ORDER BY open_time ASC
+            "#,
+        )
+        .bind(pair.to_string())
+        .bind(interval.to_string())
+        .bind(interval.open_time(from).timestamp())
+        // Open times are whole seconds: a candle opening earlier in `to`'s second is before it
+        .bind(to.timestamp() + i64::from(to.timestamp_subsec_nanos() > 0))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| candle_from_row(pair, interval, row))
+            .collect())
+    }
+
+    /// Clear all candles (for replay)
+    pub async fn clear(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM candles")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+fn candle_from_row(pair: &TradingPair, interval: CandleInterval, row: &SqliteRow) -> Candle {
+    let decimal = |column: &str| {
+        row.get::<String, _>(column)
+            .parse()
+            .unwrap_or(Decimal::ZERO)
+    };
+
+    Candle {
+        pair: pair.clone(),
+        interval,
+        open_time: DateTime::from_timestamp(row.get::<i64, _>("open_time"), 0).unwrap_or_default(),
+        open: decimal("open"),
+        high: decimal("high"),
+        low: decimal("low"),
+        close: decimal("close"),
+        volume: decimal("volume"),
+        quote_volume: decimal("quote_volume"),
+        trades: row.get::<i64, _>("trades") as u64,
+    }
+}
@@ -1,17 +1,24 @@
 //! Projection engine - coordinates replay and updates
 
 use crate::balance::BalanceProjection;
+use crate::candle::CandleProjection;
 use crate::error::ProjectionError;
+use crate::reversal::ReversalProjection;
 use crate::trade::TradeProjection;
+use crate::user::UserProjection;
 use bibank_bus::EventBus;
 use bibank_ledger::JournalEntry;
-use sqlx::SqlitePool;
+use sqlx::{Row, SqliteConnection, SqlitePool};
 use std::path::Path;
 
 /// Projection engine - coordinates replay and updates
 pub struct ProjectionEngine {
     pub balance: BalanceProjection,
     pub trade: TradeProjection,
+    pub reversal: ReversalProjection,
+    pub candle: CandleProjection,
+    pub user: UserProjection,
+    pool: SqlitePool,
 }
 
 impl ProjectionEngine {
@@ -23,36 +30,108 @@ impl ProjectionEngine {
         let balance = BalanceProjection::new(pool.clone());
         balance.init().await?;
 
-        let trade = TradeProjection::new(pool);
+        let trade = TradeProjection::new(pool.clone());
         trade.init().await?;
 
-        Ok(Self { balance, trade })
+        let reversal = ReversalProjection::new(pool.clone());
+        reversal.init().await?;
+
+        let candle = CandleProjection::new(pool.clone());
+        candle.init().await?;
+
+        let user = UserProjection::new(pool.clone());
+        user.init().await?;
+
+        // Last sequence applied, so startup only replays the tail
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS projection_meta (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self {
+            balance,
+            trade,
+            reversal,
+            candle,
+            user,
+            pool,
+        })
     }
 
     /// Apply a single entry
+    ///
+    /// Every table and the checkpoint are written in one transaction, so an
+    /// entry is either fully projected or not at all.
     pub async fn apply(&self, entry: &JournalEntry) -> Result<(), ProjectionError> {
-        self.balance.apply(entry).await?;
-        self.trade.apply(entry).await?;
+        let mut tx = self.pool.begin().await?;
+        self.balance.apply(&mut tx, entry).await?;
+        self.trade.apply(&mut tx, entry).await?;
+        self.reversal.apply(&mut tx, entry).await?;
+        self.candle.apply(&mut tx, entry).await?;
+        self.user.apply(&mut tx, entry).await?;
+        set_last_sequence(&mut tx, entry.sequence).await?;
+        tx.commit().await?;
         Ok(())
     }
 
     /// Replay all events from the bus
     pub async fn replay(&self, bus: &EventBus) -> Result<usize, ProjectionError> {
         let reader = bus.reader()?;
-        let entries = reader.read_all()?;
 
         self.balance.clear().await?;
         self.trade.clear().await?;
+        self.reversal.clear().await?;
+        self.candle.clear().await?;
+        self.user.clear().await?;
+        set_last_sequence(&mut *self.pool.acquire().await?, 0).await?;
+
+        let mut count = 0;
+        for entry in reader.iter() {
+            self.apply(&entry?).await?;
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
+    /// Apply only the entries after the last one projected
+    ///
+    /// Falls back to a full replay when the projection has no checkpoint
+    /// (created before checkpoints existed) or is ahead of the journal.
+    pub async fn catch_up(&self, bus: &EventBus) -> Result<usize, ProjectionError> {
+        let Some(last) = self.last_sequence().await? else {
+            return self.replay(bus).await;
+        };
+
+        let reader = bus.reader()?;
+        if reader.last_sequence()?.unwrap_or(0) < last {
+            return self.replay(bus).await;
+        }
 
-        let count = entries.len();
-        for entry in &entries {
-            self.balance.apply(entry).await?;
-            self.trade.apply(entry).await?;
+        let mut count = 0;
+        for entry in reader.iter_from(last + 1)? {
+            self.apply(&entry?).await?;
+            count += 1;
         }
 
         Ok(count)
     }
 
+    /// Sequence of the last entry projected (None if never checkpointed)
+    pub async fn last_sequence(&self) -> Result<Option<u64>, ProjectionError> {
+        let row = sqlx::query("SELECT value FROM projection_meta WHERE key = 'last_sequence'")
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.and_then(|r| r.get::<String, _>("value").parse().ok()))
+    }
+
     /// Get the balance projection
     pub fn balance(&self) -> &BalanceProjection {
         &self.balance
@@ -62,4 +141,161 @@ impl ProjectionEngine {
     pub fn trade(&self) -> &TradeProjection {
         &self.trade
     }
+
+    /// Get the reversal projection
+    pub fn reversal(&self) -> &ReversalProjection {
+        &self.reversal
+    }
+
+    /// Get the candle projection
+    pub fn candle(&self) -> &CandleProjection {
+        &self.candle
+    }
+
+    /// Get the user projection
+    pub fn user(&self) -> &UserProjection {
+        &self.user
+    }
+}
+
+/// Record the last entry projected
+async fn set_last_sequence(conn: &mut SqliteConnection, sequence: u64) -> Result<(), ProjectionError> {
+    sqlx::query(
+        r#"
+        INSERT INTO projection_meta (key, value) VALUES ('last_sequence', ?)
+        ON CONFLICT(key) DO UPDATE SET value = excluded.value
+        "#,
+    )
+    .bind(sequence.to_string())
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bibank_core::Amount;
+    use bibank_ledger::{AccountKey, JournalEntryBuilder, TransactionIntent};
+    use bibank_matching::{CandleInterval, TradingPair};
+    use chrono::{Duration, TimeZone, Utc};
+    use rust_decimal::Decimal;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn usdt(value: i64) -> Amount {
+        Amount::new(Decimal::new(value, 0)).unwrap()
+    }
+
+    fn entry(sequence: u64, builder: JournalEntryBuilder) -> JournalEntry {
+        let unsigned = builder
+            .correlation_id(format!("corr-{}", sequence))
+            .build_unsigned()
+            .unwrap();
+
+        JournalEntry {
+            sequence,
+            prev_hash: format!("hash-{}", sequence - 1),
+            hash: format!("hash-{}", sequence),
+            timestamp: Utc::now(),
+            intent: unsigned.intent,
+            correlation_id: unsigned.correlation_id,
+            causality_id: unsigned.causality_id,
+            postings: unsigned.postings,
+            metadata: unsigned.metadata,
+            signatures: Vec::new(),
+        }
+    }
+
+    fn deposit(sequence: u64) -> JournalEntry {
+        let builder = JournalEntryBuilder::new()
+            .intent(TransactionIntent::Deposit)
+            .debit(AccountKey::system_vault("USDT"), usdt(100))
+            .credit(AccountKey::user_available("ALICE", "USDT"), usdt(100));
+        entry(sequence, builder)
+    }
+
+    fn withdrawal(sequence: u64) -> JournalEntry {
+        let builder = JournalEntryBuilder::new()
+            .intent(TransactionIntent::Withdrawal)
+            .debit(AccountKey::user_available("ALICE", "USDT"), usdt(100))
+            .credit(AccountKey::system_vault("USDT"), usdt(100));
+        entry(sequence, builder)
+    }
+
+    /// ALICE sells 1 BTC to BOB at 100 USDT
+    fn trade(sequence: u64) -> JournalEntry {
+        let btc = Amount::new(Decimal::ONE).unwrap();
+        let builder = JournalEntryBuilder::new()
+            .intent(TransactionIntent::Trade)
+            .debit(AccountKey::user_available("ALICE", "BTC"), btc)
+            .credit(AccountKey::user_available("BOB", "BTC"), btc)
+            .debit(AccountKey::user_available("BOB", "USDT"), usdt(100))
+            .credit(AccountKey::user_available("ALICE", "USDT"), usdt(100))
+            .metadata("base_asset", json!("BTC"))
+            .metadata("quote_asset", json!("USDT"))
+            .metadata("price", json!("100"))
+            .metadata("base_amount", json!("1"));
+        entry(sequence, builder)
+    }
+
+    #[tokio::test]
+    async fn test_failed_apply_leaves_no_partial_writes() {
+        let dir = TempDir::new().unwrap();
+        let engine = ProjectionEngine::new(dir.path().join("projection.db")).await.unwrap();
+        let alice = AccountKey::user_available("ALICE", "USDT").to_string();
+
+        engine.apply(&deposit(1)).await.unwrap();
+        assert_eq!(engine.balance.get_balance(&alice).await.unwrap(), Decimal::new(100, 0));
+
+        // The withdrawal table is written last, after the balances
+        sqlx::query("DROP TABLE withdrawals").execute(&engine.pool).await.unwrap();
+        assert!(engine.apply(&withdrawal(2)).await.is_err());
+
+        assert_eq!(engine.balance.get_balance(&alice).await.unwrap(), Decimal::new(100, 0));
+        assert_eq!(engine.last_sequence().await.unwrap(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_reapplied_trade_counted_once_in_candles() {
+        let dir = TempDir::new().unwrap();
+        let engine = ProjectionEngine::new(dir.path().join("projection.db")).await.unwrap();
+        let pair = TradingPair::new("BTC", "USDT");
+
+        let (first, second) = (trade(1), trade(2));
+        for entry in [&first, &second, &first, &second] {
+            engine.apply(entry).await.unwrap();
+        }
+
+        let now = Utc::now();
+        let candles = engine
+            .candle
+            .get_range(&pair, CandleInterval::OneDay, first.timestamp, now)
+            .await
+            .unwrap();
+        assert_eq!(candles.len(), 1);
+        assert_eq!(candles[0].trades, 2);
+        assert_eq!(candles[0].volume, Decimal::new(2, 0));
+    }
+
+    #[tokio::test]
+    async fn test_candle_range_ends_mid_second() {
+        let dir = TempDir::new().unwrap();
+        let engine = ProjectionEngine::new(dir.path().join("projection.db")).await.unwrap();
+        let pair = TradingPair::new("BTC", "USDT");
+
+        // Printed in the first second of a minute, queried later in that second
+        let minute = Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap();
+        let mut print = trade(1);
+        print.timestamp = minute + Duration::milliseconds(300);
+        engine.apply(&print).await.unwrap();
+
+        let to = minute + Duration::milliseconds(900);
+        let candles = engine
+            .candle
+            .get_range(&pair, CandleInterval::OneMinute, minute, to)
+            .await
+            .unwrap();
+        assert_eq!(candles.len(), 1);
+    }
 }
@@ -3,11 +3,17 @@
 //! Projections are DISPOSABLE - they can be rebuilt from events at any time.
 
 pub mod balance;
+pub mod candle;
 pub mod engine;
 pub mod error;
+pub mod reversal;
 pub mod trade;
+pub mod user;
 
 pub use balance::BalanceProjection;
+pub use candle::CandleProjection;
 pub use engine::ProjectionEngine;
 pub use error::ProjectionError;
+pub use reversal::{ReversalProjection, ReversalRecord};
 pub use trade::{TradeProjection, TradeRecord};
+pub use user::UserProjection;
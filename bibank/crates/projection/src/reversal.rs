@@ -0,0 +1,113 @@
+//! Reversal projection - marks entries undone by a Reversal
+
+use bibank_ledger::JournalEntry;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+
+/// Reversed entry from projection
+#[derive(Debug, Clone)]
+pub struct ReversalRecord {
+    /// Sequence of the reversed entry
+    pub sequence: u64,
+    /// Sequence of the Reversal entry
+    pub reversal_sequence: u64,
+    /// Correlation ID of the reversed entry
+    pub correlation_id: String,
+    /// Why it was reversed
+    pub reason: String,
+    /// Reversal timestamp
+    pub timestamp: String,
+}
+
+/// Reversal projection - one row per reversed entry
+pub struct ReversalProjection {
+    pool: SqlitePool,
+}
+
+impl ReversalProjection {
+    /// Create a new reversal projection
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Initialize the schema
+    pub async fn init(&self) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS reversals (
+                sequence INTEGER PRIMARY KEY,
+                reversal_sequence INTEGER NOT NULL,
+                correlation_id TEXT NOT NULL,
+                reason TEXT NOT NULL,
+                timestamp TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Apply a journal entry, recording what a Reversal undoes
+    pub async fn apply(
+        &self,
+        conn: &mut SqliteConnection,
+        entry: &JournalEntry,
+    ) -> Result<(), sqlx::Error> {
+        let Some(sequence) = entry.reversed_sequence() else {
+            return Ok(());
+        };
+        let reason = entry
+            .metadata
+            .get("reason")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO reversals
+            (sequence, reversal_sequence, correlation_id, reason, timestamp)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(sequence as i64)
+        .bind(entry.sequence as i64)
+        .bind(entry.causality_id.as_deref().unwrap_or_default())
+        .bind(reason)
+        .bind(entry.timestamp.to_rfc3339())
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Reversal of an entry, if it was reversed
+    pub async fn get(&self, sequence: u64) -> Result<Option<ReversalRecord>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT sequence, reversal_sequence, correlation_id, reason, timestamp
+            FROM reversals
+            WHERE sequence = ?
+            "#,
+        )
+        .bind(sequence as i64)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| ReversalRecord {
+            sequence: row.get::<i64, _>("sequence") as u64,
+            reversal_sequence: row.get::<i64, _>("reversal_sequence") as u64,
+            correlation_id: row.get("correlation_id"),
+            reason: row.get("reason"),
+            timestamp: row.get("timestamp"),
+        }))
+    }
+
+    /// Clear all reversals (for replay)
+    pub async fn clear(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM reversals")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
@@ -1,8 +1,9 @@
 //! Trade projection - tracks trade history from events
 
 use bibank_ledger::{JournalEntry, TransactionIntent};
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
-use sqlx::{Row, SqlitePool};
+use sqlx::{Row, SqliteConnection, SqlitePool};
 
 /// Trade record from projection
 #[derive(Debug, Clone)]
@@ -25,6 +26,8 @@ pub struct TradeRecord {
     pub timestamp: String,
     /// Entry hash
     pub hash: String,
+    /// Sequence of the Reversal undoing this trade, if any
+    pub reversed_by: Option<u64>,
 }
 
 /// Trade projection - tracks trade history
@@ -89,7 +92,11 @@ impl TradeProjection {
     }
 
     /// Apply a journal entry to update trades
-    pub async fn apply(&self, entry: &JournalEntry) -> Result<(), sqlx::Error> {
+    pub async fn apply(
+        &self,
+        conn: &mut SqliteConnection,
+        entry: &JournalEntry,
+    ) -> Result<(), sqlx::Error> {
         // Only process Trade entries
         if entry.intent != TransactionIntent::Trade {
             return Ok(());
@@ -129,7 +136,8 @@ impl TradeProjection {
                         seller = Some(user_id.clone());
                         sell_asset = Some(asset.clone());
                         sell_amount = Some(posting.amount.value());
-                    } else {
+                    } else if buy_asset.is_none() {
+                        // First buyer leg only; later debits are lock refunds
                         buyer = Some(user_id.clone());
                         buy_asset = Some(asset.clone());
                         buy_amount = Some(posting.amount.value());
@@ -172,7 +180,7 @@ impl TradeProjection {
             .bind(buy_amount.to_string())
             .bind(entry.timestamp.to_rfc3339())
             .bind(&entry.hash)
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await?;
         }
 
@@ -183,8 +191,10 @@ impl TradeProjection {
     pub async fn get_user_trades(&self, user_id: &str) -> Result<Vec<TradeRecord>, sqlx::Error> {
         let rows = sqlx::query(
             r#"
-            SELECT trade_id, seller, buyer, sell_asset, sell_amount, buy_asset, buy_amount, timestamp, hash
-            FROM trades
+            SELECT t.trade_id, t.seller, t.buyer, t.sell_asset, t.sell_amount, t.buy_asset, t.buy_amount,
+                   t.timestamp, t.hash, r.reversal_sequence
+            FROM trades t
+            LEFT JOIN reversals r ON r.sequence = t.trade_id
             WHERE seller = ? OR buyer = ?
             ORDER BY trade_id DESC
             "#,
@@ -212,6 +222,9 @@ impl TradeProjection {
                     .unwrap_or(Decimal::ZERO),
                 timestamp: row.get("timestamp"),
                 hash: row.get("hash"),
+                reversed_by: row
+                    .get::<Option<i64>, _>("reversal_sequence")
+                    .map(|sequence| sequence as u64),
             })
             .collect())
     }
@@ -224,8 +237,10 @@ impl TradeProjection {
     ) -> Result<Vec<TradeRecord>, sqlx::Error> {
         let rows = sqlx::query(
             r#"
-            SELECT trade_id, seller, buyer, sell_asset, sell_amount, buy_asset, buy_amount, timestamp, hash
-            FROM trades
+            SELECT t.trade_id, t.seller, t.buyer, t.sell_asset, t.sell_amount, t.buy_asset, t.buy_amount,
+                   t.timestamp, t.hash, r.reversal_sequence
+            FROM trades t
+            LEFT JOIN reversals r ON r.sequence = t.trade_id
             WHERE (sell_asset = ? AND buy_asset = ?) OR (sell_asset = ? AND buy_asset = ?)
             ORDER BY trade_id DESC
             "#,
@@ -255,6 +270,9 @@ impl TradeProjection {
                     .unwrap_or(Decimal::ZERO),
                 timestamp: row.get("timestamp"),
                 hash: row.get("hash"),
+                reversed_by: row
+                    .get::<Option<i64>, _>("reversal_sequence")
+                    .map(|sequence| sequence as u64),
             })
             .collect())
     }
@@ -263,8 +281,10 @@ impl TradeProjection {
     pub async fn get_recent_trades(&self, limit: u32) -> Result<Vec<TradeRecord>, sqlx::Error> {
         let rows = sqlx::query(
             r#"
-            SELECT trade_id, seller, buyer, sell_asset, sell_amount, buy_asset, buy_amount, timestamp, hash
-            FROM trades
+            SELECT t.trade_id, t.seller, t.buyer, t.sell_asset, t.sell_amount, t.buy_asset, t.buy_amount,
+                   t.timestamp, t.hash, r.reversal_sequence
+            FROM trades t
+            LEFT JOIN reversals r ON r.sequence = t.trade_id
             ORDER BY trade_id DESC
             LIMIT ?
             "#,
@@ -291,10 +311,54 @@ impl TradeProjection {
                     .unwrap_or(Decimal::ZERO),
                 timestamp: row.get("timestamp"),
                 hash: row.get("hash"),
+                reversed_by: row
+                    .get::<Option<i64>, _>("reversal_sequence")
+                    .map(|sequence| sequence as u64),
             })
             .collect())
     }
 
+    /// A user's traded volume in `asset` since `since`
+    ///
+    /// Counts the `asset` leg of every trade the user was on either side
+    /// of; reversed trades don't count.
+    pub async fn user_volume(
+        &self,
+        user_id: &str,
+        asset: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Decimal, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT t.sell_asset, t.sell_amount, t.buy_asset, t.buy_amount
+            FROM trades t
+            LEFT JOIN reversals r ON r.sequence = t.trade_id
+            WHERE (t.seller = ? OR t.buyer = ?) AND (t.sell_asset = ? OR t.buy_asset = ?)
+              AND t.timestamp >= ? AND r.reversal_sequence IS NULL
+            "#,
+        )
+        .bind(user_id.to_uppercase())
+        .bind(user_id.to_uppercase())
+        .bind(asset.to_uppercase())
+        .bind(asset.to_uppercase())
+        .bind(since.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+
+        let asset = asset.to_uppercase();
+        Ok(rows
+            .iter()
+            .map(|row| {
+                let column = if row.get::<String, _>("sell_asset") == asset {
+                    "sell_amount"
+                } else {
+                    "buy_amount"
+                };
+                row.get::<String, _>(column).parse().unwrap_or(Decimal::ZERO)
+            })
+            .sum())
+    }
+
     /// Get trade count
     pub async fn count(&self) -> Result<u64, sqlx::Error> {
         let row = sqlx::query("SELECT COUNT(*) as count FROM trades")
@@ -0,0 +1,219 @@
+//! User projection - registered users and their withdrawals
+//!
+//! The `users` table mirrors the registry folded from the Compliance Ledger
+//! (synced on startup and on every change). The `withdrawals` table is
+//! derived from the journal and backs the rolling KYC withdrawal limits.
+
+use bibank_compliance::{UserRecord, UserStatus};
+use bibank_ledger::{AccountCategory, JournalEntry, Side, TransactionIntent};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqliteConnection, SqlitePool};
+use std::collections::BTreeMap;
+
+/// User projection - one row per user, one per withdrawal and asset
+#[derive(Clone)]
+pub struct UserProjection {
+    pool: SqlitePool,
+}
+
+impl UserProjection {
+    /// Create a new user projection
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Initialize the schema
+    pub async fn init(&self) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS users (
+                user_id TEXT PRIMARY KEY,
+                kyc_tier INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                onboarded_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS withdrawals (
+                sequence INTEGER NOT NULL,
+                user_id TEXT NOT NULL,
+                asset TEXT NOT NULL,
+                amount TEXT NOT NULL,
+                timestamp INTEGER NOT NULL,
+                PRIMARY KEY (sequence, user_id, asset)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            r#"
+            CREATE INDEX IF NOT EXISTS idx_withdrawals_user
+            ON withdrawals(user_id, asset, timestamp)
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Apply a journal entry, recording what a Withdrawal took from users
+    pub async fn apply(
+        &self,
+        conn: &mut SqliteConnection,
+        entry: &JournalEntry,
+    ) -> Result<(), sqlx::Error> {
+        if entry.intent != TransactionIntent::Withdrawal {
+            return Ok(());
+        }
+
+        let mut amounts: BTreeMap<(&str, &str), Decimal> = BTreeMap::new();
+        for posting in &entry.postings {
+            let account = &posting.account;
+            if posting.side == Side::Debit
+                && account.category == AccountCategory::Liability
+                && account.segment == "USER"
+                && account.sub_account == "AVAILABLE"
+            {
+                *amounts.entry((&account.id, &account.asset)).or_default() += posting.amount.value();
+            }
+        }
+
+        for ((user_id, asset), amount) in amounts {
+            sqlx::query(
+                r#"
+                INSERT OR REPLACE INTO withdrawals (sequence, user_id, asset, amount, timestamp)
+                VALUES (?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(entry.sequence as i64)
+            .bind(user_id)
+            .bind(asset)
+            .bind(amount.to_string())
+            .bind(entry.timestamp.timestamp())
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Totals per asset the user withdrew at or after `since`
+    pub async fn withdrawn_since(
+        &self,
+        user_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<BTreeMap<String, Decimal>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT asset, amount FROM withdrawals
+            WHERE user_id = ? AND timestamp >= ?
+            "#,
+        )
+        .bind(user_id)
+        .bind(since.timestamp())
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut totals: BTreeMap<String, Decimal> = BTreeMap::new();
+        for row in &rows {
+            let amount = row.get::<String, _>("amount").parse().unwrap_or(Decimal::ZERO);
+            *totals.entry(row.get("asset")).or_default() += amount;
+        }
+        Ok(totals)
+    }
+
+    /// Insert or update a user
+    pub async fn upsert(&self, user: &UserRecord) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT OR REPLACE INTO users (user_id, kyc_tier, status, onboarded_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&user.user_id)
+        .bind(user.kyc_tier as i64)
+        .bind(user.status.to_string())
+        .bind(user.onboarded_at.to_string())
+        .bind(user.updated_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Get a user
+    pub async fn get(&self, user_id: &str) -> Result<Option<UserRecord>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT user_id, kyc_tier, status, onboarded_at, updated_at
+            FROM users
+            WHERE user_id = ?
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(|row| user_from_row(&row)))
+    }
+
+    /// All users, by ID
+    pub async fn list(&self) -> Result<Vec<UserRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT user_id, kyc_tier, status, onboarded_at, updated_at
+            FROM users
+            ORDER BY user_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(user_from_row).collect())
+    }
+
+    /// Clear all users (before a resync from the Compliance Ledger)
+    pub async fn clear_users(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM users")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Clear all withdrawals (for replay)
+    pub async fn clear(&self) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM withdrawals")
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+fn user_from_row(row: &SqliteRow) -> UserRecord {
+    UserRecord {
+        user_id: row.get("user_id"),
+        kyc_tier: row.get::<i64, _>("kyc_tier") as u8,
+        status: row
+            .get::<String, _>("status")
+            .parse()
+            .unwrap_or(UserStatus::Frozen),
+        onboarded_at: row
+            .get::<String, _>("onboarded_at")
+            .parse()
+            .unwrap_or(NaiveDate::MIN),
+        updated_at: DateTime::parse_from_rfc3339(&row.get::<String, _>("updated_at"))
+            .map(|t| t.with_timezone(&Utc))
+            .unwrap_or_default(),
+    }
+}
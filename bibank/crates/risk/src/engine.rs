@@ -1,8 +1,11 @@
 //! Risk engine implementation
 
 use crate::error::RiskError;
-use crate::state::RiskState;
+use crate::state::{RiskState, INITIAL_MARGIN};
+use crate::valuation::{price_in, value_portfolio, Haircuts, Valuation};
 use bibank_ledger::{JournalEntry, UnsignedEntry};
+use bibank_oracle::PriceOracle;
+use rust_decimal::Decimal;
 
 /// Risk Engine - Pre-commit gatekeeper
 ///
@@ -10,6 +13,8 @@ use bibank_ledger::{JournalEntry, UnsignedEntry};
 /// Maintains in-memory state rebuilt from event replay.
 pub struct RiskEngine {
     state: RiskState,
+    /// Collateral haircuts for portfolio margin
+    haircuts: Haircuts,
 }
 
 impl RiskEngine {
@@ -17,9 +22,21 @@ impl RiskEngine {
     pub fn new() -> Self {
         Self {
             state: RiskState::new(),
+            haircuts: Haircuts::default(),
         }
     }
 
+    /// Use a different haircut schedule
+    pub fn with_haircuts(mut self, haircuts: Haircuts) -> Self {
+        self.haircuts = haircuts;
+        self
+    }
+
+    /// Get the haircut schedule
+    pub fn haircuts(&self) -> &Haircuts {
+        &self.haircuts
+    }
+
     /// Get reference to internal state
     pub fn state(&self) -> &RiskState {
         &self.state
@@ -81,6 +98,50 @@ impl RiskEngine {
         Ok(())
     }
 
+    /// Value a user's portfolio across all assets, after haircuts
+    pub async fn value(&self, user: &str, oracle: &dyn PriceOracle, quote: &str) -> Result<Valuation, RiskError> {
+        value_portfolio(&self.state, user, oracle, quote, &self.haircuts).await
+    }
+
+    /// Check a borrow against the initial margin, valuing the whole portfolio
+    ///
+    /// Borrowed funds add their full value to loans but only their
+    /// haircut value to collateral. Equity (collateral - loans) after the
+    /// borrow must cover INITIAL_MARGIN of the loans. Returns the valuation
+    /// before the borrow.
+    pub async fn check_borrow(
+        &self,
+        user: &str,
+        asset: &str,
+        amount: Decimal,
+        oracle: &dyn PriceOracle,
+        quote: &str,
+    ) -> Result<Valuation, RiskError> {
+        let valuation = self.value(user, oracle, quote).await?;
+        let price = price_in(oracle, asset, quote).await?;
+        let haircut = self.haircuts.rate(asset);
+
+        let new_loans = valuation.loans + amount * price;
+        if new_loans <= Decimal::ZERO {
+            return Ok(valuation);
+        }
+        let new_equity = valuation.equity() - amount * price * haircut;
+        let margin_ratio = new_equity / new_loans;
+        if margin_ratio < INITIAL_MARGIN {
+            // Largest amount with (E - a*p*h) / (L + a*p) >= INITIAL_MARGIN
+            let max_allowed = ((valuation.equity() - INITIAL_MARGIN * valuation.loans)
+                / (price * (haircut + INITIAL_MARGIN)))
+                .max(Decimal::ZERO);
+            return Err(RiskError::ExceedsMaxLeverage {
+                requested: amount.to_string(),
+                max_allowed: max_allowed.round_dp(8).normalize().to_string(),
+                margin_ratio: margin_ratio.round_dp(4).to_string(),
+            });
+        }
+
+        Ok(valuation)
+    }
+
     /// Apply a committed entry to update internal state
     ///
     /// This should be called AFTER the entry is committed to ledger.
@@ -108,7 +169,7 @@ mod tests {
     use super::*;
     use bibank_core::Amount;
     use bibank_ledger::{AccountKey, JournalEntryBuilder, TransactionIntent};
-    use rust_decimal::Decimal;
+    use bibank_oracle::MockOracle;
 
     fn amount(val: i64) -> Amount {
         Amount::new(Decimal::new(val, 0)).unwrap()
@@ -145,4 +206,79 @@ mod tests {
         let result = engine.check(&entry);
         assert!(matches!(result, Err(RiskError::InsufficientBalance { .. })));
     }
+
+    #[tokio::test]
+    async fn test_borrow_checked_against_portfolio() {
+        let mut engine = RiskEngine::new().with_haircuts(Haircuts::none());
+        let oracle = MockOracle::with_defaults();
+
+        // 0.1 BTC of collateral, no USDT
+        let deposit = JournalEntryBuilder::new()
+            .intent(TransactionIntent::Deposit)
+            .correlation_id("test-1")
+            .debit(AccountKey::system_vault("BTC"), Amount::new(Decimal::new(1, 1)).unwrap())
+            .credit(AccountKey::user_available("ALICE", "BTC"), Amount::new(Decimal::new(1, 1)).unwrap())
+            .build_unsigned()
+            .unwrap();
+        engine.apply(&entry_from(deposit));
+
+        // 5000 USDT of equity supports up to 50000 USDT of loans at 10% initial margin
+        let valuation = engine
+            .check_borrow("ALICE", "USDT", Decimal::from(50000), &oracle, "USDT")
+            .await
+            .unwrap();
+        assert_eq!(valuation.collateral, Decimal::from(5000));
+
+        let result = engine
+            .check_borrow("ALICE", "USDT", Decimal::from(50001), &oracle, "USDT")
+            .await;
+        match result {
+            Err(RiskError::ExceedsMaxLeverage { max_allowed, .. }) => assert_eq!(max_allowed, "50000"),
+            other => panic!("expected ExceedsMaxLeverage, got {:?}", other),
+        }
+
+        // Borrowing an asset without a price is rejected
+        let result = engine
+            .check_borrow("ALICE", "DOGE", Decimal::from(1), &oracle, "USDT")
+            .await;
+        assert!(matches!(result, Err(RiskError::PriceUnavailable { .. })));
+
+        // A 10% haircut on BTC leaves 4500 of equity: 45000 of USDT loans
+        let engine = RiskEngine {
+            state: std::mem::take(&mut engine.state),
+            haircuts: Haircuts::none().with("BTC", Decimal::new(10, 2)),
+        };
+        let result = engine
+            .check_borrow("ALICE", "USDT", Decimal::from(50000), &oracle, "USDT")
+            .await;
+        match result {
+            Err(RiskError::ExceedsMaxLeverage { max_allowed, .. }) => assert_eq!(max_allowed, "45000"),
+            other => panic!("expected ExceedsMaxLeverage, got {:?}", other),
+        }
+
+        // Borrowing a haircut asset counts less of it as collateral:
+        // (4500 - 0.1 * 50000 * a) / (50000 * a) >= 0.1  =>  a <= 0.45
+        let result = engine
+            .check_borrow("ALICE", "BTC", Decimal::from(5), &oracle, "USDT")
+            .await;
+        match result {
+            Err(RiskError::ExceedsMaxLeverage { max_allowed, .. }) => assert_eq!(max_allowed, "0.45"),
+            other => panic!("expected ExceedsMaxLeverage, got {:?}", other),
+        }
+    }
+
+    fn entry_from(unsigned: UnsignedEntry) -> JournalEntry {
+        JournalEntry {
+            sequence: 1,
+            prev_hash: "GENESIS".to_string(),
+            hash: "test".to_string(),
+            timestamp: chrono::Utc::now(),
+            intent: unsigned.intent,
+            correlation_id: unsigned.correlation_id,
+            causality_id: unsigned.causality_id,
+            postings: unsigned.postings,
+            metadata: unsigned.metadata,
+            signatures: Vec::new(),
+        }
+    }
 }
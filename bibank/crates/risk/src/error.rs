@@ -16,4 +16,18 @@ pub enum RiskError {
 
     #[error("Risk check failed: {0}")]
     CheckFailed(String),
+
+    #[error("No price for {asset}/{quote}: {reason}")]
+    PriceUnavailable {
+        asset: String,
+        quote: String,
+        reason: String,
+    },
+
+    #[error("Borrow of {requested} exceeds max leverage: max allowed {max_allowed}, margin ratio {margin_ratio}")]
+    ExceedsMaxLeverage {
+        requested: String,
+        max_allowed: String,
+        margin_ratio: String,
+    },
 }
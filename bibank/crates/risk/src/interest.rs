@@ -1,9 +1,10 @@
 //! Interest Accrual Module
 //!
 //! Handles compound interest calculation for margin loans.
-//! Interest is accrued daily and added to the loan principal.
+//! Interest is accrued daily and added to the loan principal, rounded to
+//! the loan asset's scale (banker's rounding by default).
 
-use bibank_core::Amount;
+use bibank_core::{Amount, Currency, RoundingMode};
 use bibank_ledger::{AccountKey, Posting, TransactionIntent, UnsignedEntry};
 use rust_decimal::Decimal;
 use serde_json::json;
@@ -18,6 +19,9 @@ pub const DEFAULT_DAILY_RATE: Decimal = Decimal::from_parts(5, 0, 0, false, 4);
 pub struct InterestCalculator {
     /// Daily interest rate (e.g., 0.0005 for 0.05%)
     daily_rate: Decimal,
+
+    /// Rounding of accrued interest to the asset's scale
+    rounding: RoundingMode,
 }
 
 impl InterestCalculator {
@@ -25,22 +29,33 @@ impl InterestCalculator {
     pub fn new() -> Self {
         Self {
             daily_rate: DEFAULT_DAILY_RATE,
+            rounding: RoundingMode::Bankers,
         }
     }
 
     /// Create with custom daily rate
     pub fn with_rate(daily_rate: Decimal) -> Self {
-        Self { daily_rate }
+        Self {
+            daily_rate,
+            ..Self::new()
+        }
+    }
+
+    /// Set the rounding mode of accrued interest
+    pub fn with_rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
     }
 
-    /// Calculate interest for a single loan
+    /// Calculate interest for a single loan (unrounded)
     pub fn calculate_interest(&self, principal: Decimal) -> Decimal {
         principal * self.daily_rate
     }
 
     /// Generate interest entries for all active loans
     ///
-    /// Returns a list of UnsignedEntry for each user/asset with a loan.
+    /// Returns a list of UnsignedEntry for each user/asset with a loan whose
+    /// interest rounds to at least one minimum unit.
     /// Each entry has:
     /// - Debit: ASSET:USER:*:*:LOAN (loan increases)
     /// - Credit: REV:SYSTEM:INTEREST:*:INCOME (revenue increases)
@@ -64,11 +79,12 @@ impl InterestCalculator {
                     let user = parts[2];
                     let asset = parts[3];
 
-                    let interest = self.calculate_interest(balance);
+                    let interest = Currency::from(asset).round(self.calculate_interest(balance), self.rounding);
                     if interest > Decimal::ZERO {
                         if let Some(entry) = self.create_interest_entry(
                             user,
                             asset,
+                            balance,
                             interest,
                             &format!("{}-{}-{}", correlation_prefix, user, asset),
                         ) {
@@ -87,6 +103,7 @@ impl InterestCalculator {
         &self,
         user: &str,
         asset: &str,
+        principal: Decimal,
         interest: Decimal,
         correlation_id: &str,
     ) -> Option<UnsignedEntry> {
@@ -106,15 +123,16 @@ impl InterestCalculator {
 
         let mut metadata = HashMap::new();
         metadata.insert("interest_rate".to_string(), json!(self.daily_rate.to_string()));
-        metadata.insert("principal".to_string(), json!(interest.to_string()));
+        metadata.insert("principal".to_string(), json!(principal.to_string()));
         metadata.insert("accrual_type".to_string(), json!("compound"));
+        metadata.insert("rounding".to_string(), json!(self.rounding.as_str()));
 
         Some(UnsignedEntry {
             intent: TransactionIntent::Interest,
             correlation_id: correlation_id.to_string(),
             causality_id: None,
             postings: vec![
-                Posting::debit(loan_account, amount.clone()),
+                Posting::debit(loan_account, amount),
                 Posting::credit(revenue_account, amount),
             ],
             metadata,
@@ -259,6 +277,27 @@ mod tests {
         assert!(entries.is_empty());
     }
 
+    #[test]
+    fn test_interest_rounded_to_asset_scale() {
+        let mut state = RiskState::new();
+        // 0.00005% daily: 3 USDT accrues 0.0000015, 1 USDT 0.0000005
+        let calc = InterestCalculator::with_rate(Decimal::new(5, 7));
+
+        state.apply_entry(&borrow_entry("ALICE", 3));
+        state.apply_entry(&borrow_entry("BOB", 1));
+
+        // USDT has 6 decimals: half to even gives 0.000002 and 0
+        let entries = calc.generate_interest_entries(&state, "daily");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].postings[0].amount.value(), Decimal::new(2, 6));
+        assert_eq!(entries[0].metadata["rounding"], json!("bankers"));
+
+        let floor = InterestCalculator::with_rate(Decimal::new(5, 7)).with_rounding(RoundingMode::Floor);
+        let entries = floor.generate_interest_entries(&state, "daily");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].postings[0].amount.value(), Decimal::new(1, 6));
+    }
+
     #[test]
     fn test_compound_interest_simulation() {
         let mut state = RiskState::new();
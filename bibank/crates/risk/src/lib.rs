@@ -7,10 +7,14 @@ pub mod engine;
 pub mod error;
 pub mod interest;
 pub mod liquidation;
+pub mod reserves;
 pub mod state;
+pub mod valuation;
 
 pub use engine::RiskEngine;
 pub use error::RiskError;
 pub use interest::{InterestCalculator, DEFAULT_DAILY_RATE};
-pub use liquidation::{LiquidationConfig, LiquidationEngine, LiquidationResult};
+pub use liquidation::{LiquidationConfig, LiquidationEngine, LiquidationPlan, LiquidationResult, SeizedCollateral};
+pub use reserves::{AssetReserves, LiabilityProof, LiabilityTree, ProofOfReserves, ReservesSummary, SumNode, SumProofStep};
 pub use state::{MarginError, RiskState, INITIAL_MARGIN, LIQUIDATION_THRESHOLD, MAINTENANCE_MARGIN, MAX_LEVERAGE};
+pub use valuation::{value_portfolio, AssetPosition, Haircuts, Valuation, DEFAULT_HAIRCUT, DEFAULT_QUOTE_ASSET};
@@ -3,12 +3,14 @@
 //! Handles auto-liquidation when margin ratio drops below maintenance threshold.
 //! Integrates with insurance fund for shortfall coverage.
 
-use bibank_core::Amount;
+use bibank_core::{Amount, Currency, RoundingMode};
 use bibank_ledger::{AccountCategory, AccountKey, JournalEntryBuilder, TransactionIntent, UnsignedEntry, LedgerError};
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde_json::json;
+use std::collections::BTreeMap;
 
 use crate::state::{RiskState, LIQUIDATION_THRESHOLD};
+use crate::valuation::{AssetPosition, Valuation};
 
 /// Liquidation result
 #[derive(Debug, Clone)]
@@ -29,6 +31,48 @@ pub struct LiquidationResult {
     pub is_full_liquidation: bool,
 }
 
+/// Collateral seized in one asset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeizedCollateral {
+    pub asset: String,
+    /// Units taken from the user's AVAILABLE balance
+    pub amount: Decimal,
+    /// Value in the quote asset
+    pub value: Decimal,
+    /// Units of `amount` paid to the liquidator
+    pub liquidator_bonus: Decimal,
+}
+
+/// Cross-asset liquidation picked from a portfolio valuation
+///
+/// The insurance fund repays `loan_repaid` of the loan and takes the seized
+/// collateral (minus the liquidator's bonus) in exchange.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiquidationPlan {
+    pub user_id: String,
+    /// Quote asset values are expressed in
+    pub quote: String,
+    /// Asset of the loan being repaid
+    pub loan_asset: String,
+    /// Units of the loan repaid
+    pub loan_repaid: Decimal,
+    /// Penalty value
+    pub penalty: Decimal,
+    /// Collateral taken, in seizure order
+    pub seized: Vec<SeizedCollateral>,
+    /// Value the available collateral fell short by (absorbed by the insurance fund)
+    pub shortfall: Decimal,
+    /// Portfolio margin ratio that triggered the liquidation
+    pub margin_ratio: Decimal,
+}
+
+impl LiquidationPlan {
+    /// Total value of seized collateral
+    pub fn seized_value(&self) -> Decimal {
+        self.seized.iter().map(|s| s.value).sum()
+    }
+}
+
 /// Configuration for liquidation engine
 #[derive(Debug, Clone)]
 pub struct LiquidationConfig {
@@ -89,14 +133,7 @@ impl LiquidationEngine {
         price: Decimal,
     ) -> Option<(Decimal, Decimal, Decimal)> {
         // Get user's loan balance
-        let loan_account = AccountKey::new(
-            AccountCategory::Liability,
-            "USER",
-            user_id,
-            asset,
-            "LOAN",
-        );
-        let loan_balance = state.get_balance(&loan_account);
+        let loan_balance = state.get_loan_balance(user_id, asset);
 
         if loan_balance.is_zero() {
             return None;
@@ -117,6 +154,11 @@ impl LiquidationEngine {
             return None;
         }
 
+        Some(self.liquidation_amounts(loan_balance))
+    }
+
+    /// Partial liquidation of a loan: (collateral_to_seize, loan_to_repay, penalty)
+    fn liquidation_amounts(&self, loan_balance: Decimal) -> (Decimal, Decimal, Decimal) {
         // Calculate how much to liquidate (partial liquidation)
         let max_liquidation = loan_balance * self.config.max_liquidation_ratio;
         let liquidation_amount = loan_balance.min(max_liquidation);
@@ -127,10 +169,14 @@ impl LiquidationEngine {
         // Total collateral to seize
         let collateral_to_seize = liquidation_amount + penalty;
 
-        Some((collateral_to_seize, liquidation_amount, penalty))
+        (collateral_to_seize, liquidation_amount, penalty)
     }
 
     /// Generate a liquidation journal entry
+    ///
+    /// `collateral_seized` below `loan_repaid + penalty` is a shortfall,
+    /// covered by the insurance fund.
+    #[allow(clippy::too_many_arguments)]
     pub fn generate_liquidation_entry(
         &self,
         user_id: &str,
@@ -141,75 +187,74 @@ impl LiquidationEngine {
         penalty: Decimal,
         correlation_id: &str,
     ) -> Result<UnsignedEntry, LedgerError> {
-        let collateral_amt = Amount::new(collateral_seized)
-            .map_err(|_| LedgerError::InvalidAccountFormat("invalid collateral amount".to_string()))?;
-        let loan_amt = Amount::new(loan_repaid)
-            .map_err(|_| LedgerError::InvalidAccountFormat("invalid loan amount".to_string()))?;
+        let amount = |value: Decimal, what: &str| {
+            Amount::new(value).map_err(|_| LedgerError::InvalidAccountFormat(format!("invalid {} amount", what)))
+        };
 
-        // User accounts
+        // User accounts (ASSET:USER:<USER>:<ASSET>:LOAN is BiBank's receivable)
         let user_available = AccountKey::user_available(user_id, asset);
-        let user_loan = AccountKey::new(
-            AccountCategory::Liability,
-            "USER",
-            user_id,
-            asset,
-            "LOAN",
-        );
+        let user_loan = RiskState::loan_account(user_id, asset);
 
         // Liquidator account
         let liquidator_available = AccountKey::user_available(liquidator_id, asset);
 
         // System accounts
-        let insurance_fund = AccountKey::new(
-            AccountCategory::Asset,
-            "SYSTEM",
-            "INSURANCE_FUND",
-            asset,
-            "MAIN",
-        );
+        let insurance_fund = Self::insurance_fund(asset);
 
         // Liquidator bonus is a portion of the penalty
-        let liquidator_bonus = penalty * self.config.liquidator_bonus_rate / self.config.penalty_rate;
+        let liquidator_bonus = if self.config.penalty_rate.is_zero() {
+            Decimal::ZERO
+        } else {
+            penalty * self.config.liquidator_bonus_rate / self.config.penalty_rate
+        };
         let insurance_portion = penalty - liquidator_bonus;
-
-        let liquidator_bonus_amt = Amount::new(liquidator_bonus)
-            .map_err(|_| LedgerError::InvalidAccountFormat("invalid bonus amount".to_string()))?;
-        let insurance_portion_amt = Amount::new(insurance_portion)
-            .map_err(|_| LedgerError::InvalidAccountFormat("invalid insurance amount".to_string()))?;
+        let shortfall = (loan_repaid + penalty - collateral_seized).max(Decimal::ZERO);
 
         // Double-entry accounting for liquidation:
-        // collateral_seized = loan_repaid + penalty = loan_repaid + insurance_portion + liquidator_bonus
+        // collateral_seized + shortfall = loan_repaid + insurance_portion + liquidator_bonus
         //
-        // 1. User loses collateral (Credit user_available = -collateral_seized)
-        // 2. User's loan liability decreases (Debit user_loan = +loan_repaid)
-        // 3. Insurance fund receives portion of penalty (Debit insurance_fund = +insurance_portion)
-        // 4. Liquidator receives bonus (Debit liquidator_available = +liquidator_bonus)
-        //
-        // Balance check:
-        // -collateral_seized + loan_repaid + insurance_portion + liquidator_bonus = 0 ✓
-
-        let entry = JournalEntryBuilder::new()
+        // 1. User loses collateral (Debit user_available = -collateral_seized)
+        // 2. BiBank's loan receivable decreases (Credit user_loan = -loan_repaid)
+        // 3. Insurance fund receives its share of the penalty, net of any shortfall it covers
+        // 4. Liquidator receives bonus (Credit liquidator_available = +liquidator_bonus)
+        let mut builder = JournalEntryBuilder::new()
             .intent(TransactionIntent::Liquidation)
             .correlation_id(correlation_id)
-            // Seize collateral from user (reduces user's asset balance)
-            .credit(user_available.clone(), collateral_amt)
-            // Reduce user's loan liability
-            .debit(user_loan, loan_amt)
-            // Insurance fund receives portion of penalty
-            .debit(insurance_fund, insurance_portion_amt)
+            // Reduce BiBank's receivable
+            .credit(user_loan, amount(loan_repaid, "loan")?)
             // Liquidator receives bonus
-            .debit(liquidator_available, liquidator_bonus_amt)
+            .credit(liquidator_available, amount(liquidator_bonus, "bonus")?);
+
+        // Seize collateral from user
+        if !collateral_seized.is_zero() {
+            builder = builder.debit(user_available, amount(collateral_seized, "collateral")?);
+        }
+
+        let insurance_net = insurance_portion - shortfall;
+        if insurance_net > Decimal::ZERO {
+            builder = builder.credit(insurance_fund, amount(insurance_net, "insurance")?);
+        } else if insurance_net < Decimal::ZERO {
+            builder = builder.debit(insurance_fund, amount(-insurance_net, "insurance")?);
+        }
+
+        let entry = builder
             .metadata("liquidated_user", json!(user_id))
             .metadata("liquidator", json!(liquidator_id))
             .metadata("asset", json!(asset))
             .metadata("collateral_seized", json!(collateral_seized.to_string()))
             .metadata("loan_repaid", json!(loan_repaid.to_string()))
             .metadata("penalty", json!(penalty.to_string()))
+            .metadata("insurance_contribution", json!(shortfall.to_string()))
             .build_unsigned()?;
 
         Ok(entry)
     }
 
+    /// Insurance fund for an asset (EQUITY:SYSTEM:INSURANCE:<ASSET>:FUND)
+    pub fn insurance_fund(asset: &str) -> AccountKey {
+        AccountKey::new(AccountCategory::Equity, "SYSTEM", "INSURANCE", asset, "FUND")
+    }
+
     /// Execute liquidation for a user
     ///
     /// Returns the liquidation result or None if user is not liquidatable
@@ -223,12 +268,175 @@ impl LiquidationEngine {
         correlation_id: &str,
     ) -> Result<Option<(UnsignedEntry, LiquidationResult)>, LedgerError> {
         // Calculate liquidation amounts
-        let (collateral_seized, loan_repaid, penalty) =
-            match self.calculate_liquidation(state, user_id, asset, price) {
-                Some(amounts) => amounts,
-                None => return Ok(None),
-            };
+        let Some(amounts) = self.calculate_liquidation(state, user_id, asset, price) else {
+            return Ok(None);
+        };
+
+        self.build_liquidation(state, user_id, liquidator_id, asset, amounts, correlation_id)
+            .map(Some)
+    }
+
+    /// Pick a liquidation from a portfolio valuation
+    ///
+    /// Repays part of the user's largest loan (by value). Collateral is
+    /// seized from AVAILABLE balances: the loan asset first (no conversion),
+    /// then the most heavily haircut assets, which frees the most margin per
+    /// unit of value taken, then the largest. Returns None if the portfolio
+    /// is above the liquidation threshold.
+    pub fn plan_liquidation(&self, valuation: &Valuation) -> Option<LiquidationPlan> {
+        if !valuation.is_liquidatable() {
+            return None;
+        }
+
+        let (loan_asset, loan) = valuation
+            .positions
+            .iter()
+            .filter(|(_, p)| p.loan > Decimal::ZERO)
+            .max_by_key(|(_, p)| p.loan_value())?;
+
+        let (_, loan_repaid, _) = self.liquidation_amounts(loan.loan);
+        let loan_repaid = Currency::from(loan_asset.as_str()).round(loan_repaid, RoundingMode::Floor);
+        let repay_value = loan_repaid * loan.price;
+        let penalty = repay_value * self.config.penalty_rate;
+
+        let mut candidates: Vec<(&String, &AssetPosition)> = valuation
+            .positions
+            .iter()
+            .filter(|(_, p)| p.available > Decimal::ZERO && p.price > Decimal::ZERO)
+            .collect();
+        candidates.sort_by(|(a_asset, a), (b_asset, b)| {
+            (*b_asset == loan_asset)
+                .cmp(&(*a_asset == loan_asset))
+                .then(b.haircut.cmp(&a.haircut))
+                .then((b.available * b.price).cmp(&(a.available * a.price)))
+        });
+
+        // Share of each seized unit that is the liquidator's bonus
+        let bonus_share = self.config.liquidator_bonus_rate / (Decimal::ONE + self.config.penalty_rate);
+
+        let mut remaining = repay_value + penalty;
+        let mut seized = Vec::new();
+        for (asset, position) in candidates {
+            if remaining <= Decimal::ZERO {
+                break;
+            }
+            let currency = Currency::from(asset.as_str());
+            let amount = (remaining / position.price)
+                .round_dp_with_strategy(currency.scale(), RoundingStrategy::AwayFromZero)
+                .min(position.available);
+            let value = amount * position.price;
+            remaining -= value;
+
+            seized.push(SeizedCollateral {
+                asset: asset.clone(),
+                amount,
+                value,
+                liquidator_bonus: currency.round(amount * bonus_share, RoundingMode::Floor),
+            });
+        }
+
+        Some(LiquidationPlan {
+            user_id: valuation.user.clone(),
+            quote: valuation.quote.clone(),
+            loan_asset: loan_asset.clone(),
+            loan_repaid,
+            penalty,
+            seized,
+            shortfall: remaining.max(Decimal::ZERO),
+            margin_ratio: valuation.margin_ratio(),
+        })
+    }
+
+    /// Generate the journal entry for a liquidation plan
+    ///
+    /// Balanced per asset: the insurance fund repays the loan in the loan
+    /// asset and receives each seized collateral asset, less the
+    /// liquidator's bonus.
+    pub fn generate_plan_entry(
+        &self,
+        plan: &LiquidationPlan,
+        liquidator_id: &str,
+        correlation_id: &str,
+    ) -> Result<UnsignedEntry, LedgerError> {
+        let amount = |value: Decimal, what: &str| {
+            Amount::new(value).map_err(|_| LedgerError::InvalidAccountFormat(format!("invalid {} amount", what)))
+        };
+
+        // Net change of the insurance fund per asset (positive = receives)
+        let mut insurance: BTreeMap<&str, Decimal> = BTreeMap::new();
+        *insurance.entry(plan.loan_asset.as_str()).or_default() -= plan.loan_repaid;
+
+        let mut builder = JournalEntryBuilder::new()
+            .intent(TransactionIntent::Liquidation)
+            .correlation_id(correlation_id)
+            // Reduce BiBank's receivable
+            .credit(RiskState::loan_account(&plan.user_id, &plan.loan_asset), amount(plan.loan_repaid, "loan")?);
+
+        for leg in &plan.seized {
+            // Seize collateral from user
+            builder = builder.debit(AccountKey::user_available(&plan.user_id, &leg.asset), amount(leg.amount, "collateral")?);
+            if leg.liquidator_bonus > Decimal::ZERO {
+                builder = builder.credit(
+                    AccountKey::user_available(liquidator_id, &leg.asset),
+                    amount(leg.liquidator_bonus, "bonus")?,
+                );
+            }
+            *insurance.entry(leg.asset.as_str()).or_default() += leg.amount - leg.liquidator_bonus;
+        }
+
+        for (asset, net) in insurance {
+            if net > Decimal::ZERO {
+                builder = builder.credit(Self::insurance_fund(asset), amount(net, "insurance")?);
+            } else if net < Decimal::ZERO {
+                builder = builder.debit(Self::insurance_fund(asset), amount(-net, "insurance")?);
+            }
+        }
+
+        let seized: Vec<_> = plan
+            .seized
+            .iter()
+            .map(|s| json!({ "asset": s.asset, "amount": s.amount.to_string(), "value": s.value.to_string() }))
+            .collect();
+
+        builder
+            .metadata("liquidated_user", json!(plan.user_id))
+            .metadata("liquidator", json!(liquidator_id))
+            .metadata("loan_asset", json!(plan.loan_asset))
+            .metadata("loan_repaid", json!(plan.loan_repaid.to_string()))
+            .metadata("penalty", json!(plan.penalty.to_string()))
+            .metadata("quote_asset", json!(plan.quote))
+            .metadata("seized", json!(seized))
+            .metadata("shortfall", json!(plan.shortfall.to_string()))
+            .metadata("margin_ratio", json!(plan.margin_ratio.round_dp(4).to_string()))
+            .build_unsigned()
+    }
+
+    /// Plan and generate a portfolio liquidation
+    ///
+    /// Returns None if the portfolio is healthy.
+    pub fn liquidate(
+        &self,
+        valuation: &Valuation,
+        liquidator_id: &str,
+        correlation_id: &str,
+    ) -> Result<Option<(UnsignedEntry, LiquidationPlan)>, LedgerError> {
+        let Some(plan) = self.plan_liquidation(valuation) else {
+            return Ok(None);
+        };
+        let entry = self.generate_plan_entry(&plan, liquidator_id, correlation_id)?;
+        Ok(Some((entry, plan)))
+    }
 
+    /// Build the liquidation entry and result, capping the seizure at the user's balance
+    fn build_liquidation(
+        &self,
+        state: &RiskState,
+        user_id: &str,
+        liquidator_id: &str,
+        asset: &str,
+        (collateral_seized, loan_repaid, penalty): (Decimal, Decimal, Decimal),
+        correlation_id: &str,
+    ) -> Result<(UnsignedEntry, LiquidationResult), LedgerError> {
         // Check if user has enough collateral
         let user_balance = state.get_balance(&AccountKey::user_available(user_id, asset));
 
@@ -261,7 +469,7 @@ impl LiquidationEngine {
             is_full_liquidation: actual_seized >= collateral_seized,
         };
 
-        Ok(Some((entry, result)))
+        Ok((entry, result))
     }
 }
 
@@ -307,26 +515,10 @@ mod tests {
             correlation_id: "test".to_string(),
             causality_id: None,
             postings: vec![
-                Posting::new(
-                    AccountKey::new(AccountCategory::Asset, "SYSTEM", "LENDING_POOL", "USDT", "MAIN"),
-                    amt,
-                    Side::Debit,
-                ),
-                Posting::new(
-                    AccountKey::new(AccountCategory::Liability, "USER", user, "USDT", "LOAN"),
-                    amt,
-                    Side::Credit,
-                ),
-                Posting::new(
-                    AccountKey::user_available(user, "USDT"),
-                    amt,
-                    Side::Credit,
-                ),
-                Posting::new(
-                    AccountKey::new(AccountCategory::Asset, "SYSTEM", "LOANS_RECEIVABLE", "USDT", "MAIN"),
-                    amt,
-                    Side::Debit,
-                ),
+                // ASSET:LOAN increases (BiBank's receivable)
+                Posting::new(RiskState::loan_account(user, "USDT"), amt, Side::Debit),
+                // LIAB:AVAILABLE increases (User's balance)
+                Posting::new(AccountKey::user_available(user, "USDT"), amt, Side::Credit),
             ],
             metadata: Default::default(),
             signatures: vec![],
@@ -387,6 +579,126 @@ mod tests {
         assert!(result.is_none());
     }
 
+    fn deposit(state: &mut RiskState, user: &str, asset: &str, amount: Decimal) {
+        let amt = Amount::new(amount).unwrap();
+        state.apply_entry(&JournalEntry {
+            postings: vec![
+                Posting::new(AccountKey::system_vault(asset), amt, Side::Debit),
+                Posting::new(AccountKey::user_available(user, asset), amt, Side::Credit),
+            ],
+            ..deposit_entry(user, 1)
+        });
+    }
+
+    /// Loan with the borrowed funds already spent
+    fn spent_loan(state: &mut RiskState, user: &str, amount: i64) {
+        let mut borrow = borrow_entry(user, amount);
+        borrow.postings.retain(|p| p.account.sub_account == "LOAN");
+        state.apply_entry(&borrow);
+    }
+
+    async fn valuation(state: &RiskState, oracle: &bibank_oracle::MockOracle) -> Valuation {
+        crate::valuation::value_portfolio(state, "ALICE", oracle, "USDT", &crate::valuation::Haircuts::default())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_plan_picks_collateral_across_assets() {
+        let engine = LiquidationEngine::default();
+        let oracle = bibank_oracle::MockOracle::with_defaults();
+        let mut state = create_test_state();
+
+        // 5000 USDT + 0.5 BTC + 5 ETH against a 40000 USDT loan
+        deposit(&mut state, "ALICE", "USDT", Decimal::from(5000));
+        deposit(&mut state, "ALICE", "BTC", Decimal::new(5, 1));
+        deposit(&mut state, "ALICE", "ETH", Decimal::from(5));
+        spent_loan(&mut state, "ALICE", 40000);
+
+        // 5000 + 25000 * 0.90 + 15000 * 0.85 = 40250: healthy
+        assert!(engine.plan_liquidation(&valuation(&state, &oracle).await).is_none());
+
+        // BTC to 40000: 5000 + 18000 + 12750 = 35750 < 40000
+        oracle.set_price(bibank_oracle::TradingPair::btc_usdt(), Decimal::from(40000));
+        let (entry, plan) = engine
+            .liquidate(&valuation(&state, &oracle).await, "LIQUIDATOR", "liq-001")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(plan.loan_asset, "USDT");
+        assert_eq!(plan.loan_repaid, Decimal::from(20000));
+        assert_eq!(plan.penalty, Decimal::from(1000));
+        assert_eq!(plan.shortfall, Decimal::ZERO);
+        assert_eq!(plan.seized_value(), Decimal::from(21000));
+
+        // Loan asset first, then ETH (15% haircut) before BTC (10%)
+        let order: Vec<_> = plan.seized.iter().map(|s| (s.asset.as_str(), s.amount)).collect();
+        assert_eq!(
+            order,
+            vec![
+                ("USDT", Decimal::from(5000)),
+                ("ETH", Decimal::from(5)),
+                ("BTC", Decimal::new(25, 3)),
+            ]
+        );
+
+        entry.validate_balance().unwrap();
+        bibank_ledger::validate_intent(&entry).unwrap();
+
+        let mut after = state;
+        after.apply_entry(&JournalEntry {
+            postings: entry.postings,
+            ..deposit_entry("ALICE", 1)
+        });
+        assert_eq!(after.get_loan_balance("ALICE", "USDT"), Decimal::from(20000));
+        assert_eq!(after.get_available_balance("ALICE", "USDT"), Decimal::ZERO);
+        assert_eq!(after.get_available_balance("ALICE", "ETH"), Decimal::ZERO);
+        assert_eq!(after.get_available_balance("ALICE", "BTC"), Decimal::new(475, 3));
+        assert!(after.get_available_balance("LIQUIDATOR", "BTC") > Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_plan_shortfall_when_collateral_runs_out() {
+        let engine = LiquidationEngine::default();
+        let oracle = bibank_oracle::MockOracle::with_defaults();
+        let mut state = create_test_state();
+
+        // 0.1 BTC (4500 after haircut) against a 40000 USDT loan
+        deposit(&mut state, "ALICE", "BTC", Decimal::new(1, 1));
+        spent_loan(&mut state, "ALICE", 40000);
+
+        let (entry, plan) = engine
+            .liquidate(&valuation(&state, &oracle).await, "LIQUIDATOR", "liq-001")
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(plan.seized.len(), 1);
+        assert_eq!(plan.seized[0].amount, Decimal::new(1, 1));
+        // 21000 owed, 5000 of BTC taken
+        assert_eq!(plan.shortfall, Decimal::from(16000));
+        entry.validate_balance().unwrap();
+    }
+
+    #[test]
+    fn test_liquidation_shortfall_covered_by_insurance() {
+        let engine = LiquidationEngine::default();
+
+        // Owes 100 + 5 penalty but only 60 left to seize
+        let entry = engine
+            .generate_liquidation_entry("ALICE", "LIQUIDATOR", "USDT", Decimal::from(60), Decimal::from(100), Decimal::from(5), "liq-001")
+            .unwrap();
+        entry.validate_balance().unwrap();
+
+        let insurance = entry
+            .postings
+            .iter()
+            .find(|p| p.account == LiquidationEngine::insurance_fund("USDT"))
+            .unwrap();
+        // 45 shortfall - 4 insurance share of the penalty
+        assert_eq!(insurance.side, Side::Debit);
+        assert_eq!(insurance.amount.value(), Decimal::from(41));
+    }
+
     #[test]
     fn test_generate_liquidation_entry() {
         let engine = LiquidationEngine::default();
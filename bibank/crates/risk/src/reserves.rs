@@ -0,0 +1,430 @@
+//! Proof of reserves / proof of liabilities
+//!
+//! For every asset, the user liabilities in [`RiskState`] (all of a user's
+//! `LIAB:USER:<id>:<asset>:*` sub-accounts added together) go into a Merkle
+//! sum tree: each node commits to its children's hashes and to the sum of
+//! their balances, so the root commits to the total owed to users. The root
+//! and total are published next to the vault balance backing them; each user
+//! gets a [`LiabilityProof`] showing their balance is included, which they
+//! can check offline against the published root.
+//!
+//! Negative balances are left out: they would let the tree understate the
+//! total. Leaves are sorted by user id, so the same state always gives the
+//! same root.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use bibank_ledger::{AccountCategory, AccountKey, NodeSide};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::state::RiskState;
+
+/// Node of a Merkle sum tree
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SumNode {
+    /// Node hash (hex)
+    pub hash: String,
+    /// Sum of the balances below
+    pub sum: Decimal,
+}
+
+impl SumNode {
+    fn leaf(asset: &str, user_id: &str, balance: Decimal) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update([0x00]);
+        for part in [asset, user_id, &balance.normalize().to_string()] {
+            hasher.update(part.as_bytes());
+            hasher.update([0x00]);
+        }
+        Self {
+            hash: hex::encode(hasher.finalize()),
+            sum: balance,
+        }
+    }
+
+    fn parent(left: &SumNode, right: &SumNode) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update([0x01]);
+        for node in [left, right] {
+            hasher.update(node.hash.as_bytes());
+            hasher.update([0x00]);
+            hasher.update(node.sum.normalize().to_string().as_bytes());
+            hasher.update([0x00]);
+        }
+        Self {
+            hash: hex::encode(hasher.finalize()),
+            sum: left.sum + right.sum,
+        }
+    }
+}
+
+/// One sibling on the path from a user's leaf to the root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SumProofStep {
+    pub hash: String,
+    pub sum: Decimal,
+    pub side: NodeSide,
+}
+
+/// Proof that a user's balance is included in an asset's liability root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiabilityProof {
+    pub asset: String,
+    /// Journal sequence the balances are taken at
+    pub sequence: u64,
+    pub user_id: String,
+    pub balance: Decimal,
+    /// Published root and total the proof leads to
+    pub root: String,
+    pub total: Decimal,
+    /// Siblings from the leaf up
+    pub path: Vec<SumProofStep>,
+}
+
+impl LiabilityProof {
+    /// Root node the proof leads to
+    ///
+    /// None if any sum on the path is negative (a sibling can't hide
+    /// liabilities by subtracting from the total).
+    pub fn compute_root(&self) -> Option<SumNode> {
+        if self.balance < Decimal::ZERO {
+            return None;
+        }
+        let mut current = SumNode::leaf(&self.asset, &self.user_id, self.balance);
+        for step in &self.path {
+            if step.sum < Decimal::ZERO {
+                return None;
+            }
+            let sibling = SumNode {
+                hash: step.hash.clone(),
+                sum: step.sum,
+            };
+            current = match step.side {
+                NodeSide::Left => SumNode::parent(&sibling, &current),
+                NodeSide::Right => SumNode::parent(&current, &sibling),
+            };
+        }
+        Some(current)
+    }
+
+    /// Check that the proof leads to its stated root and total
+    pub fn verify(&self) -> bool {
+        self.compute_root()
+            .is_some_and(|root| root.hash == self.root && root.sum == self.total)
+    }
+}
+
+/// Merkle sum tree of one asset's user liabilities
+#[derive(Debug, Clone)]
+pub struct LiabilityTree {
+    asset: String,
+    /// (user id, balance), sorted by user id
+    leaves: Vec<(String, Decimal)>,
+    /// Levels from the leaves (0) up to the root
+    levels: Vec<Vec<SumNode>>,
+}
+
+impl LiabilityTree {
+    /// Build a tree over user balances (non-positive balances are skipped)
+    pub fn new(asset: impl Into<String>, balances: BTreeMap<String, Decimal>) -> Self {
+        let asset = asset.into();
+        let leaves: Vec<(String, Decimal)> = balances
+            .into_iter()
+            .filter(|(_, balance)| *balance > Decimal::ZERO)
+            .collect();
+
+        let mut levels = vec![leaves
+            .iter()
+            .map(|(user, balance)| SumNode::leaf(&asset, user, *balance))
+            .collect::<Vec<_>>()];
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let level = levels.last().expect("levels is never empty");
+            let next = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => SumNode::parent(left, right),
+                    [odd] => odd.clone(),
+                    _ => unreachable!("chunks(2) yields one or two nodes"),
+                })
+                .collect();
+            levels.push(next);
+        }
+
+        Self {
+            asset,
+            leaves,
+            levels,
+        }
+    }
+
+    pub fn asset(&self) -> &str {
+        &self.asset
+    }
+
+    /// Root node (empty hash and zero sum when no user holds the asset)
+    pub fn root(&self) -> SumNode {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .cloned()
+            .unwrap_or(SumNode {
+                hash: String::new(),
+                sum: Decimal::ZERO,
+            })
+    }
+
+    /// Number of users in the tree
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Check if no user holds the asset
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Inclusion proof for a user (None if they hold none of the asset)
+    pub fn proof(&self, user_id: &str, sequence: u64) -> Option<LiabilityProof> {
+        let user_id = user_id.to_uppercase();
+        let mut index = self
+            .leaves
+            .binary_search_by(|(user, _)| user.as_str().cmp(&user_id))
+            .ok()?;
+        let balance = self.leaves[index].1;
+
+        let mut path = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = index ^ 1;
+            if let Some(node) = level.get(sibling) {
+                path.push(SumProofStep {
+                    hash: node.hash.clone(),
+                    sum: node.sum,
+                    side: if sibling < index { NodeSide::Left } else { NodeSide::Right },
+                });
+            }
+            index /= 2;
+        }
+
+        let root = self.root();
+        Some(LiabilityProof {
+            asset: self.asset.clone(),
+            sequence,
+            user_id,
+            balance,
+            root: root.hash,
+            total: root.sum,
+            path,
+        })
+    }
+}
+
+/// Published reserves of one asset
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetReserves {
+    pub asset: String,
+    /// Root of the liability sum tree
+    pub root: String,
+    pub total_liabilities: Decimal,
+    /// Vault balance backing the liabilities
+    pub vault_balance: Decimal,
+    pub users: usize,
+    /// Whether the vault covers the liabilities
+    pub covered: bool,
+}
+
+/// Published reserves at one journal sequence
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReservesSummary {
+    pub sequence: u64,
+    pub assets: Vec<AssetReserves>,
+}
+
+impl ReservesSummary {
+    /// Check if every asset is covered
+    pub fn is_covered(&self) -> bool {
+        self.assets.iter().all(|a| a.covered)
+    }
+
+    /// Published reserves of an asset
+    pub fn asset(&self, asset: &str) -> Option<&AssetReserves> {
+        self.assets.iter().find(|a| a.asset == asset)
+    }
+}
+
+/// Liability trees for every asset, with the vault balances backing them
+#[derive(Debug, Clone)]
+pub struct ProofOfReserves {
+    sequence: u64,
+    trees: BTreeMap<String, LiabilityTree>,
+    vaults: BTreeMap<String, Decimal>,
+}
+
+impl ProofOfReserves {
+    /// Build from the balances in `state`, as of journal entry `sequence`
+    pub fn build(state: &RiskState, sequence: u64) -> Self {
+        let mut liabilities: BTreeMap<String, BTreeMap<String, Decimal>> = BTreeMap::new();
+        let mut vaults: BTreeMap<String, Decimal> = BTreeMap::new();
+
+        for (key, balance) in state.all_balances() {
+            let Ok(account) = key.parse::<AccountKey>() else {
+                continue;
+            };
+            if account.category == AccountCategory::Liability && account.segment == "USER" {
+                *liabilities
+                    .entry(account.asset)
+                    .or_default()
+                    .entry(account.id)
+                    .or_default() += *balance;
+            } else if account == AccountKey::system_vault(&account.asset) {
+                vaults.insert(account.asset, *balance);
+            }
+        }
+
+        let assets: BTreeSet<String> = liabilities.keys().chain(vaults.keys()).cloned().collect();
+        let trees = assets
+            .into_iter()
+            .map(|asset| {
+                let balances = liabilities.remove(&asset).unwrap_or_default();
+                (asset.clone(), LiabilityTree::new(asset, balances))
+            })
+            .collect();
+
+        Self {
+            sequence,
+            trees,
+            vaults,
+        }
+    }
+
+    pub fn sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    /// Liability tree of an asset
+    pub fn tree(&self, asset: &str) -> Option<&LiabilityTree> {
+        self.trees.get(&asset.to_uppercase())
+    }
+
+    /// Roots, totals and vault balances to publish
+    pub fn summary(&self) -> ReservesSummary {
+        let assets = self
+            .trees
+            .values()
+            .map(|tree| {
+                let root = tree.root();
+                let vault_balance = self.vaults.get(tree.asset()).copied().unwrap_or_default();
+                AssetReserves {
+                    asset: tree.asset().to_string(),
+                    root: root.hash,
+                    total_liabilities: root.sum,
+                    vault_balance,
+                    users: tree.len(),
+                    covered: vault_balance >= root.sum,
+                }
+            })
+            .collect();
+        ReservesSummary {
+            sequence: self.sequence,
+            assets,
+        }
+    }
+
+    /// A user's inclusion proofs, one per asset they hold
+    pub fn proofs(&self, user_id: &str) -> Vec<LiabilityProof> {
+        self.trees
+            .values()
+            .filter_map(|tree| tree.proof(user_id, self.sequence))
+            .collect()
+    }
+
+    /// Users in any tree, sorted
+    pub fn users(&self) -> Vec<String> {
+        let users: BTreeSet<&String> = self
+            .trees
+            .values()
+            .flat_map(|tree| tree.leaves.iter().map(|(user, _)| user))
+            .collect();
+        users.into_iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bibank_core::Amount;
+    use bibank_ledger::{JournalEntry, Posting, TransactionIntent};
+    use chrono::Utc;
+    use std::collections::HashMap;
+
+    fn dec(s: &str) -> Decimal {
+        s.parse().unwrap()
+    }
+
+    fn deposit(state: &mut RiskState, user: &str, asset: &str, amount: &str) {
+        let amount = Amount::new(dec(amount)).unwrap();
+        state.apply_entry(&JournalEntry {
+            sequence: 1,
+            prev_hash: "test".to_string(),
+            hash: "test".to_string(),
+            timestamp: Utc::now(),
+            intent: TransactionIntent::Deposit,
+            correlation_id: "test".to_string(),
+            causality_id: None,
+            postings: vec![
+                Posting::debit(AccountKey::system_vault(asset), amount),
+                Posting::credit(AccountKey::user_available(user, asset), amount),
+            ],
+            metadata: HashMap::new(),
+            signatures: Vec::new(),
+        });
+    }
+
+    #[test]
+    fn test_sum_tree_proofs() {
+        let balances: BTreeMap<String, Decimal> = [("ALICE", "10"), ("BOB", "2.5"), ("CAROL", "7"), ("DAVE", "0")]
+            .into_iter()
+            .map(|(user, balance)| (user.to_string(), dec(balance)))
+            .collect();
+        let tree = LiabilityTree::new("USDT", balances);
+        assert_eq!(tree.len(), 3);
+        assert_eq!(tree.root().sum, dec("19.5"));
+
+        for user in ["alice", "BOB", "CAROL"] {
+            let proof = tree.proof(user, 7).unwrap();
+            assert!(proof.verify(), "{}", user);
+            assert_eq!(proof.total, dec("19.5"));
+        }
+        assert!(tree.proof("DAVE", 7).is_none());
+
+        // Understating a balance or a sibling's sum changes the root
+        let mut proof = tree.proof("BOB", 7).unwrap();
+        proof.balance = dec("1");
+        assert!(!proof.verify());
+        let mut proof = tree.proof("BOB", 7).unwrap();
+        proof.path[0].sum = dec("-10");
+        assert!(!proof.verify());
+    }
+
+    #[test]
+    fn test_proof_of_reserves_against_vault() {
+        let mut state = RiskState::new();
+        deposit(&mut state, "ALICE", "USDT", "100");
+        deposit(&mut state, "BOB", "USDT", "50");
+        deposit(&mut state, "ALICE", "BTC", "1");
+
+        let reserves = ProofOfReserves::build(&state, 3);
+        let summary = reserves.summary();
+        let usdt = summary.asset("USDT").unwrap();
+        assert_eq!(usdt.total_liabilities, dec("150"));
+        assert_eq!(usdt.vault_balance, dec("150"));
+        assert_eq!(usdt.users, 2);
+        assert!(summary.is_covered());
+
+        let proofs = reserves.proofs("ALICE");
+        assert_eq!(proofs.len(), 2);
+        assert!(proofs.iter().all(|p| p.verify() && p.sequence == 3));
+        assert_eq!(reserves.users(), vec!["ALICE", "BOB"]);
+    }
+}
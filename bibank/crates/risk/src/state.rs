@@ -116,6 +116,38 @@ impl RiskState {
         self.balances.clear();
     }
 
+    /// Replace all balances (restoring from a snapshot)
+    pub fn restore(&mut self, balances: impl IntoIterator<Item = (String, Decimal)>) {
+        self.balances = balances.into_iter().collect();
+    }
+
+    /// All of a user's accounts with their balances
+    pub fn user_accounts(&self, user: &str) -> Vec<(AccountKey, Decimal)> {
+        let user = user.to_uppercase();
+        self.balances
+            .iter()
+            .filter_map(|(key, &balance)| {
+                let account: AccountKey = key.parse().ok()?;
+                (account.segment == "USER" && account.id == user).then_some((account, balance))
+            })
+            .collect()
+    }
+
+    /// Users with an outstanding loan in any asset, sorted
+    pub fn borrowers(&self) -> Vec<String> {
+        let mut users: Vec<String> = self
+            .balances
+            .iter()
+            .filter(|(_, &balance)| balance > Decimal::ZERO)
+            .filter_map(|(key, _)| key.parse::<AccountKey>().ok())
+            .filter(|account| account.segment == "USER" && account.sub_account == "LOAN")
+            .map(|account| account.id)
+            .collect();
+        users.sort();
+        users.dedup();
+        users
+    }
+
     // === Phase 3: Margin Trading Methods ===
 
     /// Create a LOAN account key for a user
@@ -147,6 +179,7 @@ impl RiskState {
 
     /// Calculate margin ratio for a user/asset
     /// Margin Ratio = (Available / Loan) if Loan > 0, else infinity (represented as 100.0)
+    /// Single-asset view; cross-asset margin is in [`crate::valuation`]
     pub fn get_margin_ratio(&self, user: &str, asset: &str) -> Decimal {
         let available = self.get_available_balance(user, asset);
         let loan = self.get_loan_balance(user, asset);
@@ -161,6 +194,7 @@ impl RiskState {
 
     /// Check if a borrow would exceed max leverage
     /// Returns true if the borrow is allowed
+    /// Single-asset view; see [`crate::RiskEngine::check_borrow`] for portfolio margin
     pub fn check_borrow_allowed(
         &self,
         user: &str,
@@ -281,6 +315,22 @@ mod tests {
         assert_eq!(alice_balance, Decimal::new(100, 0));
     }
 
+    #[test]
+    fn test_restore_replaces_balances() {
+        let mut state = RiskState::new();
+        state.apply_entry(&deposit_entry("ALICE", 100));
+
+        let mut restored = RiskState::new();
+        restored.apply_entry(&deposit_entry("BOB", 5));
+        restored.restore(state.all_balances().clone());
+
+        assert_eq!(restored.all_balances(), state.all_balances());
+        assert_eq!(
+            restored.get_balance(&AccountKey::user_available("BOB", "USDT")),
+            Decimal::ZERO
+        );
+    }
+
     #[test]
     fn test_transfer_balance_check() {
         let mut state = RiskState::new();
@@ -505,6 +555,21 @@ mod tests {
         assert_eq!(loan, Decimal::new(300, 0), "Loan = 500 - 200");
     }
 
+    #[test]
+    fn test_borrowers_lists_open_loans() {
+        let mut state = RiskState::new();
+        state.apply_entry(&deposit_entry("ALICE", 100));
+        state.apply_entry(&deposit_entry("CAROL", 100));
+        state.apply_entry(&borrow_entry("CAROL", 200));
+        state.apply_entry(&borrow_entry("BOB", 300));
+        state.apply_entry(&borrow_entry("BOB", 100));
+        assert_eq!(state.borrowers(), vec!["BOB".to_string(), "CAROL".to_string()]);
+
+        // Fully repaid loans drop out
+        state.apply_entry(&repay_entry("CAROL", 200));
+        assert_eq!(state.borrowers(), vec!["BOB".to_string()]);
+    }
+
     #[test]
     fn test_margin_ratio_calculation() {
         let mut state = RiskState::new();
@@ -0,0 +1,298 @@
+//! Portfolio valuation through a price oracle
+//!
+//! Values every asset a user holds or owes in a single quote asset, so
+//! margin checks see the whole portfolio instead of one asset at a time.
+//! Collateral is AVAILABLE + LOCKED, discounted by a per-asset haircut;
+//! loans are any USER LOAN account, at full value.
+
+use bibank_oracle::{PriceOracle, TradingPair};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
+
+use crate::error::RiskError;
+use crate::state::{RiskState, LIQUIDATION_THRESHOLD};
+
+/// Quote asset margin is measured in
+pub const DEFAULT_QUOTE_ASSET: &str = "USDT";
+
+/// Haircut for assets without a configured rate
+pub const DEFAULT_HAIRCUT: Decimal = Decimal::from_parts(25, 0, 0, false, 2); // 25%
+
+/// Collateral haircuts per asset
+///
+/// A haircut of 0.10 counts 1 BTC worth 50000 as 45000 of collateral.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Haircuts {
+    rates: BTreeMap<String, Decimal>,
+    default_rate: Decimal,
+}
+
+impl Haircuts {
+    /// Schedule applying `default_rate` to every asset
+    pub fn new(default_rate: Decimal) -> Self {
+        Self {
+            rates: BTreeMap::new(),
+            default_rate,
+        }
+    }
+
+    /// No haircuts: collateral counts at full value
+    pub fn none() -> Self {
+        Self::new(Decimal::ZERO)
+    }
+
+    /// Set the haircut for an asset
+    pub fn with(mut self, asset: &str, rate: Decimal) -> Self {
+        self.rates.insert(asset.to_uppercase(), rate);
+        self
+    }
+
+    /// Haircut for an asset
+    pub fn rate(&self, asset: &str) -> Decimal {
+        self.rates.get(asset).copied().unwrap_or(self.default_rate)
+    }
+}
+
+impl Default for Haircuts {
+    /// Stablecoins at par, majors at 10-15%, everything else at 25%
+    fn default() -> Self {
+        Self::new(DEFAULT_HAIRCUT)
+            .with("USDT", Decimal::ZERO)
+            .with("USDC", Decimal::ZERO)
+            .with("BTC", Decimal::new(10, 2))
+            .with("ETH", Decimal::new(15, 2))
+    }
+}
+
+/// One asset of a portfolio
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetPosition {
+    /// AVAILABLE balance (units)
+    pub available: Decimal,
+    /// LOCKED balance (units)
+    pub locked: Decimal,
+    /// Outstanding loan (units)
+    pub loan: Decimal,
+    /// Price in the quote asset
+    pub price: Decimal,
+    /// Haircut applied to collateral
+    pub haircut: Decimal,
+}
+
+impl AssetPosition {
+    /// Collateral value after haircut
+    pub fn collateral_value(&self) -> Decimal {
+        (self.available + self.locked) * self.price * (Decimal::ONE - self.haircut)
+    }
+
+    /// Loan value
+    pub fn loan_value(&self) -> Decimal {
+        self.loan * self.price
+    }
+}
+
+/// A user's portfolio valued in one quote asset
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Valuation {
+    pub user: String,
+    /// Quote asset all values are expressed in
+    pub quote: String,
+    /// Value of AVAILABLE + LOCKED balances, after haircuts
+    pub collateral: Decimal,
+    /// Value of outstanding loans
+    pub loans: Decimal,
+    /// Balances, price and haircut per asset
+    pub positions: BTreeMap<String, AssetPosition>,
+}
+
+impl Valuation {
+    /// Net value (collateral - loans)
+    pub fn equity(&self) -> Decimal {
+        self.collateral - self.loans
+    }
+
+    /// Margin ratio = collateral / loans (100.0 when there are no loans)
+    pub fn margin_ratio(&self) -> Decimal {
+        if self.loans <= Decimal::ZERO {
+            Decimal::from(100)
+        } else {
+            self.collateral / self.loans
+        }
+    }
+
+    /// Check if the portfolio is below the liquidation threshold
+    pub fn is_liquidatable(&self) -> bool {
+        self.margin_ratio() < LIQUIDATION_THRESHOLD
+    }
+
+    /// Price used for an asset
+    pub fn price(&self, asset: &str) -> Option<Decimal> {
+        self.positions.get(asset).map(|p| p.price)
+    }
+}
+
+/// Price of `asset` in `quote` (1 for the quote asset itself)
+pub async fn price_in(oracle: &dyn PriceOracle, asset: &str, quote: &str) -> Result<Decimal, RiskError> {
+    if asset == quote {
+        return Ok(Decimal::ONE);
+    }
+    oracle
+        .get_price(&TradingPair::new(asset, quote))
+        .await
+        .map(|price| price.last)
+        .map_err(|e| RiskError::PriceUnavailable {
+            asset: asset.to_string(),
+            quote: quote.to_string(),
+            reason: e.to_string(),
+        })
+}
+
+/// Value a user's portfolio in `quote`
+pub async fn value_portfolio(
+    state: &RiskState,
+    user: &str,
+    oracle: &dyn PriceOracle,
+    quote: &str,
+    haircuts: &Haircuts,
+) -> Result<Valuation, RiskError> {
+    let mut positions: BTreeMap<String, AssetPosition> = BTreeMap::new();
+
+    for (account, balance) in state.user_accounts(user) {
+        if balance <= Decimal::ZERO {
+            continue;
+        }
+        let position = positions.entry(account.asset.clone()).or_default();
+        match account.sub_account.as_str() {
+            "AVAILABLE" => position.available += balance,
+            "LOCKED" => position.locked += balance,
+            "LOAN" => position.loan += balance,
+            _ => {}
+        }
+    }
+
+    let mut valuation = Valuation {
+        user: user.to_uppercase(),
+        quote: quote.to_string(),
+        ..Default::default()
+    };
+
+    for (asset, mut position) in positions {
+        if position == AssetPosition::default() {
+            continue;
+        }
+        position.price = price_in(oracle, &asset, quote).await?;
+        position.haircut = haircuts.rate(&asset);
+
+        valuation.collateral += position.collateral_value();
+        valuation.loans += position.loan_value();
+        valuation.positions.insert(asset, position);
+    }
+
+    Ok(valuation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bibank_core::Amount;
+    use bibank_ledger::{AccountCategory, AccountKey, JournalEntry, Posting, Side, TransactionIntent};
+    use bibank_oracle::MockOracle;
+    use chrono::Utc;
+
+    fn credit(account: AccountKey, val: i64) -> JournalEntry {
+        let amt = Amount::new(Decimal::from(val)).unwrap();
+        JournalEntry {
+            sequence: 1,
+            prev_hash: "GENESIS".to_string(),
+            hash: "test".to_string(),
+            timestamp: Utc::now(),
+            intent: TransactionIntent::Deposit,
+            correlation_id: "test".to_string(),
+            causality_id: None,
+            postings: vec![
+                Posting::new(AccountKey::system_vault(account.asset.clone()), amt, Side::Debit),
+                Posting::new(account, amt, Side::Credit),
+            ],
+            metadata: Default::default(),
+            signatures: vec![],
+        }
+    }
+
+    fn loan(user: &str, asset: &str) -> AccountKey {
+        AccountKey::new(AccountCategory::Liability, "USER", user, asset, "LOAN")
+    }
+
+    fn portfolio() -> RiskState {
+        let mut state = RiskState::new();
+        state.apply_entry(&credit(AccountKey::user_available("ALICE", "BTC"), 1));
+        state.apply_entry(&credit(AccountKey::user_locked("ALICE", "ETH"), 2));
+        state.apply_entry(&credit(AccountKey::user_available("ALICE", "USDT"), 1000));
+        state.apply_entry(&credit(loan("ALICE", "USDT"), 20000));
+        state.apply_entry(&credit(AccountKey::user_available("BOB", "BTC"), 5));
+        state
+    }
+
+    #[tokio::test]
+    async fn test_value_multi_asset_portfolio() {
+        let state = portfolio();
+        let oracle = MockOracle::with_defaults();
+        let valuation = value_portfolio(&state, "ALICE", &oracle, "USDT", &Haircuts::none())
+            .await
+            .unwrap();
+
+        // 1 BTC * 50000 + 2 ETH * 3000 + 1000 USDT
+        assert_eq!(valuation.collateral, Decimal::from(57000));
+        assert_eq!(valuation.loans, Decimal::from(20000));
+        assert_eq!(valuation.equity(), Decimal::from(37000));
+        assert_eq!(valuation.margin_ratio(), Decimal::from_str_exact("2.85").unwrap());
+        assert_eq!(valuation.price("BTC"), Some(Decimal::from(50000)));
+        assert!(!valuation.is_liquidatable());
+
+        // A price drop is visible without touching balances
+        oracle.set_price(TradingPair::btc_usdt(), Decimal::from(10000));
+        let valuation = value_portfolio(&state, "ALICE", &oracle, "USDT", &Haircuts::none())
+            .await
+            .unwrap();
+        assert_eq!(valuation.collateral, Decimal::from(17000));
+        assert!(valuation.is_liquidatable());
+    }
+
+    #[tokio::test]
+    async fn test_haircuts_discount_collateral_not_loans() {
+        let state = portfolio();
+        let oracle = MockOracle::with_defaults();
+        let valuation = value_portfolio(&state, "ALICE", &oracle, "USDT", &Haircuts::default())
+            .await
+            .unwrap();
+
+        // 50000 * 0.90 + 6000 * 0.85 + 1000
+        assert_eq!(valuation.collateral, Decimal::from(51100));
+        assert_eq!(valuation.loans, Decimal::from(20000));
+
+        let eth = &valuation.positions["ETH"];
+        assert_eq!(eth.locked, Decimal::from(2));
+        assert_eq!(eth.haircut, Decimal::new(15, 2));
+
+        let usdt = &valuation.positions["USDT"];
+        assert_eq!(usdt.loan, Decimal::from(20000));
+        assert_eq!(usdt.loan_value(), Decimal::from(20000));
+
+        // Unlisted assets fall back to the default haircut
+        assert_eq!(Haircuts::default().rate("SOL"), DEFAULT_HAIRCUT);
+    }
+
+    #[tokio::test]
+    async fn test_value_portfolio_missing_price() {
+        let mut state = RiskState::new();
+        state.apply_entry(&credit(AccountKey::user_available("ALICE", "DOGE"), 100));
+
+        let oracle = MockOracle::with_defaults();
+        let result = value_portfolio(&state, "ALICE", &oracle, "USDT", &Haircuts::default()).await;
+        assert!(matches!(result, Err(RiskError::PriceUnavailable { .. })));
+
+        let empty = value_portfolio(&state, "BOB", &oracle, "USDT", &Haircuts::default())
+            .await
+            .unwrap();
+        assert_eq!(empty.margin_ratio(), Decimal::from(100));
+    }
+}
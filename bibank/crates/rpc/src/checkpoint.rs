@@ -0,0 +1,209 @@
+//! Merkle checkpoints - inclusion proofs and audits without replaying genesis
+//!
+//! Every `checkpoint_interval` entries, the entries since the previous
+//! checkpoint are committed to with a Merkle root, signed with the system
+//! key, and appended to `checkpoints.jsonl` in the data directory.
+//! Checkpoints cover consecutive, non-overlapping ranges, so any
+//! checkpointed sequence has exactly one checkpoint to be proven against.
+//!
+//! A checkpoint is trusted when its system signature verifies and its
+//! `last_hash` still matches the journal; `bibank audit --from-checkpoint`
+//! verifies the hash chain from the last trusted one onwards. With a key
+//! registry, the signing key must have been valid at the checkpoint's last
+//! sequence.
+
+use bibank_events::{EventError, EventReader};
+use bibank_ledger::{Checkpoint, EntrySignature, KeyRegistry, LedgerError, MerkleTree};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Checkpoint file in the data directory
+pub const CHECKPOINT_FILE: &str = "checkpoints.jsonl";
+
+/// Entries between automatic checkpoints
+pub const DEFAULT_CHECKPOINT_INTERVAL: u64 = 1_000;
+
+/// Append-only checkpoint log (one JSON checkpoint per line)
+pub struct CheckpointStore {
+    path: PathBuf,
+}
+
+impl CheckpointStore {
+    /// Create a store over `path` (created on first append)
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Checkpoint file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a checkpoint
+    pub fn append(&self, checkpoint: &Checkpoint) -> Result<(), CheckpointError> {
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(checkpoint)?)?;
+        file.sync_all()?;
+        Ok(())
+    }
+
+    /// All checkpoints, oldest first
+    pub fn list(&self) -> Result<Vec<Checkpoint>, CheckpointError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut checkpoints = Vec::new();
+        for line in BufReader::new(fs::File::open(&self.path)?).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                checkpoints.push(serde_json::from_str(&line)?);
+            }
+        }
+        Ok(checkpoints)
+    }
+
+    /// Latest checkpoint
+    pub fn latest(&self) -> Result<Option<Checkpoint>, CheckpointError> {
+        Ok(self.list()?.pop())
+    }
+
+    /// Checkpoint covering entry `sequence`
+    pub fn covering(&self, sequence: u64) -> Result<Option<Checkpoint>, CheckpointError> {
+        Ok(self.list()?.into_iter().find(|c| c.covers(sequence)))
+    }
+
+    /// Latest checkpoint that is signed (by `public_key`, if given) and
+    /// still anchored in the journal
+    ///
+    /// Untrusted checkpoints are skipped with a warning, falling back to
+    /// older ones.
+    pub fn latest_trusted(&self, reader: &EventReader, public_key: Option<&str>) -> Option<Checkpoint> {
+        self.latest_anchored(reader, |signature, _| {
+            public_key.is_none_or(|expected| signature.public_key == expected)
+        })
+    }
+
+    /// Latest checkpoint signed by a key valid, in `keys`, at its last
+    /// sequence and still anchored in the journal
+    pub fn latest_trusted_by(&self, reader: &EventReader, keys: &KeyRegistry) -> Option<Checkpoint> {
+        self.latest_anchored(reader, |signature, sequence| {
+            keys.is_valid(&signature.signer_id, &signature.public_key, sequence)
+        })
+    }
+
+    fn latest_anchored(
+        &self,
+        reader: &EventReader,
+        trusts: impl Fn(&EntrySignature, u64) -> bool,
+    ) -> Option<Checkpoint> {
+        let checkpoints = match self.list() {
+            Ok(checkpoints) => checkpoints,
+            Err(e) => {
+                tracing::warn!("Cannot read checkpoints: {}", e);
+                return None;
+            }
+        };
+
+        for checkpoint in checkpoints.into_iter().rev() {
+            match check_trusted(&checkpoint, reader, &trusts) {
+                Ok(()) => return Some(checkpoint),
+                Err(e) => tracing::warn!(
+                    "Skipping checkpoint {}..{}: {}",
+                    checkpoint.from_sequence,
+                    checkpoint.to_sequence,
+                    e
+                ),
+            }
+        }
+        None
+    }
+}
+
+/// Recompute the tree a checkpoint commits to, checking its root
+pub fn rebuild(checkpoint: &Checkpoint, reader: &EventReader) -> Result<MerkleTree, CheckpointError> {
+    let mut hashes = Vec::new();
+    for entry in reader.iter_from(checkpoint.from_sequence)? {
+        let entry = entry?;
+        if entry.sequence > checkpoint.to_sequence {
+            break;
+        }
+        hashes.push(entry.hash);
+    }
+
+    let tree = MerkleTree::new(checkpoint.from_sequence, hashes)
+        .filter(|tree| tree.to_sequence() == checkpoint.to_sequence)
+        .ok_or(CheckpointError::EntryMissing(checkpoint.to_sequence))?;
+    if tree.root() != checkpoint.root {
+        return Err(CheckpointError::RootMismatch {
+            from: checkpoint.from_sequence,
+            to: checkpoint.to_sequence,
+        });
+    }
+    Ok(tree)
+}
+
+fn check_trusted(
+    checkpoint: &Checkpoint,
+    reader: &EventReader,
+    trusts: impl Fn(&EntrySignature, u64) -> bool,
+) -> Result<(), CheckpointError> {
+    checkpoint.verify_signature()?;
+    if let Some(signature) = &checkpoint.signature {
+        if !trusts(signature, checkpoint.to_sequence) {
+            return Err(CheckpointError::UnknownSigner(signature.public_key.clone()));
+        }
+    }
+
+    let entry = reader
+        .iter_from(checkpoint.to_sequence)?
+        .next()
+        .transpose()?
+        .filter(|entry| entry.sequence == checkpoint.to_sequence)
+        .ok_or(CheckpointError::EntryMissing(checkpoint.to_sequence))?;
+    if entry.hash != checkpoint.last_hash {
+        return Err(CheckpointError::HashMismatch {
+            sequence: checkpoint.to_sequence,
+            expected: entry.hash,
+            actual: checkpoint.last_hash.clone(),
+        });
+    }
+    Ok(())
+}
+
+/// Checkpoint errors
+#[derive(Debug, thiserror::Error)]
+pub enum CheckpointError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Event store error: {0}")]
+    Event(#[from] EventError),
+
+    #[error("Checkpoint signature invalid: {0}")]
+    Signature(#[from] LedgerError),
+
+    #[error("Checkpoint signed by unknown key {0}")]
+    UnknownSigner(String),
+
+    #[error("Sequence {0} is not covered by a checkpoint yet")]
+    NotCheckpointed(u64),
+
+    #[error("Journal has no entry at sequence {0}")]
+    EntryMissing(u64),
+
+    #[error("Checkpoint hash mismatch at sequence {sequence}: journal has {expected}, checkpoint has {actual}")]
+    HashMismatch {
+        sequence: u64,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Merkle root of entries {from}..{to} doesn't match the checkpoint")]
+    RootMismatch { from: u64, to: u64 },
+}
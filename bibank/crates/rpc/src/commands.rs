@@ -1,12 +1,29 @@
 //! CLI commands
 
-use bibank_core::Amount;
-use bibank_ledger::{AccountCategory, AccountKey, JournalEntryBuilder, TransactionIntent, validate_intent};
-use bibank_matching::{MatchingEngine, Order, OrderSide, TradingPair};
+use bibank_approval::PendingApproval;
+use bibank_compliance::{ApprovalLevel, Case, ReportFormat, ReportKind, ReviewDecision, UserRecord, UserStatus};
+use bibank_core::{Amount, Currency, RoundingMode};
+use bibank_dsl::RuleParser;
+use bibank_events::EventReader;
+use bibank_ledger::{
+    validate_intent, AccountCategory, AccountKey, AsOf, JournalEntry, JournalEntryBuilder,
+    OperatorSigner, Signer, SystemSigner, TransactionIntent, UnsignedEntry,
+};
+use bibank_matching::{
+    Candle, CandleInterval, Fill, MatchResult, Order, OrderBook, OrderBookDepth, OrderId, OrderSide,
+    OrderType, TradingPair,
+};
+use bibank_risk::{
+    InterestCalculator, LiabilityProof, LiquidationEngine, ReservesSummary, RiskState, DEFAULT_QUOTE_ASSET,
+};
+use chrono::{DateTime, NaiveDate, Utc};
 use rust_decimal::Decimal;
 use serde_json::json;
+use std::path::Path;
 
-use crate::context::AppContext;
+use crate::context::{AppContext, CommitError};
+use crate::scheduler::{self, JobReport};
+use crate::screening;
 
 /// Initialize the system with Genesis entry
 pub async fn init(ctx: &mut AppContext, correlation_id: &str) -> Result<(), anyhow::Error> {
@@ -40,7 +57,7 @@ pub async fn deposit(
     amount: Decimal,
     asset: &str,
     correlation_id: &str,
-) -> Result<(), anyhow::Error> {
+) -> Result<JournalEntry, anyhow::Error> {
     let amount = Amount::new(amount)?;
 
     let entry = JournalEntryBuilder::new()
@@ -56,7 +73,7 @@ pub async fn deposit(
         "✅ Deposited {} {} to {} (seq: {})",
         amount, asset, user_id, committed.sequence
     );
-    Ok(())
+    Ok(committed)
 }
 
 /// Transfer funds between users
@@ -67,7 +84,7 @@ pub async fn transfer(
     amount: Decimal,
     asset: &str,
     correlation_id: &str,
-) -> Result<(), anyhow::Error> {
+) -> Result<JournalEntry, anyhow::Error> {
     let amount = Amount::new(amount)?;
 
     let entry = JournalEntryBuilder::new()
@@ -83,7 +100,7 @@ pub async fn transfer(
         "✅ Transferred {} {} from {} to {} (seq: {})",
         amount, asset, from_user, to_user, committed.sequence
     );
-    Ok(())
+    Ok(committed)
 }
 
 /// Withdraw funds from a user
@@ -93,7 +110,7 @@ pub async fn withdraw(
     amount: Decimal,
     asset: &str,
     correlation_id: &str,
-) -> Result<(), anyhow::Error> {
+) -> Result<JournalEntry, anyhow::Error> {
     let amount = Amount::new(amount)?;
 
     let entry = JournalEntryBuilder::new()
@@ -109,7 +126,53 @@ pub async fn withdraw(
         "✅ Withdrew {} {} from {} (seq: {})",
         amount, asset, user_id, committed.sequence
     );
-    Ok(())
+    Ok(committed)
+}
+
+/// Manually adjust a user's balance (parked for multi-sig approval)
+///
+/// A positive amount credits the user, a negative one debits; the other
+/// side is `EQUITY:SYSTEM:ADJUSTMENT:<ASSET>:MAIN`.
+pub async fn adjust(
+    ctx: &mut AppContext,
+    user_id: &str,
+    amount: Decimal,
+    asset: &str,
+    reason: &str,
+    correlation_id: &str,
+) -> Result<JournalEntry, anyhow::Error> {
+    let value = Amount::new(amount.abs())?;
+    let user = AccountKey::user_available(user_id, asset);
+    let adjustment = AccountKey::new(AccountCategory::Equity, "SYSTEM", "ADJUSTMENT", asset, "MAIN");
+    let (debit, credit) = if amount.is_sign_negative() {
+        (user, adjustment)
+    } else {
+        (adjustment, user)
+    };
+
+    let entry = JournalEntryBuilder::new()
+        .intent(TransactionIntent::Adjustment)
+        .correlation_id(correlation_id)
+        .debit(debit, value)
+        .credit(credit, value)
+        .metadata("reason", json!(reason))
+        .build_unsigned()?;
+
+    let committed = ctx.commit(entry).await?;
+
+    println!(
+        "✅ Adjusted {} by {} {} (seq: {})",
+        user_id, amount, asset, committed.sequence
+    );
+    Ok(committed)
+}
+
+/// Reverse a committed entry (parked for multi-sig approval)
+pub async fn reverse(ctx: &mut AppContext, sequence: u64, reason: &str) -> Result<JournalEntry, anyhow::Error> {
+    let committed = ctx.reverse(sequence, reason).await?;
+
+    println!("✅ Reversed entry {} (seq: {})", sequence, committed.sequence);
+    Ok(committed)
 }
 
 /// Get balance for a user
@@ -136,6 +199,7 @@ pub async fn balance(ctx: &AppContext, user_id: &str) -> Result<(), anyhow::Erro
 /// Execute a trade between two users
 ///
 /// Alice sells `sell_amount` of `sell_asset` and buys `buy_amount` of `buy_asset` from Bob.
+#[allow(clippy::too_many_arguments)]
 pub async fn trade(
     ctx: &mut AppContext,
     maker: &str,        // Alice - the one selling
@@ -145,7 +209,7 @@ pub async fn trade(
     buy_amount: Decimal,
     buy_asset: &str,
     correlation_id: &str,
-) -> Result<(), anyhow::Error> {
+) -> Result<JournalEntry, anyhow::Error> {
     let sell_amt = Amount::new(sell_amount)?;
     let buy_amt = Amount::new(buy_amount)?;
 
@@ -181,7 +245,7 @@ pub async fn trade(
         "✅ Trade executed: {} sells {} {} for {} {} from {} (seq: {})",
         maker, sell_amount, sell_asset, buy_amount, buy_asset, taker, committed.sequence
     );
-    Ok(())
+    Ok(committed)
 }
 
 /// Charge a fee from a user
@@ -192,7 +256,7 @@ pub async fn fee(
     asset: &str,
     fee_type: &str,  // "trading", "withdrawal", etc.
     correlation_id: &str,
-) -> Result<(), anyhow::Error> {
+) -> Result<JournalEntry, anyhow::Error> {
     let amount = Amount::new(amount)?;
 
     // Fee account: REV:SYSTEM:FEE:<ASSET>:<FEE_TYPE>
@@ -223,10 +287,11 @@ pub async fn fee(
         "✅ Fee charged: {} {} {} from {} (seq: {})",
         amount, asset, fee_type, user_id, committed.sequence
     );
-    Ok(())
+    Ok(committed)
 }
 
 /// Execute a trade with fee (atomic: Trade + Fee entries)
+#[allow(clippy::too_many_arguments)]
 pub async fn trade_with_fee(
     ctx: &mut AppContext,
     maker: &str,
@@ -237,9 +302,9 @@ pub async fn trade_with_fee(
     buy_asset: &str,
     fee_amount: Decimal,
     correlation_id: &str,
-) -> Result<(), anyhow::Error> {
+) -> Result<(JournalEntry, JournalEntry), anyhow::Error> {
     // First execute the trade
-    trade(
+    let trade_entry = trade(
         ctx, maker, taker,
         sell_amount, sell_asset,
         buy_amount, buy_asset,
@@ -248,9 +313,9 @@ pub async fn trade_with_fee(
 
     // Then charge the fee (separate entry, atomic in business sense)
     let fee_correlation = format!("{}-fee", correlation_id);
-    fee(ctx, maker, fee_amount, sell_asset, "trading", &fee_correlation).await?;
+    let fee_entry = fee(ctx, maker, fee_amount, sell_asset, "trading", &fee_correlation).await?;
 
-    Ok(())
+    Ok((trade_entry, fee_entry))
 }
 
 // === Phase 2.1: Trade History ===
@@ -298,8 +363,107 @@ pub async fn trades(
             trade.buy_amount,
             trade.buy_asset,
         );
+        if let Some(reversal) = trade.reversed_by {
+            println!("{:>6}   ↳ reversed (seq: {})", "", reversal);
+        }
+    }
+
+    Ok(())
+}
+
+/// OHLCV candles for a pair opening in `[from, to)`
+pub async fn load_candles(
+    ctx: &AppContext,
+    pair: &TradingPair,
+    interval: CandleInterval,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> Result<Vec<Candle>, anyhow::Error> {
+    let Some(ref projection) = ctx.projection else {
+        anyhow::bail!("Projection not available");
+    };
+    if from > to {
+        anyhow::bail!("Candle range is empty: {} is after {}", from, to);
     }
+    Ok(projection.candle.get_range(pair, interval, from, to).await?)
+}
+
+/// Print OHLCV candles for a pair
+#[allow(clippy::too_many_arguments)]
+pub async fn candles(
+    ctx: &AppContext,
+    base: &str,
+    quote: &str,
+    interval: CandleInterval,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    format: &str,
+    output: Option<&Path>,
+) -> Result<(), anyhow::Error> {
+    let format = statement_format(format)?;
+    let pair = TradingPair::new(base, quote);
+    let candles = load_candles(ctx, &pair, interval, from, to).await?;
+
+    let content = match format {
+        "csv" => {
+            let mut out = String::from("open_time,open,high,low,close,volume,quote_volume,trades\n");
+            for c in &candles {
+                out += &format!(
+                    "{},{},{},{},{},{},{},{}\n",
+                    c.open_time.to_rfc3339(),
+                    c.open,
+                    c.high,
+                    c.low,
+                    c.close,
+                    c.volume,
+                    c.quote_volume,
+                    c.trades
+                );
+            }
+            out
+        }
+        "json" => serde_json::to_string_pretty(&candles)? + "\n",
+        _ => {
+            let mut out = format!("{} {} candles ({}):\n", pair, interval, candles.len());
+            out += &format!("{:-<100}\n", "");
+            out += &format!(
+                "{:<20} | {:>12} {:>12} {:>12} {:>12} | {:>12} {:>6}\n",
+                "Open time", "Open", "High", "Low", "Close", "Volume", "Trades"
+            );
+            out += &format!("{:-<100}\n", "");
+            for c in &candles {
+                out += &format!(
+                    "{:<20} | {:>12} {:>12} {:>12} {:>12} | {:>12} {:>6}\n",
+                    c.open_time.format("%Y-%m-%d %H:%M"),
+                    c.open,
+                    c.high,
+                    c.low,
+                    c.close,
+                    c.volume,
+                    c.trades
+                );
+            }
+            out
+        }
+    };
+    write_statement(&content, output, "Candles")
+}
 
+/// Show the maker/taker rates a user would pay on a pair
+pub async fn fee_preview(ctx: &AppContext, user_id: &str, base: &str, quote: &str) -> Result<(), anyhow::Error> {
+    let pair = TradingPair::new(base, quote);
+    let rate = ctx.fee_rate(user_id, &pair).await;
+    let percent = |rate: Decimal| format!("{}%", (rate * Decimal::ONE_HUNDRED).normalize());
+
+    println!("Fees for {} on {}", user_id.to_uppercase(), pair);
+    println!("   30-day volume: {} {}", rate.volume.normalize(), rate.volume_asset);
+    match rate.tier {
+        Some(tier) => println!("   Tier {}: maker {}, taker {}", tier, percent(rate.maker_rate), percent(rate.taker_rate)),
+        None => println!("   No fee tiers apply"),
+    }
+    if let Some(next) = rate.next_tier_volume {
+        println!("   Next tier at {} {}", next.normalize(), rate.volume_asset);
+    }
     Ok(())
 }
 
@@ -307,48 +471,36 @@ pub async fn trades(
 
 /// Borrow funds (margin trading)
 ///
-/// Creates a loan by crediting user's available balance from system loan pool.
+/// Creates a loan by crediting user's available balance against a loan receivable.
+/// Rejected if the portfolio, valued through the price oracle, would fall
+/// below the initial margin.
 pub async fn borrow(
     ctx: &mut AppContext,
     user_id: &str,
     amount: Decimal,
     asset: &str,
     correlation_id: &str,
-) -> Result<(), anyhow::Error> {
-    let amount = Amount::new(amount)?;
+) -> Result<JournalEntry, anyhow::Error> {
+    // Margin check on the whole portfolio (retries resolve in commit)
+    if ctx.committed_entry(correlation_id)?.is_none() {
+        ctx.risk
+            .check_borrow(user_id, asset, amount, ctx.oracle.as_ref(), DEFAULT_QUOTE_ASSET)
+            .await
+            .map_err(CommitError::Risk)?;
+    }
 
-    // LIAB:USER:<USER_ID>:<ASSET>:LOAN - tracks user's loan obligation
-    let loan_account = AccountKey::new(
-        AccountCategory::Liability,
-        "USER",
-        user_id,
-        asset,
-        "LOAN",
-    );
+    let amount = Amount::new(amount)?;
 
-    // ASSET:SYSTEM:LENDING_POOL:<ASSET>:MAIN - system lending pool
-    let lending_pool = AccountKey::new(
-        AccountCategory::Asset,
-        "SYSTEM",
-        "LENDING_POOL",
-        asset,
-        "MAIN",
-    );
+    // ASSET:USER:<USER_ID>:<ASSET>:LOAN - BiBank's receivable from the user
+    let loan_account = RiskState::loan_account(user_id, asset);
 
     let entry = JournalEntryBuilder::new()
         .intent(TransactionIntent::Borrow)
         .correlation_id(correlation_id)
-        // Debit lending pool (reduce system's lending funds)
-        .debit(lending_pool, amount)
-        // Credit loan liability (increase user's loan obligation)
-        .credit(loan_account, amount)
+        // Debit loan receivable (increase user's loan obligation)
+        .debit(loan_account, amount)
         // Credit user's available balance
         .credit(AccountKey::user_available(user_id, asset), amount)
-        // Debit a receivable (to maintain balance)
-        .debit(
-            AccountKey::new(AccountCategory::Asset, "SYSTEM", "LOANS_RECEIVABLE", asset, "MAIN"),
-            amount,
-        )
         .metadata("loan_amount", json!(amount.to_string()))
         .metadata("loan_asset", json!(asset))
         .metadata("borrower", json!(user_id))
@@ -362,7 +514,7 @@ pub async fn borrow(
         "✅ Borrowed {} {} for {} (seq: {})",
         amount, asset, user_id, committed.sequence
     );
-    Ok(())
+    Ok(committed)
 }
 
 /// Repay borrowed funds
@@ -372,39 +524,18 @@ pub async fn repay(
     amount: Decimal,
     asset: &str,
     correlation_id: &str,
-) -> Result<(), anyhow::Error> {
+) -> Result<JournalEntry, anyhow::Error> {
     let amount = Amount::new(amount)?;
 
-    let loan_account = AccountKey::new(
-        AccountCategory::Liability,
-        "USER",
-        user_id,
-        asset,
-        "LOAN",
-    );
-
-    let lending_pool = AccountKey::new(
-        AccountCategory::Asset,
-        "SYSTEM",
-        "LENDING_POOL",
-        asset,
-        "MAIN",
-    );
+    let loan_account = RiskState::loan_account(user_id, asset);
 
     let entry = JournalEntryBuilder::new()
         .intent(TransactionIntent::Repay)
         .correlation_id(correlation_id)
         // Debit user's available balance (reduce funds)
         .debit(AccountKey::user_available(user_id, asset), amount)
-        // Credit lending pool (return to system)
-        .credit(lending_pool, amount)
-        // Debit loan liability (reduce user's loan obligation)
-        .debit(loan_account, amount)
-        // Credit receivable (reduce system's receivable)
-        .credit(
-            AccountKey::new(AccountCategory::Asset, "SYSTEM", "LOANS_RECEIVABLE", asset, "MAIN"),
-            amount,
-        )
+        // Credit loan receivable (reduce user's loan obligation)
+        .credit(loan_account, amount)
         .metadata("repay_amount", json!(amount.to_string()))
         .metadata("repay_asset", json!(asset))
         .metadata("borrower", json!(user_id))
@@ -418,12 +549,21 @@ pub async fn repay(
         "✅ Repaid {} {} for {} (seq: {})",
         amount, asset, user_id, committed.sequence
     );
-    Ok(())
+    Ok(committed)
 }
 
-/// Place a limit order
+/// Place an order
 ///
 /// Locks collateral and submits order to matching engine.
+/// Each fill is settled as a Trade entry out of the LOCKED accounts.
+/// Any quantity the order type cancels instead of resting (IOC, FOK,
+/// market, post-only) is released with an OrderCancel entry.
+/// For market orders `price` is ignored: collateral is locked at the
+/// slippage-protected limit derived from the book.
+/// Retrying a placed `correlation_id` settles whatever the first attempt
+/// left unsettled and returns the original order's ID.
+/// Returns the new order's ID.
+#[allow(clippy::too_many_arguments)]
 pub async fn place_order(
     ctx: &mut AppContext,
     user_id: &str,
@@ -432,8 +572,9 @@ pub async fn place_order(
     quote: &str,
     price: Decimal,
     quantity: Decimal,
+    order_type: OrderType,
     correlation_id: &str,
-) -> Result<(), anyhow::Error> {
+) -> Result<OrderId, anyhow::Error> {
     let order_side = match side.to_lowercase().as_str() {
         "buy" => OrderSide::Buy,
         "sell" => OrderSide::Sell,
@@ -442,18 +583,60 @@ pub async fn place_order(
 
     let pair = TradingPair::new(base, quote);
 
+    // Quantities are in the base asset and prices in the quote asset, so
+    // neither may be finer than that asset's minimum unit (market orders
+    // derive their price below)
+    Amount::of(quantity, &Currency::from(pair.base.as_str()))?;
+    if !matches!(order_type, OrderType::Market { .. }) {
+        Amount::of(price, &Currency::from(pair.quote.as_str()))?;
+    }
+    ctx.matching.add_pair(pair.clone());
+
+    // A retry reuses the original order id (and market lock price) so its
+    // lock entry matches the committed one
+    let original = ctx
+        .committed_entry(correlation_id)?
+        .filter(|entry| entry.intent == TransactionIntent::OrderPlace);
+    let original_meta = |key: &str| {
+        original
+            .as_ref()
+            .and_then(|entry| entry.metadata.get(key))
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+    };
+
+    // Market orders lock at their protective limit
+    let price = match (order_type, original_meta("price")) {
+        (OrderType::Market { .. }, Some(locked)) => locked.parse()?,
+        (OrderType::Market { max_slippage }, None) => ctx
+            .matching
+            .get_book(&pair)
+            .and_then(|book| book.slippage_limit(order_side, max_slippage))
+            .ok_or_else(|| anyhow::anyhow!("No liquidity on {} for market order", pair))?,
+        _ => price,
+    };
+
     // Calculate lock amount based on side
     let (lock_asset, lock_amount) = match order_side {
-        OrderSide::Buy => (quote.to_uppercase(), price * quantity), // Lock quote (USDT)
+        OrderSide::Buy => (quote.to_uppercase(), quote_amount(quote, price, quantity)), // Lock quote (USDT)
         OrderSide::Sell => (base.to_uppercase(), quantity),          // Lock base (BTC)
     };
 
     let lock_amt = Amount::new(lock_amount)?;
 
-    // Create order (get order ID)
-    let order = Order::new(user_id, pair.clone(), order_side, price, quantity);
+    // Create order (get order ID) - user ids are upper-cased like ledger accounts
+    let order = match original_meta("order_id") {
+        Some(id) => Order::with_id(id, user_id.to_uppercase(), pair.clone(), order_side, price, quantity),
+        None => Order::new(user_id.to_uppercase(), pair.clone(), order_side, price, quantity),
+    }
+    .with_order_type(order_type);
     let order_id = order.id.clone();
 
+    // Reject bad orders (self-trade, invalid price) before locking anything
+    if original.is_none() {
+        ctx.matching.validate_order(&order)?;
+    }
+
     // Create journal entry to lock collateral
     let entry = JournalEntryBuilder::new()
         .intent(TransactionIntent::OrderPlace)
@@ -464,6 +647,7 @@ pub async fn place_order(
         .credit(AccountKey::user_locked(user_id, &lock_asset), lock_amt)
         .metadata("order_id", json!(order_id))
         .metadata("order_side", json!(side.to_lowercase()))
+        .metadata("order_type", json!(order_type.to_string()))
         .metadata("base_asset", json!(base.to_uppercase()))
         .metadata("quote_asset", json!(quote.to_uppercase()))
         .metadata("price", json!(price.to_string()))
@@ -476,11 +660,32 @@ pub async fn place_order(
 
     let committed = ctx.commit(entry).await?;
 
-    // TODO: Submit order to matching engine (ctx.matching_engine)
-    // For now, just print success
+    // Identical retry: commit returned the original lock. A crash after the
+    // lock leaves the order restored on the book with only the fills that
+    // made it to the journal; settle the rest now.
+    if original.is_some() {
+        println!(
+            "↩️  Order already placed: {} (seq: {})",
+            order_id, committed.sequence
+        );
+
+        let before = ctx.depth(&pair);
+        if let Some(order) = take_unsettled(ctx, &pair, &order_id) {
+            let settled = (1..)
+                .take_while(|i| {
+                    let fill_correlation = format!("{}-fill-{}", correlation_id, i);
+                    ctx.correlations().get(&fill_correlation).is_some()
+                })
+                .count();
+            println!("   ↻ Resuming settlement after {} fill(s)", settled);
+            match_and_settle(ctx, order, &before, correlation_id, settled + 1).await?;
+        }
+        return Ok(order_id);
+    }
 
     println!(
-        "✅ Order placed: {} {} {} @ {} {} (order_id: {}, seq: {})",
+        "✅ Order placed: {} {} {} {} @ {} {} (order_id: {}, seq: {})",
+        order_type.to_string().to_uppercase(),
         side.to_uppercase(),
         quantity,
         base.to_uppercase(),
@@ -489,30 +694,591 @@ pub async fn place_order(
         order_id,
         committed.sequence
     );
-    Ok(())
+
+    let before = ctx.depth(&pair);
+    match_and_settle(ctx, order, &before, correlation_id, 1).await?;
+
+    Ok(order_id)
+}
+
+/// Take a retried order off the book if its first attempt left it unsettled
+///
+/// A replayed order still needs matching if it crosses the book, or if its
+/// type never rests and its remainder was never released. Market orders
+/// resume as IOC at the limit they locked at.
+fn take_unsettled(ctx: &mut AppContext, pair: &TradingPair, order_id: &str) -> Option<Order> {
+    let book = ctx.matching.get_book(pair)?;
+    let order = book.get_order(order_id)?.clone();
+    if order.order_type.rests_on_book() && !book.would_cross(order.side, order.price) {
+        return None;
+    }
+
+    ctx.matching.cancel_order(pair, order_id).ok()?;
+    Some(match order.order_type {
+        OrderType::Market { .. } => order.with_order_type(OrderType::ImmediateOrCancel),
+        _ => order,
+    })
+}
+
+/// Submit an order whose lock is committed and settle the result
+///
+/// Fills are numbered from `first_fill`, so a resumed order continues after
+/// the fills its first attempt committed. `before` is the book depth to
+/// publish changes against. If a settlement entry fails to commit, the book
+/// is put back in step with the journal (see [`abort_match`]).
+async fn match_and_settle(
+    ctx: &mut AppContext,
+    order: Order,
+    before: &OrderBookDepth,
+    correlation_id: &str,
+    first_fill: usize,
+) -> Result<MatchResult, anyhow::Error> {
+    let pair = order.pair.clone();
+    let saved_book = ctx
+        .matching
+        .get_book(&pair)
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("No order book for {}", pair))?;
+
+    // Submit to matching engine and settle fills
+    let result = ctx.matching.place_order(order.clone())?;
+    ctx.publish_depth(before).await;
+
+    for (i, fill) in result.fills.iter().enumerate() {
+        let fill_correlation = format!("{}-fill-{}", correlation_id, first_fill + i);
+        let fees = fill_fees(ctx, fill).await;
+        let entry = fill_entry(fill, order.price, fees, correlation_id, &fill_correlation);
+        let committed = match settle(ctx, entry).await {
+            Ok(committed) => committed,
+            Err(e) => {
+                abort_match(ctx, saved_book, order, &result.fills[..i], correlation_id, true).await;
+                return Err(e);
+            }
+        };
+
+        println!(
+            "   ⚡ Filled {} {} @ {} {} ({} ← {}, seq: {})",
+            fill.quantity,
+            pair.base,
+            fill.price,
+            pair.quote,
+            fill.buyer_id(),
+            fill.seller_id(),
+            committed.sequence
+        );
+    }
+
+    // Release the lock behind any quantity the order type cancelled.
+    // The order never rested, so the book has nothing to remove.
+    if result.is_cancelled() {
+        let cancel_correlation = format!("{}-cancel", correlation_id);
+        let entry = unlock_entry(
+            &order.id,
+            &order.user_id,
+            &pair,
+            order.side,
+            order.price,
+            result.cancelled_quantity,
+            &cancel_correlation,
+        );
+        let committed = match settle(ctx, entry).await {
+            Ok(committed) => committed,
+            Err(e) => {
+                abort_match(ctx, saved_book, order, &result.fills, correlation_id, false).await;
+                return Err(e);
+            }
+        };
+
+        println!(
+            "   ✖ {} {} cancelled ({}, seq: {})",
+            result.cancelled_quantity, pair.base, order.order_type, committed.sequence
+        );
+    }
+
+    if result.fully_filled {
+        println!("   ✅ Order fully filled");
+    } else if result.resting_quantity() > Decimal::ZERO && !result.fills.is_empty() {
+        println!("   📖 {} {} resting on book", result.resting_quantity(), pair.base);
+    }
+
+    Ok(result)
+}
+
+/// Validate and commit a settlement entry
+async fn settle(
+    ctx: &mut AppContext,
+    entry: Result<UnsignedEntry, anyhow::Error>,
+) -> Result<JournalEntry, anyhow::Error> {
+    let entry = entry?;
+    validate_intent(&entry)?;
+    Ok(ctx.commit(entry).await?)
+}
+
+/// Put the book back in step with the journal after settlement failed
+///
+/// The book returns to `saved` with only the `settled` fills applied. When
+/// `release` is set the taker's unfilled quantity is unlocked; if that can't
+/// be committed either, the taker rests as a replay of the journal would
+/// leave it, so a retry resumes settlement.
+async fn abort_match(
+    ctx: &mut AppContext,
+    saved: OrderBook,
+    mut order: Order,
+    settled: &[Fill],
+    correlation_id: &str,
+    release: bool,
+) {
+    let pair = order.pair.clone();
+    let before = ctx.depth(&pair);
+
+    ctx.matching.restore_book(saved);
+    for fill in settled {
+        ctx.matching.apply_fill(&pair, &fill.maker_order_id, fill.quantity).ok();
+        order.fill(fill.quantity);
+    }
+
+    let released = release && {
+        let cancel_correlation = format!("{}-cancel", correlation_id);
+        let entry = unlock_entry(
+            &order.id,
+            &order.user_id,
+            &pair,
+            order.side,
+            order.price,
+            order.remaining(),
+            &cancel_correlation,
+        );
+        match settle(ctx, entry).await {
+            Ok(committed) => {
+                println!(
+                    "   ✖ {} {} released after failed settlement (seq: {})",
+                    order.remaining(),
+                    pair.base,
+                    committed.sequence
+                );
+                true
+            }
+            Err(e) => {
+                tracing::error!("Failed to release {} after failed settlement: {}", order.id, e);
+                false
+            }
+        }
+    };
+    if !released {
+        ctx.matching.restore_order(order).ok();
+    }
+
+    ctx.publish_depth(&before).await;
+}
+
+/// Quote amount of `quantity` at `price`, rounded down to the quote's scale
+///
+/// Buy locks, fill payments, refunds and unlocks all round down, so together
+/// they never exceed what the order locked. The remainder stays LOCKED until
+/// the dust sweep collects it.
+pub fn quote_amount(quote: &str, price: Decimal, quantity: Decimal) -> Decimal {
+    Currency::from(quote).round(price * quantity, RoundingMode::Floor)
+}
+
+/// Build the OrderCancel entry releasing the lock behind `quantity` of an order
+///
+/// Mirrors what place_order locked: quote at the order price for buys,
+/// base for sells.
+fn unlock_entry(
+    order_id: &str,
+    user_id: &str,
+    pair: &TradingPair,
+    side: OrderSide,
+    price: Decimal,
+    quantity: Decimal,
+    correlation_id: &str,
+) -> Result<UnsignedEntry, anyhow::Error> {
+    let (unlock_asset, unlock_amount) = match side {
+        OrderSide::Buy => (pair.quote.clone(), quote_amount(&pair.quote, price, quantity)),
+        OrderSide::Sell => (pair.base.clone(), quantity),
+    };
+    let unlock_amt = Amount::new(unlock_amount)?;
+
+    let entry = JournalEntryBuilder::new()
+        .intent(TransactionIntent::OrderCancel)
+        .correlation_id(correlation_id)
+        // Debit locked balance
+        .debit(AccountKey::user_locked(user_id, &unlock_asset), unlock_amt)
+        // Credit back to available balance
+        .credit(AccountKey::user_available(user_id, &unlock_asset), unlock_amt)
+        .metadata("order_id", json!(order_id))
+        .metadata("base_asset", json!(pair.base))
+        .metadata("quote_asset", json!(pair.quote))
+        .metadata("unlock_asset", json!(unlock_asset))
+        .metadata("unlock_amount", json!(unlock_amount.to_string()))
+        .build_unsigned()?;
+
+    Ok(entry)
+}
+
+/// Fee rates of a fill's maker and taker
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FillFees {
+    pub maker_rate: Decimal,
+    pub taker_rate: Decimal,
+}
+
+/// Fee rates of a fill's maker and taker, tiered by their 30-day volumes
+pub async fn fill_fees(ctx: &AppContext, fill: &Fill) -> FillFees {
+    FillFees {
+        maker_rate: ctx.fee_rate(&fill.maker_user_id, &fill.pair).await.maker_rate,
+        taker_rate: ctx.fee_rate(&fill.taker_user_id, &fill.pair).await.taker_rate,
+    }
+}
+
+/// Build the Trade entry settling a single fill
+///
+/// Seller's LOCKED base goes to the buyer, buyer's LOCKED quote goes to the
+/// seller. When a buy taker fills below its limit, the excess quote locked at
+/// `taker_limit` is released back to the buyer in the same entry.
+/// Fees come out of what each side receives - base for the buyer, quote for
+/// the seller - rounded down and credited to the trading fee accounts.
+pub fn fill_entry(
+    fill: &Fill,
+    taker_limit: Decimal,
+    fees: FillFees,
+    causality_id: &str,
+    correlation_id: &str,
+) -> Result<UnsignedEntry, anyhow::Error> {
+    let base = &fill.pair.base;
+    let quote = &fill.pair.quote;
+    let buyer = fill.buyer_id();
+    let seller = fill.seller_id();
+
+    let base_amt = Amount::new(fill.quantity)?;
+    let notional = quote_amount(quote, fill.price, fill.quantity);
+    let quote_amt = Amount::new(notional)?;
+
+    let (buyer_rate, seller_rate) = match fill.taker_side {
+        OrderSide::Buy => (fees.taker_rate, fees.maker_rate),
+        OrderSide::Sell => (fees.maker_rate, fees.taker_rate),
+    };
+    let buyer_fee = Currency::from(base.as_str()).round(fill.quantity * buyer_rate, RoundingMode::Floor);
+    let seller_fee = Currency::from(quote.as_str()).round(notional * seller_rate, RoundingMode::Floor);
+
+    let mut builder = JournalEntryBuilder::new()
+        .intent(TransactionIntent::Trade)
+        .correlation_id(correlation_id)
+        .causality_id(causality_id)
+        // Base leg: seller's locked base → buyer
+        .debit(AccountKey::user_locked(seller, base), base_amt)
+        .credit(AccountKey::user_available(buyer, base), Amount::new(fill.quantity - buyer_fee)?)
+        // Quote leg: buyer's locked quote → seller
+        .debit(AccountKey::user_locked(buyer, quote), quote_amt)
+        .credit(AccountKey::user_available(seller, quote), Amount::new(notional - seller_fee)?);
+
+    // Price improvement: buy taker locked at its limit, paid the maker's price
+    // (an improvement under the quote's minimum unit is left as dust)
+    let refund = quote_amount(quote, taker_limit - fill.price, fill.quantity);
+    if fill.taker_side == OrderSide::Buy && refund > Decimal::ZERO {
+        let refund = Amount::new(refund)?;
+        builder = builder
+            .debit(AccountKey::user_locked(buyer, quote), refund)
+            .credit(AccountKey::user_available(buyer, quote), refund);
+    }
+
+    if buyer_fee > Decimal::ZERO {
+        builder = builder.credit(AccountKey::trading_fee(base), Amount::new(buyer_fee)?);
+    }
+    if seller_fee > Decimal::ZERO {
+        builder = builder.credit(AccountKey::trading_fee(quote), Amount::new(seller_fee)?);
+    }
+    if buyer_fee > Decimal::ZERO || seller_fee > Decimal::ZERO {
+        let (maker_fee, taker_fee) = match fill.taker_side {
+            OrderSide::Buy => ((seller_fee, quote), (buyer_fee, base)),
+            OrderSide::Sell => ((buyer_fee, base), (seller_fee, quote)),
+        };
+        builder = builder
+            .metadata("maker_fee", json!(maker_fee.0.normalize().to_string()))
+            .metadata("maker_fee_asset", json!(maker_fee.1))
+            .metadata("taker_fee", json!(taker_fee.0.normalize().to_string()))
+            .metadata("taker_fee_asset", json!(taker_fee.1));
+    }
+
+    let entry = builder
+        .metadata("trade_id", json!(fill.id))
+        .metadata("base_asset", json!(base))
+        .metadata("quote_asset", json!(quote))
+        .metadata("price", json!(fill.price.to_string()))
+        .metadata("base_amount", json!(fill.quantity.to_string()))
+        .metadata("quote_amount", json!(notional.to_string()))
+        .metadata("maker", json!(fill.maker_user_id))
+        .metadata("taker", json!(fill.taker_user_id))
+        .metadata("maker_order_id", json!(fill.maker_order_id))
+        .metadata("taker_order_id", json!(fill.taker_order_id))
+        .metadata("taker_side", json!(fill.taker_side.to_string()))
+        .build_unsigned()?;
+
+    Ok(entry)
 }
 
 /// Cancel an open order
+///
+/// Looks up the resting order in the matching engine and unlocks
+/// whatever collateral still backs its remaining quantity.
+/// Retrying a cancel's `correlation_id` returns the original OrderCancel.
 pub async fn cancel_order(
     ctx: &mut AppContext,
     order_id: &str,
     base: &str,
     quote: &str,
     correlation_id: &str,
-) -> Result<(), anyhow::Error> {
-    // For now, we need to know the lock details from the order
-    // In a real implementation, we'd look this up from the matching engine
+) -> Result<JournalEntry, anyhow::Error> {
+    let pair = TradingPair::new(base, quote);
+
+    // The order is gone from the book after the first attempt
+    if let Some(original) = ctx.committed_entry(correlation_id)? {
+        let same_order = original.intent == TransactionIntent::OrderCancel
+            && original.metadata.get("order_id").and_then(|v| v.as_str()) == Some(order_id);
+        if !same_order {
+            return Err(CommitError::CorrelationConflict {
+                correlation_id: correlation_id.to_string(),
+                sequence: original.sequence,
+            }
+            .into());
+        }
+        println!(
+            "↩️  Order already cancelled: {} (seq: {})",
+            order_id, original.sequence
+        );
+        return Ok(original);
+    }
+
+    let Some(order) = ctx.matching.get_order(&pair, order_id) else {
+        anyhow::bail!("Order not found: {} on {}", order_id, pair);
+    };
+
+    // Remaining lock mirrors what place_order locked for the unfilled part
+    let user_id = order.user_id.clone();
+    let entry = unlock_entry(
+        order_id,
+        &user_id,
+        &pair,
+        order.side,
+        order.price,
+        order.remaining(),
+        correlation_id,
+    )?;
+    let unlock = entry.postings[0].clone();
+
+    validate_intent(&entry)?;
+
+    let committed = ctx.commit(entry).await?;
+
+    // Only drop from the book once the unlock is in the journal
+    let before = ctx.depth(&pair);
+    ctx.matching.cancel_order(&pair, order_id)?;
+    ctx.publish_depth(&before).await;
+
+    println!(
+        "✅ Order cancelled: {} (unlocked {} {} for {}, seq: {})",
+        order_id, unlock.amount, unlock.account.asset, user_id, committed.sequence
+    );
+    Ok(committed)
+}
+
+/// Snapshot the current state
+pub fn snapshot_create(ctx: &mut AppContext) -> Result<(), anyhow::Error> {
+    if !ctx.is_initialized() {
+        anyhow::bail!("Nothing to snapshot: journal is empty");
+    }
+
+    let path = ctx.snapshot()?;
+    println!(
+        "✅ Snapshot at sequence {} written to {}",
+        ctx.last_sequence(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// List snapshots with their tags
+pub fn snapshot_list(ctx: &AppContext) -> Result<(), anyhow::Error> {
+    let files = ctx.snapshots.list()?;
+    if files.is_empty() {
+        println!("No snapshots in {}", ctx.snapshots.dir().display());
+        return Ok(());
+    }
+
+    println!(
+        "{:>10} {:<20} {:>9} {:>7}  HASH",
+        "SEQUENCE", "CREATED", "ACCOUNTS", "ORDERS"
+    );
+    println!("{:-<80}", "");
+    for path in files {
+        match ctx.snapshots.load(&path) {
+            Ok(s) => println!(
+                "{:>10} {:<20} {:>9} {:>7}  {}",
+                s.sequence,
+                s.created_at.format("%Y-%m-%d %H:%M:%S"),
+                s.balances.len(),
+                s.open_orders.len(),
+                &s.hash[..s.hash.len().min(16)]
+            ),
+            Err(e) => println!("❌ {}: {}", path.display(), e),
+        }
+    }
+    Ok(())
+}
+
+/// List durable subscribers with their lag behind the journal
+pub fn subscriber_list(ctx: &AppContext) -> Result<(), anyhow::Error> {
+    let checkpoints = ctx.subscriber_checkpoints()?;
+    if checkpoints.is_empty() {
+        println!("No durable subscribers in {}", ctx.subscribers.dir().display());
+        return Ok(());
+    }
+
+    let head = ctx.last_sequence();
+    println!("{:<20} {:>10} {:>8} {:>6}  UPDATED", "SUBSCRIBER", "SEQUENCE", "LAG", "DEAD");
+    println!("{:-<70}", "");
+    for c in checkpoints {
+        println!(
+            "{:<20} {:>10} {:>8} {:>6}  {}",
+            c.subscriber,
+            c.last_sequence,
+            c.lag(head),
+            c.dead_letters,
+            c.updated_at.format("%Y-%m-%d %H:%M:%S")
+        );
+    }
+    Ok(())
+}
+
+/// List the entries a subscriber gave up on
+pub fn subscriber_dead_letters(ctx: &AppContext, name: &str) -> Result<(), anyhow::Error> {
+    let letters = ctx.subscribers.dead_letters(name)?;
+    if letters.is_empty() {
+        println!("No dead letters for {}", name);
+        return Ok(());
+    }
+
+    for letter in letters {
+        println!(
+            "❌ seq {} ({:?}, {}) after {} attempts at {}: {}",
+            letter.sequence,
+            letter.entry.intent,
+            letter.entry.correlation_id,
+            letter.attempts,
+            letter.failed_at.format("%Y-%m-%d %H:%M:%S"),
+            letter.error
+        );
+    }
+    Ok(())
+}
+
+/// Rotate the system key, saving the new key to `output`
+pub fn keygen_rotate(ctx: &mut AppContext, output: &Path) -> Result<(), anyhow::Error> {
+    let old_key = ctx
+        .signer
+        .as_ref()
+        .map(|s| s.public_key_hex())
+        .ok_or(crate::keys::KeyStoreError::NoSigner)?;
+    let next = SystemSigner::generate();
+    let seed = next.seed_hex();
+    let new_key = next.public_key_hex();
+
+    // Save the key before the registry hands over to it
+    std::fs::write(output, &seed)?;
+    ctx.rotate_system_key(std::sync::Arc::new(next))?;
+
+    println!("✅ Rotated system key from sequence {}", ctx.last_sequence() + 1);
+    println!("   Old key: {}", old_key);
+    println!("   New key: {}", new_key);
+    println!("   Private key saved to: {}", output.display());
+    println!();
+    println!("To use: export BIBANK_SYSTEM_KEY={}", seed);
+    Ok(())
+}
+
+/// List signer keys and the sequences they are valid for
+pub fn keys_list(ctx: &AppContext) -> Result<(), anyhow::Error> {
+    let registry = ctx.keys.registry()?;
+    if registry.is_empty() {
+        println!("No keys in {}", ctx.keys.path().display());
+        return Ok(());
+    }
+
+    println!("{:<12} {:>8} {:>8}  {:<18} ENDED", "SIGNER", "FROM", "UNTIL", "PUBLIC KEY");
+    println!("{:-<80}", "");
+    for key in registry.keys() {
+        println!(
+            "{:<12} {:>8} {:>8}  {:<18} {}",
+            key.signer_id,
+            key.valid_from,
+            key.valid_until.map_or("-".to_string(), |s| s.to_string()),
+            format!("{}…", &key.public_key[..16.min(key.public_key.len())]),
+            key.ended_by.as_deref().unwrap_or("active")
+        );
+    }
+    Ok(())
+}
+
+/// Verify a snapshot (latest if no sequence) against the journal
+pub fn snapshot_verify(ctx: &AppContext, sequence: Option<u64>) -> Result<(), anyhow::Error> {
+    let snapshot = match sequence {
+        Some(sequence) => ctx.snapshots.find(sequence)?,
+        None => {
+            let Some(path) = ctx.snapshots.list()?.pop() else {
+                anyhow::bail!("No snapshots in {}", ctx.snapshots.dir().display());
+            };
+            ctx.snapshots.load(&path)?
+        }
+    };
 
-    // TODO: Look up order from matching engine
-    // let order = ctx.matching_engine.get_order(&pair, order_id)?;
+    let reader = EventReader::from_directory(ctx.journal_path())?;
+    let verified = snapshot.verify(&reader)?;
 
-    // For demonstration, we'll require the user to provide unlock info
-    // In reality, this would be looked up from projection/matching engine
+    println!(
+        "✅ Snapshot at sequence {} verified (hash chain of {} entries, balances and open orders match replay)",
+        snapshot.sequence, verified
+    );
+    Ok(())
+}
+
+/// Checkpoint the entries since the last checkpoint
+pub fn checkpoint_create(ctx: &mut AppContext) -> Result<(), anyhow::Error> {
+    match ctx.checkpoint()? {
+        Some(c) => {
+            println!("✅ Checkpoint {}..{}", c.from_sequence, c.to_sequence);
+            println!("   Root: {}", c.root);
+            if c.signature.is_none() {
+                println!("   ⚠️  Unsigned (BIBANK_SYSTEM_KEY not set)");
+            }
+        }
+        None => println!("No entries since the last checkpoint"),
+    }
+    Ok(())
+}
 
-    println!("⚠️  Order cancellation requires order lookup (not yet implemented)");
-    println!("   Order ID: {}", order_id);
-    println!("   Pair: {}/{}", base.to_uppercase(), quote.to_uppercase());
+/// List checkpoints
+pub fn checkpoint_list(ctx: &AppContext) -> Result<(), anyhow::Error> {
+    let checkpoints = ctx.checkpoints.list()?;
+    println!("{:>10} {:>10} {:<20} {:<16} Signed", "From", "To", "Created", "Root");
+    println!("{:-<70}", "");
+    for c in checkpoints {
+        println!(
+            "{:>10} {:>10} {:<20} {:<16} {}",
+            c.from_sequence,
+            c.to_sequence,
+            c.created_at.format("%Y-%m-%d %H:%M:%S"),
+            &c.root[..16],
+            if c.verify_signature().is_ok() { "yes" } else { "no" }
+        );
+    }
+    Ok(())
+}
 
+/// Print an inclusion proof for an entry, with its checkpoint
+pub fn checkpoint_prove(ctx: &AppContext, sequence: u64) -> Result<(), anyhow::Error> {
+    let (proof, checkpoint) = ctx.inclusion_proof(sequence)?;
+    let output = json!({ "proof": proof, "checkpoint": checkpoint });
+    println!("{}", serde_json::to_string_pretty(&output)?);
     Ok(())
 }
 
@@ -541,14 +1307,7 @@ pub async fn margin_status(ctx: &AppContext, user_id: &str) -> Result<(), anyhow
     println!("\n💰 Loans:");
     let mut has_loans = false;
     for asset in ["USDT", "BTC", "ETH"] {
-        let loan_account = AccountKey::new(
-            AccountCategory::Liability,
-            "USER",
-            user_id,
-            asset,
-            "LOAN",
-        );
-        let loan = state.get_balance(&loan_account);
+        let loan = state.get_loan_balance(user_id, asset);
 
         if !loan.is_zero() {
             println!("   {}: {}", asset, loan);
@@ -560,19 +1319,33 @@ pub async fn margin_status(ctx: &AppContext, user_id: &str) -> Result<(), anyhow
         println!("   No active loans");
     }
 
-    // Show margin ratio (simplified)
-    let usdt_balance = state.get_balance(&AccountKey::user_available(user_id, "USDT"));
-    let usdt_loan = state.get_balance(&AccountKey::new(
-        AccountCategory::Liability,
-        "USER",
-        user_id,
-        "USDT",
-        "LOAN",
-    ));
+    // Show margin ratio across all assets, valued through the oracle
+    let valuation = match ctx.risk.value(user_id, ctx.oracle.as_ref(), DEFAULT_QUOTE_ASSET).await {
+        Ok(valuation) => valuation,
+        Err(e) => {
+            println!("\n⚠️  Cannot value portfolio: {}", e);
+            return Ok(());
+        }
+    };
+
+    println!("\n💱 Valuation ({}):", valuation.quote);
+    for (asset, position) in &valuation.positions {
+        println!(
+            "   {}: price={}, haircut={}%, collateral={:.2}, loan={:.2}",
+            asset,
+            position.price,
+            position.haircut * Decimal::from(100),
+            position.collateral_value(),
+            position.loan_value()
+        );
+    }
 
-    if !usdt_loan.is_zero() {
-        let margin_ratio = (usdt_balance / usdt_loan) * Decimal::from(100);
-        println!("\n📈 Margin Ratio (USDT): {:.2}%", margin_ratio);
+    if !valuation.loans.is_zero() {
+        let margin_ratio = valuation.margin_ratio() * Decimal::from(100);
+        println!(
+            "\n📈 Margin Ratio: {:.2}% (collateral {:.2} / loans {:.2} {})",
+            margin_ratio, valuation.collateral, valuation.loans, valuation.quote
+        );
 
         if margin_ratio < Decimal::from(120) {
             println!("   ⚠️  WARNING: Below maintenance margin (120%)");
@@ -586,6 +1359,683 @@ pub async fn margin_status(ctx: &AppContext, user_id: &str) -> Result<(), anyhow
     Ok(())
 }
 
+/// Run the daily interest accrual job
+pub async fn interest_accrual(
+    ctx: &mut AppContext,
+    date: NaiveDate,
+    dry_run: bool,
+) -> Result<(), anyhow::Error> {
+    let report = scheduler::run_interest_accrual(ctx, &InterestCalculator::new(), date, dry_run).await?;
+    print_job_report(&report);
+    Ok(())
+}
+
+/// Run a liquidation sweep over all borrowers
+pub async fn liquidation_sweep(
+    ctx: &mut AppContext,
+    liquidator: &str,
+    dry_run: bool,
+) -> Result<(), anyhow::Error> {
+    let report =
+        scheduler::run_liquidation_sweep(ctx, &LiquidationEngine::default(), liquidator, dry_run).await?;
+    print_job_report(&report);
+    Ok(())
+}
+
+/// Sweep rounding residue from LOCKED balances into the dust accounts
+pub async fn dust_sweep(ctx: &mut AppContext, dry_run: bool) -> Result<(), anyhow::Error> {
+    let report = scheduler::run_dust_sweep(ctx, dry_run).await?;
+    print_job_report(&report);
+    Ok(())
+}
+
+/// Compile a rule file against the registered predicates
+pub fn rules_check(ctx: &AppContext, path: &Path) -> Result<(), anyhow::Error> {
+    let source = std::fs::read_to_string(path)?;
+    let rule_set = match RuleParser::new(&source).with_predicates(ctx.rules.predicates()).parse() {
+        Ok(rule_set) => rule_set,
+        Err(e) => anyhow::bail!("{}:{}", path.display(), e),
+    };
+
+    println!("✅ Rule set {} {} ({} rules)", rule_set.name, rule_set.version, rule_set.rules.len());
+    println!("   Hash: {}", rule_set.hash());
+    if !rule_set.approved_by.is_empty() {
+        println!("   Approved by: {}", rule_set.approved_by.join(", "));
+    }
+    Ok(())
+}
+
+/// Activate the data directory's rule file if it changed
+pub async fn rules_reload(ctx: &mut AppContext, performed_by: &str) -> Result<(), anyhow::Error> {
+    let reload = ctx.reload_rules(performed_by).await?;
+    match (&reload.hash, reload.changed) {
+        (Some(hash), true) => println!("✅ Activated {} ({} rules, {})", reload.name, reload.rules, hash),
+        (Some(hash), false) => println!("Rule set {} unchanged ({})", reload.name, hash),
+        (None, true) => println!("✅ Rule set deactivated (no {})", screening::RULES_FILE),
+        (None, false) => println!("No {} in the data directory", screening::RULES_FILE),
+    }
+    Ok(())
+}
+
+/// List review cases
+pub async fn case_list(ctx: &AppContext, level: Option<&str>, all: bool) -> Result<(), anyhow::Error> {
+    let level: Option<ApprovalLevel> = level
+        .map(|l| serde_json::from_value(json!(l.to_lowercase())))
+        .transpose()
+        .map_err(|_| anyhow::anyhow!("Approval level must be one of l1, l2, l3, l4"))?;
+    let cases = ctx.cases().await?;
+    let cases: Vec<&Case> = if all {
+        cases.all().filter(|c| level.is_none_or(|l| c.required_approval == l)).collect()
+    } else {
+        cases.open(level)
+    };
+
+    println!("Review cases{}", if all { "" } else { " (open)" });
+    println!("{:-<60}", "");
+    if cases.is_empty() {
+        println!("   None");
+    }
+    for case in cases {
+        println!(
+            "   {} {:?} {:?} {} {} - {}",
+            case.flag_id, case.status, case.required_approval, case.user_id, case.correlation_id, case.reason
+        );
+        println!(
+            "      expires {}, assigned to {}",
+            case.expires_at.format("%Y-%m-%d %H:%M"),
+            case.assigned_to.as_deref().unwrap_or("-")
+        );
+    }
+    Ok(())
+}
+
+/// Assign a review case
+pub async fn case_assign(
+    ctx: &mut AppContext,
+    flag_id: &str,
+    reviewer: &str,
+    assigned_by: &str,
+) -> Result<(), anyhow::Error> {
+    let case = ctx.assign_case(flag_id, reviewer, assigned_by).await?;
+    println!("✅ Assigned {} ({:?}) to {}", case.flag_id, case.required_approval, reviewer);
+    Ok(())
+}
+
+/// Approve or reject a review case
+pub async fn case_resolve(
+    ctx: &mut AppContext,
+    flag_id: &str,
+    decision: ReviewDecision,
+    reviewer: &str,
+    notes: &str,
+) -> Result<(), anyhow::Error> {
+    let (case, entry) = ctx.resolve_case(flag_id, decision, reviewer, notes).await?;
+    println!("✅ Case {} {:?} by {}", case.flag_id, case.status, reviewer);
+    if let Some(entry) = entry {
+        println!("   Sequence: {} ({})", entry.sequence, entry.correlation_id);
+    }
+    Ok(())
+}
+
+/// Escalate overdue review cases
+pub async fn case_escalate(ctx: &mut AppContext) -> Result<(), anyhow::Error> {
+    let escalated = ctx.escalate_cases(chrono::Utc::now()).await?;
+    if escalated.is_empty() {
+        println!("No cases to escalate");
+    }
+    for case in escalated {
+        println!("⚠️  Escalated {} to {:?}", case.flag_id, case.required_approval);
+    }
+    Ok(())
+}
+
+/// Register a user with a KYC tier
+pub async fn user_register(
+    ctx: &mut AppContext,
+    user_id: &str,
+    kyc_tier: u8,
+    onboarded_at: NaiveDate,
+    performed_by: &str,
+) -> Result<(), anyhow::Error> {
+    let user = ctx.register_user(user_id, kyc_tier, onboarded_at, performed_by).await?;
+    println!("✅ Registered {} (KYC tier {}, onboarded {})", user.user_id, user.kyc_tier, user.onboarded_at);
+    Ok(())
+}
+
+/// Change a user's KYC tier
+pub async fn user_set_tier(
+    ctx: &mut AppContext,
+    user_id: &str,
+    kyc_tier: u8,
+    reason: &str,
+    performed_by: &str,
+) -> Result<(), anyhow::Error> {
+    let user = ctx.set_user_tier(user_id, kyc_tier, reason, performed_by).await?;
+    println!("✅ {} is now KYC tier {}", user.user_id, user.kyc_tier);
+    Ok(())
+}
+
+/// Freeze, unfreeze or close a user
+///
+/// Freezing or closing first cancels the user's resting orders while their
+/// locks can still be released; a frozen maker could not settle a fill.
+pub async fn user_set_status(
+    ctx: &mut AppContext,
+    user_id: &str,
+    status: UserStatus,
+    reason: &str,
+    performed_by: &str,
+) -> Result<UserRecord, anyhow::Error> {
+    let user_id = user_id.to_uppercase();
+    let open = ctx
+        .users()
+        .get(&user_id)
+        .is_some_and(|user| user.status != UserStatus::Closed);
+
+    if open && status.blocks_debits() {
+        cancel_user_orders(ctx, &user_id, &status.to_string()).await?;
+    }
+
+    let user = ctx.set_user_status(&user_id, status, reason, performed_by).await?;
+    println!("✅ {} is now {}", user.user_id, user.status);
+    Ok(user)
+}
+
+/// Cancel every resting order of a user, unlocking the funds behind them
+///
+/// Each cancel's correlation id is `<prefix>-cancel-<order_id>`. Returns the
+/// number of orders cancelled.
+pub(crate) async fn cancel_user_orders(
+    ctx: &mut AppContext,
+    user_id: &str,
+    prefix: &str,
+) -> Result<usize, anyhow::Error> {
+    let resting: Vec<(TradingPair, OrderId)> = ctx
+        .matching
+        .resting_orders()
+        .into_iter()
+        .filter(|order| order.user_id == user_id)
+        .map(|order| (order.pair.clone(), order.id.clone()))
+        .collect();
+    for (pair, order_id) in &resting {
+        let correlation_id = format!("{}-cancel-{}", prefix, order_id);
+        cancel_order(ctx, order_id, &pair.base, &pair.quote, &correlation_id).await?;
+    }
+    Ok(resting.len())
+}
+
+/// Show one registered user, or all of them
+pub async fn users(ctx: &AppContext, user_id: Option<&str>) -> Result<(), anyhow::Error> {
+    let Some(ref projection) = ctx.projection else {
+        anyhow::bail!("Projection not available");
+    };
+
+    let users: Vec<UserRecord> = match user_id {
+        Some(user_id) => {
+            let user_id = user_id.to_uppercase();
+            let user = projection.user.get(&user_id).await?;
+            vec![user.ok_or_else(|| anyhow::anyhow!("User not found: {}", user_id))?]
+        }
+        None => projection.user.list().await?,
+    };
+
+    if users.is_empty() {
+        println!("No users registered");
+        return Ok(());
+    }
+
+    println!("{:<12} | {:>4} | {:<8} | {:<10} | Updated", "User", "Tier", "Status", "Onboarded");
+    println!("{:-<70}", "");
+    for user in users {
+        println!(
+            "{:<12} | {:>4} | {:<8} | {:<10} | {}",
+            user.user_id,
+            user.kyc_tier,
+            user.status.to_string(),
+            user.onboarded_at.to_string(),
+            user.updated_at.format("%Y-%m-%d %H:%M")
+        );
+    }
+    Ok(())
+}
+
+/// Generate a CTR or SAR report, to a file or stdout
+pub async fn report(
+    ctx: &AppContext,
+    kind: &str,
+    from: NaiveDate,
+    to: NaiveDate,
+    format: &str,
+    threshold: Option<Decimal>,
+    output: Option<&Path>,
+) -> Result<(), anyhow::Error> {
+    let kind: ReportKind = serde_json::from_value(json!(kind.to_lowercase()))
+        .map_err(|_| anyhow::anyhow!("Report type must be ctr or sar"))?;
+    let format: ReportFormat = serde_json::from_value(json!(format.to_lowercase()))
+        .map_err(|_| anyhow::anyhow!("Report format must be json or csv"))?;
+    if from > to {
+        anyhow::bail!("Report range is empty: {} is after {}", from, to);
+    }
+
+    let (content, input_hash, count) = match kind {
+        ReportKind::Ctr => {
+            let report = ctx.ctr_report(from, to, threshold).await?;
+            let content = match format {
+                ReportFormat::Json => serde_json::to_string_pretty(&report)? + "\n",
+                ReportFormat::Csv => report.to_csv(),
+            };
+            (content, report.input_hash, report.rows.len())
+        }
+        ReportKind::Sar => {
+            let report = ctx.sar_report(from, to).await?;
+            let content = match format {
+                ReportFormat::Json => serde_json::to_string_pretty(&report)? + "\n",
+                ReportFormat::Csv => report.to_csv(),
+            };
+            (content, report.input_hash, report.drafts.len())
+        }
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, &content)?;
+            println!(
+                "✅ {} report {}..{}: {} records written to {}",
+                kind.as_str().to_uppercase(),
+                from,
+                to,
+                count,
+                path.display()
+            );
+            println!("   Input hash: {}", input_hash);
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// Statement output format: table, csv or json
+fn statement_format(format: &str) -> Result<&'static str, anyhow::Error> {
+    match format.to_lowercase().as_str() {
+        "table" => Ok("table"),
+        "csv" => Ok("csv"),
+        "json" => Ok("json"),
+        _ => anyhow::bail!("Statement format must be table, csv or json"),
+    }
+}
+
+/// Print a statement, or write it to `output`
+fn write_statement(content: &str, output: Option<&Path>, name: &str) -> Result<(), anyhow::Error> {
+    match output {
+        Some(path) => {
+            std::fs::write(path, content)?;
+            println!("✅ {} written to {}", name, path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// Trial balance per asset
+///
+/// Fails after printing if debits and credits differ for any asset.
+pub fn trial_balance(
+    ctx: &AppContext,
+    as_of: Option<AsOf>,
+    format: &str,
+    output: Option<&Path>,
+) -> Result<(), anyhow::Error> {
+    let format = statement_format(format)?;
+    let tb = ctx.ledger_balances(as_of)?.trial_balance();
+
+    let content = match format {
+        "csv" => tb.to_csv(),
+        "json" => serde_json::to_string_pretty(&tb)? + "\n",
+        _ => {
+            let mut out = format!("Trial balance at sequence {}\n", tb.sequence);
+            for asset in &tb.assets {
+                out += &format!("\n{}\n{:<45} {:>20} {:>20}\n{:-<87}\n", asset.asset, "Account", "Debit", "Credit", "");
+                for line in &asset.lines {
+                    out += &format!("{:<45} {:>20} {:>20}\n", line.account, blank_zero(line.debit), blank_zero(line.credit));
+                }
+                out += &format!("{:<45} {:>20} {:>20}\n", "Total", asset.total_debit, asset.total_credit);
+            }
+            out
+        }
+    };
+    write_statement(&content, output, "Trial balance")?;
+    tb.check()?;
+    Ok(())
+}
+
+/// Balance sheet per asset
+///
+/// Fails after printing if assets differ from liabilities plus equity for
+/// any asset.
+pub fn balance_sheet(
+    ctx: &AppContext,
+    as_of: Option<AsOf>,
+    format: &str,
+    output: Option<&Path>,
+) -> Result<(), anyhow::Error> {
+    let format = statement_format(format)?;
+    let sheet = ctx.ledger_balances(as_of)?.balance_sheet();
+
+    let content = match format {
+        "csv" => sheet.to_csv(),
+        "json" => serde_json::to_string_pretty(&sheet)? + "\n",
+        _ => {
+            let mut out = format!("Balance sheet at sequence {}\n", sheet.sequence);
+            for s in &sheet.sheets {
+                out += &format!("\n{}\n{:-<66}\n", s.asset, "");
+                for (section, lines, total) in [
+                    ("Assets", &s.assets, s.total_assets),
+                    ("Liabilities", &s.liabilities, s.total_liabilities),
+                    ("Equity", &s.equity, s.total_equity),
+                ] {
+                    out += &format!("{}\n", section);
+                    for line in lines {
+                        out += &format!("  {:<43} {:>20}\n", line.account, line.amount);
+                    }
+                    out += &format!("  {:<43} {:>20}\n", format!("Total {}", section.to_lowercase()), total);
+                }
+                out += &format!("  {:<43} {:>20}\n", "Retained earnings", s.retained_earnings);
+                out += &format!(
+                    "{:<45} {:>20}\n",
+                    "Liabilities + equity",
+                    s.total_liabilities_and_equity()
+                );
+            }
+            out
+        }
+    };
+    write_statement(&content, output, "Balance sheet")?;
+    sheet.check()?;
+    Ok(())
+}
+
+/// Income statement per asset for `from..=to`
+pub fn income_statement(
+    ctx: &AppContext,
+    from: NaiveDate,
+    to: NaiveDate,
+    format: &str,
+    output: Option<&Path>,
+) -> Result<(), anyhow::Error> {
+    let format = statement_format(format)?;
+    if from > to {
+        anyhow::bail!("Statement period is empty: {} is after {}", from, to);
+    }
+    let statement = ctx.income_statement(from, to)?;
+
+    let content = match format {
+        "csv" => statement.to_csv(),
+        "json" => serde_json::to_string_pretty(&statement)? + "\n",
+        _ => {
+            let mut out = format!("Income statement {}..{}\n", from, to);
+            if statement.statements.is_empty() {
+                out += "   No revenue or expenses\n";
+            }
+            for s in &statement.statements {
+                out += &format!("\n{}\n{:-<46}\n", s.asset, "");
+                for (label, amount) in [
+                    ("Fee revenue", s.fee_revenue),
+                    ("Interest revenue", s.interest_revenue),
+                    ("Liquidation fees", s.liquidation_fees),
+                    ("Other revenue", s.other_revenue),
+                    ("Total revenue", s.total_revenue()),
+                    ("Expenses", s.expenses),
+                    ("Net income", s.net_income()),
+                ] {
+                    out += &format!("{:<25} {:>20}\n", label, amount);
+                }
+            }
+            out
+        }
+    };
+    write_statement(&content, output, "Income statement")
+}
+
+fn blank_zero(amount: Decimal) -> String {
+    if amount.is_zero() {
+        String::new()
+    } else {
+        amount.to_string()
+    }
+}
+
+/// Publish liability roots and totals, checked against the vault balances
+///
+/// With `output`, the summary goes to `<output>/reserves.json` and each
+/// user's inclusion proofs to `<output>/proofs/<USER>.json`.
+pub fn reserves_export(ctx: &AppContext, sequence: Option<u64>, output: Option<&Path>) -> Result<(), anyhow::Error> {
+    let reserves = ctx.proof_of_reserves(sequence)?;
+    let summary = reserves.summary();
+
+    if let Some(dir) = output {
+        let proofs_dir = dir.join("proofs");
+        std::fs::create_dir_all(&proofs_dir)?;
+        std::fs::write(dir.join("reserves.json"), serde_json::to_string_pretty(&summary)? + "\n")?;
+        let users = reserves.users();
+        for user in &users {
+            let proofs = json!({
+                "user": user,
+                "sequence": reserves.sequence(),
+                "proofs": reserves.proofs(user),
+            });
+            std::fs::write(
+                proofs_dir.join(format!("{}.json", user)),
+                serde_json::to_string_pretty(&proofs)? + "\n",
+            )?;
+        }
+        println!(
+            "✅ Reserves at sequence {} written to {} ({} user proofs)",
+            summary.sequence,
+            dir.display(),
+            users.len()
+        );
+    } else {
+        println!("Proof of reserves at sequence {}", summary.sequence);
+    }
+
+    println!(
+        "{:<8} {:>20} {:>20} {:>6} {:<16} Covered",
+        "Asset", "Liabilities", "Vault", "Users", "Root"
+    );
+    println!("{:-<88}", "");
+    for asset in &summary.assets {
+        println!(
+            "{:<8} {:>20} {:>20} {:>6} {:<16} {}",
+            asset.asset,
+            asset.total_liabilities,
+            asset.vault_balance,
+            asset.users,
+            asset.root.get(..16).unwrap_or("-"),
+            if asset.covered { "yes" } else { "NO" }
+        );
+    }
+    if !summary.is_covered() {
+        println!("⚠️  Vault balances don't cover user liabilities");
+    }
+    Ok(())
+}
+
+/// Print a user's liability inclusion proofs
+pub fn reserves_prove(ctx: &AppContext, user_id: &str, sequence: Option<u64>) -> Result<(), anyhow::Error> {
+    let reserves = ctx.proof_of_reserves(sequence)?;
+    let output = json!({
+        "user": user_id.to_uppercase(),
+        "sequence": reserves.sequence(),
+        "proofs": reserves.proofs(user_id),
+    });
+    println!("{}", serde_json::to_string_pretty(&output)?);
+    Ok(())
+}
+
+/// Verify a user's liability proofs offline, optionally against the published summary
+pub fn reserves_verify(proof_path: &Path, published: Option<&Path>) -> Result<(), anyhow::Error> {
+    #[derive(serde::Deserialize)]
+    struct ProofFile {
+        user: String,
+        sequence: u64,
+        proofs: Vec<LiabilityProof>,
+    }
+
+    let file: ProofFile = serde_json::from_str(&std::fs::read_to_string(proof_path)?)?;
+    let summary: Option<ReservesSummary> = match published {
+        Some(path) => Some(serde_json::from_str(&std::fs::read_to_string(path)?)?),
+        None => None,
+    };
+
+    let mut failures = 0;
+    for proof in &file.proofs {
+        let mut problems = Vec::new();
+        if proof.user_id != file.user.to_uppercase() || proof.sequence != file.sequence {
+            problems.push("proof is for another user or sequence".to_string());
+        }
+        if !proof.verify() {
+            problems.push("path doesn't lead to the stated root and total".to_string());
+        }
+        if let Some(summary) = &summary {
+            match summary.asset(&proof.asset) {
+                _ if summary.sequence != proof.sequence => {
+                    problems.push(format!("published summary is at sequence {}", summary.sequence))
+                }
+                Some(published) if published.root == proof.root && published.total_liabilities == proof.total => {}
+                Some(_) => problems.push("root or total differs from the published one".to_string()),
+                None => problems.push("asset is not in the published summary".to_string()),
+            }
+        }
+
+        if problems.is_empty() {
+            println!("✅ {} {} included (total {}, root {})", proof.asset, proof.balance, proof.total, proof.root);
+        } else {
+            failures += 1;
+            println!("⚠️  {} {}: {}", proof.asset, proof.balance, problems.join("; "));
+        }
+    }
+
+    if failures > 0 {
+        anyhow::bail!("{} of {} liability proofs failed", failures, file.proofs.len());
+    }
+    println!(
+        "✅ {} liability proofs for {} at sequence {} verified",
+        file.proofs.len(),
+        file.user.to_uppercase(),
+        file.sequence
+    );
+    Ok(())
+}
+
+fn print_job_report(report: &JobReport) {
+    let mode = if report.dry_run { " (dry run)" } else { "" };
+    println!("Job {} [{}]{}", report.job.as_str(), report.run_id, mode);
+    println!("{:-<50}", "");
+
+    if report.entries.is_empty() {
+        println!("   Nothing to do");
+    }
+    for entry in &report.entries {
+        let sequence = entry
+            .sequence
+            .map_or_else(|| "-".to_string(), |seq| format!("#{}", seq));
+        println!(
+            "   {} {} {} {} ({})",
+            sequence, entry.user, entry.amount, entry.asset, entry.correlation_id
+        );
+    }
+    if !report.skipped.is_empty() {
+        println!("   Skipped {} already committed", report.skipped.len());
+    }
+    for (target, reason) in &report.failed {
+        println!("   ❌ {}: {}", target, reason);
+    }
+}
+
+/// List approvals (pending only unless `all`)
+pub fn approval_list(ctx: &AppContext, all: bool) -> Result<(), anyhow::Error> {
+    let approvals = if all {
+        ctx.approvals.list_all()?
+    } else {
+        ctx.approvals.list_pending()?
+    };
+
+    if approvals.is_empty() {
+        println!("No approvals");
+        return Ok(());
+    }
+
+    for approval in approvals {
+        let entry = ctx.approvals.entry_of(&approval)?;
+        println!(
+            "{} [{}] {:?} {} - {}/{} signatures, expires {}",
+            approval.id,
+            approval.status.as_str(),
+            entry.intent,
+            entry.correlation_id,
+            approval.collected_signatures.len(),
+            approval.required_signatures,
+            approval.expires_at.format("%Y-%m-%d %H:%M:%S")
+        );
+        for posting in &entry.postings {
+            println!("   {:?} {} {}", posting.side, posting.account, posting.amount);
+        }
+        if !approval.collected_signatures.is_empty() {
+            println!("   Signed by: {}", approval.signers().join(", "));
+        }
+        if let Some(reason) = &approval.rejection_reason {
+            println!("   Reason: {}", reason);
+        }
+    }
+    Ok(())
+}
+
+/// Sign a parked entry with an approver key, committing it once approved
+pub async fn approval_sign(
+    ctx: &mut AppContext,
+    approval_id: &str,
+    signer: &OperatorSigner,
+) -> Result<Option<JournalEntry>, anyhow::Error> {
+    let approval = ctx.approvals.get_approval(approval_id)?;
+    let entry = ctx.approvals.entry_of(&approval)?;
+    let signature = signer.sign_approval(&entry);
+
+    let (approval, committed) = ctx.approve(approval_id, &signature).await?;
+    match &committed {
+        Some(entry) => println!(
+            "✅ {} approved by {} and committed (seq: {})",
+            approval.id,
+            approval.signers().join(", "),
+            entry.sequence
+        ),
+        None => println!(
+            "✅ Signed {} as {} ({} more signatures required)",
+            approval.id,
+            signer.signer_id(),
+            approval.signatures_remaining()
+        ),
+    }
+    Ok(committed)
+}
+
+/// Reject a parked entry, signed with an approver key
+pub fn reject_approval(
+    ctx: &AppContext,
+    approval_id: &str,
+    signer: &OperatorSigner,
+    reason: Option<&str>,
+) -> Result<PendingApproval, CommitError> {
+    let signature = signer.sign_rejection(approval_id, reason);
+    let approval = ctx.approvals.reject(approval_id, reason, &signature)?;
+
+    println!(
+        "❌ Rejected {}: {}",
+        approval.id,
+        approval.rejection_reason.as_deref().unwrap_or_default()
+    );
+    Ok(approval)
+}
+
 /// Show order book depth
 pub async fn order_book(
     ctx: &AppContext,
@@ -593,11 +2043,32 @@ pub async fn order_book(
     quote: &str,
     depth: usize,
 ) -> Result<(), anyhow::Error> {
-    // TODO: Get from ctx.matching_engine when integrated
-    println!("Order Book: {}/{}", base.to_uppercase(), quote.to_uppercase());
+    let pair = TradingPair::new(base, quote);
+
+    println!("Order Book: {}", pair);
     println!("{:-<60}", "");
-    println!("(Order book not yet integrated with context)");
-    println!("\nTo see order book, matching engine needs to be integrated.");
+
+    let Some(book) = ctx.matching.get_depth(&pair, depth) else {
+        println!("(No orders for this pair)");
+        return Ok(());
+    };
+
+    println!("{:>20} | {:>20}", "Price", "Quantity");
+    println!("{:-<60}", "");
+
+    // Asks highest first so the spread sits in the middle
+    for (price, qty) in book.asks.iter().rev() {
+        println!("{:>20} | {:>20}  ASK", price, qty);
+    }
+
+    match book.spread() {
+        Some(spread) => println!("{:-^60}", format!(" spread {} ", spread)),
+        None => println!("{:-<60}", ""),
+    }
+
+    for (price, qty) in &book.bids {
+        println!("{:>20} | {:>20}  BID", price, qty);
+    }
 
     Ok(())
 }
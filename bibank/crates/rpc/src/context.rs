@@ -1,13 +1,59 @@
 //! Application context - wires everything together
 
-use bibank_bus::EventBus;
-use bibank_events::{EventReader, EventStore};
-use bibank_ledger::{hash::calculate_entry_hash, JournalEntry, Signer, SystemSigner, UnsignedEntry};
+use bibank_approval::{ApprovalConfig, ApprovalError, ApprovalStore, ApprovalWorkflow, PendingApproval};
+use bibank_bus::{
+    BusError, EventBus, LedgerEvent, SubscriberCheckpoint, SubscriberStore, WebhookConfig, WebhookSubscriber,
+};
+use bibank_compliance::{
+    check_kyc_tier, Case, CaseBook, ComplianceEngine, ComplianceError, ComplianceEvent, CtrBuilder,
+    CtrReport, ReviewDecision, RuleAction, SarBuilder, SarReport, UserRecord, UserRegistry, UserStatus,
+};
+use bibank_core::Currency;
+use bibank_dsl::{RuleSet, RuleSetHook};
+use bibank_events::{EventReader, EventStore, StoreConfig};
+use bibank_hooks::{HookContext, HookDecision, TransactionExecutor};
+use bibank_ledger::{
+    hash::calculate_entry_hash, AsOf, Checkpoint, EntrySignature, IncomeStatement, IncomeStatementBuilder,
+    validate_intent, JournalEntry, KeyEvent, LedgerBalances, MerkleProof, MerkleTree, Side, Signer, SystemSigner,
+    AccountCategory, TransactionIntent, UnsignedEntry, REVERSAL_PREFIX,
+};
+use bibank_matching::{
+    FeeRate, FeeSchedule, MatchingEngine, Order, OrderBookDepth, OrderSide, OrderType, TradePrint,
+    TradingPair, FEE_VOLUME_DAYS,
+};
+use bibank_oracle::{HttpOracle, MedianOracle, MockOracle, PriceOracle, ReplayOracle};
 use bibank_projection::ProjectionEngine;
-use bibank_risk::{RiskEngine, RiskError};
-use chrono::Utc;
+use bibank_risk::{ProofOfReserves, RiskEngine, RiskError, RiskState};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
+use tokio::sync::RwLock;
+
+use crate::checkpoint::{self, CheckpointError, CheckpointStore, DEFAULT_CHECKPOINT_INTERVAL};
+use crate::idempotency::{self, CorrelationIndex, CorrelationLog, Lookup};
+use crate::keys::{self, KeyStore, KeyStoreError};
+use crate::screening::{self, ProjectedWithdrawals, RulesReload};
+use crate::snapshot::{
+    Snapshot, SnapshotError, SnapshotStore, DEFAULT_SNAPSHOT_INTERVAL, DEFAULT_SNAPSHOT_RETAIN,
+};
+
+/// Durable subscriber checkpoints and dead letters, in the data directory
+pub const SUBSCRIBERS_DIR: &str = "subscribers";
+
+/// Signer ID of the system key
+pub const SYSTEM_SIGNER: &str = "SYSTEM";
+
+/// Webhook endpoints, in the data directory (optional)
+pub const WEBHOOKS_FILE: &str = "webhooks.json";
+
+/// Decimal places of custom tokens, in the data directory (optional)
+pub const CURRENCIES_FILE: &str = "currencies.json";
+
+/// Maker/taker fee schedule, in the data directory (optional)
+pub const FEES_FILE: &str = "fees.json";
 
 /// Application context - wires together all components
 pub struct AppContext {
@@ -16,10 +62,45 @@ pub struct AppContext {
     pub bus: EventBus,
     pub projection: Option<ProjectionEngine>,
     pub signer: Option<Arc<dyn Signer>>,
+    pub matching: MatchingEngine,
+    pub snapshots: SnapshotStore,
+    /// Signed Merkle checkpoints over the journal
+    pub checkpoints: CheckpointStore,
+    /// Checkpoints and dead letters of durable bus subscribers
+    pub subscribers: SubscriberStore,
+    /// Signer key activations, rotations and revocations
+    pub keys: KeyStore,
+    /// Prices for valuing collateral and loans
+    pub oracle: Arc<dyn PriceOracle>,
+    /// Maker/taker fees charged on fills
+    pub fees: FeeSchedule,
+    /// Multi-sig approvals for Adjustments and large withdrawals/transfers
+    pub approvals: ApprovalWorkflow,
+    /// AML screening hooks, recording decisions in the Compliance Ledger
+    pub screening: TransactionExecutor,
+    /// Rules from the rule file, evaluated by the screening hooks
+    pub rules: Arc<RuleSetHook>,
+    rules_path: PathBuf,
+    /// Latest rule set activation recorded in the Compliance Ledger
+    active_rules: Option<ComplianceEvent>,
+    /// Registered users, folded from the Compliance Ledger
+    users: UserRegistry,
+    /// Committed correlation ids, for idempotent retries
+    correlations: CorrelationIndex,
+    /// On-disk copy of `correlations`; dropped if an append fails
+    correlation_log: Option<CorrelationLog>,
     journal_path: PathBuf,
     projection_path: PathBuf,
     last_sequence: u64,
     last_hash: String,
+    /// Entries between automatic snapshots (0 disables)
+    snapshot_interval: u64,
+    /// Sequence covered by the latest snapshot
+    snapshot_sequence: u64,
+    /// Entries between automatic checkpoints (0 disables)
+    checkpoint_interval: u64,
+    /// Last sequence covered by a checkpoint
+    checkpoint_sequence: u64,
 }
 
 impl AppContext {
@@ -28,34 +109,64 @@ impl AppContext {
         let data_path = data_path.as_ref();
         let journal_path = data_path.join("journal");
         let projection_path = data_path.join("projection.db");
+        let snapshots = SnapshotStore::new(data_path.join("snapshots"));
+        let checkpoints = CheckpointStore::new(data_path.join(checkpoint::CHECKPOINT_FILE));
+        let checkpoint_sequence = checkpoints.latest()?.map_or(0, |c| c.to_sequence);
+        let subscribers = SubscriberStore::new(data_path.join(SUBSCRIBERS_DIR));
 
         // Create directories
         std::fs::create_dir_all(&journal_path)?;
+        register_currencies(data_path)?;
 
-        // Initialize components
-        let event_store = EventStore::new(&journal_path)?;
-        let bus = EventBus::new(&journal_path);
+        // Initialize components (opening the store repairs a torn tail)
+        let store_config = StoreConfig::default().fsync(fsync_from_env());
+        let event_store = EventStore::open(&journal_path, store_config)?;
+        let mut bus = EventBus::new(&journal_path);
+        for endpoint in webhook_config(data_path)?.endpoints {
+            bus.subscribe_durable(WebhookSubscriber::new(endpoint).durable(subscribers.clone()));
+        }
         let mut risk = RiskEngine::new();
+        let mut matching = MatchingEngine::new();
 
-        // Replay events to rebuild state
         let reader = EventReader::from_directory(&journal_path)?;
-        let entries = reader.read_all()?;
+        let mut last_sequence = 0;
+        let mut last_hash = "GENESIS".to_string();
+        let correlation_log = CorrelationLog::new(data_path.join(idempotency::CORRELATION_LOG_FILE));
+        let (mut correlations, logged_sequence) = correlation_log.load(&reader)?;
 
-        let (last_sequence, last_hash) = if let Some(last) = entries.last() {
-            (last.sequence, last.hash.clone())
-        } else {
-            (0, "GENESIS".to_string())
-        };
+        // Start from the latest snapshot still matching the journal
+        if let Some(snapshot) = snapshots.latest_valid(&reader) {
+            snapshot.restore(&mut risk, &mut matching);
+            last_sequence = snapshot.sequence;
+            last_hash = snapshot.hash;
+        }
+        let snapshot_sequence = last_sequence;
+
+        // Stream the journal without holding it in memory, from the first
+        // entry missing from the snapshot or the correlation log
+        for entry in reader.iter_from(snapshot_sequence.min(logged_sequence) + 1)? {
+            let entry = entry?;
+            if entry.sequence > logged_sequence && correlations.record(&entry) {
+                correlation_log.append(&entry)?;
+            }
+            if entry.sequence <= snapshot_sequence {
+                continue;
+            }
 
-        // Rebuild risk state from events
-        risk.replay(entries.iter());
+            // Rebuild risk state and open orders
+            risk.apply(&entry);
+            replay_order_entry(&mut matching, &entry);
+
+            last_sequence = entry.sequence;
+            last_hash = entry.hash;
+        }
 
         // Initialize projection
         let projection = ProjectionEngine::new(&projection_path).await.ok();
 
-        // Replay projection if available
+        // Bring projection up to date with the journal (if available)
         if let Some(ref proj) = projection {
-            proj.replay(&bus).await.ok();
+            proj.catch_up(&bus).await.ok();
         }
 
         // Initialize system signer from env var (Phase 2)
@@ -64,29 +175,256 @@ impl AppContext {
             .and_then(|key| SystemSigner::from_hex(&key).ok())
             .map(|s| Arc::new(s) as Arc<dyn Signer>);
 
-        Ok(Self {
+        // The first system key is trusted from genesis; after that it must be the active one
+        let keys = KeyStore::new(data_path.join(keys::KEYS_FILE));
+        if let Some(ref signer) = signer {
+            let registry = keys.registry()?;
+            let public_key = signer.public_key_hex();
+            if !registry.knows(SYSTEM_SIGNER) {
+                keys.append(&KeyEvent::activated(SYSTEM_SIGNER, &public_key, 1))?;
+            } else if registry.active(SYSTEM_SIGNER).is_none_or(|k| k.public_key != public_key) {
+                return Err(KeyStoreError::InactiveSigner(public_key).into());
+            }
+        }
+
+        let oracle = oracle_from_env()?;
+        let approvals = ApprovalWorkflow::new(
+            ApprovalStore::new(data_path.join("approvals.db"))?,
+            approval_config_from_env()?,
+        );
+        let rules = Arc::new(RuleSetHook::new(
+            screening::RULES_PRIORITY,
+            RuleSet::default(),
+            screening::predicates(),
+        ));
+        let withdrawals = ProjectedWithdrawals(projection.as_ref().map(|p| p.user().clone()));
+        let screening = screening::executor(
+            &data_path.join(screening::LEDGER_FILE),
+            screening::config(data_path)?,
+            rules.clone(),
+            Arc::new(withdrawals),
+            oracle.clone(),
+        )?;
+        let (active_rules, users) = match screening.compliance_engine() {
+            Some(engine) => {
+                let engine = engine.read().await;
+                let users = UserRegistry::from_events(&engine.ledger().read_all()?);
+                (screening::active_rule_set(engine.ledger())?, users)
+            }
+            None => (None, UserRegistry::default()),
+        };
+
+        // The users table is a view of the Compliance Ledger: resync it
+        if let Some(ref proj) = projection {
+            proj.user().clear_users().await.ok();
+            for user in users.users() {
+                proj.user().upsert(user).await.ok();
+            }
+        }
+
+        let mut ctx = Self {
             risk,
             event_store,
             bus,
             projection,
             signer,
+            matching,
+            snapshots,
+            checkpoints,
+            subscribers,
+            keys,
+            oracle,
+            fees: fee_schedule(data_path)?,
+            approvals,
+            screening,
+            rules,
+            rules_path: data_path.join(screening::RULES_FILE),
+            active_rules,
+            users,
+            correlations,
+            correlation_log: Some(correlation_log),
             journal_path,
             projection_path,
             last_sequence,
             last_hash,
-        })
+            snapshot_interval: snapshot_interval_from_env(),
+            snapshot_sequence,
+            checkpoint_interval: checkpoint_interval_from_env(),
+            checkpoint_sequence,
+        };
+
+        // A rule file that doesn't parse fails startup rather than running without it
+        ctx.reload_rules("startup").await?;
+
+        // Replayed a long tail: snapshot now so the next startup is short
+        if let Err(e) = ctx.snapshot_if_due() {
+            tracing::warn!("Failed to write snapshot: {}", e);
+        }
+        if let Err(e) = ctx.checkpoint_if_due() {
+            tracing::warn!("Failed to write checkpoint: {}", e);
+        }
+
+        Ok(ctx)
     }
 
     /// Commit an unsigned entry
     ///
-    /// Flow: Risk Check → Sign → Append → Apply
+    /// Flow: Idempotency → Risk Check → Screening → Approval → Sign → Append
+    /// → Apply → Post-commit hooks
+    ///
+    /// Retrying a committed correlation_id with the same payload returns the
+    /// original entry without appending anything. Entries blocked by a
+    /// pre-validation hook are reported as [`CommitError::Blocked`]; entries
+    /// that need approval are parked and reported as
+    /// [`CommitError::PendingApproval`]. Flagged funds are moved into review
+    /// right after the entry is appended.
     pub async fn commit(&mut self, unsigned: UnsignedEntry) -> Result<JournalEntry, CommitError> {
-        // 1. Validate double-entry balance
+        self.commit_with(unsigned, Vec::new(), true).await
+    }
+
+    /// Reverse committed entry `sequence` with a mirrored entry
+    ///
+    /// Reversals always need multi-sig approval, so the entry is parked
+    /// ([`CommitError::PendingApproval`]). Entries whose funds have since been
+    /// spent fail the risk check, now and again when the approval completes.
+    /// Review entries are settled through their compliance case, and an
+    /// entry the case already reversed can't be reversed again.
+    pub async fn reverse(&mut self, sequence: u64, reason: &str) -> Result<JournalEntry, CommitError> {
+        let original = self.read_entry(sequence)?;
+        if !original.is_reversible() || screening::is_review_entry(&original.correlation_id) {
+            return Err(CommitError::NotReversible {
+                sequence,
+                intent: original.intent,
+            });
+        }
+        let reversals = [
+            format!("{}{}", REVERSAL_PREFIX, sequence),
+            format!("{}{}", screening::REVIEW_REVERSAL_PREFIX, original.correlation_id),
+        ];
+        if let Some(reversal) = reversals.iter().find_map(|id| self.correlations.get(id)) {
+            return Err(CommitError::AlreadyReversed {
+                sequence,
+                reversal_sequence: reversal.sequence,
+            });
+        }
+
+        let reversal = original.reversal(reason);
+        validate_intent(&reversal)?;
+        self.commit(reversal).await
+    }
+
+    /// Add an approver's signature to a parked entry
+    ///
+    /// Once enough approvers have signed, the entry is committed with their
+    /// signatures attached. If the commit fails, the approval is closed with
+    /// the reason.
+    pub async fn approve(
+        &mut self,
+        approval_id: &str,
+        signature: &EntrySignature,
+    ) -> Result<(PendingApproval, Option<JournalEntry>), CommitError> {
+        let (approval, approved) = self.approvals.approve(approval_id, signature)?;
+        if !approved {
+            return Ok((approval, None));
+        }
+
+        let unsigned = self.approvals.entry_of(&approval)?;
+        match self.commit_with(unsigned, approval.entry_signatures(), false).await {
+            Ok(entry) => Ok((approval, Some(entry))),
+            Err(e) => {
+                self.approvals.mark_failed(approval_id, &e.to_string())?;
+                Err(e)
+            }
+        }
+    }
+
+    async fn commit_with(
+        &mut self,
+        unsigned: UnsignedEntry,
+        approver_signatures: Vec<EntrySignature>,
+        check_approval: bool,
+    ) -> Result<JournalEntry, CommitError> {
+        // 0. Idempotency check on correlation_id
+        match self.correlations.check(&unsigned) {
+            Lookup::New => {}
+            Lookup::Retry(sequence) => return self.read_entry(sequence),
+            Lookup::Conflict(sequence) => {
+                return Err(CommitError::CorrelationConflict {
+                    correlation_id: unsigned.correlation_id,
+                    sequence,
+                })
+            }
+        }
+
+        // 1. Validate double-entry balance and each asset's precision
         unsigned.validate_balance().map_err(CommitError::Ledger)?;
+        unsigned.validate_scale().map_err(CommitError::Ledger)?;
+
+        // 1b. Frozen and closed users can't be debited
+        self.check_user_status(&unsigned)?;
 
         // 2. Risk check (pre-commit gatekeeper)
         self.risk.check(&unsigned).map_err(CommitError::Risk)?;
 
+        // 2b. AML pre-validation hooks, with the registered user's KYC tier and age
+        let hook_ctx = screening::hook_context(&unsigned).map(|ctx| match self.users.get(&ctx.user_id) {
+            Some(user) => {
+                let age = user.account_age_days(Utc::now().date_naive());
+                ctx.with_kyc_level(user.kyc_tier).with_account_age(age)
+            }
+            None => ctx,
+        });
+        if let Some(ref hook_ctx) = hook_ctx {
+            self.pre_validate(hook_ctx).await?;
+        }
+
+        // 2c. Park entries that need multi-sig approval
+        if check_approval && self.approvals.requires_approval(&unsigned) {
+            let approval = self.approvals.submit(&unsigned)?;
+            return Err(CommitError::PendingApproval {
+                approval_id: approval.id.clone(),
+                signatures_remaining: approval.signatures_remaining(),
+            });
+        }
+
+        let entry = self.append(unsigned, approver_signatures).await?;
+
+        // 10. Post-commit hooks: the entry stands, flagged funds go into review
+        if let Some(ref hook_ctx) = hook_ctx {
+            self.post_commit(&entry, hook_ctx).await;
+        }
+
+        Ok(entry)
+    }
+
+    /// Reject entries debiting a user whose status blocks debits
+    fn check_user_status(&self, unsigned: &UnsignedEntry) -> Result<(), CommitError> {
+        for posting in &unsigned.postings {
+            let account = &posting.account;
+            if posting.side != Side::Debit
+                || account.category != AccountCategory::Liability
+                || account.segment != "USER"
+            {
+                continue;
+            }
+            if let Some(user) = self.users.get(&account.id) {
+                if user.status.blocks_debits() {
+                    return Err(CommitError::UserFrozen {
+                        user_id: user.user_id.clone(),
+                        status: user.status,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Sign, append and apply a checked entry
+    async fn append(
+        &mut self,
+        unsigned: UnsignedEntry,
+        approver_signatures: Vec<EntrySignature>,
+    ) -> Result<JournalEntry, CommitError> {
         // 3. Sign the entry (add sequence, prev_hash, hash, timestamp)
         let sequence = self.last_sequence + 1;
         let prev_hash = self.last_hash.clone();
@@ -102,7 +440,7 @@ impl AppContext {
             causality_id: unsigned.causality_id,
             postings: unsigned.postings,
             metadata: unsigned.metadata,
-            signatures: Vec::new(), // Phase 2: Will be signed after hash calculation
+            signatures: approver_signatures, // System signature is added after hash calculation
         };
 
         entry.hash = calculate_entry_hash(&entry);
@@ -123,24 +461,514 @@ impl AppContext {
 
         // 7. Update risk state
         self.risk.apply(&entry);
+        self.record_correlation(&entry);
 
-        // 8. Update projection (if available)
+        // 8. Update projection (if available); on failure, catch up from its
+        // checkpoint so it doesn't silently fall behind the journal
         if let Some(ref projection) = self.projection {
-            projection.apply(&entry).await.ok();
+            if let Err(e) = projection.apply(&entry).await {
+                tracing::warn!("Projection failed at sequence {}: {}; resyncing", entry.sequence, e);
+                if let Err(e) = projection.catch_up(&self.bus).await {
+                    tracing::error!("Projection resync failed: {}", e);
+                }
+            }
         }
 
         // 9. Update last sequence/hash
         self.last_sequence = entry.sequence;
         self.last_hash = entry.hash.clone();
 
+        // 9b. Wake durable subscribers, print trades on the market data feed
+        self.bus.publish(LedgerEvent::entry_committed(entry.clone())).await.ok();
+        if let Some(trade) = TradePrint::from_entry(&entry) {
+            self.bus.publish(LedgerEvent::trade_printed(trade)).await.ok();
+        }
+
         Ok(entry)
     }
 
+    /// Full L2 depth of a pair's book (empty if the pair has no book)
+    pub fn depth(&self, pair: &TradingPair) -> OrderBookDepth {
+        self.matching
+            .get_depth(pair, usize::MAX)
+            .unwrap_or_else(|| OrderBookDepth {
+                pair: pair.clone(),
+                bids: Vec::new(),
+                asks: Vec::new(),
+            })
+    }
+
+    /// Publish the levels of a book that changed since `before`
+    pub async fn publish_depth(&self, before: &OrderBookDepth) {
+        let after = self.depth(&before.pair);
+        let changes = before.changes_to(&after);
+        if changes.is_empty() {
+            return;
+        }
+        let event = LedgerEvent::depth_updated(after.pair, changes, self.last_sequence);
+        self.bus.publish(event).await.ok();
+    }
+
+    /// Fee rates for a user on a pair, tiered by their 30-day traded volume
+    ///
+    /// Volume comes from the trade projection; without one, the user is
+    /// rated on zero volume.
+    pub async fn fee_rate(&self, user_id: &str, pair: &TradingPair) -> FeeRate {
+        let since = Utc::now() - chrono::Duration::days(FEE_VOLUME_DAYS);
+        let volume = match self.projection {
+            Some(ref projection) => projection
+                .trade
+                .user_volume(user_id, &self.fees.volume_asset, since)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("Failed to read trade volume of {}: {}", user_id, e);
+                    Decimal::ZERO
+                }),
+            None => Decimal::ZERO,
+        };
+        self.fees.rate(pair, volume)
+    }
+
+    /// Run pre-validation hooks, rejecting blocked entries
+    ///
+    /// A failing hook blocks the entry (fail closed).
+    async fn pre_validate(&self, hook_ctx: &HookContext) -> Result<(), CommitError> {
+        let decision = self
+            .screening
+            .pre_validate(hook_ctx)
+            .await
+            .unwrap_or_else(|e| HookDecision::block(e.to_string(), "HOOK_FAILURE"));
+
+        match decision {
+            HookDecision::Allow => Ok(()),
+            HookDecision::Block { reason, code } => Err(CommitError::Blocked {
+                correlation_id: hook_ctx.correlation_id.clone(),
+                reason,
+                code,
+            }),
+        }
+    }
+
+    /// Run post-commit hooks and lock flagged funds for review
+    ///
+    /// The entry is already in the journal, so failures are logged rather
+    /// than returned.
+    async fn post_commit(&mut self, entry: &JournalEntry, hook_ctx: &HookContext) {
+        let result = match self.screening.post_commit(hook_ctx).await {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::error!("Post-commit hooks failed for {}: {}", entry.correlation_id, e);
+                return;
+            }
+        };
+        if !result.lock_applied {
+            return;
+        }
+
+        tracing::warn!(
+            "{} flagged for review: {:?}",
+            entry.correlation_id,
+            result.rules_triggered
+        );
+        let Some(lock) = screening::review_lock_entry(entry, &result) else {
+            return;
+        };
+        if let Err(e) = self.commit_unscreened(lock).await {
+            tracing::error!("Failed to lock {} for review: {}", entry.correlation_id, e);
+        }
+    }
+
+    /// Commit a compliance entry: review lock, release or reversal
+    ///
+    /// Not screened and never parked for approval; the compliance decision
+    /// is the approval. Retries return the committed entry.
+    async fn commit_unscreened(&mut self, unsigned: UnsignedEntry) -> Result<JournalEntry, CommitError> {
+        if let Some(committed) = self.correlations.get(&unsigned.correlation_id) {
+            return self.read_entry(committed.sequence);
+        }
+        unsigned.validate_balance()?;
+        unsigned.validate_scale()?;
+        self.risk.check(&unsigned).map_err(CommitError::Risk)?;
+        self.append(unsigned, Vec::new()).await
+    }
+
+    fn compliance(&self) -> &Arc<RwLock<ComplianceEngine>> {
+        self.screening
+            .compliance_engine()
+            .expect("screening is built with a compliance engine")
+    }
+
+    /// Review cases from the Compliance Ledger
+    pub async fn cases(&self) -> Result<CaseBook, CommitError> {
+        Ok(self.compliance().read().await.cases()?)
+    }
+
+    /// Assign an open case to a reviewer
+    pub async fn assign_case(
+        &mut self,
+        flag_id: &str,
+        reviewer_id: &str,
+        assigned_by: &str,
+    ) -> Result<Case, CommitError> {
+        Ok(self
+            .compliance()
+            .write()
+            .await
+            .assign_case(flag_id, reviewer_id, assigned_by)?)
+    }
+
+    /// Approve or reject a case, settling the locked funds
+    ///
+    /// Approval releases the funds from REVIEW to AVAILABLE; rejection
+    /// reverses the flagged entry. The financial entry is committed before
+    /// the decision is recorded, so a failed commit leaves the case open.
+    /// Cases without a lock (e.g. flagged withdrawals) only record the decision.
+    pub async fn resolve_case(
+        &mut self,
+        flag_id: &str,
+        decision: ReviewDecision,
+        reviewer_id: &str,
+        notes: &str,
+    ) -> Result<(Case, Option<JournalEntry>), CommitError> {
+        let case = self.compliance().read().await.reviewable_case(flag_id, reviewer_id)?;
+
+        let lock_id = format!("{}{}", screening::REVIEW_LOCK_PREFIX, case.correlation_id);
+        let settlement = match self.committed_entry(&lock_id)? {
+            Some(lock) => match decision {
+                ReviewDecision::Approved => Some(screening::release_entry(&lock, &case)),
+                _ => {
+                    let flagged = self
+                        .committed_entry(&case.correlation_id)?
+                        .ok_or_else(|| ComplianceError::TransactionNotFound(case.correlation_id.clone()))?;
+                    screening::reversal_entry(&flagged, &lock, &case)
+                }
+            },
+            None => None,
+        };
+        let entry = match settlement {
+            Some(mut unsigned) => {
+                unsigned
+                    .metadata
+                    .insert("reviewer".to_string(), serde_json::json!(reviewer_id));
+                Some(self.commit_unscreened(unsigned).await?)
+            }
+            None => None,
+        };
+
+        let case = self
+            .compliance()
+            .write()
+            .await
+            .resolve_case(flag_id, decision, reviewer_id, notes)?;
+        Ok((case, entry))
+    }
+
+    /// Escalate open cases past their expiry to the next approval level
+    pub async fn escalate_cases(&mut self, now: DateTime<Utc>) -> Result<Vec<Case>, CommitError> {
+        Ok(self.compliance().write().await.escalate_expired(now)?)
+    }
+
+    /// Registered users
+    pub fn users(&self) -> &UserRegistry {
+        &self.users
+    }
+
+    /// Register a user with a KYC tier
+    pub async fn register_user(
+        &mut self,
+        user_id: &str,
+        kyc_tier: u8,
+        onboarded_at: NaiveDate,
+        performed_by: &str,
+    ) -> Result<UserRecord, CommitError> {
+        check_kyc_tier(kyc_tier)?;
+        let user_id = user_id.to_uppercase();
+        if self.users.get(&user_id).is_some() {
+            return Err(ComplianceError::UserExists(user_id).into());
+        }
+        let event = ComplianceEvent::user_updated(
+            &user_id,
+            kyc_tier,
+            UserStatus::Active,
+            onboarded_at,
+            "registered",
+            performed_by,
+        );
+        self.record_user(&user_id, event).await
+    }
+
+    /// Change a user's KYC tier
+    pub async fn set_user_tier(
+        &mut self,
+        user_id: &str,
+        kyc_tier: u8,
+        reason: &str,
+        performed_by: &str,
+    ) -> Result<UserRecord, CommitError> {
+        check_kyc_tier(kyc_tier)?;
+        let user = self.open_user(user_id)?;
+        let event = ComplianceEvent::user_updated(
+            &user.user_id,
+            kyc_tier,
+            user.status,
+            user.onboarded_at,
+            reason,
+            performed_by,
+        );
+        self.record_user(&user.user_id, event).await
+    }
+
+    /// Freeze, unfreeze or close a user (closing is final)
+    pub async fn set_user_status(
+        &mut self,
+        user_id: &str,
+        status: UserStatus,
+        reason: &str,
+        performed_by: &str,
+    ) -> Result<UserRecord, CommitError> {
+        let user = self.open_user(user_id)?;
+        let event = ComplianceEvent::user_updated(
+            &user.user_id,
+            user.kyc_tier,
+            status,
+            user.onboarded_at,
+            reason,
+            performed_by,
+        );
+        self.record_user(&user.user_id, event).await
+    }
+
+    /// A registered user that isn't closed
+    fn open_user(&self, user_id: &str) -> Result<UserRecord, CommitError> {
+        let user_id = user_id.to_uppercase();
+        let user = self
+            .users
+            .get(&user_id)
+            .ok_or_else(|| ComplianceError::UserNotFound(user_id.clone()))?;
+        if user.status == UserStatus::Closed {
+            return Err(ComplianceError::UserClosed(user_id).into());
+        }
+        Ok(user.clone())
+    }
+
+    /// Record a user change in the Compliance Ledger, registry and projection
+    async fn record_user(&mut self, user_id: &str, event: ComplianceEvent) -> Result<UserRecord, CommitError> {
+        self.compliance().write().await.record_event(&event)?;
+        self.users.apply(&event);
+
+        let user = self
+            .users
+            .get(user_id)
+            .cloned()
+            .ok_or_else(|| ComplianceError::UserNotFound(user_id.to_string()))?;
+        if let Some(ref projection) = self.projection {
+            projection.user().upsert(&user).await.ok();
+        }
+        Ok(user)
+    }
+
+    /// Currency Transaction Report for `from..=to`
+    ///
+    /// The threshold defaults to the compliance config's `ctr_threshold`.
+    pub async fn ctr_report(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        threshold: Option<Decimal>,
+    ) -> Result<CtrReport, CommitError> {
+        let threshold = match threshold {
+            Some(threshold) => threshold,
+            None => self.compliance().read().await.config().ctr_threshold,
+        };
+        let mut builder = CtrBuilder::new(from, to, threshold);
+        for entry in EventReader::from_directory(&self.journal_path)?.iter() {
+            builder.add(&entry?);
+        }
+        Ok(builder.finish())
+    }
+
+    /// SAR drafts for cases opened in `from..=to`
+    pub async fn sar_report(&self, from: NaiveDate, to: NaiveDate) -> Result<SarReport, CommitError> {
+        let events = self.compliance().read().await.ledger().read_all()?;
+        let mut builder = SarBuilder::new(from, to, &events);
+        for entry in EventReader::from_directory(&self.journal_path)?.iter() {
+            builder.add(&entry?);
+        }
+        Ok(builder.finish())
+    }
+
+    /// Account balances as of a cutoff (None: the whole journal)
+    pub fn ledger_balances(&self, as_of: Option<AsOf>) -> Result<LedgerBalances, CommitError> {
+        let mut balances = LedgerBalances::new(as_of);
+        for entry in EventReader::from_directory(&self.journal_path)?.iter() {
+            balances.apply(&entry?);
+        }
+        Ok(balances)
+    }
+
+    /// Income statement for `from..=to`
+    pub fn income_statement(&self, from: NaiveDate, to: NaiveDate) -> Result<IncomeStatement, CommitError> {
+        let mut builder = IncomeStatementBuilder::new(from, to);
+        for entry in EventReader::from_directory(&self.journal_path)?.iter() {
+            builder.add(&entry?);
+        }
+        Ok(builder.finish())
+    }
+
+    /// Liability sum trees and vault balances as of entry `sequence`
+    ///
+    /// Defaults to the current state; earlier sequences are rebuilt by
+    /// replaying the journal up to them.
+    pub fn proof_of_reserves(&self, sequence: Option<u64>) -> Result<ProofOfReserves, CommitError> {
+        let sequence = sequence.unwrap_or(self.last_sequence);
+        if sequence > self.last_sequence {
+            return Err(CommitError::EntryMissing(sequence));
+        }
+        if sequence == self.last_sequence {
+            return Ok(ProofOfReserves::build(self.risk.state(), sequence));
+        }
+
+        let mut state = RiskState::new();
+        for entry in EventReader::from_directory(&self.journal_path)?.iter() {
+            let entry = entry?;
+            if entry.sequence > sequence {
+                break;
+            }
+            state.apply_entry(&entry);
+        }
+        Ok(ProofOfReserves::build(&state, sequence))
+    }
+
+    /// Load the rule file, activating it if it changed
+    ///
+    /// Activations, and deactivations when the file is removed, are recorded
+    /// in the Compliance Ledger. A file that doesn't parse leaves the active
+    /// rules in place.
+    pub async fn reload_rules(&mut self, performed_by: &str) -> Result<RulesReload, anyhow::Error> {
+        let rule_set = screening::load_rules(&self.rules_path, self.rules.predicates())?;
+        let hash = rule_set.as_ref().map(RuleSet::hash);
+        let active_hash = match &self.active_rules {
+            Some(ComplianceEvent::RuleSetChanged { rule_set_hash, .. }) => Some(rule_set_hash.clone()),
+            _ => None,
+        };
+        let rule_set = rule_set.unwrap_or_default();
+        let reload = RulesReload {
+            name: rule_set.name.clone(),
+            version: rule_set.version.clone(),
+            hash: hash.clone(),
+            rules: rule_set.rules.len(),
+            changed: hash != active_hash,
+        };
+
+        if reload.changed {
+            let event = match (&hash, &self.active_rules) {
+                (Some(hash), _) => Some(ComplianceEvent::rule_set_changed(
+                    &rule_set.name,
+                    &rule_set.version,
+                    hash,
+                    RuleAction::Activated,
+                    performed_by,
+                    rule_set.approved_by.clone(),
+                )),
+                (
+                    None,
+                    Some(ComplianceEvent::RuleSetChanged {
+                        rule_set_name,
+                        rule_set_version,
+                        rule_set_hash,
+                        approved_by,
+                        ..
+                    }),
+                ) => Some(ComplianceEvent::rule_set_changed(
+                    rule_set_name,
+                    rule_set_version,
+                    rule_set_hash,
+                    RuleAction::Deactivated,
+                    performed_by,
+                    approved_by.clone(),
+                )),
+                (None, _) => None,
+            };
+            if let (Some(event), Some(engine)) = (&event, self.screening.compliance_engine()) {
+                engine.write().await.record_event(event)?;
+            }
+            tracing::info!(
+                "Rule set {} ({} rules) {} by {}",
+                if reload.name.is_empty() { "<none>" } else { &reload.name },
+                reload.rules,
+                if hash.is_some() { "activated" } else { "deactivated" },
+                performed_by
+            );
+            self.active_rules = event.filter(|_| hash.is_some());
+        }
+
+        self.rules.replace(rule_set);
+        Ok(reload)
+    }
+
+    /// Rotate the system key to `next`, effective from the next entry
+    ///
+    /// The current key signs the hand-off; `next` signs from now on.
+    pub fn rotate_system_key(&mut self, next: Arc<dyn Signer>) -> Result<KeyEvent, KeyStoreError> {
+        let current = self.signer.as_ref().ok_or(KeyStoreError::NoSigner)?;
+        let from_sequence = self.last_sequence + 1;
+        let next_key = next.public_key_hex();
+        let event = KeyEvent::rotated(&next_key, from_sequence, current.sign_handoff(&next_key, from_sequence));
+
+        self.keys.append(&event)?;
+        self.signer = Some(next);
+        Ok(event)
+    }
+
+    /// Activate a signer's key (operators; the system key is rotated instead)
+    pub fn activate_key(
+        &self,
+        signer_id: &str,
+        public_key: &str,
+        from_sequence: Option<u64>,
+    ) -> Result<KeyEvent, KeyStoreError> {
+        let event = KeyEvent::activated(signer_id, public_key, from_sequence.unwrap_or(self.last_sequence + 1));
+        self.keys.append(&event)?;
+        Ok(event)
+    }
+
+    /// Revoke a key, from the next entry unless an earlier sequence is given
+    pub fn revoke_key(
+        &self,
+        signer_id: &str,
+        public_key: &str,
+        from_sequence: Option<u64>,
+        reason: &str,
+    ) -> Result<KeyEvent, KeyStoreError> {
+        let event = KeyEvent::revoked(
+            signer_id,
+            public_key,
+            from_sequence.unwrap_or(self.last_sequence + 1),
+            reason,
+        );
+        self.keys.append(&event)?;
+        Ok(event)
+    }
+
     /// Get journal path
     pub fn journal_path(&self) -> &Path {
         &self.journal_path
     }
 
+    /// Checkpoints of durable subscribers, saved or registered
+    ///
+    /// Registered subscribers that haven't run yet show where they'll start.
+    pub fn subscriber_checkpoints(&self) -> Result<Vec<SubscriberCheckpoint>, BusError> {
+        let mut checkpoints = self.subscribers.checkpoints()?;
+        for subscriber in self.bus.durable_subscribers() {
+            if !checkpoints.iter().any(|c| c.subscriber == subscriber.name()) {
+                checkpoints.push(subscriber.checkpoint()?);
+            }
+        }
+        checkpoints.sort_by(|a, b| a.subscriber.cmp(&b.subscriber));
+        Ok(checkpoints)
+    }
+
     /// Get projection path
     pub fn projection_path(&self) -> &Path {
         &self.projection_path
@@ -155,6 +983,350 @@ impl AppContext {
     pub fn last_sequence(&self) -> u64 {
         self.last_sequence
     }
+
+    /// Get last entry hash
+    pub fn last_hash(&self) -> &str {
+        &self.last_hash
+    }
+
+    /// Committed correlation ids
+    pub fn correlations(&self) -> &CorrelationIndex {
+        &self.correlations
+    }
+
+    /// Record a committed entry's id, in memory and in the correlation log
+    ///
+    /// The entry is already in the journal, so a failed append only stops
+    /// the log; the next startup picks up from its last record.
+    fn record_correlation(&mut self, entry: &JournalEntry) {
+        if !self.correlations.record(entry) {
+            return;
+        }
+        if let Some(ref log) = self.correlation_log {
+            if let Err(e) = log.append(entry) {
+                tracing::warn!("Correlation log stopped at sequence {}: {}", entry.sequence, e);
+                self.correlation_log = None;
+            }
+        }
+    }
+
+    /// Entry committed under `correlation_id`, read back from the journal
+    pub fn committed_entry(&self, correlation_id: &str) -> Result<Option<JournalEntry>, CommitError> {
+        match self.correlations.get(correlation_id) {
+            Some(committed) => self.read_entry(committed.sequence).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Read a single entry from the journal
+    fn read_entry(&self, sequence: u64) -> Result<JournalEntry, CommitError> {
+        let reader = EventReader::from_directory(&self.journal_path)?;
+        reader
+            .iter_from(sequence)?
+            .next()
+            .transpose()?
+            .filter(|entry| entry.sequence == sequence)
+            .ok_or(CommitError::EntryMissing(sequence))
+    }
+
+    /// Write a snapshot of the current state and prune old ones
+    ///
+    /// Only call between commands: the order books must agree with the journal.
+    pub fn snapshot(&mut self) -> Result<PathBuf, SnapshotError> {
+        let snapshot = Snapshot::capture(
+            self.last_sequence,
+            &self.last_hash,
+            self.risk.state(),
+            &self.matching,
+        );
+        let path = self.snapshots.save(&snapshot)?;
+        self.snapshots.prune(DEFAULT_SNAPSHOT_RETAIN)?;
+        self.snapshot_sequence = self.last_sequence;
+        Ok(path)
+    }
+
+    /// Take a snapshot once `snapshot_interval` entries have been committed since the last one
+    pub fn snapshot_if_due(&mut self) -> Result<Option<PathBuf>, SnapshotError> {
+        let due = self.snapshot_interval > 0
+            && self.last_sequence >= self.snapshot_sequence + self.snapshot_interval;
+        if !due {
+            return Ok(None);
+        }
+        self.snapshot().map(Some)
+    }
+
+    /// Set the automatic snapshot interval (0 disables)
+    pub fn set_snapshot_interval(&mut self, interval: u64) {
+        self.snapshot_interval = interval;
+    }
+
+    /// Checkpoint the entries committed since the last checkpoint
+    ///
+    /// The checkpoint is signed with the system key, if one is configured.
+    /// Returns None when there is nothing new to cover.
+    pub fn checkpoint(&mut self) -> Result<Option<Checkpoint>, CheckpointError> {
+        if self.last_sequence <= self.checkpoint_sequence {
+            return Ok(None);
+        }
+
+        let reader = EventReader::from_directory(&self.journal_path)?;
+        let mut hashes = Vec::new();
+        for entry in reader.iter_from(self.checkpoint_sequence + 1)? {
+            hashes.push(entry?.hash);
+        }
+        let Some(tree) = MerkleTree::new(self.checkpoint_sequence + 1, hashes) else {
+            return Ok(None);
+        };
+
+        let mut checkpoint = tree.checkpoint();
+        if let Some(ref signer) = self.signer {
+            checkpoint.signature = Some(signer.sign_checkpoint(&checkpoint));
+        }
+        self.checkpoints.append(&checkpoint)?;
+        self.checkpoint_sequence = checkpoint.to_sequence;
+        Ok(Some(checkpoint))
+    }
+
+    /// Checkpoint once `checkpoint_interval` entries have been committed since the last one
+    pub fn checkpoint_if_due(&mut self) -> Result<Option<Checkpoint>, CheckpointError> {
+        let due = self.checkpoint_interval > 0
+            && self.last_sequence >= self.checkpoint_sequence + self.checkpoint_interval;
+        if !due {
+            return Ok(None);
+        }
+        self.checkpoint()
+    }
+
+    /// Set the automatic checkpoint interval (0 disables)
+    pub fn set_checkpoint_interval(&mut self, interval: u64) {
+        self.checkpoint_interval = interval;
+    }
+
+    /// Inclusion proof for entry `sequence`, with the checkpoint it proves against
+    pub fn inclusion_proof(&self, sequence: u64) -> Result<(MerkleProof, Checkpoint), CheckpointError> {
+        let checkpoint = self
+            .checkpoints
+            .covering(sequence)?
+            .ok_or(CheckpointError::NotCheckpointed(sequence))?;
+        let reader = EventReader::from_directory(&self.journal_path)?;
+        let proof = checkpoint::rebuild(&checkpoint, &reader)?
+            .proof(sequence)
+            .ok_or(CheckpointError::NotCheckpointed(sequence))?;
+        Ok((proof, checkpoint))
+    }
+}
+
+/// Rebuild the order books by replaying one order lifecycle entry
+///
+/// OrderPlace restores the order as resting, Trade entries apply their fills
+/// to the maker and taker orders, and OrderCancel removes what is left.
+pub(crate) fn replay_order_entry(matching: &mut MatchingEngine, entry: &JournalEntry) {
+    match entry.intent {
+        TransactionIntent::OrderPlace => {
+            if let Some(order) = order_from_entry(entry) {
+                matching.add_pair(order.pair.clone());
+                matching.restore_order(order).ok();
+            }
+        }
+        TransactionIntent::OrderCancel => {
+            if let (Some(order_id), Some(pair)) = (meta_str(entry, "order_id"), pair_from_entry(entry)) {
+                matching.cancel_order(&pair, order_id).ok();
+            }
+        }
+        TransactionIntent::Trade => {
+            // Manual trades (no order ids) never touched the book
+            let (Some(pair), Some(quantity)) = (pair_from_entry(entry), meta_decimal(entry, "base_amount")) else {
+                return;
+            };
+            for key in ["maker_order_id", "taker_order_id"] {
+                if let Some(order_id) = meta_str(entry, key) {
+                    matching.apply_fill(&pair, order_id, quantity).ok();
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Automatic checkpoint interval from BIBANK_CHECKPOINT_INTERVAL (0 disables)
+fn checkpoint_interval_from_env() -> u64 {
+    std::env::var("BIBANK_CHECKPOINT_INTERVAL")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_CHECKPOINT_INTERVAL)
+}
+
+/// Automatic snapshot interval from BIBANK_SNAPSHOT_INTERVAL (0 disables)
+fn snapshot_interval_from_env() -> u64 {
+    std::env::var("BIBANK_SNAPSHOT_INTERVAL")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_SNAPSHOT_INTERVAL)
+}
+
+/// Price oracle from BIBANK_PRICE_FEED
+///
+/// A comma-separated list of sources: `http(s)://` URL templates (see
+/// [`HttpOracle`]) or CSV replay files. Several sources are aggregated by
+/// median. Without a feed, fixed mock prices are used.
+fn oracle_from_env() -> Result<Arc<dyn PriceOracle>, anyhow::Error> {
+    let Ok(feed) = std::env::var("BIBANK_PRICE_FEED") else {
+        return Ok(Arc::new(MockOracle::with_defaults()));
+    };
+
+    let mut sources: Vec<Arc<dyn PriceOracle>> = Vec::new();
+    for source in feed.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        if source.starts_with("http://") || source.starts_with("https://") {
+            sources.push(Arc::new(HttpOracle::new(source)));
+        } else {
+            sources.push(Arc::new(ReplayOracle::from_path(source)?));
+        }
+    }
+
+    Ok(match sources.len() {
+        0 => Arc::new(MockOracle::with_defaults()),
+        1 => sources.remove(0),
+        _ => Arc::new(sources.into_iter().fold(MedianOracle::new(), MedianOracle::source)),
+    })
+}
+
+/// Webhook endpoints from `webhooks.json` (none if the file is missing)
+fn webhook_config(data_path: &Path) -> Result<WebhookConfig, anyhow::Error> {
+    let path = data_path.join(WEBHOOKS_FILE);
+    if !path.exists() {
+        return Ok(WebhookConfig::default());
+    }
+    WebhookConfig::from_file(&path).map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))
+}
+
+/// Fee schedule from `fees.json` (no fees if missing)
+fn fee_schedule(data_path: &Path) -> Result<FeeSchedule, anyhow::Error> {
+    let path = data_path.join(FEES_FILE);
+    if !path.exists() {
+        return Ok(FeeSchedule::default());
+    }
+    FeeSchedule::from_file(&path).map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))
+}
+
+/// Register custom token scales from `currencies.json` (`{"CODE": scale}`)
+fn register_currencies(data_path: &Path) -> Result<(), anyhow::Error> {
+    let path = data_path.join(CURRENCIES_FILE);
+    if !path.exists() {
+        return Ok(());
+    }
+    let scales: BTreeMap<String, u32> = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+    for (code, scale) in scales {
+        Currency::register(&code, scale).map_err(|e| anyhow::anyhow!("{}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Approval workflow settings from the environment
+///
+/// - `BIBANK_APPROVERS`: `ID=PUBKEY_HEX` pairs, comma-separated
+/// - `BIBANK_APPROVAL_QUORUM`: signatures required (default 2, `0` turns approvals off)
+/// - `BIBANK_APPROVAL_THRESHOLDS`: `ASSET=AMOUNT` pairs for withdrawals/transfers
+///   (default `USDT=100000`; other assets need approval for any amount)
+///
+/// A quorum the registered approvers can't reach fails startup.
+fn approval_config_from_env() -> Result<ApprovalConfig, anyhow::Error> {
+    let mut config = ApprovalConfig::default();
+
+    let approvers = env_pairs("BIBANK_APPROVERS")?;
+    let thresholds = env_pairs("BIBANK_APPROVAL_THRESHOLDS")?;
+    let quorum = std::env::var("BIBANK_APPROVAL_QUORUM").ok();
+
+    // Nothing configured: approvals are off rather than parking entries nobody can sign
+    if approvers.is_empty() && thresholds.is_empty() && quorum.is_none() {
+        tracing::warn!("BIBANK_APPROVERS not set: multi-sig approvals are off");
+        config.required_signatures = 0;
+        return Ok(config);
+    }
+
+    for (id, public_key) in approvers {
+        config = config.with_approver(id, public_key);
+    }
+    for (asset, amount) in thresholds {
+        let amount = Decimal::from_str(&amount)
+            .map_err(|e| anyhow::anyhow!("BIBANK_APPROVAL_THRESHOLDS: bad amount for {}: {}", asset, e))?;
+        config = config.with_threshold(&asset, amount);
+    }
+    if let Some(quorum) = quorum {
+        config.required_signatures = quorum
+            .parse()
+            .map_err(|e| anyhow::anyhow!("BIBANK_APPROVAL_QUORUM: {}", e))?;
+    }
+
+    config
+        .validate()
+        .map_err(|e| anyhow::anyhow!("BIBANK_APPROVERS: {}", e))?;
+    Ok(config)
+}
+
+/// Parse a comma-separated `KEY=VALUE` list from an env var
+fn env_pairs(var: &str) -> Result<Vec<(String, String)>, anyhow::Error> {
+    let Ok(list) = std::env::var(var) else {
+        return Ok(Vec::new());
+    };
+    list.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|pair| {
+            pair.split_once('=')
+                .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+                .ok_or_else(|| anyhow::anyhow!("{}: expected KEY=VALUE, got '{}'", var, pair))
+        })
+        .collect()
+}
+
+/// fsync-per-commit mode, enabled with BIBANK_FSYNC=1
+fn fsync_from_env() -> bool {
+    std::env::var("BIBANK_FSYNC").is_ok_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+}
+
+/// Reconstruct an order from its OrderPlace entry
+fn order_from_entry(entry: &JournalEntry) -> Option<Order> {
+    let order_id = meta_str(entry, "order_id")?;
+    let side = match meta_str(entry, "order_side")? {
+        "buy" => OrderSide::Buy,
+        "sell" => OrderSide::Sell,
+        _ => return None,
+    };
+    let price = meta_decimal(entry, "price")?;
+    let quantity = meta_decimal(entry, "quantity")?;
+    // Entries written before order types were recorded are limit orders
+    let order_type = match meta_str(entry, "order_type") {
+        Some(order_type) => order_type.parse::<OrderType>().ok()?,
+        None => OrderType::Limit,
+    };
+
+    // The lock is debited from the owner's AVAILABLE account
+    let user_id = entry
+        .postings
+        .iter()
+        .find(|p| p.side == Side::Debit)
+        .map(|p| p.account.id.clone())?;
+
+    let mut order = Order::with_id(order_id, user_id, pair_from_entry(entry)?, side, price, quantity)
+        .with_order_type(order_type);
+    order.created_at = entry.timestamp;
+    order.updated_at = entry.timestamp;
+    Some(order)
+}
+
+fn pair_from_entry(entry: &JournalEntry) -> Option<TradingPair> {
+    Some(TradingPair::new(
+        meta_str(entry, "base_asset")?,
+        meta_str(entry, "quote_asset")?,
+    ))
+}
+
+fn meta_str<'a>(entry: &'a JournalEntry, key: &str) -> Option<&'a str> {
+    entry.metadata.get(key)?.as_str()
+}
+
+fn meta_decimal(entry: &JournalEntry, key: &str) -> Option<Decimal> {
+    Decimal::from_str(meta_str(entry, key)?).ok()
 }
 
 /// Errors during commit
@@ -168,4 +1340,38 @@ pub enum CommitError {
 
     #[error("Event store error: {0}")]
     Event(#[from] bibank_events::EventError),
+
+    #[error("correlation_id {correlation_id} already committed at sequence {sequence} with a different payload")]
+    CorrelationConflict { correlation_id: String, sequence: u64 },
+
+    #[error("Journal has no entry at sequence {0}")]
+    EntryMissing(u64),
+
+    #[error("Approval error: {0}")]
+    Approval(#[from] ApprovalError),
+
+    #[error("Blocked by compliance: {reason} (code: {code})")]
+    Blocked {
+        correlation_id: String,
+        reason: String,
+        code: String,
+    },
+
+    #[error("Entry parked for approval {approval_id} ({signatures_remaining} signatures required)")]
+    PendingApproval {
+        approval_id: String,
+        signatures_remaining: usize,
+    },
+
+    #[error("Compliance error: {0}")]
+    Compliance(#[from] ComplianceError),
+
+    #[error("Entry {sequence} ({intent:?}) can't be reversed")]
+    NotReversible { sequence: u64, intent: TransactionIntent },
+
+    #[error("Entry {sequence} was already reversed at sequence {reversal_sequence}")]
+    AlreadyReversed { sequence: u64, reversal_sequence: u64 },
+
+    #[error("User {user_id} is {status}: debits are blocked")]
+    UserFrozen { user_id: String, status: UserStatus },
 }
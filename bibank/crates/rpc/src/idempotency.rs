@@ -0,0 +1,344 @@
+//! Idempotent commits - index of committed correlation ids
+//!
+//! Every committed entry is recorded under its correlation_id together with a
+//! fingerprint of its payload (intent, causality, postings, metadata). A retry
+//! carrying the same payload resolves to the original entry; the same id with
+//! a different payload is a conflict. Snapshots leave the index out so they
+//! stay bounded; instead each new id is appended to `correlations.jsonl` in
+//! the data directory as it is committed, and startup only records the
+//! journal entries after the log's last line.
+
+use bibank_events::{EventError, EventReader};
+use bibank_ledger::{JournalEntry, Posting, TransactionIntent, UnsignedEntry};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// Correlation log file in the data directory
+pub const CORRELATION_LOG_FILE: &str = "correlations.jsonl";
+
+/// Where a correlation id was committed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommittedRef {
+    pub sequence: u64,
+    /// Payload fingerprint (see [`fingerprint`])
+    pub fingerprint: String,
+}
+
+/// Outcome of looking up an entry about to be committed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lookup {
+    /// Correlation id not seen before
+    New,
+    /// Same correlation id and payload, committed at this sequence
+    Retry(u64),
+    /// Same correlation id with a different payload, committed at this sequence
+    Conflict(u64),
+}
+
+/// Committed correlation ids
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CorrelationIndex {
+    entries: BTreeMap<String, CommittedRef>,
+}
+
+impl CorrelationIndex {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a committed entry; returns whether its id was new
+    ///
+    /// The first entry wins: journals written before commits were idempotent
+    /// may repeat an id, and retries must resolve to the earliest entry.
+    pub fn record(&mut self, entry: &JournalEntry) -> bool {
+        if self.entries.contains_key(&entry.correlation_id) {
+            return false;
+        }
+        self.entries.insert(
+            entry.correlation_id.clone(),
+            CommittedRef {
+                sequence: entry.sequence,
+                fingerprint: entry_fingerprint(entry),
+            },
+        );
+        true
+    }
+
+    /// Look up an entry about to be committed
+    pub fn check(&self, unsigned: &UnsignedEntry) -> Lookup {
+        match self.entries.get(&unsigned.correlation_id) {
+            None => Lookup::New,
+            Some(committed) if committed.fingerprint == unsigned_fingerprint(unsigned) => {
+                Lookup::Retry(committed.sequence)
+            }
+            Some(committed) => Lookup::Conflict(committed.sequence),
+        }
+    }
+
+    /// Get the commit recorded for a correlation id
+    pub fn get(&self, correlation_id: &str) -> Option<&CommittedRef> {
+        self.entries.get(correlation_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// One committed correlation id in the log
+#[derive(Debug, Serialize, Deserialize)]
+struct LogRecord {
+    correlation_id: String,
+    sequence: u64,
+    fingerprint: String,
+    /// Entry hash, anchoring the log in the journal
+    hash: String,
+}
+
+/// Append-only copy of the index (one JSON record per new id)
+///
+/// Like snapshots, the log is disposable: one that doesn't parse or whose
+/// last record no longer matches the journal is removed and rebuilt.
+pub struct CorrelationLog {
+    path: PathBuf,
+}
+
+impl CorrelationLog {
+    /// Create a log over `path` (created on first append)
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Log file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a committed entry's id
+    pub fn append(&self, entry: &JournalEntry) -> Result<(), CorrelationLogError> {
+        let record = LogRecord {
+            correlation_id: entry.correlation_id.clone(),
+            sequence: entry.sequence,
+            fingerprint: entry_fingerprint(entry),
+            hash: entry.hash.clone(),
+        };
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(&record)?)?;
+        Ok(())
+    }
+
+    /// Load the index and the sequence of the last entry it covers
+    ///
+    /// A log that can't be trusted is removed with a warning, returning an
+    /// empty index to be rebuilt from the whole journal.
+    pub fn load(&self, reader: &EventReader) -> Result<(CorrelationIndex, u64), CorrelationLogError> {
+        match self.read(reader) {
+            Ok(loaded) => Ok(loaded),
+            Err(e) => {
+                tracing::warn!("Rebuilding correlation log {}: {}", self.path.display(), e);
+                match fs::remove_file(&self.path) {
+                    Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+                    _ => Ok((CorrelationIndex::new(), 0)),
+                }
+            }
+        }
+    }
+
+    fn read(&self, reader: &EventReader) -> Result<(CorrelationIndex, u64), CorrelationLogError> {
+        let mut index = CorrelationIndex::new();
+        if !self.path.exists() {
+            return Ok((index, 0));
+        }
+
+        let mut last = None;
+        for line in BufReader::new(fs::File::open(&self.path)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: LogRecord = serde_json::from_str(&line)?;
+            index.entries.entry(record.correlation_id).or_insert(CommittedRef {
+                sequence: record.sequence,
+                fingerprint: record.fingerprint,
+            });
+            last = Some((record.sequence, record.hash));
+        }
+
+        let Some((sequence, hash)) = last else {
+            return Ok((index, 0));
+        };
+        let anchored = reader
+            .iter_from(sequence)?
+            .next()
+            .transpose()?
+            .is_some_and(|entry| entry.sequence == sequence && entry.hash == hash);
+        if !anchored {
+            return Err(CorrelationLogError::NotAnchored(sequence));
+        }
+        Ok((index, sequence))
+    }
+}
+
+/// Correlation log errors
+#[derive(Debug, thiserror::Error)]
+pub enum CorrelationLogError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Event store error: {0}")]
+    Event(#[from] EventError),
+
+    #[error("Last record (sequence {0}) doesn't match the journal")]
+    NotAnchored(u64),
+}
+
+/// Fingerprint of an entry's payload
+///
+/// Covers everything a client controls and nothing the commit adds
+/// (sequence, hashes, timestamp, signatures). Amounts are normalized so
+/// `100` and `100.00` are the same payload; metadata keys are sorted.
+pub fn fingerprint(
+    intent: TransactionIntent,
+    causality_id: Option<&str>,
+    postings: &[Posting],
+    metadata: &HashMap<String, Value>,
+) -> String {
+    let postings: Vec<Value> = postings
+        .iter()
+        .map(|p| {
+            json!({
+                "account": p.account.to_string(),
+                "side": p.side,
+                "amount": p.amount.value().normalize().to_string(),
+            })
+        })
+        .collect();
+    let metadata: BTreeMap<&String, &Value> = metadata.iter().collect();
+
+    let payload = json!({
+        "intent": intent,
+        "causality_id": causality_id,
+        "postings": postings,
+        "metadata": metadata,
+    });
+    hex::encode(Sha256::digest(payload.to_string()))
+}
+
+/// Fingerprint of an entry about to be committed
+pub fn unsigned_fingerprint(entry: &UnsignedEntry) -> String {
+    fingerprint(
+        entry.intent,
+        entry.causality_id.as_deref(),
+        &entry.postings,
+        &entry.metadata,
+    )
+}
+
+/// Fingerprint of a committed entry
+pub fn entry_fingerprint(entry: &JournalEntry) -> String {
+    fingerprint(
+        entry.intent,
+        entry.causality_id.as_deref(),
+        &entry.postings,
+        &entry.metadata,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bibank_core::Amount;
+    use bibank_ledger::{AccountKey, JournalEntryBuilder};
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+
+    fn deposit(correlation_id: &str, amount: Decimal) -> UnsignedEntry {
+        let amount = Amount::new(amount).unwrap();
+        JournalEntryBuilder::new()
+            .intent(TransactionIntent::Deposit)
+            .correlation_id(correlation_id)
+            .debit(AccountKey::system_vault("USDT"), amount)
+            .credit(AccountKey::user_available("ALICE", "USDT"), amount)
+            .metadata("memo", json!("top-up"))
+            .metadata("channel", json!("bank"))
+            .build_unsigned()
+            .unwrap()
+    }
+
+    fn committed(unsigned: &UnsignedEntry, sequence: u64) -> JournalEntry {
+        JournalEntry {
+            sequence,
+            prev_hash: format!("hash-{}", sequence - 1),
+            hash: format!("hash-{}", sequence),
+            timestamp: Utc::now(),
+            intent: unsigned.intent,
+            correlation_id: unsigned.correlation_id.clone(),
+            causality_id: unsigned.causality_id.clone(),
+            postings: unsigned.postings.clone(),
+            metadata: unsigned.metadata.clone(),
+            signatures: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_normalizes_amounts_and_metadata_order() {
+        let plain = deposit("dep-1", Decimal::new(100, 0));
+        let scaled = deposit("dep-1", Decimal::new(10000, 2));
+        assert_eq!(unsigned_fingerprint(&plain), unsigned_fingerprint(&scaled));
+
+        // Metadata inserted in a different order is the same payload
+        let mut reordered = plain.clone();
+        reordered.metadata = HashMap::new();
+        reordered.metadata.insert("channel".to_string(), json!("bank"));
+        reordered.metadata.insert("memo".to_string(), json!("top-up"));
+        assert_eq!(unsigned_fingerprint(&plain), unsigned_fingerprint(&reordered));
+
+        // Commit-time fields don't count
+        assert_eq!(unsigned_fingerprint(&plain), entry_fingerprint(&committed(&plain, 7)));
+
+        let other = deposit("dep-1", Decimal::new(101, 0));
+        assert_ne!(unsigned_fingerprint(&plain), unsigned_fingerprint(&other));
+    }
+
+    #[test]
+    fn test_check_detects_retry_and_conflict() {
+        let mut index = CorrelationIndex::new();
+        let original = deposit("dep-1", Decimal::new(100, 0));
+        assert_eq!(index.check(&original), Lookup::New);
+
+        index.record(&committed(&original, 3));
+        assert_eq!(index.check(&deposit("dep-1", Decimal::new(10000, 2))), Lookup::Retry(3));
+        assert_eq!(index.check(&deposit("dep-1", Decimal::new(5, 0))), Lookup::Conflict(3));
+        assert_eq!(index.check(&deposit("dep-2", Decimal::new(100, 0))), Lookup::New);
+
+        let mut causal = original.clone();
+        causal.causality_id = Some("req-9".to_string());
+        assert_eq!(index.check(&causal), Lookup::Conflict(3));
+    }
+
+    #[test]
+    fn test_first_entry_wins() {
+        let mut index = CorrelationIndex::new();
+        index.record(&committed(&deposit("dep-1", Decimal::new(100, 0)), 3));
+        index.record(&committed(&deposit("dep-1", Decimal::new(5, 0)), 4));
+
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.get("dep-1").unwrap().sequence, 3);
+        assert_eq!(index.check(&deposit("dep-1", Decimal::new(100, 0))), Lookup::Retry(3));
+    }
+}
@@ -0,0 +1,85 @@
+//! Key registry journal - signer key activations, rotations and revocations
+//!
+//! Appended to `keys.jsonl` in the data directory, one [`KeyEvent`] per
+//! line. The first time the server runs with `BIBANK_SYSTEM_KEY` set, that
+//! key is activated from genesis; later keys come in through
+//! `bibank keygen rotate`, signed over by the key they replace.
+//! `bibank audit --verify-signatures` checks every signature against the
+//! key valid at its entry's sequence.
+
+use bibank_ledger::{KeyEvent, KeyRegistry, LedgerError};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+/// Key registry file in the data directory
+pub const KEYS_FILE: &str = "keys.jsonl";
+
+/// Append-only key registry journal
+pub struct KeyStore {
+    path: PathBuf,
+}
+
+impl KeyStore {
+    /// Create a store over `path` (created on first append)
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Registry file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an event, if it is consistent with the registry so far
+    pub fn append(&self, event: &KeyEvent) -> Result<KeyRegistry, KeyStoreError> {
+        let mut registry = self.registry()?;
+        registry.apply(event)?;
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(event)?)?;
+        file.sync_all()?;
+        Ok(registry)
+    }
+
+    /// All events, oldest first
+    pub fn events(&self) -> Result<Vec<KeyEvent>, KeyStoreError> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let mut events = Vec::new();
+        for line in BufReader::new(fs::File::open(&self.path)?).lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                events.push(serde_json::from_str(&line)?);
+            }
+        }
+        Ok(events)
+    }
+
+    /// Registry folded from the journal
+    pub fn registry(&self) -> Result<KeyRegistry, KeyStoreError> {
+        Ok(KeyRegistry::from_events(&self.events()?)?)
+    }
+}
+
+/// Key registry errors
+#[derive(Debug, thiserror::Error)]
+pub enum KeyStoreError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    Registry(#[from] LedgerError),
+
+    #[error("No system key configured (set BIBANK_SYSTEM_KEY)")]
+    NoSigner,
+
+    #[error("BIBANK_SYSTEM_KEY {0} is not the active SYSTEM key in the key registry")]
+    InactiveSigner(String),
+}
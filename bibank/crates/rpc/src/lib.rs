@@ -2,7 +2,19 @@
 //!
 //! This crate provides the CLI binary and command orchestration.
 
+pub mod checkpoint;
 pub mod commands;
 pub mod context;
+pub mod idempotency;
+pub mod keys;
+pub mod scheduler;
+pub mod screening;
+pub mod server;
+pub mod snapshot;
 
-pub use context::AppContext;
+pub use checkpoint::{CheckpointError, CheckpointStore};
+pub use context::{AppContext, CommitError};
+pub use keys::{KeyStore, KeyStoreError};
+pub use scheduler::{JobReport, SchedulerConfig};
+pub use server::{RpcError, RpcHandle, RpcRequest, RpcResponse};
+pub use snapshot::{Snapshot, SnapshotError, SnapshotStore};
@@ -1,9 +1,14 @@
 //! BiBank CLI - Main entry point
 
-use bibank_rpc::{commands, AppContext};
+use bibank_compliance::{ReviewDecision, UserStatus};
+use bibank_ledger::{AsOf, OperatorSigner, Signer};
+use bibank_matching::{CandleInterval, OrderType};
+use bibank_rpc::{commands, scheduler, server, AppContext, SchedulerConfig};
+use chrono::{DateTime, NaiveDate, Utc};
 use clap::{Parser, Subcommand};
 use rust_decimal::Decimal;
 use std::path::PathBuf;
+use std::time::Duration;
 use uuid::Uuid;
 
 #[derive(Parser)]
@@ -64,6 +69,38 @@ enum Commands {
         correlation_id: Option<String>,
     },
 
+    /// Reverse a committed entry with mirrored postings (requires multi-sig approval)
+    Reverse {
+        /// Sequence of the entry to reverse
+        sequence: u64,
+        /// Reason for the reversal
+        #[arg(long)]
+        reason: String,
+    },
+
+    /// Adjust a user's balance (requires multi-sig approval)
+    Adjust {
+        /// User ID
+        user: String,
+        /// Amount (negative to debit the user)
+        #[arg(allow_hyphen_values = true)]
+        amount: Decimal,
+        /// Asset/currency code
+        asset: String,
+        /// Why the adjustment is needed
+        #[arg(long)]
+        reason: String,
+        /// Optional correlation ID
+        #[arg(long)]
+        correlation_id: Option<String>,
+    },
+
+    /// Review entries awaiting multi-sig approval
+    Approval {
+        #[command(subcommand)]
+        action: ApprovalAction,
+    },
+
     /// Check balance for a user
     Balance {
         /// User ID
@@ -77,11 +114,111 @@ enum Commands {
         reset: bool,
     },
 
+    /// Manage state snapshots
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+
+    /// Durable event subscribers: lag and dead letters
+    Subscribers {
+        #[command(subcommand)]
+        action: SubscriberAction,
+    },
+
     /// Audit the ledger (verify hash chain)
     Audit {
         /// Also verify digital signatures
         #[arg(long)]
         verify_signatures: bool,
+        /// Only verify entries after the last trusted checkpoint
+        #[arg(long)]
+        from_checkpoint: bool,
+    },
+
+    /// Merkle checkpoints and inclusion proofs
+    Checkpoint {
+        #[command(subcommand)]
+        action: CheckpointAction,
+    },
+
+    /// Proof of reserves: liability sum trees and user inclusion proofs
+    Reserves {
+        #[command(subcommand)]
+        action: ReservesAction,
+    },
+
+    /// Run as a long-lived JSON-RPC server
+    Serve {
+        /// HTTP listen address
+        #[arg(long, default_value = server::DEFAULT_LISTEN_ADDR)]
+        listen: String,
+        /// Also serve line-delimited JSON-RPC on this Unix socket
+        #[arg(long)]
+        socket: Option<PathBuf>,
+        /// Seconds between interest accrual checks (0 disables)
+        #[arg(long, default_value_t = scheduler::DEFAULT_INTEREST_INTERVAL_SECS)]
+        interest_interval: u64,
+        /// Seconds between liquidation sweeps (0 disables)
+        #[arg(long, default_value_t = scheduler::DEFAULT_SWEEP_INTERVAL_SECS)]
+        sweep_interval: u64,
+        /// Seconds between compliance rule file checks (0 disables)
+        #[arg(long, default_value_t = scheduler::DEFAULT_RULES_INTERVAL_SECS)]
+        rules_interval: u64,
+        /// Seconds between review case escalation checks (0 disables)
+        #[arg(long, default_value_t = scheduler::DEFAULT_ESCALATION_INTERVAL_SECS)]
+        escalation_interval: u64,
+    },
+
+    /// Run scheduled jobs by hand
+    Jobs {
+        #[command(subcommand)]
+        action: JobAction,
+    },
+
+    /// Manage compliance rule files
+    Rules {
+        #[command(subcommand)]
+        action: RulesAction,
+    },
+
+    /// Review cases for flagged transactions
+    Cases {
+        #[command(subcommand)]
+        action: CaseAction,
+    },
+
+    /// Manage registered users: KYC tiers and account status
+    User {
+        #[command(subcommand)]
+        action: UserAction,
+    },
+
+    /// Generate a regulatory report (ctr or sar) for a date range
+    Report {
+        /// Report type: ctr (currency transactions) or sar (suspicious activity drafts)
+        kind: String,
+        /// First day (YYYY-MM-DD, UTC)
+        #[arg(long)]
+        from: NaiveDate,
+        /// Last day, inclusive (defaults to --from)
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        /// Output format: json or csv
+        #[arg(long, default_value = "json")]
+        format: String,
+        /// CTR threshold (defaults to the compliance config's)
+        #[arg(long)]
+        threshold: Option<Decimal>,
+        /// Write to a file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Financial statements from the ledger
+    Statement {
+        #[command(subcommand)]
+        action: StatementAction,
     },
 
     // === Phase 2: Trade and Fee ===
@@ -130,11 +267,19 @@ enum Commands {
 
     /// Generate a new system key
     Keygen {
+        #[command(subcommand)]
+        action: Option<KeygenAction>,
         /// Output file path
         #[arg(long, default_value = "system.key")]
         output: PathBuf,
     },
 
+    /// Signer key registry: validity of keys by sequence
+    Keys {
+        #[command(subcommand)]
+        action: KeysAction,
+    },
+
     // === Phase 2.1: Trade History ===
 
     /// List trade history
@@ -153,6 +298,39 @@ enum Commands {
         limit: u32,
     },
 
+    /// OHLCV candles for a trading pair
+    Candles {
+        /// Base asset (e.g., BTC)
+        base: String,
+        /// Quote asset (e.g., USDT)
+        quote: String,
+        /// Candle width: 1m, 5m, 1h or 1d
+        #[arg(long, default_value = "1m")]
+        interval: CandleInterval,
+        /// Start of the range (RFC 3339)
+        #[arg(long)]
+        from: DateTime<Utc>,
+        /// End of the range, exclusive (RFC 3339, defaults to now)
+        #[arg(long)]
+        to: Option<DateTime<Utc>>,
+        /// Output format: table, csv or json
+        #[arg(long, default_value = "table")]
+        format: String,
+        /// Write to a file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Preview a user's maker/taker fee rates on a trading pair
+    Fees {
+        /// User ID
+        user: String,
+        /// Base asset (e.g., BTC)
+        base: String,
+        /// Quote asset (e.g., USDT)
+        quote: String,
+    },
+
     // === Phase 3: Margin Trading ===
 
     /// Borrow funds (margin trading)
@@ -195,6 +373,29 @@ enum Commands {
         price: Decimal,
         /// Quantity (in base asset)
         quantity: Decimal,
+        /// Order type: limit, ioc, fok or post-only
+        #[arg(long, default_value = "limit")]
+        order_type: String,
+        /// Optional correlation ID
+        #[arg(long)]
+        correlation_id: Option<String>,
+    },
+
+    /// Place a market order with slippage protection
+    MarketOrder {
+        /// User ID
+        user: String,
+        /// Order side: buy or sell
+        side: String,
+        /// Base asset (e.g., BTC)
+        base: String,
+        /// Quote asset (e.g., USDT)
+        quote: String,
+        /// Quantity (in base asset)
+        quantity: Decimal,
+        /// Maximum slippage from the best price (0.01 = 1%)
+        #[arg(long, default_value = "0.01")]
+        max_slippage: Decimal,
         /// Optional correlation ID
         #[arg(long)]
         correlation_id: Option<String>,
@@ -231,6 +432,374 @@ enum Commands {
     },
 }
 
+#[derive(Subcommand)]
+enum CheckpointAction {
+    /// Checkpoint the entries since the last checkpoint
+    Create,
+
+    /// List checkpoints
+    List,
+
+    /// Print an inclusion proof for an entry
+    Prove {
+        /// Entry sequence
+        sequence: u64,
+    },
+}
+
+#[derive(Subcommand)]
+enum StatementAction {
+    /// Trial balance per asset
+    TrialBalance {
+        /// Include entries up to this sequence
+        #[arg(long, conflicts_with = "at")]
+        sequence: Option<u64>,
+        /// Include entries committed at or before this time (RFC 3339)
+        #[arg(long)]
+        at: Option<DateTime<Utc>>,
+        /// Output format: table, csv or json
+        #[arg(long, default_value = "table")]
+        format: String,
+        /// Write to a file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Balance sheet per asset (fails if assets != liabilities + equity)
+    BalanceSheet {
+        /// Include entries up to this sequence
+        #[arg(long, conflicts_with = "at")]
+        sequence: Option<u64>,
+        /// Include entries committed at or before this time (RFC 3339)
+        #[arg(long)]
+        at: Option<DateTime<Utc>>,
+        /// Output format: table, csv or json
+        #[arg(long, default_value = "table")]
+        format: String,
+        /// Write to a file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Income statement per asset for a period
+    Income {
+        /// First day (YYYY-MM-DD, UTC)
+        #[arg(long)]
+        from: NaiveDate,
+        /// Last day, inclusive (defaults to --from)
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        /// Output format: table, csv or json
+        #[arg(long, default_value = "table")]
+        format: String,
+        /// Write to a file instead of stdout
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReservesAction {
+    /// Publish liability roots and totals, checked against the vaults
+    Export {
+        /// Journal sequence to take balances at (defaults to the latest)
+        #[arg(long)]
+        sequence: Option<u64>,
+        /// Directory for reserves.json and per-user proofs
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Print a user's inclusion proofs
+    Prove {
+        /// User ID
+        user: String,
+        /// Journal sequence to take balances at (defaults to the latest)
+        #[arg(long)]
+        sequence: Option<u64>,
+    },
+
+    /// Verify a user's inclusion proofs (offline)
+    Verify {
+        /// Proof file from `reserves prove` or `reserves export`
+        proof: PathBuf,
+        /// Published reserves.json to check the roots and totals against
+        #[arg(long)]
+        published: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum KeygenAction {
+    /// Replace the system key from the next entry on (the current
+    /// BIBANK_SYSTEM_KEY signs the hand-off)
+    Rotate {
+        /// Output file path for the new key
+        #[arg(long, default_value = "system.next.key")]
+        output: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum KeysAction {
+    /// Show every key and the sequences it is valid for
+    List,
+
+    /// Activate a signer's key (e.g. an approver)
+    Activate {
+        /// Signer ID
+        signer: String,
+        /// Public key (hex)
+        public_key: String,
+        /// First valid sequence (defaults to the next entry)
+        #[arg(long)]
+        from_sequence: Option<u64>,
+    },
+
+    /// Revoke a key
+    Revoke {
+        /// Signer ID
+        signer: String,
+        /// Public key (hex)
+        public_key: String,
+        /// Why the key is revoked
+        #[arg(long)]
+        reason: String,
+        /// First invalid sequence (defaults to the next entry)
+        #[arg(long)]
+        from_sequence: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum SubscriberAction {
+    /// Show each subscriber's checkpoint and lag behind the journal
+    List,
+
+    /// Show entries a subscriber gave up on
+    DeadLetters {
+        /// Subscriber name
+        name: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum SnapshotAction {
+    /// Snapshot the current state
+    Create,
+
+    /// List snapshots on disk
+    List,
+
+    /// Verify a snapshot against the hash chain and a full replay
+    Verify {
+        /// Snapshot sequence (defaults to the latest)
+        sequence: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ApprovalAction {
+    /// List pending approvals
+    List {
+        /// Include approved, rejected and expired approvals
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Sign a pending approval (commits the entry once enough approvers sign)
+    Sign {
+        /// Approval ID
+        id: String,
+        /// Approver ID
+        #[arg(long)]
+        signer: String,
+        /// File holding the approver's key (defaults to BIBANK_APPROVER_KEY)
+        #[arg(long)]
+        key_file: Option<PathBuf>,
+    },
+
+    /// Reject a pending approval (signed like an approval)
+    Reject {
+        /// Approval ID
+        id: String,
+        /// Approver ID
+        #[arg(long)]
+        signer: String,
+        /// File holding the approver's key (defaults to BIBANK_APPROVER_KEY)
+        #[arg(long)]
+        key_file: Option<PathBuf>,
+        /// Reason for rejection
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Generate an approver key
+    Keygen {
+        /// Approver ID
+        signer: String,
+        /// Output file path
+        #[arg(long, default_value = "approver.key")]
+        output: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum JobAction {
+    /// Accrue one day of interest on every loan
+    Interest {
+        /// Accrual date (defaults to today, UTC)
+        #[arg(long)]
+        date: Option<NaiveDate>,
+        /// Report what would be committed without committing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Liquidate every portfolio below the liquidation threshold
+    Liquidate {
+        /// User credited with liquidation bonuses
+        #[arg(long, default_value = scheduler::SWEEP_LIQUIDATOR)]
+        liquidator: String,
+        /// Report what would be committed without committing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Sweep rounding residue from LOCKED balances into the dust accounts
+    DustSweep {
+        /// Report what would be committed without committing
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum RulesAction {
+    /// Compile a rule file and report errors without activating it
+    Check {
+        /// Rule file path
+        file: PathBuf,
+    },
+
+    /// Activate the data directory's rule file if it changed
+    Reload {
+        /// Recorded as the operator who activated the rules
+        #[arg(long, default_value = "cli")]
+        performed_by: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CaseAction {
+    /// List open cases, oldest expiry first
+    List {
+        /// Only cases at this approval level (l1-l4)
+        #[arg(long)]
+        level: Option<String>,
+        /// Include closed cases
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Assign a case to a reviewer
+    Assign {
+        /// Flag ID
+        id: String,
+        /// Reviewer ID
+        reviewer: String,
+        /// Who made the assignment (defaults to the reviewer)
+        #[arg(long)]
+        by: Option<String>,
+    },
+
+    /// Approve a case, releasing the locked funds
+    Approve {
+        /// Flag ID
+        id: String,
+        /// Reviewer ID
+        #[arg(long)]
+        reviewer: String,
+        #[arg(long, default_value = "")]
+        notes: String,
+    },
+
+    /// Reject a case, reversing the flagged transaction
+    Reject {
+        /// Flag ID
+        id: String,
+        /// Reviewer ID
+        #[arg(long)]
+        reviewer: String,
+        #[arg(long, default_value = "")]
+        notes: String,
+    },
+
+    /// Escalate cases past their expiry to the next approval level
+    Escalate,
+}
+
+#[derive(Subcommand)]
+enum UserAction {
+    /// Register a user
+    Register {
+        /// User ID (will be uppercased)
+        user: String,
+        /// KYC tier (0-4)
+        #[arg(long)]
+        tier: u8,
+        /// Onboarding date (YYYY-MM-DD, defaults to today)
+        #[arg(long)]
+        onboarded: Option<NaiveDate>,
+        /// Operator ID
+        #[arg(long, default_value = "cli")]
+        by: String,
+    },
+
+    /// Change a user's KYC tier
+    SetTier {
+        user: String,
+        /// New KYC tier (0-4)
+        tier: u8,
+        #[arg(long)]
+        reason: String,
+        #[arg(long, default_value = "cli")]
+        by: String,
+    },
+
+    /// Freeze a user, blocking every debit from their accounts
+    Freeze {
+        user: String,
+        #[arg(long)]
+        reason: String,
+        #[arg(long, default_value = "cli")]
+        by: String,
+    },
+
+    /// Lift a freeze
+    Unfreeze {
+        user: String,
+        #[arg(long)]
+        reason: String,
+        #[arg(long, default_value = "cli")]
+        by: String,
+    },
+
+    /// Close a user (final; debits stay blocked)
+    Close {
+        user: String,
+        #[arg(long)]
+        reason: String,
+        #[arg(long, default_value = "cli")]
+        by: String,
+    },
+
+    /// Show a registered user
+    Show { user: String },
+
+    /// List registered users
+    List,
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Initialize tracing
@@ -238,6 +807,14 @@ async fn main() -> anyhow::Result<()> {
 
     let cli = Cli::parse();
 
+    // Verifying a liability proof needs no data directory
+    if let Commands::Reserves {
+        action: ReservesAction::Verify { proof, published },
+    } = &cli.command
+    {
+        return commands::reserves_verify(proof, published.as_deref());
+    }
+
     // Create application context
     let mut ctx = AppContext::new(&cli.data).await?;
 
@@ -278,6 +855,47 @@ async fn main() -> anyhow::Result<()> {
             commands::withdraw(&mut ctx, &user, amount, &asset, &correlation_id).await?;
         }
 
+        Commands::Adjust {
+            user,
+            amount,
+            asset,
+            reason,
+            correlation_id,
+        } => {
+            let correlation_id = correlation_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+            commands::adjust(&mut ctx, &user, amount, &asset, &reason, &correlation_id).await?;
+        }
+
+        Commands::Reverse { sequence, reason } => {
+            commands::reverse(&mut ctx, sequence, &reason).await?;
+        }
+
+        Commands::Approval { action } => match action {
+            ApprovalAction::List { all } => commands::approval_list(&ctx, all)?,
+            ApprovalAction::Sign { id, signer, key_file } => {
+                let signer = approver_key(signer, key_file)?;
+                commands::approval_sign(&mut ctx, &id, &signer).await?;
+            }
+            ApprovalAction::Reject {
+                id,
+                signer,
+                key_file,
+                reason,
+            } => {
+                let signer = approver_key(signer, key_file)?;
+                commands::reject_approval(&ctx, &id, &signer, reason.as_deref())?;
+            }
+            ApprovalAction::Keygen { signer, output } => {
+                let key = OperatorSigner::generate(&signer);
+                std::fs::write(&output, key.seed_hex())?;
+                println!("✅ Generated approver key for {}", signer);
+                println!("   Private key saved to: {}", output.display());
+                println!("   Public key: {}", key.public_key_hex());
+                println!();
+                println!("Register with: BIBANK_APPROVERS={}={}", signer, key.public_key_hex());
+            }
+        },
+
         Commands::Balance { user } => {
             commands::balance(&ctx, &user).await?;
         }
@@ -305,15 +923,106 @@ async fn main() -> anyhow::Result<()> {
             );
         }
 
-        Commands::Audit { verify_signatures } => {
+        Commands::Snapshot { action } => match action {
+            SnapshotAction::Create => commands::snapshot_create(&mut ctx)?,
+            SnapshotAction::List => commands::snapshot_list(&ctx)?,
+            SnapshotAction::Verify { sequence } => commands::snapshot_verify(&ctx, sequence)?,
+        },
+
+        Commands::Subscribers { action } => match action {
+            SubscriberAction::List => commands::subscriber_list(&ctx)?,
+            SubscriberAction::DeadLetters { name } => commands::subscriber_dead_letters(&ctx, &name)?,
+        },
+
+        Commands::Keys { action } => match action {
+            KeysAction::List => commands::keys_list(&ctx)?,
+            KeysAction::Activate {
+                signer,
+                public_key,
+                from_sequence,
+            } => {
+                let event = ctx.activate_key(&signer, &public_key, from_sequence)?;
+                println!("✅ Activated key for {}: {}", signer, public_key);
+                if let bibank_ledger::KeyEvent::Activated { from_sequence, .. } = event {
+                    println!("   Valid from sequence {}", from_sequence);
+                }
+            }
+            KeysAction::Revoke {
+                signer,
+                public_key,
+                reason,
+                from_sequence,
+            } => {
+                let event = ctx.revoke_key(&signer, &public_key, from_sequence, &reason)?;
+                println!("✅ Revoked key for {}: {}", signer, public_key);
+                if let bibank_ledger::KeyEvent::Revoked { from_sequence, .. } = event {
+                    println!("   Invalid from sequence {}", from_sequence);
+                }
+            }
+        },
+
+        Commands::Checkpoint { action } => match action {
+            CheckpointAction::Create => commands::checkpoint_create(&mut ctx)?,
+            CheckpointAction::List => commands::checkpoint_list(&ctx)?,
+            CheckpointAction::Prove { sequence } => commands::checkpoint_prove(&ctx, sequence)?,
+        },
+
+        Commands::Reserves { action } => match action {
+            ReservesAction::Export { sequence, output } => {
+                commands::reserves_export(&ctx, sequence, output.as_deref())?
+            }
+            ReservesAction::Prove { user, sequence } => commands::reserves_prove(&ctx, &user, sequence)?,
+            ReservesAction::Verify { .. } => unreachable!("handled before the context is created"),
+        },
+
+        Commands::Audit {
+            verify_signatures,
+            from_checkpoint,
+        } => {
             use bibank_events::EventReader;
-            use bibank_ledger::hash::verify_chain;
+            use bibank_ledger::hash::{verify_chain, verify_chain_from};
 
             let reader = EventReader::from_directory(ctx.journal_path())?;
-            let entries = reader.read_all()?;
+
+            let keys = ctx.keys.registry()?;
+
+            // Entries up to a trusted checkpoint are taken as verified
+            let checkpoint = if from_checkpoint {
+                let checkpoint = if keys.is_empty() {
+                    let public_key = ctx.signer.as_ref().map(|s| s.public_key_hex());
+                    ctx.checkpoints.latest_trusted(&reader, public_key.as_deref())
+                } else {
+                    ctx.checkpoints.latest_trusted_by(&reader, &keys)
+                };
+                match &checkpoint {
+                    Some(c) => println!(
+                        "Starting from checkpoint {}..{} (root {})",
+                        c.from_sequence,
+                        c.to_sequence,
+                        &c.root[..16]
+                    ),
+                    None => println!("⚠️  No trusted checkpoint, auditing from genesis"),
+                }
+                checkpoint
+            } else {
+                None
+            };
+
+            let (entries, chain) = match &checkpoint {
+                Some(c) => {
+                    let entries = reader.read_from(c.to_sequence + 1)?;
+                    let chain = verify_chain_from(&entries, &c.last_hash);
+                    (entries, chain)
+                }
+                None => {
+                    let entries = reader.read_all()?;
+                    let chain = verify_chain(&entries);
+                    (entries, chain)
+                }
+            };
 
             // Verify hash chain
-            match verify_chain(&entries) {
+            match chain {
                 Ok(()) => {
                     println!("✅ Hash chain verified ({} entries)", entries.len());
                 }
@@ -325,14 +1034,29 @@ async fn main() -> anyhow::Result<()> {
 
             // Verify signatures if requested
             if verify_signatures {
+                if keys.is_empty() {
+                    println!("⚠️  Key registry is empty: signatures only checked against their embedded keys");
+                }
                 let mut signed_count = 0;
                 let mut unsigned_count = 0;
+                let mut approved_count = 0;
 
                 for entry in &entries {
+                    if !entry.approvers().is_empty() {
+                        approved_count += 1;
+                    } else if entry.intent == bibank_ledger::TransactionIntent::Adjustment {
+                        println!("⚠️  Adjustment at seq {} has no approver signatures", entry.sequence);
+                    }
+
                     if entry.signatures.is_empty() {
                         unsigned_count += 1;
                     } else {
-                        match entry.verify_signatures() {
+                        let verified = if keys.is_empty() {
+                            entry.verify_signatures()
+                        } else {
+                            keys.verify_entry(entry)
+                        };
+                        match verified {
                             Ok(()) => signed_count += 1,
                             Err(e) => {
                                 println!("❌ Signature verification failed at seq {}: {}", entry.sequence, e);
@@ -343,9 +1067,118 @@ async fn main() -> anyhow::Result<()> {
                 }
 
                 println!("✅ Signatures verified: {} signed, {} unsigned (Phase 1)", signed_count, unsigned_count);
+                println!("   {} entries carry approver signatures", approved_count);
+                if !keys.is_empty() {
+                    println!("   Every signing key was valid at its entry ({} registered keys)", keys.len());
+                }
             }
         }
 
+        Commands::Serve {
+            listen,
+            socket,
+            interest_interval,
+            sweep_interval,
+            rules_interval,
+            escalation_interval,
+        } => {
+            let every = |secs: u64| (secs > 0).then(|| Duration::from_secs(secs));
+            let config = SchedulerConfig {
+                interest_interval: every(interest_interval),
+                sweep_interval: every(sweep_interval),
+                rules_interval: every(rules_interval),
+                escalation_interval: every(escalation_interval),
+            };
+            serve(ctx, &listen, socket, config).await?;
+        }
+
+        Commands::Jobs { action } => match action {
+            JobAction::Interest { date, dry_run } => {
+                let date = date.unwrap_or_else(|| Utc::now().date_naive());
+                commands::interest_accrual(&mut ctx, date, dry_run).await?;
+            }
+            JobAction::Liquidate { liquidator, dry_run } => {
+                commands::liquidation_sweep(&mut ctx, &liquidator, dry_run).await?;
+            }
+            JobAction::DustSweep { dry_run } => commands::dust_sweep(&mut ctx, dry_run).await?,
+        },
+
+        Commands::Rules { action } => match action {
+            RulesAction::Check { file } => commands::rules_check(&ctx, &file)?,
+            RulesAction::Reload { performed_by } => commands::rules_reload(&mut ctx, &performed_by).await?,
+        },
+
+        Commands::Cases { action } => match action {
+            CaseAction::List { level, all } => commands::case_list(&ctx, level.as_deref(), all).await?,
+            CaseAction::Assign { id, reviewer, by } => {
+                let by = by.unwrap_or_else(|| reviewer.clone());
+                commands::case_assign(&mut ctx, &id, &reviewer, &by).await?;
+            }
+            CaseAction::Approve { id, reviewer, notes } => {
+                commands::case_resolve(&mut ctx, &id, ReviewDecision::Approved, &reviewer, &notes).await?;
+            }
+            CaseAction::Reject { id, reviewer, notes } => {
+                commands::case_resolve(&mut ctx, &id, ReviewDecision::Rejected, &reviewer, &notes).await?;
+            }
+            CaseAction::Escalate => commands::case_escalate(&mut ctx).await?,
+        },
+
+        Commands::User { action } => match action {
+            UserAction::Register {
+                user,
+                tier,
+                onboarded,
+                by,
+            } => {
+                let onboarded = onboarded.unwrap_or_else(|| Utc::now().date_naive());
+                commands::user_register(&mut ctx, &user, tier, onboarded, &by).await?;
+            }
+            UserAction::SetTier { user, tier, reason, by } => {
+                commands::user_set_tier(&mut ctx, &user, tier, &reason, &by).await?;
+            }
+            UserAction::Freeze { user, reason, by } => {
+                commands::user_set_status(&mut ctx, &user, UserStatus::Frozen, &reason, &by).await?;
+            }
+            UserAction::Unfreeze { user, reason, by } => {
+                commands::user_set_status(&mut ctx, &user, UserStatus::Active, &reason, &by).await?;
+            }
+            UserAction::Close { user, reason, by } => {
+                commands::user_set_status(&mut ctx, &user, UserStatus::Closed, &reason, &by).await?;
+            }
+            UserAction::Show { user } => commands::users(&ctx, Some(&user)).await?,
+            UserAction::List => commands::users(&ctx, None).await?,
+        },
+
+        Commands::Report {
+            kind,
+            from,
+            to,
+            format,
+            threshold,
+            output,
+        } => {
+            let to = to.unwrap_or(from);
+            commands::report(&ctx, &kind, from, to, &format, threshold, output.as_deref()).await?;
+        }
+
+        Commands::Statement { action } => match action {
+            StatementAction::TrialBalance {
+                sequence,
+                at,
+                format,
+                output,
+            } => commands::trial_balance(&ctx, as_of(sequence, at), &format, output.as_deref())?,
+            StatementAction::BalanceSheet {
+                sequence,
+                at,
+                format,
+                output,
+            } => commands::balance_sheet(&ctx, as_of(sequence, at), &format, output.as_deref())?,
+            StatementAction::Income { from, to, format, output } => {
+                commands::income_statement(&ctx, from, to.unwrap_or(from), &format, output.as_deref())?
+            }
+        },
+
         Commands::Trade {
             maker,
             taker,
@@ -387,8 +1220,13 @@ async fn main() -> anyhow::Result<()> {
             commands::fee(&mut ctx, &user, amount, &asset, &fee_type, &correlation_id).await?;
         }
 
-        Commands::Keygen { output } => {
-            use bibank_ledger::{Signer, SystemSigner};
+        Commands::Keygen {
+            action: Some(KeygenAction::Rotate { output }),
+            ..
+        } => commands::keygen_rotate(&mut ctx, &output)?,
+
+        Commands::Keygen { action: None, output } => {
+            use bibank_ledger::SystemSigner;
 
             let signer = SystemSigner::generate();
             let seed = signer.seed_hex();
@@ -398,7 +1236,7 @@ async fn main() -> anyhow::Result<()> {
             println!("✅ Generated system key");
             println!("   Private key saved to: {}", output.display());
             println!("   Public key: {}", pubkey);
-            println!("");
+            println!();
             println!("To use: export BIBANK_SYSTEM_KEY={}", seed);
         }
 
@@ -415,6 +1253,21 @@ async fn main() -> anyhow::Result<()> {
             commands::trades(&ctx, user.as_deref(), pair, limit).await?;
         }
 
+        Commands::Candles {
+            base,
+            quote,
+            interval,
+            from,
+            to,
+            format,
+            output,
+        } => {
+            let to = to.unwrap_or_else(Utc::now);
+            commands::candles(&ctx, &base, &quote, interval, from, to, &format, output.as_deref()).await?;
+        }
+
+        Commands::Fees { user, base, quote } => commands::fee_preview(&ctx, &user, &base, &quote).await?,
+
         // === Phase 3: Margin Trading ===
 
         Commands::Borrow {
@@ -444,9 +1297,14 @@ async fn main() -> anyhow::Result<()> {
             quote,
             price,
             quantity,
+            order_type,
             correlation_id,
         } => {
             let correlation_id = correlation_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+            let order_type: OrderType = order_type.parse()?;
+            if matches!(order_type, OrderType::Market { .. }) {
+                anyhow::bail!("Use market-order to place market orders");
+            }
             commands::place_order(
                 &mut ctx,
                 &user,
@@ -455,6 +1313,30 @@ async fn main() -> anyhow::Result<()> {
                 &quote,
                 price,
                 quantity,
+                order_type,
+                &correlation_id,
+            ).await?;
+        }
+
+        Commands::MarketOrder {
+            user,
+            side,
+            base,
+            quote,
+            quantity,
+            max_slippage,
+            correlation_id,
+        } => {
+            let correlation_id = correlation_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+            commands::place_order(
+                &mut ctx,
+                &user,
+                &side,
+                &base,
+                &quote,
+                Decimal::ZERO,
+                quantity,
+                OrderType::Market { max_slippage },
                 &correlation_id,
             ).await?;
         }
@@ -480,3 +1362,62 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Statement cutoff from --sequence / --at (clap keeps them exclusive)
+/// Load an approver's key from `key_file` or BIBANK_APPROVER_KEY
+fn approver_key(signer: String, key_file: Option<PathBuf>) -> anyhow::Result<OperatorSigner> {
+    let seed = match key_file {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => std::env::var("BIBANK_APPROVER_KEY")
+            .map_err(|_| anyhow::anyhow!("Pass --key-file or set BIBANK_APPROVER_KEY"))?,
+    };
+    Ok(OperatorSigner::from_hex(signer, &seed)?)
+}
+
+fn as_of(sequence: Option<u64>, at: Option<DateTime<Utc>>) -> Option<AsOf> {
+    sequence.map(AsOf::Sequence).or(at.map(AsOf::Time))
+}
+
+/// Run the JSON-RPC server until Ctrl-C
+async fn serve(
+    ctx: AppContext,
+    listen: &str,
+    socket: Option<PathBuf>,
+    config: SchedulerConfig,
+) -> anyhow::Result<()> {
+    let (handle, writer) = server::spawn_writer(ctx);
+    let jobs = scheduler::spawn_scheduler(handle.clone(), config);
+
+    let listener = tokio::net::TcpListener::bind(listen).await?;
+    println!("🚀 JSON-RPC listening on http://{}", listener.local_addr()?);
+    let mut transports = vec![tokio::spawn(server::serve_http(listener, handle.clone()))];
+
+    if let Some(path) = socket {
+        #[cfg(unix)]
+        {
+            // A socket file left behind by a previous run blocks bind
+            if path.exists() {
+                std::fs::remove_file(&path)?;
+            }
+            let listener = tokio::net::UnixListener::bind(&path)?;
+            println!("🚀 JSON-RPC listening on unix:{}", path.display());
+            transports.push(tokio::spawn(server::serve_unix(listener, handle.clone())));
+        }
+        #[cfg(not(unix))]
+        anyhow::bail!("Unix sockets are not supported on this platform: {}", path.display());
+    }
+
+    tokio::signal::ctrl_c().await?;
+    println!("Shutting down...");
+
+    // Stop accepting requests, then let the writer drain what is queued
+    jobs.abort();
+    for transport in &transports {
+        transport.abort();
+    }
+    handle.shutdown().await;
+    let ctx = writer.await?;
+    println!("✅ Stopped at sequence {}", ctx.last_sequence());
+
+    Ok(())
+}
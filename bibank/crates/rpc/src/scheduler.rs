@@ -0,0 +1,406 @@
+//! Scheduled jobs - interest accrual, liquidation sweeps, rule reloads and
+//! review case escalation
+//!
+//! Jobs commit through [`AppContext::commit`] like any other command, and
+//! stamp `job` and `run_id` into each entry's metadata. Interest accrual is
+//! idempotent per day and per loan: its correlation ids are
+//! `interest-<date>-<USER>-<ASSET>`, and loans already accrued for the day
+//! are skipped. A liquidation sweep values every borrower's portfolio
+//! through the oracle and liquidates those below the threshold. A dust
+//! sweep moves rounding residue - LOCKED balances no open order backs - to
+//! the `REV:SYSTEM:DUST` accounts, and returns larger unbacked locks to the
+//! user's AVAILABLE balance.
+//!
+//! In serve mode, [`spawn_scheduler`] triggers both jobs through the writer
+//! task, so they are serialized with client requests. It also reloads the
+//! compliance rule file, so edits take effect without a restart, and
+//! escalates review cases left open past their expiry.
+
+use bibank_core::{Amount, Currency, RoundingMode};
+use bibank_ledger::{validate_intent, AccountKey, JournalEntryBuilder, TransactionIntent, UnsignedEntry};
+use bibank_matching::OrderSide;
+use bibank_risk::{InterestCalculator, LiquidationEngine, DEFAULT_QUOTE_ASSET};
+use chrono::{NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::Interval;
+
+use crate::commands::{self, quote_amount};
+use crate::context::AppContext;
+use crate::server::{RpcHandle, RpcRequest};
+
+/// Seconds between liquidation sweeps in serve mode
+pub const DEFAULT_SWEEP_INTERVAL_SECS: u64 = 60;
+
+/// Seconds between interest accrual checks in serve mode (accrues once per day)
+pub const DEFAULT_INTEREST_INTERVAL_SECS: u64 = 3600;
+
+/// Seconds between compliance rule file checks in serve mode
+pub const DEFAULT_RULES_INTERVAL_SECS: u64 = 5;
+
+/// Seconds between review case escalation checks in serve mode
+pub const DEFAULT_ESCALATION_INTERVAL_SECS: u64 = 60;
+
+/// Account credited with liquidation bonuses from sweeps
+pub const SWEEP_LIQUIDATOR: &str = "LIQUIDATOR";
+
+/// Scheduled job kinds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Job {
+    InterestAccrual,
+    LiquidationSweep,
+    DustSweep,
+}
+
+impl Job {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Job::InterestAccrual => "interest_accrual",
+            Job::LiquidationSweep => "liquidation_sweep",
+            Job::DustSweep => "dust_sweep",
+        }
+    }
+}
+
+/// One entry produced by a job
+#[derive(Debug, Clone, Serialize)]
+pub struct JobEntry {
+    pub correlation_id: String,
+    pub user: String,
+    pub asset: String,
+    /// Interest accrued, loan repaid, dust swept or lock released
+    pub amount: String,
+    /// Committed sequence (None in dry-run mode)
+    pub sequence: Option<u64>,
+}
+
+/// Outcome of a job run
+#[derive(Debug, Clone, Serialize)]
+pub struct JobReport {
+    pub job: Job,
+    pub run_id: String,
+    pub dry_run: bool,
+    /// Entries committed (or that would be, in dry-run mode)
+    pub entries: Vec<JobEntry>,
+    /// Correlation ids already committed by an earlier run
+    pub skipped: Vec<String>,
+    /// Users or entries that failed, with the reason
+    pub failed: Vec<(String, String)>,
+}
+
+impl JobReport {
+    fn new(job: Job, run_id: String, dry_run: bool) -> Self {
+        Self {
+            job,
+            run_id,
+            dry_run,
+            entries: Vec::new(),
+            skipped: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+/// Stamp the job run into an entry's metadata
+fn tag(entry: &mut UnsignedEntry, job: Job, run_id: &str) {
+    entry.metadata.insert("job".to_string(), json!(job.as_str()));
+    entry.metadata.insert("run_id".to_string(), json!(run_id));
+}
+
+/// Accrue one day of interest on every loan
+///
+/// Loans already accrued for `date` are skipped, so the job can run any
+/// number of times a day.
+pub async fn run_interest_accrual(
+    ctx: &mut AppContext,
+    calculator: &InterestCalculator,
+    date: NaiveDate,
+    dry_run: bool,
+) -> Result<JobReport, anyhow::Error> {
+    let run_id = format!("interest-{}", date);
+    let mut report = JobReport::new(Job::InterestAccrual, run_id.clone(), dry_run);
+
+    let mut entries = calculator.generate_interest_entries(ctx.risk.state(), &run_id);
+    entries.sort_by(|a, b| a.correlation_id.cmp(&b.correlation_id));
+
+    for mut entry in entries {
+        if ctx.correlations().get(&entry.correlation_id).is_some() {
+            report.skipped.push(entry.correlation_id);
+            continue;
+        }
+        tag(&mut entry, Job::InterestAccrual, &run_id);
+        entry
+            .metadata
+            .insert("accrual_date".to_string(), json!(date.to_string()));
+
+        let loan = &entry.postings[0];
+        let mut job_entry = JobEntry {
+            correlation_id: entry.correlation_id.clone(),
+            user: loan.account.id.clone(),
+            asset: loan.account.asset.clone(),
+            amount: loan.amount.value().normalize().to_string(),
+            sequence: None,
+        };
+
+        if !dry_run {
+            match ctx.commit(entry).await {
+                Ok(committed) => job_entry.sequence = Some(committed.sequence),
+                Err(e) => {
+                    report.failed.push((job_entry.correlation_id, e.to_string()));
+                    continue;
+                }
+            }
+        }
+        report.entries.push(job_entry);
+    }
+
+    Ok(report)
+}
+
+/// Liquidate every portfolio below the liquidation threshold
+///
+/// Each borrower is valued through the context's oracle; one liquidation
+/// per user per run, seizing collateral as picked by the engine. Collateral
+/// locked in open orders counts towards the valuation but can only be
+/// seized from AVAILABLE, so an underwater user's resting orders are
+/// cancelled first (`<correlation_id>-cancel-<order_id>`) and the
+/// liquidation is planned again on the released balances.
+pub async fn run_liquidation_sweep(
+    ctx: &mut AppContext,
+    engine: &LiquidationEngine,
+    liquidator: &str,
+    dry_run: bool,
+) -> Result<JobReport, anyhow::Error> {
+    let run_id = format!("sweep-{}", Utc::now().format("%Y%m%dT%H%M%S%.3f"));
+    let mut report = JobReport::new(Job::LiquidationSweep, run_id.clone(), dry_run);
+
+    for user in ctx.risk.state().borrowers() {
+        let valuation = match ctx.risk.value(&user, ctx.oracle.as_ref(), DEFAULT_QUOTE_ASSET).await {
+            Ok(valuation) => valuation,
+            Err(e) => {
+                report.failed.push((user, e.to_string()));
+                continue;
+            }
+        };
+
+        let correlation_id = format!("liquidation-{}-{}", run_id, user);
+        let mut liquidation = engine.liquidate(&valuation, liquidator, &correlation_id);
+        if !dry_run && matches!(liquidation, Ok(Some(_))) {
+            match commands::cancel_user_orders(ctx, &user, &correlation_id).await {
+                Ok(0) => {}
+                Ok(_) => {
+                    liquidation = match ctx.risk.value(&user, ctx.oracle.as_ref(), DEFAULT_QUOTE_ASSET).await {
+                        Ok(valuation) => engine.liquidate(&valuation, liquidator, &correlation_id),
+                        Err(e) => {
+                            report.failed.push((user, e.to_string()));
+                            continue;
+                        }
+                    };
+                }
+                Err(e) => {
+                    report.failed.push((user, e.to_string()));
+                    continue;
+                }
+            }
+        }
+        let (mut entry, plan) = match liquidation {
+            Ok(Some(liquidation)) => liquidation,
+            Ok(None) => continue,
+            Err(e) => {
+                report.failed.push((user, e.to_string()));
+                continue;
+            }
+        };
+        tag(&mut entry, Job::LiquidationSweep, &run_id);
+
+        let mut job_entry = JobEntry {
+            correlation_id,
+            user: user.clone(),
+            asset: plan.loan_asset.clone(),
+            amount: plan.loan_repaid.normalize().to_string(),
+            sequence: None,
+        };
+
+        if !dry_run {
+            match ctx.commit(entry).await {
+                Ok(committed) => job_entry.sequence = Some(committed.sequence),
+                Err(e) => {
+                    report.failed.push((user, e.to_string()));
+                    continue;
+                }
+            }
+        }
+        report.entries.push(job_entry);
+    }
+
+    Ok(report)
+}
+
+/// Sweep rounding residue out of LOCKED balances
+///
+/// Open orders keep what backs their remaining quantity: the base for
+/// sells, the rounded-down quote value at the order price for buys. Up to
+/// one minimum unit above that is rounding residue and moves to the asset's
+/// dust account in a Fee entry. Anything larger is not dust, so it goes
+/// back to the user's AVAILABLE balance in an OrderCancel entry whose
+/// correlation id ends in `-release`.
+pub async fn run_dust_sweep(ctx: &mut AppContext, dry_run: bool) -> Result<JobReport, anyhow::Error> {
+    let run_id = format!("dust-{}", Utc::now().format("%Y%m%dT%H%M%S%.3f"));
+    let mut report = JobReport::new(Job::DustSweep, run_id.clone(), dry_run);
+
+    // Collateral still backing open orders, per LOCKED account
+    let mut backing: BTreeMap<String, Decimal> = BTreeMap::new();
+    for order in ctx.matching.resting_orders() {
+        let (asset, amount) = match order.side {
+            OrderSide::Buy => (&order.pair.quote, quote_amount(&order.pair.quote, order.price, order.remaining())),
+            OrderSide::Sell => (&order.pair.base, order.remaining()),
+        };
+        *backing.entry(AccountKey::user_locked(&order.user_id, asset).to_string()).or_default() += amount;
+    }
+
+    let mut residues: Vec<(String, String, Decimal)> = Vec::new();
+    for (key, &balance) in ctx.risk.state().all_balances() {
+        let parts: Vec<&str> = key.split(':').collect();
+        let [_, "USER", user, asset, "LOCKED"] = parts.as_slice() else {
+            continue;
+        };
+        let free = balance - backing.get(key).copied().unwrap_or(Decimal::ZERO);
+        let unbacked = Currency::from(*asset).round(free, RoundingMode::Floor);
+        if unbacked > Decimal::ZERO {
+            residues.push((user.to_string(), asset.to_string(), unbacked));
+        }
+    }
+    residues.sort();
+
+    for (user, asset, unbacked) in residues {
+        let locked = AccountKey::user_locked(&user, &asset);
+        let amount = Amount::new(unbacked)?;
+        let (correlation_id, builder) = if unbacked <= Currency::from(asset.as_str()).min_unit() {
+            let builder = JournalEntryBuilder::new()
+                .intent(TransactionIntent::Fee)
+                .debit(locked, amount)
+                .credit(AccountKey::dust(&asset), amount)
+                .metadata("fee_type", json!("rounding_dust"));
+            (format!("{}-{}-{}", run_id, user, asset), builder)
+        } else {
+            let builder = JournalEntryBuilder::new()
+                .intent(TransactionIntent::OrderCancel)
+                .debit(locked, amount)
+                .credit(AccountKey::user_available(&user, &asset), amount)
+                .metadata("unlock_asset", json!(asset))
+                .metadata("unlock_amount", json!(unbacked.to_string()));
+            (format!("{}-{}-{}-release", run_id, user, asset), builder)
+        };
+        let entry = builder
+            .correlation_id(&correlation_id)
+            .build_unsigned()
+            .and_then(|mut entry| {
+                validate_intent(&entry)?;
+                tag(&mut entry, Job::DustSweep, &run_id);
+                Ok(entry)
+            });
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                report.failed.push((correlation_id, e.to_string()));
+                continue;
+            }
+        };
+
+        let mut job_entry = JobEntry {
+            correlation_id,
+            user,
+            asset,
+            amount: unbacked.normalize().to_string(),
+            sequence: None,
+        };
+
+        if !dry_run {
+            match ctx.commit(entry).await {
+                Ok(committed) => job_entry.sequence = Some(committed.sequence),
+                Err(e) => {
+                    report.failed.push((job_entry.correlation_id, e.to_string()));
+                    continue;
+                }
+            }
+        }
+        report.entries.push(job_entry);
+    }
+
+    Ok(report)
+}
+
+/// Intervals for the serve-mode scheduler (None disables a job)
+#[derive(Debug, Clone)]
+pub struct SchedulerConfig {
+    pub interest_interval: Option<Duration>,
+    pub sweep_interval: Option<Duration>,
+    pub rules_interval: Option<Duration>,
+    pub escalation_interval: Option<Duration>,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            interest_interval: Some(Duration::from_secs(DEFAULT_INTEREST_INTERVAL_SECS)),
+            sweep_interval: Some(Duration::from_secs(DEFAULT_SWEEP_INTERVAL_SECS)),
+            rules_interval: Some(Duration::from_secs(DEFAULT_RULES_INTERVAL_SECS)),
+            escalation_interval: Some(Duration::from_secs(DEFAULT_ESCALATION_INTERVAL_SECS)),
+        }
+    }
+}
+
+/// Spawn the scheduler, triggering jobs through the writer task
+pub fn spawn_scheduler(handle: RpcHandle, config: SchedulerConfig) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interest = config.interest_interval.map(tokio::time::interval);
+        let mut sweep = config.sweep_interval.map(tokio::time::interval);
+        let mut rules = config.rules_interval.map(tokio::time::interval);
+        let mut escalation = config.escalation_interval.map(tokio::time::interval);
+
+        loop {
+            tokio::select! {
+                _ = tick(&mut interest) => trigger(&handle, "run_interest", Value::Null).await,
+                _ = tick(&mut sweep) => trigger(&handle, "run_liquidation_sweep", Value::Null).await,
+                _ = tick(&mut rules) => {
+                    trigger(&handle, "reload_rules", json!({ "performed_by": "scheduler" })).await
+                }
+                _ = tick(&mut escalation) => trigger(&handle, "escalate_cases", Value::Null).await,
+            }
+        }
+    })
+}
+
+/// Wait for the next tick (forever if the job is disabled)
+async fn tick(interval: &mut Option<Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+async fn trigger(handle: &RpcHandle, method: &'static str, params: Value) {
+    let response = handle.call(RpcRequest::new(method, params)).await;
+    if let Some(error) = response.error {
+        tracing::warn!("Scheduled {} failed: {}", method, error);
+    } else if let Some(result) = response.result {
+        if result["changed"].as_bool() == Some(true) {
+            tracing::info!("Scheduled {} activated rule set {}", method, result["hash"]);
+        }
+        let count = result["entries"].as_array().map_or(0, Vec::len);
+        if count > 0 {
+            tracing::info!("Scheduled {} committed {} entries", method, count);
+        }
+        let escalated = result["escalated"].as_array().map_or(0, Vec::len);
+        if escalated > 0 {
+            tracing::warn!("Scheduled {} escalated {} review cases", method, escalated);
+        }
+    }
+}
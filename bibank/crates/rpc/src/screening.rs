@@ -0,0 +1,359 @@
+//! AML screening of commits
+//!
+//! Customer fund movements (deposits, withdrawals and transfers) run through
+//! the [`TransactionExecutor`]: pre-validation hooks can block an entry
+//! before it reaches the journal, and post-commit hooks can flag it. Flagged
+//! funds are moved out of the receiving user's AVAILABLE account into
+//! REVIEW, where they sit until a compliance officer decides. Every decision
+//! is written to the Compliance Ledger (`compliance.jsonl` in the data
+//! directory). Thresholds are read from `compliance.json` there, if present.
+//!
+//! Rules written in the DSL's text format are read from `compliance.rules`
+//! and run alongside the built-in hooks. The file is reloaded while the
+//! server runs; each change is recorded in the Compliance Ledger as a
+//! `RuleSetChanged` event with the rule set's hash and approvers.
+//!
+//! Each flag opens a review case. Approving it releases the locked funds
+//! back to AVAILABLE; rejecting it reverses the flagged entry out of REVIEW.
+//!
+//! Withdrawals are held to the rolling daily and monthly limits of the user's
+//! KYC tier (`kyc_limits` in `compliance.json`, tier 0 for unregistered
+//! users), counted from the projection's withdrawal history and valued in
+//! `kyc_limit_asset` through the oracle.
+
+use bibank_compliance::{
+    Case, ComplianceConfig, ComplianceEngine, ComplianceError, ComplianceEvent, ComplianceLedger,
+    RuleAction,
+};
+use bibank_dsl::{PredicateRegistry, RuleParser, RuleSet, RuleSetHook};
+use bibank_hooks::{
+    ExecutionResult, ExecutorBuilder, HookContext, HookError, HookRegistry, HookResult, KycLimitHook,
+    LargeTxHook, NewAccountHook, PepCheckHook, SanctionsHook, TransactionExecutor, WithdrawalVolume,
+};
+use bibank_ledger::{
+    AccountCategory, AccountKey, JournalEntry, Posting, Side, TransactionIntent, UnsignedEntry,
+};
+use bibank_oracle::PriceOracle;
+use bibank_projection::UserProjection;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Intents screened by the hooks
+pub const SCREENED_INTENTS: [TransactionIntent; 3] = [
+    TransactionIntent::Deposit,
+    TransactionIntent::Withdrawal,
+    TransactionIntent::Transfer,
+];
+
+/// Correlation id prefix of review lock entries
+pub const REVIEW_LOCK_PREFIX: &str = "review-lock-";
+
+/// Correlation id prefix of entries releasing approved funds
+pub const REVIEW_RELEASE_PREFIX: &str = "review-release-";
+
+/// Correlation id prefix of entries reversing rejected transactions
+pub const REVIEW_REVERSAL_PREFIX: &str = "review-reversal-";
+
+/// Check if a correlation id is a review lock, release or reversal
+///
+/// These entries belong to their compliance case, which settles them.
+pub fn is_review_entry(correlation_id: &str) -> bool {
+    [REVIEW_LOCK_PREFIX, REVIEW_RELEASE_PREFIX, REVIEW_REVERSAL_PREFIX]
+        .iter()
+        .any(|prefix| correlation_id.starts_with(prefix))
+}
+
+/// Compliance Ledger file in the data directory
+pub const LEDGER_FILE: &str = "compliance.jsonl";
+
+/// Optional compliance thresholds file in the data directory
+pub const CONFIG_FILE: &str = "compliance.json";
+
+/// Optional rule file in the data directory
+pub const RULES_FILE: &str = "compliance.rules";
+
+/// Priority of the rule file hook (after the built-in hooks)
+pub const RULES_PRIORITY: u32 = 50;
+
+/// Priority of the KYC withdrawal limit hook (between sanctions and PEP)
+pub const KYC_LIMIT_PRIORITY: u32 = 15;
+
+/// Outcome of loading the rule file
+#[derive(Debug, Clone, Serialize)]
+pub struct RulesReload {
+    /// Rule set name (empty when there is no rule file)
+    pub name: String,
+    pub version: String,
+    /// Hash of the compiled rule set (None when there is no rule file)
+    pub hash: Option<String>,
+    /// Number of rules
+    pub rules: usize,
+    /// Whether the active rule set changed
+    pub changed: bool,
+}
+
+/// Compliance thresholds from `compliance.json` (defaults if missing)
+pub fn config(data_path: &Path) -> Result<ComplianceConfig, std::io::Error> {
+    let path = data_path.join(CONFIG_FILE);
+    if path.exists() {
+        ComplianceConfig::from_file(&path)
+    } else {
+        Ok(ComplianceConfig::default())
+    }
+}
+
+/// Named predicates rule files can use in `custom("...")` conditions
+pub fn predicates() -> PredicateRegistry {
+    PredicateRegistry::new()
+        .with("is_deposit", |ctx| ctx.intent == "Deposit")
+        .with("is_withdrawal", |ctx| ctx.intent == "Withdrawal")
+        .with("is_transfer", |ctx| ctx.intent == "Transfer")
+        .with("has_destination", |ctx| ctx.destination.is_some())
+}
+
+/// Compile a rule file (None if it doesn't exist)
+///
+/// Parse errors are returned as [`bibank_dsl::ParseError`], with the line and
+/// column of the problem.
+pub fn load_rules(path: &Path, predicates: &PredicateRegistry) -> Result<Option<RuleSet>, anyhow::Error> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let source = std::fs::read_to_string(path)?;
+    let rule_set = RuleParser::new(&source).with_predicates(predicates).parse()?;
+    Ok(Some(rule_set))
+}
+
+/// Latest rule set activation in the Compliance Ledger
+///
+/// None if no rule set was ever activated, or the last one was deactivated.
+pub fn active_rule_set(ledger: &ComplianceLedger) -> Result<Option<ComplianceEvent>, ComplianceError> {
+    let latest = ledger
+        .read_all()?
+        .into_iter()
+        .rfind(|event| matches!(event, ComplianceEvent::RuleSetChanged { .. }));
+    Ok(latest.filter(|event| {
+        matches!(
+            event,
+            ComplianceEvent::RuleSetChanged {
+                action: RuleAction::Activated,
+                ..
+            }
+        )
+    }))
+}
+
+/// Withdrawal history from the projection
+///
+/// Without a projection the limits can't be checked, so the hook fails and
+/// the registry's fail policy decides.
+pub struct ProjectedWithdrawals(pub Option<UserProjection>);
+
+#[async_trait::async_trait]
+impl WithdrawalVolume for ProjectedWithdrawals {
+    async fn withdrawn_since(&self, user_id: &str, since: DateTime<Utc>) -> HookResult<BTreeMap<String, Decimal>> {
+        let projection = self
+            .0
+            .as_ref()
+            .ok_or_else(|| HookError::Internal("projection unavailable".to_string()))?;
+        projection
+            .withdrawn_since(user_id, since)
+            .await
+            .map_err(|e| HookError::Internal(e.to_string()))
+    }
+}
+
+/// Screening pipeline with the built-in AML hooks and the rule file hook
+///
+/// Users listed in `BIBANK_WATCHLIST` (comma-separated) are blocked by the
+/// sanctions hook; thresholds, KYC limits and the hook failure policy come
+/// from `config`. Withdrawals are valued in the KYC limit asset through
+/// `oracle`.
+pub fn executor(
+    ledger_path: &Path,
+    config: ComplianceConfig,
+    rules: Arc<RuleSetHook>,
+    withdrawals: Arc<dyn WithdrawalVolume>,
+    oracle: Arc<dyn PriceOracle>,
+) -> Result<TransactionExecutor, ComplianceError> {
+    let sanctions = SanctionsHook::new(10);
+    if let Ok(watchlist) = std::env::var("BIBANK_WATCHLIST") {
+        for user in watchlist.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+            sanctions.add_to_watchlist(&user.to_uppercase());
+        }
+    }
+
+    let large_tx = config.large_tx_threshold;
+    let mut registry = HookRegistry::new().with_fail_policy(config.external_fail_policy);
+    registry.register_pre_hook(Arc::new(sanctions));
+    registry.register_pre_hook(Arc::new(KycLimitHook::new(
+        KYC_LIMIT_PRIORITY,
+        config.kyc_limits.clone(),
+        &config.kyc_limit_asset,
+        withdrawals,
+        oracle,
+    )));
+    registry.register_pre_hook(Arc::new(PepCheckHook::new(20, large_tx)));
+    registry.register_post_hook(Arc::new(NewAccountHook::new(
+        30,
+        config.new_account_days,
+        large_tx / Decimal::TWO,
+    )));
+    registry.register_post_hook(Arc::new(LargeTxHook::new(40, large_tx)));
+    registry.register_pre_hook(rules.clone());
+    registry.register_post_hook(rules);
+
+    let engine = ComplianceEngine::new(config, ComplianceLedger::new(ledger_path)?);
+    Ok(ExecutorBuilder::new()
+        .with_registry(registry)
+        .with_compliance_engine(engine)
+        .build())
+}
+
+/// Hook context for an entry (None if the intent isn't screened)
+///
+/// The user is the one whose funds leave (sender/withdrawer), or the one
+/// receiving them for deposits. Transfers name the receiver as destination.
+pub fn hook_context(unsigned: &UnsignedEntry) -> Option<HookContext> {
+    if !SCREENED_INTENTS.contains(&unsigned.intent) {
+        return None;
+    }
+
+    let user_postings: Vec<&Posting> = unsigned
+        .postings
+        .iter()
+        .filter(|p| is_user_funds(&p.account))
+        .collect();
+    let initiator = user_postings
+        .iter()
+        .find(|p| p.side == Side::Debit)
+        .or_else(|| user_postings.first())?;
+
+    let mut ctx = HookContext::new(
+        &unsigned.correlation_id,
+        &initiator.account.id,
+        format!("{:?}", unsigned.intent),
+        initiator.amount.value(),
+        &initiator.account.asset,
+    );
+
+    let receiver = user_postings
+        .iter()
+        .find(|p| p.side == Side::Credit && p.account.id != initiator.account.id);
+    if let Some(receiver) = receiver {
+        ctx = ctx.with_destination(&receiver.account.id);
+    } else if let Some(destination) = unsigned.metadata.get("destination").and_then(|v| v.as_str()) {
+        ctx = ctx.with_destination(destination);
+    }
+
+    Some(ctx)
+}
+
+/// Entry moving flagged funds into review
+///
+/// Whatever the flagged entry credited to users' AVAILABLE accounts is moved
+/// to their REVIEW accounts. Flagged withdrawals have already left, so there
+/// is nothing to lock (None).
+pub fn review_lock_entry(entry: &JournalEntry, result: &ExecutionResult) -> Option<UnsignedEntry> {
+    let mut postings = Vec::new();
+    for posting in &entry.postings {
+        if posting.side != Side::Credit || !is_user_funds(&posting.account) {
+            continue;
+        }
+        let user = &posting.account.id;
+        let asset = &posting.account.asset;
+        postings.push(Posting::debit(AccountKey::user_available(user, asset), posting.amount));
+        postings.push(Posting::credit(AccountKey::user_review(user, asset), posting.amount));
+    }
+    if postings.is_empty() {
+        return None;
+    }
+
+    let mut metadata = HashMap::new();
+    metadata.insert("compliance_lock".to_string(), json!(true));
+    metadata.insert("flagged_sequence".to_string(), json!(entry.sequence));
+    metadata.insert("rules_triggered".to_string(), json!(result.rules_triggered));
+    if let Some(flag_id) = &result.flag_id {
+        metadata.insert("flag_id".to_string(), json!(flag_id));
+    }
+
+    Some(UnsignedEntry {
+        intent: TransactionIntent::Transfer,
+        correlation_id: format!("{}{}", REVIEW_LOCK_PREFIX, entry.correlation_id),
+        causality_id: Some(entry.correlation_id.clone()),
+        postings,
+        metadata,
+    })
+}
+
+/// Entry releasing approved funds from REVIEW back to AVAILABLE
+///
+/// The mirror image of the review lock.
+pub fn release_entry(lock: &JournalEntry, case: &Case) -> UnsignedEntry {
+    let postings = lock
+        .postings
+        .iter()
+        .map(|p| Posting::new(p.account.clone(), p.amount, p.side.opposite()))
+        .collect();
+
+    UnsignedEntry {
+        intent: TransactionIntent::Transfer,
+        correlation_id: format!("{}{}", REVIEW_RELEASE_PREFIX, case.correlation_id),
+        causality_id: Some(case.correlation_id.clone()),
+        postings,
+        metadata: case_metadata(case, lock),
+    }
+}
+
+/// Entry reversing a rejected transaction out of REVIEW
+///
+/// Every posting of the flagged entry is undone; what it credited to users
+/// is taken from REVIEW, where the lock moved it. Deposits are reversed as
+/// withdrawals back out of the vault, transfers back to the sender. Other
+/// intents are never locked (None).
+pub fn reversal_entry(flagged: &JournalEntry, lock: &JournalEntry, case: &Case) -> Option<UnsignedEntry> {
+    let intent = match flagged.intent {
+        TransactionIntent::Deposit => TransactionIntent::Withdrawal,
+        TransactionIntent::Transfer => TransactionIntent::Transfer,
+        _ => return None,
+    };
+
+    let postings = flagged
+        .postings
+        .iter()
+        .map(|p| {
+            let account = if p.side == Side::Credit && is_user_funds(&p.account) {
+                AccountKey::user_review(&p.account.id, &p.account.asset)
+            } else {
+                p.account.clone()
+            };
+            Posting::new(account, p.amount, p.side.opposite())
+        })
+        .collect();
+
+    Some(UnsignedEntry {
+        intent,
+        correlation_id: format!("{}{}", REVIEW_REVERSAL_PREFIX, case.correlation_id),
+        causality_id: Some(case.correlation_id.clone()),
+        postings,
+        metadata: case_metadata(case, lock),
+    })
+}
+
+fn case_metadata(case: &Case, lock: &JournalEntry) -> HashMap<String, serde_json::Value> {
+    let mut metadata = HashMap::new();
+    metadata.insert("compliance_case".to_string(), json!(case.flag_id));
+    metadata.insert("lock_sequence".to_string(), json!(lock.sequence));
+    metadata
+}
+
+fn is_user_funds(account: &AccountKey) -> bool {
+    account.category == AccountCategory::Liability
+        && account.segment == "USER"
+        && account.sub_account == "AVAILABLE"
+}
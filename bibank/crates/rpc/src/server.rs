@@ -0,0 +1,1245 @@
+//! JSON-RPC server - long-running mode for the bibank binary
+//!
+//! `bibank serve` keeps one AppContext in memory instead of rebuilding it on
+//! every invocation. Requests arrive as JSON-RPC 2.0 over HTTP (`POST /`) or
+//! a Unix socket (one request per line) and are forwarded to a single writer
+//! task that owns the context, so commits are applied one at a time in
+//! arrival order.
+
+use std::collections::BTreeMap;
+
+use axum::body::Bytes;
+use axum::extract::State;
+use axum::routing::post;
+use axum::{Json, Router};
+use bibank_approval::{ApprovalError, PendingApproval, StoreError};
+use bibank_compliance::{
+    ApprovalLevel, ComplianceError, ReportFormat, ReportKind, ReviewDecision, UserRecord, UserStatus,
+};
+use bibank_core::amount::AmountError;
+use bibank_dsl::ParseError;
+use bibank_ledger::{AccountKey, AsOf, Checkpoint, EntrySignature, JournalEntry, LedgerError, MerkleProof};
+use bibank_matching::{CandleInterval, MatchingError, OrderType, TradingPair};
+use bibank_risk::{InterestCalculator, LiquidationEngine, RiskError};
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, oneshot};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::checkpoint::CheckpointError;
+use crate::commands;
+use crate::context::{AppContext, CommitError};
+use crate::scheduler::{self, SWEEP_LIQUIDATOR};
+
+/// JSON-RPC protocol version
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// Requests queued for the writer before callers wait
+pub const WRITER_QUEUE_CAPACITY: usize = 1024;
+
+/// Default HTTP listen address
+pub const DEFAULT_LISTEN_ADDR: &str = "127.0.0.1:8080";
+
+/// JSON-RPC request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcRequest {
+    #[serde(default)]
+    pub jsonrpc: String,
+    #[serde(default)]
+    pub id: Value,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+impl RpcRequest {
+    pub fn new(method: impl Into<String>, params: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id: json!(1),
+            method: method.into(),
+            params,
+        }
+    }
+
+    pub fn id(mut self, id: impl Into<Value>) -> Self {
+        self.id = id.into();
+        self
+    }
+}
+
+/// JSON-RPC response (exactly one of `result` / `error` is set)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcResponse {
+    pub jsonrpc: String,
+    pub id: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    pub fn success(id: Value, result: Value) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    pub fn failure(id: Value, error: RpcError) -> Self {
+        Self {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            id,
+            result: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Structured JSON-RPC error
+///
+/// `data.kind` names the failure in snake_case (e.g. `insufficient_balance`)
+/// so clients can branch without parsing `message`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, thiserror::Error)]
+#[error("{message} (code {code})")]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    // Standard JSON-RPC codes
+    pub const PARSE_ERROR: i64 = -32700;
+    pub const INVALID_REQUEST: i64 = -32600;
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const INVALID_PARAMS: i64 = -32602;
+    pub const INTERNAL_ERROR: i64 = -32603;
+
+    // Application codes
+    pub const COMMAND_FAILED: i64 = -32000;
+    pub const LEDGER_REJECTED: i64 = -32001;
+    pub const RISK_REJECTED: i64 = -32002;
+    pub const EVENT_STORE_ERROR: i64 = -32003;
+    pub const ORDER_REJECTED: i64 = -32004;
+    pub const SHUTTING_DOWN: i64 = -32005;
+    pub const CORRELATION_CONFLICT: i64 = -32006;
+    pub const PENDING_APPROVAL: i64 = -32007;
+    pub const APPROVAL_REJECTED: i64 = -32008;
+    pub const COMPLIANCE_BLOCKED: i64 = -32009;
+    pub const RULES_REJECTED: i64 = -32010;
+    pub const CASE_REJECTED: i64 = -32011;
+
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    /// Attach `{"kind": kind, ...fields}` as error data
+    pub fn with_kind(mut self, kind: &str, fields: Value) -> Self {
+        let mut data = json!({ "kind": kind });
+        if let (Some(data), Value::Object(fields)) = (data.as_object_mut(), fields) {
+            data.extend(fields);
+        }
+        self.data = Some(data);
+        self
+    }
+
+    /// The `data.kind` of this error, if any
+    pub fn kind(&self) -> Option<&str> {
+        self.data.as_ref()?.get("kind")?.as_str()
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Self::new(Self::INVALID_PARAMS, message)
+    }
+
+    fn shutting_down() -> Self {
+        Self::new(Self::SHUTTING_DOWN, "Server is shutting down")
+    }
+}
+
+impl From<&CommitError> for RpcError {
+    fn from(err: &CommitError) -> Self {
+        match err {
+            CommitError::Ledger(e) => RpcError::from(e),
+            CommitError::Risk(e) => {
+                let error = RpcError::new(Self::RISK_REJECTED, err.to_string());
+                match e {
+                    RiskError::InsufficientBalance {
+                        account,
+                        available,
+                        required,
+                    } => error.with_kind(
+                        "insufficient_balance",
+                        json!({ "account": account, "available": available, "required": required }),
+                    ),
+                    RiskError::AccountNotFound(account) => {
+                        error.with_kind("account_not_found", json!({ "account": account }))
+                    }
+                    RiskError::CheckFailed(_) => error.with_kind("check_failed", json!({})),
+                    RiskError::PriceUnavailable { asset, quote, .. } => error.with_kind(
+                        "price_unavailable",
+                        json!({ "asset": asset, "quote": quote }),
+                    ),
+                    RiskError::ExceedsMaxLeverage {
+                        requested,
+                        max_allowed,
+                        margin_ratio,
+                    } => error.with_kind(
+                        "exceeds_max_leverage",
+                        json!({ "requested": requested, "max_allowed": max_allowed, "margin_ratio": margin_ratio }),
+                    ),
+                }
+            }
+            CommitError::Event(_) => {
+                RpcError::new(Self::EVENT_STORE_ERROR, err.to_string()).with_kind("event_store", json!({}))
+            }
+            CommitError::CorrelationConflict {
+                correlation_id,
+                sequence,
+            } => RpcError::new(Self::CORRELATION_CONFLICT, err.to_string()).with_kind(
+                "correlation_conflict",
+                json!({ "correlation_id": correlation_id, "sequence": sequence }),
+            ),
+            CommitError::EntryMissing(sequence) => RpcError::new(Self::EVENT_STORE_ERROR, err.to_string())
+                .with_kind("entry_missing", json!({ "sequence": sequence })),
+            CommitError::Blocked {
+                correlation_id,
+                reason,
+                code,
+            } => RpcError::new(Self::COMPLIANCE_BLOCKED, err.to_string()).with_kind(
+                "compliance_blocked",
+                json!({ "correlation_id": correlation_id, "reason": reason, "code": code }),
+            ),
+            CommitError::PendingApproval {
+                approval_id,
+                signatures_remaining,
+            } => RpcError::new(Self::PENDING_APPROVAL, err.to_string()).with_kind(
+                "pending_approval",
+                json!({ "approval_id": approval_id, "signatures_remaining": signatures_remaining }),
+            ),
+            CommitError::Approval(e) => {
+                let kind = match e {
+                    ApprovalError::NotFound(_) | ApprovalError::Store(StoreError::NotFound(_)) => {
+                        "approval_not_found"
+                    }
+                    ApprovalError::UnknownApprover(_) => "unknown_approver",
+                    ApprovalError::InvalidSignature => "invalid_signature",
+                    ApprovalError::DuplicateSignature(_) => "duplicate_signature",
+                    ApprovalError::Expired => "approval_expired",
+                    ApprovalError::AlreadyProcessed(_) | ApprovalError::CannotSignRejected => "approval_closed",
+                    ApprovalError::Conflict { .. } => "correlation_conflict",
+                    _ => "approval",
+                };
+                RpcError::new(Self::APPROVAL_REJECTED, err.to_string()).with_kind(kind, json!({}))
+            }
+            CommitError::Compliance(e) => {
+                let kind = match e {
+                    ComplianceError::ReviewNotFound(_) | ComplianceError::TransactionNotFound(_) => {
+                        "case_not_found"
+                    }
+                    ComplianceError::ReviewAlreadyResolved(_) => "case_closed",
+                    ComplianceError::ReviewAssigned(_, _) => "case_assigned",
+                    ComplianceError::UserNotFound(_) => "user_not_found",
+                    ComplianceError::UserExists(_) => "user_exists",
+                    ComplianceError::UserClosed(_) => "user_closed",
+                    ComplianceError::InvalidKycTier(_) => "invalid_kyc_tier",
+                    _ => "compliance",
+                };
+                RpcError::new(Self::CASE_REJECTED, err.to_string()).with_kind(kind, json!({}))
+            }
+            CommitError::NotReversible { sequence, intent } => RpcError::new(Self::LEDGER_REJECTED, err.to_string())
+                .with_kind("not_reversible", json!({ "sequence": sequence, "intent": intent })),
+            CommitError::AlreadyReversed {
+                sequence,
+                reversal_sequence,
+            } => RpcError::new(Self::LEDGER_REJECTED, err.to_string()).with_kind(
+                "already_reversed",
+                json!({ "sequence": sequence, "reversal_sequence": reversal_sequence }),
+            ),
+            CommitError::UserFrozen { user_id, status } => RpcError::new(Self::COMPLIANCE_BLOCKED, err.to_string())
+                .with_kind("user_frozen", json!({ "user_id": user_id, "status": status })),
+        }
+    }
+}
+
+impl From<CommitError> for RpcError {
+    fn from(err: CommitError) -> Self {
+        (&err).into()
+    }
+}
+
+impl From<CheckpointError> for RpcError {
+    fn from(err: CheckpointError) -> Self {
+        match err {
+            CheckpointError::NotCheckpointed(sequence) => RpcError::invalid_params(err.to_string())
+                .with_kind("not_checkpointed", json!({ "sequence": sequence })),
+            _ => RpcError::new(Self::EVENT_STORE_ERROR, err.to_string()).with_kind("checkpoint", json!({})),
+        }
+    }
+}
+
+impl From<&LedgerError> for RpcError {
+    fn from(err: &LedgerError) -> Self {
+        let kind = match err {
+            LedgerError::UnbalancedEntry { .. } => "unbalanced_entry",
+            LedgerError::InvalidIntentPosting { .. }
+            | LedgerError::InvalidTradePostings { .. }
+            | LedgerError::InvalidTradeAssets { .. } => "invalid_postings",
+            LedgerError::InvalidAccountFormat(_) | LedgerError::UnknownCategory(_) => "invalid_account",
+            LedgerError::UnbalancedBooks { .. } => "unbalanced_books",
+            _ => "ledger",
+        };
+        RpcError::new(Self::LEDGER_REJECTED, err.to_string()).with_kind(kind, json!({}))
+    }
+}
+
+impl From<anyhow::Error> for RpcError {
+    fn from(err: anyhow::Error) -> Self {
+        if let Some(e) = err.downcast_ref::<CommitError>() {
+            return e.into();
+        }
+        if let Some(e) = err.downcast_ref::<LedgerError>() {
+            return e.into();
+        }
+        if let Some(e) = err.downcast_ref::<AmountError>() {
+            return RpcError::invalid_params(e.to_string()).with_kind("invalid_amount", json!({}));
+        }
+        if let Some(e) = err.downcast_ref::<MatchingError>() {
+            return RpcError::new(Self::ORDER_REJECTED, e.to_string()).with_kind("order_rejected", json!({}));
+        }
+        if let Some(e) = err.downcast_ref::<ParseError>() {
+            return RpcError::new(Self::RULES_REJECTED, format!("Invalid rule file: {}", e)).with_kind(
+                "rule_parse_error",
+                json!({ "line": e.line, "column": e.column, "message": e.message }),
+            );
+        }
+        RpcError::new(Self::COMMAND_FAILED, err.to_string())
+    }
+}
+
+/// Messages to the writer task
+enum Message {
+    Call {
+        request: RpcRequest,
+        reply: oneshot::Sender<RpcResponse>,
+    },
+    Shutdown,
+}
+
+/// Cloneable handle to the writer task
+#[derive(Clone)]
+pub struct RpcHandle {
+    tx: mpsc::Sender<Message>,
+}
+
+impl RpcHandle {
+    /// Send a request to the writer and wait for its response
+    pub async fn call(&self, request: RpcRequest) -> RpcResponse {
+        let id = request.id.clone();
+        let (reply, rx) = oneshot::channel();
+
+        if self.tx.send(Message::Call { request, reply }).await.is_err() {
+            return RpcResponse::failure(id, RpcError::shutting_down());
+        }
+        rx.await
+            .unwrap_or_else(|_| RpcResponse::failure(id, RpcError::shutting_down()))
+    }
+
+    /// Stop the writer once the requests queued ahead of this one are done
+    pub async fn shutdown(&self) {
+        self.tx.send(Message::Shutdown).await.ok();
+    }
+}
+
+/// Spawn the single writer task that owns the context
+///
+/// The task runs until shutdown (or until every handle is dropped), then
+/// returns the context. Requests run to completion even if their caller goes
+/// away, so a command is never abandoned halfway through its commits.
+pub fn spawn_writer(mut ctx: AppContext) -> (RpcHandle, JoinHandle<AppContext>) {
+    let (tx, mut rx) = mpsc::channel(WRITER_QUEUE_CAPACITY);
+
+    // Durable subscribers follow the journal until the context is dropped
+    ctx.bus.spawn_durable();
+
+    let task = tokio::spawn(async move {
+        while let Some(Message::Call { request, reply }) = rx.recv().await {
+            let response = handle_request(&mut ctx, request).await;
+
+            // Between requests is a quiescent point
+            if let Err(e) = ctx.snapshot_if_due() {
+                tracing::warn!("Snapshot failed: {}", e);
+            }
+            if let Err(e) = ctx.checkpoint_if_due() {
+                tracing::warn!("Checkpoint failed: {}", e);
+            }
+
+            reply.send(response).ok();
+        }
+        ctx
+    });
+
+    (RpcHandle { tx }, task)
+}
+
+/// Handle a single request against the context
+pub async fn handle_request(ctx: &mut AppContext, request: RpcRequest) -> RpcResponse {
+    let id = request.id.clone();
+
+    if request.jsonrpc != JSONRPC_VERSION {
+        return RpcResponse::failure(
+            id,
+            RpcError::new(RpcError::INVALID_REQUEST, "jsonrpc must be \"2.0\""),
+        );
+    }
+
+    match dispatch(ctx, &request.method, request.params).await {
+        Ok(result) => RpcResponse::success(id, result),
+        Err(error) => RpcResponse::failure(id, error),
+    }
+}
+
+/// Parse a raw request body into a request, or the error response for it
+pub fn parse_request(body: &[u8]) -> Result<RpcRequest, Box<RpcResponse>> {
+    let value: Value = serde_json::from_slice(body).map_err(|e| {
+        Box::new(RpcResponse::failure(
+            Value::Null,
+            RpcError::new(RpcError::PARSE_ERROR, e.to_string()),
+        ))
+    })?;
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+
+    serde_json::from_value(value).map_err(|e| {
+        Box::new(RpcResponse::failure(
+            id,
+            RpcError::new(RpcError::INVALID_REQUEST, e.to_string()),
+        ))
+    })
+}
+
+// === Methods ===
+
+#[derive(Deserialize)]
+struct UserAmountParams {
+    user: String,
+    amount: Decimal,
+    asset: String,
+    correlation_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TransferParams {
+    from: String,
+    to: String,
+    amount: Decimal,
+    asset: String,
+    correlation_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TradeParams {
+    maker: String,
+    taker: String,
+    sell: Decimal,
+    sell_asset: String,
+    buy: Decimal,
+    buy_asset: String,
+    fee: Option<Decimal>,
+    correlation_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PlaceOrderParams {
+    user: String,
+    side: String,
+    base: String,
+    quote: String,
+    price: Option<Decimal>,
+    quantity: Decimal,
+    #[serde(default = "default_order_type")]
+    order_type: String,
+    max_slippage: Option<Decimal>,
+    correlation_id: Option<String>,
+}
+
+fn default_order_type() -> String {
+    "limit".to_string()
+}
+
+#[derive(Deserialize)]
+struct CancelOrderParams {
+    order_id: String,
+    base: String,
+    quote: String,
+    correlation_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct UserParams {
+    user: String,
+}
+
+#[derive(Deserialize)]
+struct AdjustParams {
+    user: String,
+    /// Signed amount: positive credits the user, negative debits
+    amount: Decimal,
+    asset: String,
+    reason: String,
+    correlation_id: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ListApprovalsParams {
+    #[serde(default)]
+    all: bool,
+}
+
+#[derive(Deserialize)]
+struct SignApprovalParams {
+    approval_id: String,
+    signature: EntrySignature,
+}
+
+#[derive(Deserialize)]
+struct RejectApprovalParams {
+    approval_id: String,
+    reason: Option<String>,
+    /// Approver's signature over the rejection
+    signature: EntrySignature,
+}
+
+#[derive(Deserialize)]
+struct RunInterestParams {
+    /// Accrual date (defaults to today, UTC)
+    date: Option<NaiveDate>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Deserialize)]
+struct RunSweepParams {
+    liquidator: Option<String>,
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Deserialize)]
+struct RunDustSweepParams {
+    #[serde(default)]
+    dry_run: bool,
+}
+
+#[derive(Deserialize)]
+struct ListCasesParams {
+    level: Option<ApprovalLevel>,
+    #[serde(default)]
+    all: bool,
+}
+
+#[derive(Deserialize)]
+struct AssignCaseParams {
+    flag_id: String,
+    reviewer: String,
+    assigned_by: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ResolveCaseParams {
+    flag_id: String,
+    reviewer: String,
+    #[serde(default)]
+    notes: String,
+}
+
+#[derive(Deserialize)]
+struct RegisterUserParams {
+    user_id: String,
+    kyc_tier: u8,
+    /// Defaults to today
+    onboarded_at: Option<NaiveDate>,
+    performed_by: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SetUserTierParams {
+    user_id: String,
+    kyc_tier: u8,
+    reason: String,
+    performed_by: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SetUserStatusParams {
+    user_id: String,
+    status: UserStatus,
+    reason: String,
+    performed_by: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GetUserParams {
+    user_id: String,
+}
+
+#[derive(Deserialize)]
+struct ReloadRulesParams {
+    performed_by: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DeadLettersParams {
+    subscriber: String,
+}
+
+#[derive(Deserialize)]
+struct InclusionProofParams {
+    sequence: u64,
+}
+
+#[derive(Deserialize)]
+struct VerifyProofParams {
+    proof: MerkleProof,
+    checkpoint: Checkpoint,
+}
+
+#[derive(Deserialize)]
+struct ReservesParams {
+    /// Journal sequence to take balances at (defaults to the latest)
+    sequence: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct LiabilityProofParams {
+    user: String,
+    sequence: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct ReverseParams {
+    sequence: u64,
+    reason: String,
+}
+
+#[derive(Deserialize)]
+struct StatementParams {
+    /// Cutoff by entry sequence
+    sequence: Option<u64>,
+    /// Cutoff by commit time
+    at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    format: ReportFormat,
+}
+
+impl StatementParams {
+    fn as_of(&self) -> Result<Option<AsOf>, RpcError> {
+        match (self.sequence, self.at) {
+            (Some(_), Some(_)) => Err(RpcError::invalid_params("Give either sequence or at, not both")),
+            (Some(sequence), None) => Ok(Some(AsOf::Sequence(sequence))),
+            (None, Some(at)) => Ok(Some(AsOf::Time(at))),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct CandlesParams {
+    base: String,
+    quote: String,
+    interval: CandleInterval,
+    from: DateTime<Utc>,
+    #[serde(default)]
+    to: Option<DateTime<Utc>>,
+}
+
+#[derive(Deserialize)]
+struct FeePreviewParams {
+    user: String,
+    base: String,
+    quote: String,
+}
+
+#[derive(Deserialize)]
+struct IncomeStatementParams {
+    from: NaiveDate,
+    to: NaiveDate,
+    #[serde(default)]
+    format: ReportFormat,
+}
+
+#[derive(Deserialize)]
+struct ReportParams {
+    kind: ReportKind,
+    from: NaiveDate,
+    to: NaiveDate,
+    #[serde(default)]
+    format: ReportFormat,
+    /// CTR threshold (defaults to the compliance config's)
+    threshold: Option<Decimal>,
+}
+
+fn params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    // Methods without required params accept a missing params member
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|e| RpcError::invalid_params(e.to_string()))
+}
+
+fn correlation_id(id: Option<String>) -> String {
+    id.unwrap_or_else(|| Uuid::new_v4().to_string())
+}
+
+/// Summary of a committed entry returned to clients
+fn entry_result(entry: &JournalEntry) -> Value {
+    json!({
+        "sequence": entry.sequence,
+        "hash": entry.hash,
+        "intent": entry.intent,
+        "correlation_id": entry.correlation_id,
+    })
+}
+
+async fn dispatch(ctx: &mut AppContext, method: &str, raw: Value) -> Result<Value, RpcError> {
+    match method {
+        "init" => {
+            let correlation_id = Uuid::new_v4().to_string();
+            commands::init(ctx, &correlation_id).await?;
+            Ok(json!({ "sequence": ctx.last_sequence() }))
+        }
+
+        "deposit" => {
+            let p: UserAmountParams = params(raw)?;
+            let cid = correlation_id(p.correlation_id);
+            let entry = commands::deposit(ctx, &p.user, p.amount, &p.asset, &cid).await?;
+            Ok(entry_result(&entry))
+        }
+
+        "transfer" => {
+            let p: TransferParams = params(raw)?;
+            let cid = correlation_id(p.correlation_id);
+            let entry = commands::transfer(ctx, &p.from, &p.to, p.amount, &p.asset, &cid).await?;
+            Ok(entry_result(&entry))
+        }
+
+        "withdraw" => {
+            let p: UserAmountParams = params(raw)?;
+            let cid = correlation_id(p.correlation_id);
+            let entry = commands::withdraw(ctx, &p.user, p.amount, &p.asset, &cid).await?;
+            Ok(entry_result(&entry))
+        }
+
+        "trade" => {
+            let p: TradeParams = params(raw)?;
+            let cid = correlation_id(p.correlation_id);
+            match p.fee {
+                Some(fee) => {
+                    let (trade, fee) = commands::trade_with_fee(
+                        ctx, &p.maker, &p.taker, p.sell, &p.sell_asset, p.buy, &p.buy_asset, fee, &cid,
+                    )
+                    .await?;
+                    let mut result = entry_result(&trade);
+                    result["fee"] = entry_result(&fee);
+                    Ok(result)
+                }
+                None => {
+                    let entry = commands::trade(
+                        ctx, &p.maker, &p.taker, p.sell, &p.sell_asset, p.buy, &p.buy_asset, &cid,
+                    )
+                    .await?;
+                    Ok(entry_result(&entry))
+                }
+            }
+        }
+
+        "borrow" => {
+            let p: UserAmountParams = params(raw)?;
+            let cid = correlation_id(p.correlation_id);
+            let entry = commands::borrow(ctx, &p.user, p.amount, &p.asset, &cid).await?;
+            Ok(entry_result(&entry))
+        }
+
+        "repay" => {
+            let p: UserAmountParams = params(raw)?;
+            let cid = correlation_id(p.correlation_id);
+            let entry = commands::repay(ctx, &p.user, p.amount, &p.asset, &cid).await?;
+            Ok(entry_result(&entry))
+        }
+
+        "place_order" => {
+            let p: PlaceOrderParams = params(raw)?;
+            let cid = correlation_id(p.correlation_id);
+
+            let order_type = p
+                .order_type
+                .parse::<OrderType>()
+                .map_err(|e| RpcError::invalid_params(e.to_string()))?;
+            let order_type = match order_type {
+                OrderType::Market { max_slippage } => OrderType::Market {
+                    max_slippage: p.max_slippage.unwrap_or(max_slippage),
+                },
+                other => other,
+            };
+            let price = match (order_type, p.price) {
+                (OrderType::Market { .. }, _) => Decimal::ZERO,
+                (_, Some(price)) => price,
+                (_, None) => return Err(RpcError::invalid_params("price is required for limit orders")),
+            };
+
+            let order_id = commands::place_order(
+                ctx, &p.user, &p.side, &p.base, &p.quote, price, p.quantity, order_type, &cid,
+            )
+            .await?;
+
+            let pair = TradingPair::new(&p.base, &p.quote);
+            let resting = ctx
+                .matching
+                .get_order(&pair, &order_id)
+                .map(|order| order.remaining())
+                .unwrap_or_default();
+            Ok(json!({
+                "order_id": order_id,
+                "sequence": ctx.last_sequence(),
+                "resting_quantity": resting.to_string(),
+            }))
+        }
+
+        "cancel_order" => {
+            let p: CancelOrderParams = params(raw)?;
+            let cid = correlation_id(p.correlation_id);
+            let entry = commands::cancel_order(ctx, &p.order_id, &p.base, &p.quote, &cid).await?;
+            Ok(entry_result(&entry))
+        }
+
+        "candles" => {
+            let p: CandlesParams = params(raw)?;
+            let to = p.to.unwrap_or_else(Utc::now);
+            if p.from > to {
+                return Err(RpcError::invalid_params(format!(
+                    "Candle range is empty: {} is after {}",
+                    p.from, to
+                )));
+            }
+            let pair = TradingPair::new(&p.base, &p.quote);
+            let candles = commands::load_candles(ctx, &pair, p.interval, p.from, to).await?;
+            Ok(json!({
+                "pair": pair.to_string(),
+                "interval": p.interval,
+                "candles": candles,
+            }))
+        }
+
+        "fee_preview" => {
+            let p: FeePreviewParams = params(raw)?;
+            let rate = ctx.fee_rate(&p.user, &TradingPair::new(&p.base, &p.quote)).await;
+            Ok(serde_json::to_value(rate).expect("fee rate serializes"))
+        }
+
+        "balance" => {
+            let p: UserParams = params(raw)?;
+            Ok(json!({
+                "user": p.user.to_uppercase(),
+                "balances": user_balances(ctx, &p.user),
+            }))
+        }
+
+        "adjust" => {
+            let p: AdjustParams = params(raw)?;
+            let cid = correlation_id(p.correlation_id);
+            let entry = commands::adjust(ctx, &p.user, p.amount, &p.asset, &p.reason, &cid).await?;
+            Ok(entry_result(&entry))
+        }
+
+        "reverse" => {
+            let p: ReverseParams = params(raw)?;
+            let entry = ctx.reverse(p.sequence, &p.reason).await?;
+            Ok(entry_result(&entry))
+        }
+
+        "list_approvals" => {
+            let p: ListApprovalsParams = params(raw)?;
+            let approvals = if p.all {
+                ctx.approvals.list_all()
+            } else {
+                ctx.approvals.list_pending()
+            }
+            .map_err(CommitError::from)?;
+            Ok(json!({ "approvals": approvals.iter().map(approval_result).collect::<Vec<_>>() }))
+        }
+
+        "sign_approval" => {
+            let p: SignApprovalParams = params(raw)?;
+            let (approval, entry) = ctx.approve(&p.approval_id, &p.signature).await?;
+            let mut result = approval_result(&approval);
+            if let Some(entry) = entry {
+                result["entry"] = entry_result(&entry);
+            }
+            Ok(result)
+        }
+
+        "reject_approval" => {
+            let p: RejectApprovalParams = params(raw)?;
+            let approval = ctx
+                .approvals
+                .reject(&p.approval_id, p.reason.as_deref(), &p.signature)
+                .map_err(CommitError::from)?;
+            Ok(approval_result(&approval))
+        }
+
+        "run_interest" => {
+            let p: RunInterestParams = params(raw)?;
+            let date = p.date.unwrap_or_else(|| Utc::now().date_naive());
+            let report =
+                scheduler::run_interest_accrual(ctx, &InterestCalculator::new(), date, p.dry_run).await?;
+            Ok(serde_json::to_value(report).expect("report serializes"))
+        }
+
+        "run_liquidation_sweep" => {
+            let p: RunSweepParams = params(raw)?;
+            let liquidator = p.liquidator.as_deref().unwrap_or(SWEEP_LIQUIDATOR);
+            let report =
+                scheduler::run_liquidation_sweep(ctx, &LiquidationEngine::default(), liquidator, p.dry_run)
+                    .await?;
+            Ok(serde_json::to_value(report).expect("report serializes"))
+        }
+
+        "run_dust_sweep" => {
+            let p: RunDustSweepParams = params(raw)?;
+            let report = scheduler::run_dust_sweep(ctx, p.dry_run).await?;
+            Ok(serde_json::to_value(report).expect("report serializes"))
+        }
+
+        "list_cases" => {
+            let p: ListCasesParams = params(raw)?;
+            let cases = ctx.cases().await?;
+            let cases: Vec<_> = if p.all {
+                cases.all().filter(|c| p.level.is_none_or(|l| c.required_approval == l)).collect()
+            } else {
+                cases.open(p.level)
+            };
+            Ok(json!(cases))
+        }
+
+        "assign_case" => {
+            let p: AssignCaseParams = params(raw)?;
+            let assigned_by = p.assigned_by.as_deref().unwrap_or(&p.reviewer);
+            let case = ctx.assign_case(&p.flag_id, &p.reviewer, assigned_by).await?;
+            Ok(json!(case))
+        }
+
+        "approve_case" | "reject_case" => {
+            let p: ResolveCaseParams = params(raw)?;
+            let decision = if method == "approve_case" {
+                ReviewDecision::Approved
+            } else {
+                ReviewDecision::Rejected
+            };
+            let (case, entry) = ctx.resolve_case(&p.flag_id, decision, &p.reviewer, &p.notes).await?;
+            let mut result = json!({ "case": case });
+            if let Some(entry) = entry {
+                result["entry"] = entry_result(&entry);
+            }
+            Ok(result)
+        }
+
+        "escalate_cases" => {
+            let escalated = ctx.escalate_cases(Utc::now()).await?;
+            Ok(json!({ "escalated": escalated }))
+        }
+
+        "register_user" => {
+            let p: RegisterUserParams = params(raw)?;
+            let onboarded_at = p.onboarded_at.unwrap_or_else(|| Utc::now().date_naive());
+            let performed_by = p.performed_by.as_deref().unwrap_or("rpc");
+            let user = ctx.register_user(&p.user_id, p.kyc_tier, onboarded_at, performed_by).await?;
+            Ok(json!(user))
+        }
+
+        "set_user_tier" => {
+            let p: SetUserTierParams = params(raw)?;
+            let performed_by = p.performed_by.as_deref().unwrap_or("rpc");
+            let user = ctx.set_user_tier(&p.user_id, p.kyc_tier, &p.reason, performed_by).await?;
+            Ok(json!(user))
+        }
+
+        "set_user_status" => {
+            let p: SetUserStatusParams = params(raw)?;
+            let performed_by = p.performed_by.as_deref().unwrap_or("rpc");
+            let user = commands::user_set_status(ctx, &p.user_id, p.status, &p.reason, performed_by).await?;
+            Ok(json!(user))
+        }
+
+        "get_user" => {
+            let p: GetUserParams = params(raw)?;
+            let user_id = p.user_id.to_uppercase();
+            let user = ctx
+                .users()
+                .get(&user_id)
+                .ok_or_else(|| CommitError::from(ComplianceError::UserNotFound(user_id)))?;
+            Ok(json!(user))
+        }
+
+        "list_users" => {
+            let users: Vec<&UserRecord> = ctx.users().users().collect();
+            Ok(json!({ "users": users }))
+        }
+
+        "reload_rules" => {
+            let p: ReloadRulesParams = params(raw)?;
+            let reload = ctx.reload_rules(p.performed_by.as_deref().unwrap_or("rpc")).await?;
+            Ok(serde_json::to_value(reload).expect("reload serializes"))
+        }
+
+        "create_checkpoint" => {
+            let checkpoint = ctx.checkpoint()?;
+            Ok(json!(checkpoint))
+        }
+
+        "list_checkpoints" => {
+            let checkpoints = ctx.checkpoints.list()?;
+            Ok(json!(checkpoints))
+        }
+
+        "list_subscribers" => {
+            let head = ctx.last_sequence();
+            let subscribers: Vec<Value> = ctx
+                .subscriber_checkpoints()
+                .map_err(anyhow::Error::from)?
+                .into_iter()
+                .map(|c| {
+                    let lag = c.lag(head);
+                    let mut value = json!(c);
+                    value["lag"] = json!(lag);
+                    value
+                })
+                .collect();
+            Ok(json!({ "head": head, "subscribers": subscribers }))
+        }
+
+        "dead_letters" => {
+            let p: DeadLettersParams = params(raw)?;
+            let letters = ctx.subscribers.dead_letters(&p.subscriber).map_err(anyhow::Error::from)?;
+            Ok(json!({ "dead_letters": letters }))
+        }
+
+        "inclusion_proof" => {
+            let p: InclusionProofParams = params(raw)?;
+            let (proof, checkpoint) = ctx.inclusion_proof(p.sequence)?;
+            Ok(json!({ "proof": proof, "checkpoint": checkpoint }))
+        }
+
+        "verify_proof" => {
+            let p: VerifyProofParams = params(raw)?;
+            // Signed, and by this server's key when it has one
+            let signature_valid = p.checkpoint.verify_signature().is_ok()
+                && ctx.signer.as_ref().is_none_or(|signer| {
+                    p.checkpoint
+                        .signature
+                        .as_ref()
+                        .is_some_and(|s| s.public_key == signer.public_key_hex())
+                });
+            Ok(json!({
+                "included": p.checkpoint.verify_proof(&p.proof),
+                "signature_valid": signature_valid,
+            }))
+        }
+
+        "trial_balance" => {
+            let p: StatementParams = params(raw)?;
+            let trial_balance = ctx.ledger_balances(p.as_of()?)?.trial_balance();
+            trial_balance.check().map_err(|e| RpcError::from(&e))?;
+            Ok(match p.format {
+                ReportFormat::Json => json!(trial_balance),
+                ReportFormat::Csv => json!({ "sequence": trial_balance.sequence, "csv": trial_balance.to_csv() }),
+            })
+        }
+
+        "balance_sheet" => {
+            let p: StatementParams = params(raw)?;
+            let sheet = ctx.ledger_balances(p.as_of()?)?.balance_sheet();
+            sheet.check().map_err(|e| RpcError::from(&e))?;
+            Ok(match p.format {
+                ReportFormat::Json => json!(sheet),
+                ReportFormat::Csv => json!({ "sequence": sheet.sequence, "csv": sheet.to_csv() }),
+            })
+        }
+
+        "income_statement" => {
+            let p: IncomeStatementParams = params(raw)?;
+            if p.from > p.to {
+                return Err(RpcError::invalid_params(format!(
+                    "Statement period is empty: {} is after {}",
+                    p.from, p.to
+                )));
+            }
+            let statement = ctx.income_statement(p.from, p.to)?;
+            Ok(match p.format {
+                ReportFormat::Json => json!(statement),
+                ReportFormat::Csv => json!({ "from": p.from, "to": p.to, "csv": statement.to_csv() }),
+            })
+        }
+
+        "proof_of_reserves" => {
+            let p: ReservesParams = params(raw)?;
+            let reserves = ctx.proof_of_reserves(reserves_sequence(ctx, p.sequence)?)?;
+            Ok(json!(reserves.summary()))
+        }
+
+        "liability_proof" => {
+            let p: LiabilityProofParams = params(raw)?;
+            let reserves = ctx.proof_of_reserves(reserves_sequence(ctx, p.sequence)?)?;
+            Ok(json!({
+                "user": p.user.to_uppercase(),
+                "sequence": reserves.sequence(),
+                "proofs": reserves.proofs(&p.user),
+            }))
+        }
+
+        "report" => {
+            let p: ReportParams = params(raw)?;
+            if p.from > p.to {
+                return Err(RpcError::invalid_params(format!(
+                    "Report range is empty: {} is after {}",
+                    p.from, p.to
+                )));
+            }
+            let (report, csv) = match p.kind {
+                ReportKind::Ctr => {
+                    let report = ctx.ctr_report(p.from, p.to, p.threshold).await?;
+                    (serde_json::to_value(&report), report.to_csv())
+                }
+                ReportKind::Sar => {
+                    let report = ctx.sar_report(p.from, p.to).await?;
+                    (serde_json::to_value(&report), report.to_csv())
+                }
+            };
+            let report = report.expect("report serializes");
+            Ok(match p.format {
+                ReportFormat::Json => report,
+                ReportFormat::Csv => json!({
+                    "kind": p.kind,
+                    "from": p.from,
+                    "to": p.to,
+                    "input_hash": report["input_hash"],
+                    "csv": csv,
+                }),
+            })
+        }
+
+        _ => Err(RpcError::new(
+            RpcError::METHOD_NOT_FOUND,
+            format!("Method not found: {}", method),
+        )),
+    }
+}
+
+/// Check a requested reserves sequence against the journal
+fn reserves_sequence(ctx: &AppContext, sequence: Option<u64>) -> Result<Option<u64>, RpcError> {
+    match sequence {
+        Some(sequence) if sequence > ctx.last_sequence() => Err(RpcError::invalid_params(format!(
+            "Sequence {} is past the end of the journal ({})",
+            sequence,
+            ctx.last_sequence()
+        ))),
+        _ => Ok(sequence),
+    }
+}
+
+/// Summary of an approval, with the parked entry
+fn approval_result(approval: &PendingApproval) -> Value {
+    let entry: Value = serde_json::from_str(&approval.unsigned_entry_json).unwrap_or(Value::Null);
+    json!({
+        "approval_id": approval.id,
+        "status": approval.status.as_str(),
+        "signers": approval.signers(),
+        "signatures_remaining": approval.signatures_remaining(),
+        "expires_at": approval.expires_at,
+        "rejection_reason": approval.rejection_reason,
+        "unsigned_entry": entry,
+    })
+}
+
+/// Non-zero balances of a user: asset -> sub-account -> balance
+fn user_balances(ctx: &AppContext, user_id: &str) -> BTreeMap<String, BTreeMap<String, Decimal>> {
+    let user_id = user_id.to_uppercase();
+    let mut balances: BTreeMap<String, BTreeMap<String, Decimal>> = BTreeMap::new();
+
+    for (key, balance) in ctx.risk.state().all_balances() {
+        let Ok(account) = key.parse::<AccountKey>() else {
+            continue;
+        };
+        if account.segment == "USER" && account.id == user_id && !balance.is_zero() {
+            balances
+                .entry(account.asset)
+                .or_default()
+                .insert(account.sub_account, *balance);
+        }
+    }
+
+    balances
+}
+
+// === Transports ===
+
+/// HTTP router: JSON-RPC requests are POSTed to `/`
+pub fn router(handle: RpcHandle) -> Router {
+    Router::new().route("/", post(http_rpc)).with_state(handle)
+}
+
+async fn http_rpc(State(handle): State<RpcHandle>, body: Bytes) -> Json<RpcResponse> {
+    match parse_request(&body) {
+        Ok(request) => Json(handle.call(request).await),
+        Err(response) => Json(*response),
+    }
+}
+
+/// Serve JSON-RPC over HTTP until the listener fails
+pub async fn serve_http(listener: TcpListener, handle: RpcHandle) -> std::io::Result<()> {
+    axum::serve(listener, router(handle)).await
+}
+
+/// Serve line-delimited JSON-RPC over a Unix socket
+///
+/// Each line is one request; each response is written back as one line.
+#[cfg(unix)]
+pub async fn serve_unix(listener: tokio::net::UnixListener, handle: RpcHandle) -> std::io::Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let handle = handle.clone();
+
+        tokio::spawn(async move {
+            let (read, mut write) = stream.into_split();
+            let mut lines = BufReader::new(read).lines();
+
+            while let Ok(Some(line)) = lines.next_line().await {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let response = match parse_request(line.as_bytes()) {
+                    Ok(request) => handle.call(request).await,
+                    Err(response) => *response,
+                };
+
+                let mut out = serde_json::to_vec(&response).expect("response serializes");
+                out.push(b'\n');
+                if write.write_all(&out).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+}
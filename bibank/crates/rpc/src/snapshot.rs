@@ -0,0 +1,346 @@
+//! State snapshots - startup without replaying the whole journal
+//!
+//! A snapshot captures the RiskState balances and the resting orders as of
+//! one journal entry, tagged with that entry's sequence and hash. On startup
+//! the latest snapshot whose tag still matches the journal is loaded and only
+//! the entries after it are replayed. Correlation ids are left out so
+//! snapshots don't grow with the journal; they have their own log, see
+//! [`crate::idempotency`].
+//! Snapshots are disposable: deleting them only makes the next startup slower.
+
+use bibank_events::{EventError, EventReader};
+use bibank_ledger::hash::verify_chain;
+use bibank_matching::{MatchingEngine, Order};
+use bibank_risk::{RiskEngine, RiskState};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::context::replay_order_entry;
+
+/// Snapshot file format version
+pub const SNAPSHOT_VERSION: u32 = 3;
+
+/// Entries between automatic snapshots
+pub const DEFAULT_SNAPSHOT_INTERVAL: u64 = 10_000;
+
+/// Snapshots kept on disk after pruning
+pub const DEFAULT_SNAPSHOT_RETAIN: usize = 3;
+
+/// Point-in-time copy of the in-memory state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    /// Sequence of the last entry covered
+    pub sequence: u64,
+    /// Hash of the last entry covered
+    pub hash: String,
+    pub created_at: DateTime<Utc>,
+    /// RiskState balances (AccountKey string -> balance)
+    pub balances: BTreeMap<String, Decimal>,
+    /// Resting orders, in time priority per price level
+    pub open_orders: Vec<Order>,
+    /// SHA-256 of the snapshot serialized with this field empty
+    pub checksum: String,
+}
+
+impl Snapshot {
+    /// Capture the current state as of entry `sequence` / `hash`
+    pub fn capture(
+        sequence: u64,
+        hash: impl Into<String>,
+        risk: &RiskState,
+        matching: &MatchingEngine,
+    ) -> Self {
+        let mut snapshot = Self {
+            version: SNAPSHOT_VERSION,
+            sequence,
+            hash: hash.into(),
+            created_at: Utc::now(),
+            balances: risk
+                .all_balances()
+                .iter()
+                .map(|(k, v)| (k.clone(), *v))
+                .collect(),
+            open_orders: matching.resting_orders().into_iter().cloned().collect(),
+            checksum: String::new(),
+        };
+        snapshot.checksum = snapshot.compute_checksum();
+        snapshot
+    }
+
+    /// Compute the checksum over everything but the checksum itself
+    pub fn compute_checksum(&self) -> String {
+        let unsealed = Self {
+            checksum: String::new(),
+            ..self.clone()
+        };
+        let bytes = serde_json::to_vec(&unsealed).expect("snapshot serializes");
+        hex::encode(Sha256::digest(bytes))
+    }
+
+    /// Restore this snapshot into a risk engine and matching engine
+    pub fn restore(&self, risk: &mut RiskEngine, matching: &mut MatchingEngine) {
+        risk.state_mut()
+            .restore(self.balances.iter().map(|(k, v)| (k.clone(), *v)));
+
+        for order in &self.open_orders {
+            matching.add_pair(order.pair.clone());
+            matching.restore_order(order.clone()).ok();
+        }
+    }
+
+    /// Check the snapshot's tag against the journal entry it claims to cover
+    pub fn check_anchor(&self, reader: &EventReader) -> Result<(), SnapshotError> {
+        let entry = reader
+            .iter_from(self.sequence)?
+            .next()
+            .transpose()?
+            .filter(|entry| entry.sequence == self.sequence)
+            .ok_or(SnapshotError::EntryMissing(self.sequence))?;
+
+        if entry.hash != self.hash {
+            return Err(SnapshotError::HashMismatch {
+                sequence: self.sequence,
+                expected: entry.hash,
+                actual: self.hash.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Fully verify the snapshot against the journal
+    ///
+    /// Verifies the hash chain up to the snapshot's sequence, then replays
+    /// those entries and compares the rebuilt state with the snapshot.
+    /// Returns the number of entries verified.
+    pub fn verify(&self, reader: &EventReader) -> Result<usize, SnapshotError> {
+        let mut entries = Vec::new();
+        for entry in reader.iter() {
+            let entry = entry?;
+            if entry.sequence > self.sequence {
+                break;
+            }
+            entries.push(entry);
+        }
+
+        verify_chain(&entries).map_err(|e| SnapshotError::Chain(e.to_string()))?;
+
+        match entries.last() {
+            Some(last) if last.sequence == self.sequence => {
+                if last.hash != self.hash {
+                    return Err(SnapshotError::HashMismatch {
+                        sequence: self.sequence,
+                        expected: last.hash.clone(),
+                        actual: self.hash.clone(),
+                    });
+                }
+            }
+            _ => return Err(SnapshotError::EntryMissing(self.sequence)),
+        }
+
+        let mut risk = RiskEngine::new();
+        let mut matching = MatchingEngine::new();
+        for entry in &entries {
+            risk.apply(entry);
+            replay_order_entry(&mut matching, entry);
+        }
+        let rebuilt = Snapshot::capture(self.sequence, &self.hash, risk.state(), &matching);
+
+        if let Some(account) = first_balance_mismatch(&self.balances, &rebuilt.balances) {
+            return Err(SnapshotError::StateMismatch {
+                sequence: self.sequence,
+                detail: format!("balance of {}", account),
+            });
+        }
+        if order_fingerprint(&self.open_orders) != order_fingerprint(&rebuilt.open_orders) {
+            return Err(SnapshotError::StateMismatch {
+                sequence: self.sequence,
+                detail: "open orders".to_string(),
+            });
+        }
+
+        Ok(entries.len())
+    }
+}
+
+/// First account whose balance differs (missing counts as zero)
+fn first_balance_mismatch(
+    a: &BTreeMap<String, Decimal>,
+    b: &BTreeMap<String, Decimal>,
+) -> Option<String> {
+    a.keys()
+        .chain(b.keys())
+        .find(|key| {
+            a.get(*key).copied().unwrap_or_default() != b.get(*key).copied().unwrap_or_default()
+        })
+        .cloned()
+}
+
+/// Order ids with their remaining quantity, sorted
+fn order_fingerprint(orders: &[Order]) -> Vec<(String, Decimal)> {
+    let mut fingerprint: Vec<_> = orders
+        .iter()
+        .map(|o| (o.id.clone(), o.remaining()))
+        .collect();
+    fingerprint.sort();
+    fingerprint
+}
+
+/// Snapshot files in a directory, named by sequence
+pub struct SnapshotStore {
+    dir: PathBuf,
+}
+
+impl SnapshotStore {
+    /// Create a store over `dir` (created on first save)
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Snapshot directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Path of the snapshot for `sequence`
+    pub fn path_for(&self, sequence: u64) -> PathBuf {
+        self.dir.join(format!("snapshot-{:020}.json", sequence))
+    }
+
+    /// Write a snapshot atomically (temp file + rename)
+    pub fn save(&self, snapshot: &Snapshot) -> Result<PathBuf, SnapshotError> {
+        fs::create_dir_all(&self.dir)?;
+
+        let path = self.path_for(snapshot.sequence);
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec_pretty(snapshot)?)?;
+        fs::File::open(&tmp)?.sync_all()?;
+        fs::rename(&tmp, &path)?;
+
+        Ok(path)
+    }
+
+    /// Snapshot files, oldest first
+    pub fn list(&self) -> Result<Vec<PathBuf>, SnapshotError> {
+        let mut files = Vec::new();
+
+        if self.dir.exists() {
+            for entry in fs::read_dir(&self.dir)? {
+                let path = entry?.path();
+                let is_snapshot = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with("snapshot-") && n.ends_with(".json"));
+                if is_snapshot {
+                    files.push(path);
+                }
+            }
+        }
+
+        files.sort();
+        Ok(files)
+    }
+
+    /// Load a snapshot, checking its version and checksum
+    pub fn load(&self, path: &Path) -> Result<Snapshot, SnapshotError> {
+        let snapshot: Snapshot = serde_json::from_slice(&fs::read(path)?)?;
+
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(snapshot.version));
+        }
+        if snapshot.compute_checksum() != snapshot.checksum {
+            return Err(SnapshotError::ChecksumMismatch(path.display().to_string()));
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Load the snapshot for `sequence`
+    pub fn find(&self, sequence: u64) -> Result<Snapshot, SnapshotError> {
+        let path = self.path_for(sequence);
+        if !path.exists() {
+            return Err(SnapshotError::NotFound(sequence));
+        }
+        self.load(&path)
+    }
+
+    /// Latest snapshot that loads cleanly and still matches the journal
+    ///
+    /// Invalid snapshots are skipped with a warning, falling back to older ones.
+    pub fn latest_valid(&self, reader: &EventReader) -> Option<Snapshot> {
+        let files = match self.list() {
+            Ok(files) => files,
+            Err(e) => {
+                tracing::warn!("Cannot list snapshots: {}", e);
+                return None;
+            }
+        };
+
+        for path in files.iter().rev() {
+            match self
+                .load(path)
+                .and_then(|s| s.check_anchor(reader).map(|_| s))
+            {
+                Ok(snapshot) => return Some(snapshot),
+                Err(e) => tracing::warn!("Skipping snapshot {}: {}", path.display(), e),
+            }
+        }
+        None
+    }
+
+    /// Delete all but the newest `keep` snapshots; returns how many were removed
+    pub fn prune(&self, keep: usize) -> Result<usize, SnapshotError> {
+        let files = self.list()?;
+        let excess = files.len().saturating_sub(keep);
+
+        for path in &files[..excess] {
+            fs::remove_file(path)?;
+        }
+        Ok(excess)
+    }
+}
+
+/// Snapshot errors
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Event store error: {0}")]
+    Event(#[from] EventError),
+
+    #[error("No snapshot at sequence {0}")]
+    NotFound(u64),
+
+    #[error("Unsupported snapshot version: {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("Snapshot checksum mismatch: {0}")]
+    ChecksumMismatch(String),
+
+    #[error("Journal has no entry at sequence {0}")]
+    EntryMissing(u64),
+
+    #[error("Hash mismatch at sequence {sequence}: journal has {expected}, snapshot has {actual}")]
+    HashMismatch {
+        sequence: u64,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Hash chain broken: {0}")]
+    Chain(String),
+
+    #[error("Snapshot at sequence {sequence} disagrees with replay: {detail}")]
+    StateMismatch { sequence: u64, detail: String },
+}
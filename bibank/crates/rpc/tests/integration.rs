@@ -8,6 +8,7 @@ use bibank_events::EventReader;
 use bibank_ledger::{
     hash::verify_chain, AccountCategory, AccountKey, JournalEntryBuilder, TransactionIntent,
 };
+use bibank_matching::OrderType;
 use bibank_rpc::AppContext;
 use rust_decimal::Decimal;
 use tempfile::TempDir;
@@ -16,6 +17,27 @@ fn amount(val: i64) -> Amount {
     Amount::new(Decimal::new(val, 0)).unwrap()
 }
 
+/// Helper: AML thresholds high enough that large fundings and bursts of
+/// commits aren't flagged into review
+fn relax_screening(data_path: &std::path::Path) {
+    std::fs::write(
+        data_path.join(bibank_rpc::screening::CONFIG_FILE),
+        r#"{
+            "large_tx_threshold": "1000000000",
+            "ctr_threshold": "1000000000",
+            "structuring_threshold": "1000000000",
+            "velocity_tx_threshold": 1000000
+        }"#,
+    )
+    .unwrap();
+}
+
+/// Register a long-standing user at `tier`, above tier 0's withdrawal limits
+async fn register(ctx: &mut AppContext, user: &str, tier: u8) {
+    let onboarded = chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+    bibank_rpc::commands::user_register(ctx, user, tier, onboarded, "ops").await.unwrap();
+}
+
 /// Test: Genesis → Deposit → Transfer → Balance check
 #[tokio::test]
 async fn test_full_workflow() {
@@ -255,6 +277,56 @@ async fn test_replay_rebuilds_state() {
     }
 }
 
+/// Test: A torn record left by a crash is truncated and the chain continues
+#[tokio::test]
+async fn test_crash_recovery_truncates_torn_record() {
+    use std::io::Write;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+
+    let segment = {
+        let mut ctx = AppContext::new(data_path).await.unwrap();
+
+        let genesis = JournalEntryBuilder::new()
+            .intent(TransactionIntent::Genesis)
+            .correlation_id("genesis-1")
+            .debit(AccountKey::system_vault("USDT"), amount(1_000_000))
+            .credit(
+                AccountKey::new(AccountCategory::Equity, "SYSTEM", "CAPITAL", "USDT", "MAIN"),
+                amount(1_000_000),
+            )
+            .build_unsigned()
+            .unwrap();
+        ctx.commit(genesis).await.unwrap();
+
+        ctx.event_store.current_segment_path().unwrap().to_path_buf()
+    };
+
+    // Crash mid-append: a partial record with no newline
+    let mut file = std::fs::OpenOptions::new().append(true).open(&segment).unwrap();
+    file.write_all(b"1a2b3c4d {\"sequence\":2,\"prev_hash\":").unwrap();
+    drop(file);
+
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    assert_eq!(ctx.last_sequence(), 1);
+
+    let deposit = JournalEntryBuilder::new()
+        .intent(TransactionIntent::Deposit)
+        .correlation_id("deposit-1")
+        .debit(AccountKey::system_vault("USDT"), amount(100))
+        .credit(AccountKey::user_available("ALICE", "USDT"), amount(100))
+        .build_unsigned()
+        .unwrap();
+    ctx.commit(deposit).await.unwrap();
+
+    let reader = EventReader::from_directory(ctx.journal_path()).unwrap();
+    let entries = reader.read_all().unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(verify_chain(&entries).is_ok());
+    assert_eq!(reader.read_from(2).unwrap()[0].correlation_id, "deposit-1");
+}
+
 /// Test: Double-entry validation rejects unbalanced entries
 #[test]
 fn test_double_entry_validation() {
@@ -806,3 +878,2872 @@ async fn test_margin_ratio_tracking() {
     // Not liquidatable (ratio > 1.0)
     assert!(!ctx.risk.state().is_liquidatable("ALICE", "USDT"));
 }
+
+/// Test: Borrow limits come from the whole portfolio, priced by the oracle
+#[tokio::test]
+async fn test_borrow_checked_against_oracle_prices() {
+    use bibank_oracle::ReplayOracle;
+    use bibank_rpc::{commands, CommitError};
+    use bibank_risk::RiskError;
+    use std::sync::Arc;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+
+    // Recorded feed: BTC at 10000
+    let oracle = Arc::new(ReplayOracle::from_csv("1769335200,BTC/USDT,10000\n").unwrap());
+    ctx.oracle = oracle.clone();
+
+    commands::init(&mut ctx, "genesis-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::ONE, "BTC", "dep-1").await.unwrap();
+
+    // 10000 USDT of BTC counts as 9000 after its 10% haircut,
+    // allowing 90000 USDT of loans at 10% initial margin
+    let err = commands::borrow(&mut ctx, "ALICE", Decimal::from(90_001), "USDT", "borrow-1")
+        .await
+        .unwrap_err();
+    match err.downcast_ref::<CommitError>() {
+        Some(CommitError::Risk(RiskError::ExceedsMaxLeverage { max_allowed, .. })) => {
+            assert_eq!(max_allowed, "90000")
+        }
+        other => panic!("expected ExceedsMaxLeverage, got {:?}", other),
+    }
+
+    commands::borrow(&mut ctx, "ALICE", Decimal::from(60_000), "USDT", "borrow-1")
+        .await
+        .unwrap();
+
+    // A retry is not re-checked against the now-larger loan
+    let retry = commands::borrow(&mut ctx, "ALICE", Decimal::from(60_000), "USDT", "borrow-1")
+        .await
+        .unwrap();
+    assert_eq!(retry.sequence, 3);
+
+    // Collateral is BTC (after haircut) + borrowed USDT; the loan is valued in USDT
+    let valuation = ctx.risk.value("ALICE", ctx.oracle.as_ref(), "USDT").await.unwrap();
+    assert_eq!(valuation.collateral, Decimal::from(69_000));
+    assert_eq!(valuation.loans, Decimal::from(60_000));
+
+    // No more room at this price
+    let err = commands::borrow(&mut ctx, "ALICE", Decimal::from(40_000), "USDT", "borrow-2")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<CommitError>(),
+        Some(CommitError::Risk(RiskError::ExceedsMaxLeverage { .. }))
+    ));
+}
+
+/// Test: A USDT loan against BTC collateral is liquidated out of the BTC
+#[tokio::test]
+async fn test_cross_asset_liquidation_commits() {
+    use bibank_oracle::ReplayOracle;
+    use bibank_risk::LiquidationEngine;
+    use bibank_rpc::commands;
+    use chrono::DateTime;
+    use std::sync::Arc;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+
+    // BTC at 10000, then 5000
+    let oracle = Arc::new(
+        ReplayOracle::from_csv("1769335200,BTC/USDT,10000\n1769338800,BTC/USDT,5000\n").unwrap(),
+    );
+    oracle.set_as_of(DateTime::from_timestamp(1_769_335_200, 0));
+    ctx.oracle = oracle.clone();
+
+    commands::init(&mut ctx, "genesis-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::ONE, "BTC", "dep-1").await.unwrap();
+    register(&mut ctx, "ALICE", 1).await;
+    commands::borrow(&mut ctx, "ALICE", Decimal::from(6000), "USDT", "borrow-1").await.unwrap();
+    commands::withdraw(&mut ctx, "ALICE", Decimal::from(6000), "USDT", "wd-1").await.unwrap();
+
+    // 9000 / 6000: healthy
+    let liquidation = LiquidationEngine::default();
+    let valuation = ctx.risk.value("ALICE", ctx.oracle.as_ref(), "USDT").await.unwrap();
+    assert!(liquidation.plan_liquidation(&valuation).is_none());
+
+    // 4500 / 6000: BTC is the only collateral left to seize
+    oracle.set_as_of(DateTime::from_timestamp(1_769_338_800, 0));
+    let valuation = ctx.risk.value("ALICE", ctx.oracle.as_ref(), "USDT").await.unwrap();
+    let (entry, plan) = liquidation.liquidate(&valuation, "LIQUIDATOR", "liq-1").unwrap().unwrap();
+    assert_eq!(plan.loan_repaid, Decimal::from(3000));
+    assert_eq!(plan.seized[0].asset, "BTC");
+    assert_eq!(plan.seized[0].amount, Decimal::new(63, 2)); // 3150 / 5000
+
+    ctx.commit(entry).await.unwrap();
+    let state = ctx.risk.state();
+    assert_eq!(state.get_loan_balance("ALICE", "USDT"), Decimal::from(3000));
+    assert_eq!(state.get_available_balance("ALICE", "BTC"), Decimal::new(37, 2));
+}
+
+// ============================================================================
+// Phase 3: Order Matching Tests
+// ============================================================================
+
+/// Helper: Genesis, then fund ALICE with USDT and BOB with BTC
+async fn setup_traders(ctx: &mut AppContext) {
+    let genesis = JournalEntryBuilder::new()
+        .intent(TransactionIntent::Genesis)
+        .correlation_id("genesis-1")
+        .debit(AccountKey::system_vault("USDT"), amount(1_000_000))
+        .credit(
+            AccountKey::new(AccountCategory::Equity, "SYSTEM", "CAPITAL", "USDT", "MAIN"),
+            amount(1_000_000),
+        )
+        .build_unsigned()
+        .unwrap();
+    ctx.commit(genesis).await.unwrap();
+
+    for (user, asset, value) in [("ALICE", "USDT", 200_000), ("BOB", "BTC", 10)] {
+        let deposit = JournalEntryBuilder::new()
+            .intent(TransactionIntent::Deposit)
+            .correlation_id(format!("deposit-{}", user))
+            .debit(AccountKey::system_vault(asset), amount(value))
+            .credit(AccountKey::user_available(user, asset), amount(value))
+            .build_unsigned()
+            .unwrap();
+        ctx.commit(deposit).await.unwrap();
+    }
+}
+
+/// Test: Crossing orders settle out of LOCKED, with price improvement refunded
+#[tokio::test]
+async fn test_order_fill_settles_locked_funds() {
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    setup_traders(&mut ctx).await;
+
+    // BOB rests 1 BTC @ 50000
+    commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::ONE, OrderType::Limit, "ask-1")
+        .await
+        .unwrap();
+    assert_eq!(
+        ctx.risk.state().get_balance(&AccountKey::user_locked("BOB", "BTC")),
+        Decimal::ONE
+    );
+
+    // ALICE lifts it with a 50100 limit, fills at the maker's 50000
+    commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(50_100, 0), Decimal::ONE, OrderType::Limit, "bid-1")
+        .await
+        .unwrap();
+
+    let state = ctx.risk.state();
+    assert_eq!(state.get_balance(&AccountKey::user_available("ALICE", "BTC")), Decimal::ONE);
+    assert_eq!(state.get_balance(&AccountKey::user_available("ALICE", "USDT")), Decimal::new(150_000, 0));
+    assert_eq!(state.get_balance(&AccountKey::user_locked("ALICE", "USDT")), Decimal::ZERO);
+    assert_eq!(state.get_balance(&AccountKey::user_available("BOB", "USDT")), Decimal::new(50_000, 0));
+    assert_eq!(state.get_balance(&AccountKey::user_locked("BOB", "BTC")), Decimal::ZERO);
+    assert_eq!(ctx.matching.total_order_count(), 0);
+
+    // Trade entry references both orders and the taker's placement
+    let entries = EventReader::from_directory(ctx.journal_path()).unwrap().read_all().unwrap();
+    let trade = entries.last().unwrap();
+    assert_eq!(trade.intent, TransactionIntent::Trade);
+    assert_eq!(trade.causality_id.as_deref(), Some("bid-1"));
+    assert!(trade.metadata.contains_key("maker_order_id"));
+    assert!(trade.metadata.contains_key("taker_order_id"));
+}
+
+/// Test: Cancel releases exactly the lock behind the unfilled remainder
+#[tokio::test]
+async fn test_cancel_order_unlocks_remaining() {
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    setup_traders(&mut ctx).await;
+
+    let ask = commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::new(2, 0), OrderType::Limit, "ask-1")
+        .await
+        .unwrap();
+    commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::new(5, 1), OrderType::Limit, "bid-1")
+        .await
+        .unwrap();
+
+    commands::cancel_order(&mut ctx, &ask, "BTC", "USDT", "cancel-1").await.unwrap();
+
+    let state = ctx.risk.state();
+    assert_eq!(state.get_balance(&AccountKey::user_locked("BOB", "BTC")), Decimal::ZERO);
+    assert_eq!(state.get_balance(&AccountKey::user_available("BOB", "BTC")), Decimal::new(95, 1));
+    assert_eq!(state.get_balance(&AccountKey::user_available("BOB", "USDT")), Decimal::new(25_000, 0));
+    assert_eq!(ctx.matching.total_order_count(), 0);
+
+    // Cancelling again fails: the order is gone from the book
+    assert!(commands::cancel_order(&mut ctx, &ask, "BTC", "USDT", "cancel-2").await.is_err());
+}
+
+/// Test: Self-trade is rejected before any collateral is locked
+#[tokio::test]
+async fn test_self_trade_rejected_before_lock() {
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    setup_traders(&mut ctx).await;
+
+    let deposit = JournalEntryBuilder::new()
+        .intent(TransactionIntent::Deposit)
+        .correlation_id("deposit-bob-usdt")
+        .debit(AccountKey::system_vault("USDT"), amount(100_000))
+        .credit(AccountKey::user_available("BOB", "USDT"), amount(100_000))
+        .build_unsigned()
+        .unwrap();
+    ctx.commit(deposit).await.unwrap();
+
+    commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::ONE, OrderType::Limit, "ask-1")
+        .await
+        .unwrap();
+    let seq = ctx.last_sequence();
+
+    let result = commands::place_order(&mut ctx, "BOB", "buy", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::ONE, OrderType::Limit, "bid-1").await;
+
+    assert!(result.is_err());
+    assert_eq!(ctx.last_sequence(), seq, "No lock entry for rejected order");
+    assert_eq!(
+        ctx.risk.state().get_balance(&AccountKey::user_locked("BOB", "USDT")),
+        Decimal::ZERO
+    );
+}
+
+/// Test: A fill that fails to commit rolls the book back and releases the taker
+#[tokio::test]
+async fn test_failed_fill_rolls_back_book() {
+    use bibank_matching::TradingPair;
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let data_path = temp_dir.path();
+    let pair = TradingPair::btc_usdt();
+
+    let ask_2 = {
+        let mut ctx = AppContext::new(data_path).await.unwrap();
+        setup_traders(&mut ctx).await;
+
+        commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::ONE, OrderType::Limit, "ask-1")
+            .await
+            .unwrap();
+        let ask_2 = commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(50_100, 0), Decimal::ONE, OrderType::Limit, "ask-2")
+            .await
+            .unwrap();
+
+        // Out-of-band unlock: the ledger no longer backs BOB's second ask
+        let unlock = JournalEntryBuilder::new()
+            .intent(TransactionIntent::OrderCancel)
+            .correlation_id("manual-unlock")
+            .debit(AccountKey::user_locked("BOB", "BTC"), amount(1))
+            .credit(AccountKey::user_available("BOB", "BTC"), amount(1))
+            .build_unsigned()
+            .unwrap();
+        ctx.commit(unlock).await.unwrap();
+
+        // First fill settles, second one fails the risk check
+        let result = commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(50_100, 0), Decimal::new(2, 0), OrderType::Limit, "bid-1").await;
+        assert!(result.is_err());
+        assert!(ctx.correlations().get("bid-1-fill-1").is_some());
+        assert!(ctx.correlations().get("bid-1-fill-2").is_none());
+
+        // Book matches the journal: second ask untouched, no taker left
+        let book = ctx.matching.get_book(&pair).unwrap();
+        assert_eq!(book.order_count(), 1);
+        assert_eq!(book.get_order(&ask_2).unwrap().remaining(), Decimal::ONE);
+
+        // Taker's unfilled quantity is released, not stuck in LOCKED
+        let state = ctx.risk.state();
+        assert_eq!(state.get_balance(&AccountKey::user_available("ALICE", "BTC")), Decimal::ONE);
+        assert_eq!(state.get_balance(&AccountKey::user_locked("ALICE", "USDT")), Decimal::ZERO);
+        assert_eq!(state.get_balance(&AccountKey::user_available("ALICE", "USDT")), Decimal::new(150_000, 0));
+        ask_2
+    };
+
+    // Replaying the journal rebuilds the same book
+    let ctx = AppContext::new(data_path).await.unwrap();
+    assert_eq!(ctx.matching.total_order_count(), 1);
+    assert_eq!(ctx.matching.get_order(&pair, &ask_2).unwrap().remaining(), Decimal::ONE);
+}
+
+/// Test: Order books are rebuilt from the journal on restart
+#[tokio::test]
+async fn test_order_book_rebuilt_on_restart() {
+    use bibank_matching::TradingPair;
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let data_path = temp_dir.path();
+    let pair = TradingPair::btc_usdt();
+
+    let (partial, cancelled, resting_bid) = {
+        let mut ctx = AppContext::new(data_path).await.unwrap();
+        setup_traders(&mut ctx).await;
+
+        let partial = commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::new(3, 0), OrderType::Limit, "ask-1")
+            .await
+            .unwrap();
+        let cancelled = commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(52_000, 0), Decimal::ONE, OrderType::Limit, "ask-2")
+            .await
+            .unwrap();
+        commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::ONE, OrderType::Limit, "bid-1")
+            .await
+            .unwrap();
+        let resting_bid = commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(49_000, 0), Decimal::ONE, OrderType::Limit, "bid-2")
+            .await
+            .unwrap();
+        commands::cancel_order(&mut ctx, &cancelled, "BTC", "USDT", "cancel-1").await.unwrap();
+
+        (partial, cancelled, resting_bid)
+    };
+
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+
+    assert_eq!(ctx.matching.total_order_count(), 2);
+    assert!(ctx.matching.get_order(&pair, &cancelled).is_none());
+    assert_eq!(ctx.matching.get_order(&pair, &partial).unwrap().remaining(), Decimal::new(2, 0));
+    assert_eq!(ctx.matching.get_order(&pair, &partial).unwrap().user_id, "BOB");
+    assert_eq!(ctx.matching.best_bid(&pair), Some(Decimal::new(49_000, 0)));
+
+    // The rebuilt book still settles: cancelling the bid releases its lock
+    commands::cancel_order(&mut ctx, &resting_bid, "BTC", "USDT", "cancel-2").await.unwrap();
+    assert_eq!(
+        ctx.risk.state().get_balance(&AccountKey::user_locked("ALICE", "USDT")),
+        Decimal::ZERO
+    );
+}
+
+/// Test: A resting post-only order keeps its type across a restart
+#[tokio::test]
+async fn test_post_only_order_replayed_with_type() {
+    use bibank_matching::TradingPair;
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let data_path = temp_dir.path();
+    let pair = TradingPair::btc_usdt();
+
+    let order_id = {
+        let mut ctx = AppContext::new(data_path).await.unwrap();
+        setup_traders(&mut ctx).await;
+
+        commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(51_000, 0), Decimal::ONE, OrderType::PostOnly, "post-1")
+            .await
+            .unwrap()
+    };
+
+    let ctx = AppContext::new(data_path).await.unwrap();
+    let order = ctx.matching.get_order(&pair, &order_id).unwrap();
+    assert_eq!(order.order_type, OrderType::PostOnly);
+    assert_eq!(order.remaining(), Decimal::ONE);
+}
+
+/// Test: Market buy locks at its slippage limit and releases the unfilled remainder
+#[tokio::test]
+async fn test_market_order_slippage_and_unlock() {
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let data_path = temp_dir.path();
+
+    {
+        let mut ctx = AppContext::new(data_path).await.unwrap();
+        setup_traders(&mut ctx).await;
+
+        commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::ONE, OrderType::Limit, "ask-1")
+            .await
+            .unwrap();
+        commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(50_400, 0), Decimal::ONE, OrderType::Limit, "ask-2")
+            .await
+            .unwrap();
+        commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(51_000, 0), Decimal::ONE, OrderType::Limit, "ask-3")
+            .await
+            .unwrap();
+
+        // 1% above 50000 reaches 50500: takes the first two asks only
+        let market = OrderType::Market { max_slippage: Decimal::new(1, 2) };
+        commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::ZERO, Decimal::new(3, 0), market, "mkt-1")
+            .await
+            .unwrap();
+
+        let state = ctx.risk.state();
+        assert_eq!(state.get_balance(&AccountKey::user_available("ALICE", "BTC")), Decimal::new(2, 0));
+        assert_eq!(state.get_balance(&AccountKey::user_available("ALICE", "USDT")), Decimal::new(99_600, 0));
+        assert_eq!(state.get_balance(&AccountKey::user_locked("ALICE", "USDT")), Decimal::ZERO);
+        assert_eq!(ctx.matching.total_order_count(), 1);
+
+        let entries = EventReader::from_directory(ctx.journal_path()).unwrap().read_all().unwrap();
+        let unlock = entries.last().unwrap();
+        assert_eq!(unlock.intent, TransactionIntent::OrderCancel);
+        assert_eq!(unlock.correlation_id, "mkt-1-cancel");
+    }
+
+    // Nothing of the market order survives a restart
+    let ctx = AppContext::new(data_path).await.unwrap();
+    assert_eq!(ctx.matching.total_order_count(), 1);
+    assert_eq!(ctx.matching.best_bid(&bibank_matching::TradingPair::btc_usdt()), None);
+}
+
+/// Test: Market order on an empty book is rejected before locking
+#[tokio::test]
+async fn test_market_order_without_liquidity() {
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    setup_traders(&mut ctx).await;
+    let seq = ctx.last_sequence();
+
+    let result = commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::ZERO, Decimal::ONE, OrderType::market(), "mkt-1").await;
+
+    assert!(result.is_err());
+    assert_eq!(ctx.last_sequence(), seq);
+}
+
+/// Test: IOC, FOK and post-only remainders are cancelled and unlocked, never rested
+#[tokio::test]
+async fn test_time_in_force_orders_unlock_remainder() {
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    setup_traders(&mut ctx).await;
+
+    // ALICE bids 1 BTC @ 50000
+    commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::ONE, OrderType::Limit, "bid-1")
+        .await
+        .unwrap();
+
+    // FOK for 2 BTC cannot be filled: killed, nothing traded
+    commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::new(2, 0), OrderType::FillOrKill, "fok-1")
+        .await
+        .unwrap();
+    assert_eq!(ctx.risk.state().get_balance(&AccountKey::user_locked("BOB", "BTC")), Decimal::ZERO);
+    assert_eq!(ctx.risk.state().get_balance(&AccountKey::user_available("BOB", "BTC")), Decimal::new(10, 0));
+
+    // Post-only that would cross is cancelled
+    commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(49_000, 0), Decimal::ONE, OrderType::PostOnly, "post-1")
+        .await
+        .unwrap();
+    assert_eq!(ctx.risk.state().get_balance(&AccountKey::user_locked("BOB", "BTC")), Decimal::ZERO);
+    assert_eq!(ctx.matching.total_order_count(), 1);
+
+    // IOC for 3 BTC fills 1, cancels 2
+    commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(49_000, 0), Decimal::new(3, 0), OrderType::ImmediateOrCancel, "ioc-1")
+        .await
+        .unwrap();
+
+    let state = ctx.risk.state();
+    assert_eq!(state.get_balance(&AccountKey::user_locked("BOB", "BTC")), Decimal::ZERO);
+    assert_eq!(state.get_balance(&AccountKey::user_available("BOB", "BTC")), Decimal::new(9, 0));
+    assert_eq!(state.get_balance(&AccountKey::user_available("BOB", "USDT")), Decimal::new(50_000, 0));
+    assert_eq!(state.get_balance(&AccountKey::user_available("ALICE", "BTC")), Decimal::ONE);
+    assert_eq!(ctx.matching.total_order_count(), 0);
+}
+
+// ============================================================================
+// Market Data Tests
+// ============================================================================
+
+/// Test: Order flow publishes depth deltas and trade prints, folded into candles
+#[tokio::test]
+async fn test_market_data_feed_and_candles() {
+    use bibank_bus::LedgerEvent;
+    use bibank_matching::{CandleInterval, DepthChange, OrderSide, TradingPair};
+    use bibank_rpc::{commands, RpcRequest};
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    setup_traders(&mut ctx).await;
+    let mut feed = ctx.bus.receiver();
+
+    let ask = commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::new(2, 0), OrderType::Limit, "ask-1")
+        .await
+        .unwrap();
+    commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::new(5, 1), OrderType::Limit, "bid-1")
+        .await
+        .unwrap();
+    commands::cancel_order(&mut ctx, &ask, "BTC", "USDT", "cancel-1").await.unwrap();
+
+    // Committed entries share the channel
+    let mut events = Vec::new();
+    while let Ok(event) = feed.try_recv() {
+        if !matches!(event, LedgerEvent::EntryCommitted { .. }) {
+            events.push(event);
+        }
+    }
+    let pair = TradingPair::btc_usdt();
+    assert!(events.iter().all(|e| e.pair() == Some(&pair)));
+
+    let level = |quantity| vec![DepthChange { side: OrderSide::Sell, price: Decimal::new(50_000, 0), quantity }];
+    let depths: Vec<_> = events
+        .iter()
+        .filter_map(|e| match e {
+            LedgerEvent::DepthUpdated { changes, .. } => Some(changes.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(depths, vec![level(Decimal::new(2, 0)), level(Decimal::new(15, 1)), level(Decimal::ZERO)]);
+
+    let trades: Vec<_> = events
+        .iter()
+        .filter_map(|e| match e {
+            LedgerEvent::TradePrinted { trade } => Some(trade.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(trades.len(), 1);
+    let trade = &trades[0];
+    assert_eq!(trade.price, Decimal::new(50_000, 0));
+    assert_eq!(trade.quantity, Decimal::new(5, 1));
+    assert_eq!(trade.taker_side, Some(OrderSide::Buy));
+
+    // Every interval has one candle holding the print
+    for interval in CandleInterval::ALL {
+        let from = interval.open_time(trade.timestamp);
+        let to = from + chrono::Duration::seconds(interval.seconds());
+        let candles = commands::load_candles(&ctx, &pair, interval, from, to).await.unwrap();
+        assert_eq!(candles.len(), 1);
+        assert_eq!(candles[0].open, trade.price);
+        assert_eq!(candles[0].volume, trade.quantity);
+        assert_eq!(candles[0].quote_volume, Decimal::new(25_000, 0));
+        assert_eq!(candles[0].trades, 1);
+    }
+
+    // Candles rebuild identically from the journal
+    let from = trade.timestamp - chrono::Duration::days(1);
+    ctx.projection.as_ref().unwrap().replay(&ctx.bus).await.unwrap();
+    let rebuilt = commands::load_candles(&ctx, &pair, CandleInterval::OneMinute, from, chrono::Utc::now())
+        .await
+        .unwrap();
+    assert_eq!(rebuilt.len(), 1);
+    assert_eq!(rebuilt[0].trades, 1);
+
+    let (handle, _writer) = bibank_rpc::server::spawn_writer(ctx);
+    let result = handle
+        .call(RpcRequest::new("candles", json!({
+            "base": "btc", "quote": "usdt", "interval": "1h", "from": from,
+        })))
+        .await
+        .result
+        .unwrap();
+    assert_eq!(result["pair"], json!("BTC/USDT"));
+    assert_eq!(result["interval"], json!("1h"));
+    assert_eq!(result["candles"][0]["close"], json!("50000"));
+
+    let response = handle
+        .call(RpcRequest::new("candles", json!({
+            "base": "BTC", "quote": "USDT", "interval": "2m", "from": from,
+        })))
+        .await;
+    assert_eq!(response.error.unwrap().code, bibank_rpc::RpcError::INVALID_PARAMS);
+}
+
+// ============================================================================
+// Snapshot Tests
+// ============================================================================
+
+/// Test: Startup from a snapshot plus tail matches a full replay
+#[tokio::test]
+async fn test_snapshot_restart_replays_only_tail() {
+    use bibank_matching::TradingPair;
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let data_path = temp_dir.path();
+    let pair = TradingPair::btc_usdt();
+
+    let (ask, snapshot_sequence) = {
+        let mut ctx = AppContext::new(data_path).await.unwrap();
+        setup_traders(&mut ctx).await;
+
+        let ask = commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::new(3, 0), OrderType::Limit, "ask-1")
+            .await
+            .unwrap();
+        commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::ONE, OrderType::Limit, "bid-1")
+            .await
+            .unwrap();
+
+        ctx.snapshot().unwrap();
+        let snapshot_sequence = ctx.last_sequence();
+
+        // Tail: another fill against the snapshotted order
+        commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::ONE, OrderType::Limit, "bid-2")
+            .await
+            .unwrap();
+
+        (ask, snapshot_sequence)
+    };
+
+    let ctx = AppContext::new(data_path).await.unwrap();
+    let snapshot = ctx.snapshots.find(snapshot_sequence).unwrap();
+    assert_eq!(snapshot.open_orders.len(), 1);
+
+    // Same state as a replay from scratch
+    let full = {
+        std::fs::remove_dir_all(ctx.snapshots.dir()).unwrap();
+        AppContext::new(data_path).await.unwrap()
+    };
+    assert_eq!(ctx.last_sequence(), full.last_sequence());
+    assert_eq!(ctx.risk.state().all_balances(), full.risk.state().all_balances());
+    assert_eq!(ctx.matching.get_order(&pair, &ask).unwrap().remaining(), Decimal::ONE);
+    assert_eq!(full.matching.get_order(&pair, &ask).unwrap().remaining(), Decimal::ONE);
+
+    let reader = EventReader::from_directory(ctx.journal_path()).unwrap();
+    assert_eq!(snapshot.verify(&reader).unwrap() as u64, snapshot_sequence);
+}
+
+/// Test: Snapshots that no longer match the journal are ignored
+#[tokio::test]
+async fn test_invalid_snapshot_falls_back_to_replay() {
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let data_path = temp_dir.path();
+
+    let path = {
+        let mut ctx = AppContext::new(data_path).await.unwrap();
+        setup_traders(&mut ctx).await;
+        ctx.snapshot().unwrap()
+    };
+
+    // Tamper with a balance: checksum no longer matches
+    let tampered = std::fs::read_to_string(&path)
+        .unwrap()
+        .replace("\"200000\"", "\"999999\"");
+    std::fs::write(&path, tampered).unwrap();
+
+    let ctx = AppContext::new(data_path).await.unwrap();
+    let reader = EventReader::from_directory(ctx.journal_path()).unwrap();
+    assert!(ctx.snapshots.latest_valid(&reader).is_none());
+    assert_eq!(
+        ctx.risk.state().get_balance(&AccountKey::user_available("ALICE", "USDT")),
+        Decimal::new(200_000, 0)
+    );
+
+    // A snapshot tagged with a hash the journal doesn't have is rejected too
+    let mut forged = bibank_rpc::Snapshot::capture(
+        1,
+        "not-the-hash",
+        ctx.risk.state(),
+        &ctx.matching,
+    );
+    forged.checksum = forged.compute_checksum();
+    assert!(matches!(
+        forged.check_anchor(&reader),
+        Err(bibank_rpc::SnapshotError::HashMismatch { .. })
+    ));
+}
+
+/// Test: Projection catches up from its checkpoint instead of rebuilding
+#[tokio::test]
+async fn test_projection_catches_up_from_checkpoint() {
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let data_path = temp_dir.path();
+
+    {
+        let mut ctx = AppContext::new(data_path).await.unwrap();
+        setup_traders(&mut ctx).await;
+    }
+
+    let ctx = AppContext::new(data_path).await.unwrap();
+    let projection = ctx.projection.as_ref().unwrap();
+    assert_eq!(projection.last_sequence().await.unwrap(), Some(ctx.last_sequence()));
+
+    let balance = projection
+        .balance
+        .get_balance(&AccountKey::user_available("ALICE", "USDT").to_string())
+        .await
+        .unwrap();
+    assert_eq!(balance, Decimal::new(200_000, 0));
+}
+
+/// Helper: durable subscriber recording delivered sequences, failing on one correlation id
+struct Collector {
+    seen: std::sync::Mutex<Vec<u64>>,
+    reject: &'static str,
+}
+
+#[async_trait::async_trait]
+impl bibank_bus::EventSubscriber for Collector {
+    fn name(&self) -> &str {
+        "collector"
+    }
+
+    async fn handle(&self, event: &bibank_bus::LedgerEvent) -> Result<(), bibank_bus::BusError> {
+        let bibank_bus::LedgerEvent::EntryCommitted { entry, .. } = event else {
+            return Ok(());
+        };
+        if entry.correlation_id == self.reject {
+            return Err(bibank_bus::BusError::SubscriberFailed {
+                name: "collector".to_string(),
+                reason: "downstream rejected".to_string(),
+            });
+        }
+        self.seen.lock().unwrap().push(entry.sequence);
+        Ok(())
+    }
+}
+
+fn collector(ctx: &mut AppContext) -> std::sync::Arc<Collector> {
+    use bibank_bus::{DurableSubscriber, RetryPolicy};
+
+    let collector = std::sync::Arc::new(Collector {
+        seen: std::sync::Mutex::new(Vec::new()),
+        reject: "dep-bad",
+    });
+    let policy = RetryPolicy {
+        max_attempts: 2,
+        initial_backoff: std::time::Duration::from_millis(1),
+        max_backoff: std::time::Duration::from_millis(5),
+    };
+    let durable = DurableSubscriber::new(collector.clone(), ctx.subscribers.clone()).with_retry_policy(policy);
+    ctx.bus.subscribe_durable(durable);
+    collector
+}
+
+/// Test: durable subscribers catch up from their checkpoint and dead-letter failures
+#[tokio::test]
+async fn test_durable_subscribers_catch_up() {
+    use bibank_rpc::{commands, RpcRequest};
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    relax_screening(data_path);
+
+    // Committed before any subscriber exists
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    commands::init(&mut ctx, "init-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(100), "USDT", "dep-1").await.unwrap();
+    drop(ctx);
+
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    let first = collector(&mut ctx);
+    let (handle, writer) = bibank_rpc::server::spawn_writer(ctx);
+    let call = |method: &str, params: serde_json::Value| handle.call(RpcRequest::new(method, params));
+
+    for correlation_id in ["dep-2", "dep-bad", "dep-3"] {
+        let params = json!({ "user": "ALICE", "amount": "10", "asset": "USDT", "correlation_id": correlation_id });
+        call("deposit", params).await.result.unwrap();
+    }
+
+    // The subscriber task follows the journal head
+    for _ in 0..200 {
+        if first.seen.lock().unwrap().last() == Some(&5) {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(*first.seen.lock().unwrap(), vec![1, 2, 3, 5]);
+
+    let listed = call("list_subscribers", json!(null)).await.result.unwrap();
+    assert_eq!(listed["head"], json!(5));
+    assert_eq!(listed["subscribers"][0]["subscriber"], json!("collector"));
+    assert_eq!(listed["subscribers"][0]["lag"], json!(0));
+    assert_eq!(listed["subscribers"][0]["dead_letters"], json!(1));
+    let letters = call("dead_letters", json!({ "subscriber": "collector" })).await.result.unwrap();
+    assert_eq!(letters["dead_letters"][0]["sequence"], json!(4));
+    assert_eq!(letters["dead_letters"][0]["attempts"], json!(2));
+
+    handle.shutdown().await;
+    drop(writer.await.unwrap());
+
+    // Committed while the subscriber is down: delivered on the next start, nothing else
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(10), "USDT", "dep-4").await.unwrap();
+    assert_eq!(ctx.subscriber_checkpoints().unwrap()[0].lag(ctx.last_sequence()), 1);
+    drop(ctx);
+
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    let second = collector(&mut ctx);
+    let passes = ctx.bus.catch_up_durable().await.unwrap();
+    assert_eq!(passes[0].1.delivered, 1);
+    assert_eq!(*second.seen.lock().unwrap(), vec![6]);
+}
+
+/// Helper: local HTTP stub failing the first request with 503, then 200;
+/// forwards each raw request
+async fn webhook_stub() -> (String, tokio::sync::mpsc::UnboundedReceiver<String>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        let mut status = "503 Service Unavailable";
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            loop {
+                let n = stream.read(&mut buf).await.unwrap_or(0);
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).into_owned();
+                let complete = text.split_once("\r\n\r\n").is_some_and(|(head, body)| {
+                    let length = head
+                        .lines()
+                        .find_map(|l| l.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().to_string()))
+                        .and_then(|v| v.parse::<usize>().ok())
+                        .unwrap_or(0);
+                    body.len() >= length
+                });
+                if n == 0 || complete {
+                    tx.send(text).ok();
+                    break;
+                }
+            }
+            let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+            stream.write_all(response.as_bytes()).await.ok();
+            status = "200 OK";
+        }
+    });
+
+    (url, rx)
+}
+
+/// Test: webhooks receive signed, filtered entries and retry failed deliveries
+#[tokio::test]
+async fn test_webhook_delivery() {
+    use bibank_bus::webhook::{verify_signature, SEQUENCE_HEADER, SIGNATURE_HEADER, TIMESTAMP_HEADER};
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    relax_screening(data_path);
+
+    let (url, mut requests) = webhook_stub().await;
+    let config = serde_json::json!({
+        "endpoints": [{
+            "name": "alice-deposits",
+            "url": url,
+            "secret": "whsec-test",
+            "filter": { "intents": ["deposit"], "users": ["ALICE"] },
+            "max_attempts": 3
+        }]
+    });
+    std::fs::write(data_path.join(bibank_rpc::context::WEBHOOKS_FILE), config.to_string()).unwrap();
+
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    commands::init(&mut ctx, "init-1").await.unwrap();
+    commands::deposit(&mut ctx, "BOB", Decimal::from(100), "USDT", "dep-bob").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(100), "USDT", "dep-alice").await.unwrap();
+
+    let passes = ctx.bus.catch_up_durable().await.unwrap();
+    assert_eq!(passes[0].0, "webhook-alice-deposits");
+    assert_eq!(passes[0].1.dead_lettered, 0);
+
+    // Only ALICE's deposit (sequence 3) went out: rejected once, then accepted
+    let header = |request: &str, name: &str| {
+        request
+            .lines()
+            .find_map(|l| l.split_once(':').filter(|(k, _)| k.eq_ignore_ascii_case(name)))
+            .map(|(_, v)| v.trim().to_string())
+            .unwrap()
+    };
+    for _ in 0..2 {
+        let request = requests.recv().await.unwrap();
+        assert_eq!(header(&request, SEQUENCE_HEADER), "3");
+
+        let body = request.split_once("\r\n\r\n").unwrap().1;
+        let timestamp: i64 = header(&request, TIMESTAMP_HEADER).parse().unwrap();
+        let signature = header(&request, SIGNATURE_HEADER);
+        assert!(verify_signature("whsec-test", timestamp, body.as_bytes(), &signature));
+        assert!(!verify_signature("wrong-secret", timestamp, body.as_bytes(), &signature));
+
+        let payload: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(payload["entry"]["correlation_id"], serde_json::json!("dep-alice"));
+    }
+    assert!(requests.try_recv().is_err());
+
+    let checkpoint = &ctx.subscriber_checkpoints().unwrap()[0];
+    assert_eq!(checkpoint.last_sequence, 3);
+    assert_eq!(checkpoint.dead_letters, 0);
+}
+
+// ============================================================================
+// JSON-RPC Server Tests
+// ============================================================================
+
+/// Helper: Server over a fresh data directory with ALICE holding 1000 USDT
+async fn start_server(data_path: &std::path::Path) -> (bibank_rpc::RpcHandle, tokio::task::JoinHandle<AppContext>) {
+    use bibank_rpc::RpcRequest;
+    use serde_json::json;
+
+    relax_screening(data_path);
+    let ctx = AppContext::new(data_path).await.unwrap();
+    let (handle, writer) = bibank_rpc::server::spawn_writer(ctx);
+
+    let response = handle.call(RpcRequest::new("init", json!(null))).await;
+    assert!(response.error.is_none(), "{:?}", response.error);
+    let response = handle
+        .call(RpcRequest::new(
+            "deposit",
+            json!({ "user": "ALICE", "amount": "1000", "asset": "USDT" }),
+        ))
+        .await;
+    assert!(response.error.is_none(), "{:?}", response.error);
+
+    (handle, writer)
+}
+
+/// Test: Concurrent requests are committed one at a time by the writer
+#[tokio::test]
+async fn test_server_serializes_concurrent_commits() {
+    use bibank_rpc::RpcRequest;
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (handle, writer) = start_server(temp_dir.path()).await;
+
+    let mut tasks = Vec::new();
+    for i in 0..20 {
+        let handle = handle.clone();
+        tasks.push(tokio::spawn(async move {
+            handle
+                .call(
+                    RpcRequest::new(
+                        "transfer",
+                        json!({ "from": "ALICE", "to": "BOB", "amount": "10", "asset": "USDT" }),
+                    )
+                    .id(i),
+                )
+                .await
+        }));
+    }
+
+    let mut sequences = Vec::new();
+    for task in tasks {
+        let response = task.await.unwrap();
+        let result = response.result.expect("transfer succeeds");
+        sequences.push(result["sequence"].as_u64().unwrap());
+    }
+    sequences.sort();
+    assert_eq!(sequences, (3..=22).collect::<Vec<_>>());
+
+    let response = handle
+        .call(RpcRequest::new("balance", json!({ "user": "bob" })))
+        .await;
+    assert_eq!(response.result.unwrap()["balances"]["USDT"]["AVAILABLE"], json!("200"));
+
+    // Shutdown hands the context back, in sync with the journal
+    handle.shutdown().await;
+    let ctx = writer.await.unwrap();
+    assert_eq!(ctx.last_sequence(), 22);
+
+    let reader = EventReader::from_directory(temp_dir.path().join("journal")).unwrap();
+    assert_eq!(reader.last_sequence().unwrap(), Some(22));
+}
+
+/// Test: Failures come back as structured JSON-RPC errors
+#[tokio::test]
+async fn test_server_structured_errors() {
+    use bibank_rpc::{RpcError, RpcRequest};
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (handle, _writer) = start_server(temp_dir.path()).await;
+
+    // Risk rejection carries the numbers
+    let response = handle
+        .call(RpcRequest::new(
+            "withdraw",
+            json!({ "user": "ALICE", "amount": "5000", "asset": "USDT" }),
+        ).id("w-1"))
+        .await;
+    assert_eq!(response.id, json!("w-1"));
+    let error = response.error.unwrap();
+    assert_eq!(error.code, RpcError::RISK_REJECTED);
+    assert_eq!(error.kind(), Some("insufficient_balance"));
+    assert_eq!(error.data.as_ref().unwrap()["required"], json!("5000"));
+
+    // Borrow beyond 10x of the portfolio's equity
+    let error = handle
+        .call(RpcRequest::new(
+            "borrow",
+            json!({ "user": "ALICE", "amount": "20000", "asset": "USDT" }),
+        ))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.code, RpcError::RISK_REJECTED);
+    assert_eq!(error.kind(), Some("exceeds_max_leverage"));
+    assert_eq!(error.data.as_ref().unwrap()["max_allowed"], json!("10000"));
+
+    let error = handle
+        .call(RpcRequest::new("deposit", json!({ "user": "ALICE" })))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.code, RpcError::INVALID_PARAMS);
+
+    let error = handle
+        .call(RpcRequest::new("place_order", json!({
+            "user": "ALICE", "side": "buy", "base": "BTC", "quote": "USDT", "quantity": "1",
+        })))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.code, RpcError::INVALID_PARAMS);
+
+    let error = handle
+        .call(RpcRequest::new("mint", json!({})))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.code, RpcError::METHOD_NOT_FOUND);
+
+    let response = bibank_rpc::server::parse_request(b"{not json").unwrap_err();
+    assert_eq!(response.error.unwrap().code, RpcError::PARSE_ERROR);
+
+    // Rejected requests leave no trace in the journal
+    let response = handle.call(RpcRequest::new("balance", json!({ "user": "ALICE" }))).await;
+    assert_eq!(response.result.unwrap()["balances"]["USDT"]["AVAILABLE"], json!("1000"));
+}
+
+/// Test: Orders through the server lock, match and report what rests
+#[tokio::test]
+async fn test_server_order_methods() {
+    use bibank_rpc::RpcRequest;
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (handle, _writer) = start_server(temp_dir.path()).await;
+
+    let response = handle
+        .call(RpcRequest::new("place_order", json!({
+            "user": "ALICE", "side": "buy", "base": "BTC", "quote": "USDT",
+            "price": "100", "quantity": "2",
+        })))
+        .await;
+    let result = response.result.unwrap();
+    assert_eq!(result["resting_quantity"], json!("2"));
+    let order_id = result["order_id"].as_str().unwrap().to_string();
+
+    let balances = handle
+        .call(RpcRequest::new("balance", json!({ "user": "ALICE" })))
+        .await
+        .result
+        .unwrap();
+    assert_eq!(balances["balances"]["USDT"]["LOCKED"], json!("200"));
+
+    let response = handle
+        .call(RpcRequest::new("cancel_order", json!({
+            "order_id": order_id, "base": "BTC", "quote": "USDT",
+        })))
+        .await;
+    assert_eq!(response.result.unwrap()["intent"], json!("order_cancel"));
+
+    let balances = handle
+        .call(RpcRequest::new("balance", json!({ "user": "ALICE" })))
+        .await
+        .result
+        .unwrap();
+    assert_eq!(balances["balances"]["USDT"]["AVAILABLE"], json!("1000"));
+    assert!(balances["balances"]["USDT"].get("LOCKED").is_none());
+}
+
+/// Test: HTTP and Unix socket transports speak JSON-RPC
+#[tokio::test]
+async fn test_server_http_and_unix_transports() {
+    use bibank_rpc::server;
+    use serde_json::{json, Value};
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+    let temp_dir = TempDir::new().unwrap();
+    let (handle, _writer) = start_server(temp_dir.path()).await;
+
+    // HTTP
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(server::serve_http(listener, handle.clone()));
+
+    let body = json!({
+        "jsonrpc": "2.0", "id": 7, "method": "balance", "params": { "user": "ALICE" },
+    })
+    .to_string();
+    let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+    let request = format!(
+        "POST / HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        addr,
+        body.len(),
+        body
+    );
+    stream.write_all(request.as_bytes()).await.unwrap();
+    let mut raw = String::new();
+    stream.read_to_string(&mut raw).await.unwrap();
+
+    assert!(raw.starts_with("HTTP/1.1 200"));
+    let response: Value = serde_json::from_str(raw.split("\r\n\r\n").nth(1).unwrap()).unwrap();
+    assert_eq!(response["id"], json!(7));
+    assert_eq!(response["result"]["balances"]["USDT"]["AVAILABLE"], json!("1000"));
+
+    // Unix socket: one request per line
+    let socket_path = temp_dir.path().join("bibank.sock");
+    let listener = tokio::net::UnixListener::bind(&socket_path).unwrap();
+    tokio::spawn(server::serve_unix(listener, handle.clone()));
+
+    let stream = tokio::net::UnixStream::connect(&socket_path).await.unwrap();
+    let (read, mut write) = stream.into_split();
+    let mut lines = BufReader::new(read).lines();
+
+    write
+        .write_all(b"{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"withdraw\",\"params\":{\"user\":\"ALICE\",\"amount\":\"250\",\"asset\":\"USDT\"}}\n")
+        .await
+        .unwrap();
+    let response: Value = serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+    assert_eq!(response["result"]["sequence"], json!(3));
+
+    write.write_all(b"garbage\n").await.unwrap();
+    let response: Value = serde_json::from_str(&lines.next_line().await.unwrap().unwrap()).unwrap();
+    assert_eq!(response["error"]["code"], json!(bibank_rpc::RpcError::PARSE_ERROR));
+}
+
+// ============================================================================
+// Idempotency Tests
+// ============================================================================
+
+/// Test: Retried correlation ids return the original entry, across restarts
+#[tokio::test]
+async fn test_commit_is_idempotent_by_correlation_id() {
+    use bibank_rpc::commands;
+    use bibank_rpc::CommitError;
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let data_path = temp_dir.path();
+    let alice = AccountKey::user_available("ALICE", "USDT");
+
+    let original = {
+        let mut ctx = AppContext::new(data_path).await.unwrap();
+        setup_traders(&mut ctx).await;
+
+        let original = commands::deposit(&mut ctx, "ALICE", Decimal::new(100, 0), "USDT", "dep-retry")
+            .await
+            .unwrap();
+
+        // Same payload (amount written differently): original entry, nothing appended
+        let retry = commands::deposit(&mut ctx, "ALICE", Decimal::new(10000, 2), "USDT", "dep-retry")
+            .await
+            .unwrap();
+        assert_eq!(retry.sequence, original.sequence);
+        assert_eq!(retry.hash, original.hash);
+        assert_eq!(ctx.last_sequence(), original.sequence);
+        assert_eq!(ctx.risk.state().get_balance(&alice), Decimal::new(200_100, 0));
+
+        // Snapshot part of the index, then commit past it
+        ctx.snapshot().unwrap();
+        commands::deposit(&mut ctx, "ALICE", Decimal::ONE, "USDT", "dep-after-snapshot")
+            .await
+            .unwrap();
+        original
+    };
+
+    // Index comes from the correlation log, not the snapshot
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    let retry = commands::deposit(&mut ctx, "ALICE", Decimal::new(100, 0), "USDT", "dep-retry")
+        .await
+        .unwrap();
+    assert_eq!(retry.sequence, original.sequence);
+    assert!(ctx.correlations().get("dep-after-snapshot").is_some());
+
+    // Same id, different payload
+    let err = commands::deposit(&mut ctx, "ALICE", Decimal::new(999, 0), "USDT", "dep-retry")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<CommitError>(),
+        Some(CommitError::CorrelationConflict { sequence, .. }) if *sequence == original.sequence
+    ));
+    let err = commands::withdraw(&mut ctx, "ALICE", Decimal::new(100, 0), "USDT", "dep-retry")
+        .await
+        .unwrap_err();
+    assert!(matches!(err.downcast_ref::<CommitError>(), Some(CommitError::CorrelationConflict { .. })));
+
+    assert_eq!(ctx.risk.state().get_balance(&alice), Decimal::new(200_101, 0));
+}
+
+/// Test: The correlation log is loaded on startup, and rebuilt when it doesn't match the journal
+#[tokio::test]
+async fn test_correlation_log_persists_index() {
+    use bibank_rpc::commands;
+    use bibank_rpc::idempotency::CORRELATION_LOG_FILE;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    let log_path = data_path.join(CORRELATION_LOG_FILE);
+
+    {
+        let mut ctx = AppContext::new(data_path).await.unwrap();
+        commands::init(&mut ctx, "init-1").await.unwrap();
+        commands::deposit(&mut ctx, "ALICE", Decimal::from(100), "USDT", "dep-1").await.unwrap();
+        ctx.snapshot().unwrap();
+        commands::deposit(&mut ctx, "ALICE", Decimal::from(50), "USDT", "dep-2").await.unwrap();
+    }
+    let log = std::fs::read_to_string(&log_path).unwrap();
+    assert_eq!(log.lines().count(), 3);
+
+    // Ids before the log's last record come from the log, not the journal
+    let ghost = r#"{"correlation_id":"ghost","sequence":1,"fingerprint":"x","hash":"x"}"#;
+    std::fs::write(&log_path, format!("{}\n{}", ghost, log)).unwrap();
+    {
+        let mut ctx = AppContext::new(data_path).await.unwrap();
+        assert!(ctx.correlations().get("ghost").is_some());
+        let retry = commands::deposit(&mut ctx, "ALICE", Decimal::from(50), "USDT", "dep-2").await.unwrap();
+        assert_eq!(retry.sequence, 3);
+        assert_eq!(ctx.last_sequence(), 3);
+    }
+
+    // A log whose last record isn't in the journal is rebuilt from it
+    let stale = r#"{"correlation_id":"stale","sequence":9,"fingerprint":"x","hash":"x"}"#;
+    std::fs::write(&log_path, format!("{}{}\n", log, stale)).unwrap();
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    assert!(ctx.correlations().get("stale").is_none());
+    assert_eq!(ctx.correlations().get("dep-1").unwrap().sequence, 2);
+    assert_eq!(std::fs::read_to_string(&log_path).unwrap(), log);
+
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(25), "USDT", "dep-3").await.unwrap();
+    assert_eq!(std::fs::read_to_string(&log_path).unwrap().lines().count(), 4);
+}
+
+/// Test: Retried order placement and cancel don't lock or unlock twice
+#[tokio::test]
+async fn test_order_retries_are_idempotent() {
+    use bibank_matching::TradingPair;
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    setup_traders(&mut ctx).await;
+    let pair = TradingPair::btc_usdt();
+
+    let order_id = commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::ONE, OrderType::Limit, "bid-retry")
+        .await
+        .unwrap();
+    let sequence = ctx.last_sequence();
+
+    let retry = commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::ONE, OrderType::Limit, "bid-retry")
+        .await
+        .unwrap();
+    assert_eq!(retry, order_id);
+    assert_eq!(ctx.last_sequence(), sequence);
+    assert_eq!(ctx.matching.get_book(&pair).unwrap().order_count(), 1);
+    assert_eq!(
+        ctx.risk.state().get_balance(&AccountKey::user_locked("ALICE", "USDT")),
+        Decimal::new(50_000, 0)
+    );
+
+    // Different order under the same id is rejected
+    assert!(commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(49_000, 0), Decimal::ONE, OrderType::Limit, "bid-retry")
+        .await
+        .is_err());
+
+    let cancel = commands::cancel_order(&mut ctx, &order_id, "BTC", "USDT", "cancel-retry")
+        .await
+        .unwrap();
+    let retry = commands::cancel_order(&mut ctx, &order_id, "BTC", "USDT", "cancel-retry")
+        .await
+        .unwrap();
+    assert_eq!(retry.sequence, cancel.sequence);
+    assert_eq!(
+        ctx.risk.state().get_balance(&AccountKey::user_available("ALICE", "USDT")),
+        Decimal::new(200_000, 0)
+    );
+}
+
+/// Test: Retrying an order whose first attempt crashed mid-settlement settles the rest
+#[tokio::test]
+async fn test_order_retry_resumes_settlement() {
+    use bibank_matching::{Fill, OrderSide, TradingPair};
+    use bibank_rpc::commands::{self, FillFees};
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let data_path = temp_dir.path();
+    let pair = TradingPair::btc_usdt();
+    let price = Decimal::new(50_000, 0);
+    let quantity = Decimal::new(2, 0);
+
+    let (ask_1, ask_2) = {
+        let mut ctx = AppContext::new(data_path).await.unwrap();
+        setup_traders(&mut ctx).await;
+
+        let ask_1 = commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", price, Decimal::ONE, OrderType::Limit, "ask-1")
+            .await
+            .unwrap();
+        let ask_2 = commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", price, Decimal::ONE, OrderType::Limit, "ask-2")
+            .await
+            .unwrap();
+
+        // ALICE's buy crashed after its lock and first fill were journaled
+        let lock_amount = commands::quote_amount("USDT", price, quantity);
+        let lock = JournalEntryBuilder::new()
+            .intent(TransactionIntent::OrderPlace)
+            .correlation_id("bid-resume")
+            .debit(AccountKey::user_available("ALICE", "USDT"), Amount::new(lock_amount).unwrap())
+            .credit(AccountKey::user_locked("ALICE", "USDT"), Amount::new(lock_amount).unwrap())
+            .metadata("order_id", json!("bid-crashed"))
+            .metadata("order_side", json!("buy"))
+            .metadata("order_type", json!("limit"))
+            .metadata("base_asset", json!("BTC"))
+            .metadata("quote_asset", json!("USDT"))
+            .metadata("price", json!(price.to_string()))
+            .metadata("quantity", json!(quantity.to_string()))
+            .metadata("lock_asset", json!("USDT"))
+            .metadata("lock_amount", json!(lock_amount.to_string()))
+            .build_unsigned()
+            .unwrap();
+        ctx.commit(lock).await.unwrap();
+
+        let fill = Fill::new(pair.clone(), "bid-crashed".to_string(), ask_1.clone(), "ALICE".to_string(), "BOB".to_string(), OrderSide::Buy, price, Decimal::ONE);
+        let entry = commands::fill_entry(&fill, price, FillFees::default(), "bid-resume", "bid-resume-fill-1").unwrap();
+        ctx.commit(entry).await.unwrap();
+
+        (ask_1, ask_2)
+    };
+
+    // Replay leaves the bid on the book, crossing BOB's second ask
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    assert!(ctx.matching.get_order(&pair, &ask_1).is_none());
+    assert!(ctx.matching.get_order(&pair, "bid-crashed").is_some());
+
+    let order_id = commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", price, quantity, OrderType::Limit, "bid-resume")
+        .await
+        .unwrap();
+    assert_eq!(order_id, "bid-crashed");
+    assert!(ctx.correlations().get("bid-resume-fill-2").is_some());
+    assert!(ctx.matching.get_order(&pair, &ask_2).is_none());
+    assert_eq!(ctx.matching.total_order_count(), 0);
+
+    let state = ctx.risk.state();
+    assert_eq!(state.get_balance(&AccountKey::user_available("ALICE", "BTC")), quantity);
+    assert_eq!(state.get_balance(&AccountKey::user_locked("ALICE", "USDT")), Decimal::ZERO);
+    assert_eq!(state.get_balance(&AccountKey::user_available("BOB", "USDT")), Decimal::new(100_000, 0));
+
+    // Settled now: a further retry changes nothing
+    let sequence = ctx.last_sequence();
+    commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", price, quantity, OrderType::Limit, "bid-resume")
+        .await
+        .unwrap();
+    assert_eq!(ctx.last_sequence(), sequence);
+}
+
+/// Test: Server reports correlation conflicts as a structured error
+#[tokio::test]
+async fn test_server_correlation_conflict() {
+    use bibank_rpc::{RpcError, RpcRequest};
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (handle, _writer) = start_server(temp_dir.path()).await;
+
+    let params = json!({ "user": "ALICE", "amount": "5", "asset": "USDT", "correlation_id": "req-1" });
+    let first = handle.call(RpcRequest::new("withdraw", params.clone())).await.result.unwrap();
+    let retry = handle.call(RpcRequest::new("withdraw", params)).await.result.unwrap();
+    assert_eq!(first, retry);
+
+    let error = handle
+        .call(RpcRequest::new(
+            "withdraw",
+            json!({ "user": "ALICE", "amount": "6", "asset": "USDT", "correlation_id": "req-1" }),
+        ))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.code, RpcError::CORRELATION_CONFLICT);
+    assert_eq!(error.kind(), Some("correlation_conflict"));
+    assert_eq!(error.data.unwrap()["sequence"], first["sequence"]);
+}
+
+// ============================================================================
+// Scheduler Tests
+// ============================================================================
+
+/// Test: Interest accrues once per day and loan, however often the job runs
+#[tokio::test]
+async fn test_interest_accrual_is_idempotent_per_day() {
+    use bibank_risk::InterestCalculator;
+    use bibank_rpc::{commands, scheduler};
+    use chrono::NaiveDate;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+
+    commands::init(&mut ctx, "genesis-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::ONE, "BTC", "dep-1").await.unwrap();
+    commands::borrow(&mut ctx, "ALICE", Decimal::from(10000), "USDT", "borrow-1").await.unwrap();
+    let sequence = ctx.last_sequence();
+
+    let calculator = InterestCalculator::new();
+    let day = NaiveDate::from_ymd_opt(2026, 1, 25).unwrap();
+
+    // Dry run reports the entry without committing it
+    let report = scheduler::run_interest_accrual(&mut ctx, &calculator, day, true).await.unwrap();
+    assert_eq!(report.entries.len(), 1);
+    assert_eq!(report.entries[0].sequence, None);
+    assert_eq!(ctx.last_sequence(), sequence);
+
+    let report = scheduler::run_interest_accrual(&mut ctx, &calculator, day, false).await.unwrap();
+    assert_eq!(report.run_id, "interest-2026-01-25");
+    assert_eq!(report.entries[0].correlation_id, "interest-2026-01-25-ALICE-USDT");
+    assert_eq!(report.entries[0].amount, "5"); // 10000 * 0.0005
+    assert_eq!(report.entries[0].sequence, Some(sequence + 1));
+    assert!(report.failed.is_empty());
+
+    let entry = ctx.committed_entry("interest-2026-01-25-ALICE-USDT").unwrap().unwrap();
+    assert_eq!(entry.intent, TransactionIntent::Interest);
+    assert_eq!(entry.metadata["job"], "interest_accrual");
+    assert_eq!(entry.metadata["run_id"], "interest-2026-01-25");
+    assert_eq!(entry.metadata["principal"], "10000");
+
+    // Same day again: nothing new
+    let report = scheduler::run_interest_accrual(&mut ctx, &calculator, day, false).await.unwrap();
+    assert!(report.entries.is_empty());
+    assert_eq!(report.skipped, vec!["interest-2026-01-25-ALICE-USDT".to_string()]);
+    assert_eq!(ctx.last_sequence(), sequence + 1);
+
+    // Next day compounds on the new principal
+    let next = day.succ_opt().unwrap();
+    let report = scheduler::run_interest_accrual(&mut ctx, &calculator, next, false).await.unwrap();
+    assert_eq!(report.entries.len(), 1);
+    assert_eq!(
+        ctx.risk.state().get_loan_balance("ALICE", "USDT"),
+        Decimal::from_str_exact("10010.0025").unwrap()
+    );
+}
+
+/// Test: A sweep liquidates underwater portfolios after a price drop
+#[tokio::test]
+async fn test_liquidation_sweep_after_price_drop() {
+    use bibank_oracle::ReplayOracle;
+    use bibank_risk::LiquidationEngine;
+    use bibank_rpc::{commands, scheduler};
+    use chrono::DateTime;
+    use std::sync::Arc;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+
+    let oracle = Arc::new(
+        ReplayOracle::from_csv("1769335200,BTC/USDT,10000\n1769338800,BTC/USDT,5000\n").unwrap(),
+    );
+    oracle.set_as_of(DateTime::from_timestamp(1_769_335_200, 0));
+    ctx.oracle = oracle.clone();
+
+    commands::init(&mut ctx, "genesis-1").await.unwrap();
+    for user in ["ALICE", "BOB"] {
+        commands::deposit(&mut ctx, user, Decimal::ONE, "BTC", &format!("dep-{}", user)).await.unwrap();
+    }
+    register(&mut ctx, "ALICE", 1).await;
+    commands::borrow(&mut ctx, "ALICE", Decimal::from(6000), "USDT", "borrow-1").await.unwrap();
+    commands::withdraw(&mut ctx, "ALICE", Decimal::from(6000), "USDT", "wd-1").await.unwrap();
+    commands::borrow(&mut ctx, "BOB", Decimal::from(1000), "USDT", "borrow-2").await.unwrap();
+
+    let engine = LiquidationEngine::default();
+    let report = scheduler::run_liquidation_sweep(&mut ctx, &engine, "LIQUIDATOR", false).await.unwrap();
+    assert!(report.entries.is_empty());
+
+    oracle.set_as_of(DateTime::from_timestamp(1_769_338_800, 0));
+    let sequence = ctx.last_sequence();
+
+    let report = scheduler::run_liquidation_sweep(&mut ctx, &engine, "LIQUIDATOR", true).await.unwrap();
+    assert_eq!(report.entries.len(), 1);
+    assert_eq!(ctx.last_sequence(), sequence);
+
+    // Only ALICE is underwater; BOB stays healthy
+    let report = scheduler::run_liquidation_sweep(&mut ctx, &engine, "LIQUIDATOR", false).await.unwrap();
+    assert_eq!(report.entries.len(), 1);
+    assert_eq!(report.entries[0].user, "ALICE");
+    assert_eq!(report.entries[0].amount, "3000");
+    assert_eq!(report.entries[0].sequence, Some(sequence + 1));
+
+    let entry = ctx.committed_entry(&report.entries[0].correlation_id).unwrap().unwrap();
+    assert_eq!(entry.intent, TransactionIntent::Liquidation);
+    assert_eq!(entry.metadata["job"], "liquidation_sweep");
+    assert_eq!(entry.metadata["run_id"], report.run_id.as_str());
+
+    let state = ctx.risk.state();
+    assert_eq!(state.get_loan_balance("ALICE", "USDT"), Decimal::from(3000));
+    assert_eq!(state.get_loan_balance("BOB", "USDT"), Decimal::from(1000));
+}
+
+/// Test: A sweep cancels the borrower's resting orders to seize the collateral they lock
+#[tokio::test]
+async fn test_liquidation_sweep_cancels_resting_orders() {
+    use bibank_matching::TradingPair;
+    use bibank_oracle::ReplayOracle;
+    use bibank_risk::LiquidationEngine;
+    use bibank_rpc::{commands, scheduler};
+    use chrono::DateTime;
+    use std::sync::Arc;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+
+    let oracle = Arc::new(
+        ReplayOracle::from_csv("1769335200,BTC/USDT,10000\n1769338800,BTC/USDT,5000\n").unwrap(),
+    );
+    oracle.set_as_of(DateTime::from_timestamp(1_769_335_200, 0));
+    ctx.oracle = oracle.clone();
+
+    commands::init(&mut ctx, "genesis-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::ONE, "BTC", "dep-1").await.unwrap();
+    register(&mut ctx, "ALICE", 1).await;
+    commands::borrow(&mut ctx, "ALICE", Decimal::from(6000), "USDT", "borrow-1").await.unwrap();
+    commands::withdraw(&mut ctx, "ALICE", Decimal::from(6000), "USDT", "wd-1").await.unwrap();
+
+    // All of ALICE's BTC rests in an ask far above the market
+    let order_id = commands::place_order(&mut ctx, "ALICE", "sell", "BTC", "USDT", Decimal::from(20000), Decimal::ONE, OrderType::Limit, "ask-1")
+        .await
+        .unwrap();
+    assert_eq!(ctx.risk.state().get_available_balance("ALICE", "BTC"), Decimal::ZERO);
+
+    oracle.set_as_of(DateTime::from_timestamp(1_769_338_800, 0));
+    let engine = LiquidationEngine::default();
+    let report = scheduler::run_liquidation_sweep(&mut ctx, &engine, "LIQUIDATOR", false).await.unwrap();
+    assert_eq!(report.entries.len(), 1, "{:?}", report.failed);
+
+    let correlation_id = &report.entries[0].correlation_id;
+    let cancel = ctx.committed_entry(&format!("{}-cancel-{}", correlation_id, order_id)).unwrap().unwrap();
+    assert_eq!(cancel.intent, TransactionIntent::OrderCancel);
+    assert!(ctx.matching.get_order(&TradingPair::btc_usdt(), &order_id).is_none());
+
+    // The seizure came out of the released BTC, with no shortfall
+    let entry = ctx.committed_entry(correlation_id).unwrap().unwrap();
+    let shortfall: Decimal = entry.metadata["shortfall"].as_str().unwrap().parse().unwrap();
+    assert!(shortfall.is_zero());
+    let state = ctx.risk.state();
+    assert_eq!(state.get_loan_balance("ALICE", "USDT"), Decimal::from(3000));
+    assert_eq!(state.get_balance(&AccountKey::user_locked("ALICE", "BTC")), Decimal::ZERO);
+    assert!(state.get_available_balance("ALICE", "BTC") < Decimal::ONE);
+}
+
+/// Test: Fills pay maker/taker fees tiered by 30-day volume
+#[tokio::test]
+async fn test_fill_fees_with_volume_tiers() {
+    use bibank_matching::TradingPair;
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(
+        temp_dir.path().join("fees.json"),
+        r#"{
+            "volume_asset": "USDT",
+            "default": [
+                { "min_volume": "0", "maker_rate": "0.001", "taker_rate": "0.002" },
+                { "min_volume": "1000", "maker_rate": "0", "taker_rate": "0.001" }
+            ]
+        }"#,
+    )
+    .unwrap();
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    commands::init(&mut ctx, "genesis-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(2000), "USDT", "dep-1").await.unwrap();
+    commands::deposit(&mut ctx, "BOB", Decimal::ONE, "BTC", "dep-2").await.unwrap();
+
+    // BOB makes, ALICE takes 0.1 BTC for 500 USDT
+    let price = Decimal::from(5000);
+    commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", price, Decimal::new(1, 1), OrderType::Limit, "ask-1")
+        .await
+        .unwrap();
+    commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", price, Decimal::new(1, 1), OrderType::Limit, "bid-1")
+        .await
+        .unwrap();
+
+    // Taker pays 0.2% of the base received, maker 0.1% of the quote
+    let state = ctx.risk.state();
+    assert_eq!(state.get_balance(&AccountKey::user_available("ALICE", "BTC")), Decimal::new(998, 4));
+    assert_eq!(state.get_balance(&AccountKey::user_available("BOB", "USDT")), Decimal::new(4995, 1));
+    assert_eq!(state.get_balance(&AccountKey::trading_fee("BTC")), Decimal::new(2, 4));
+    assert_eq!(state.get_balance(&AccountKey::trading_fee("USDT")), Decimal::new(5, 1));
+
+    let fill = ctx.committed_entry("bid-1-fill-1").unwrap().unwrap();
+    assert_eq!(fill.intent, TransactionIntent::Trade);
+    assert_eq!(fill.metadata["maker_fee"], "0.5");
+    assert_eq!(fill.metadata["taker_fee_asset"], "BTC");
+    assert_eq!(fill.metadata["base_amount"], "0.1");
+
+    // 500 USDT traded: still the first tier
+    let pair = TradingPair::btc_usdt();
+    let rate = ctx.fee_rate("ALICE", &pair).await;
+    assert_eq!(rate.volume, Decimal::from(500));
+    assert_eq!(rate.tier, Some(0));
+    assert_eq!(rate.next_tier_volume, Some(Decimal::from(1000)));
+
+    commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", price, Decimal::new(1, 1), OrderType::Limit, "ask-2")
+        .await
+        .unwrap();
+    commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", price, Decimal::new(1, 1), OrderType::Limit, "bid-2")
+        .await
+        .unwrap();
+
+    // 1000 USDT reaches the second tier for both sides
+    let rate = ctx.fee_rate("BOB", &pair).await;
+    assert_eq!(rate.tier, Some(1));
+    assert_eq!(rate.maker_rate, Decimal::ZERO);
+    assert_eq!(rate.taker_rate, Decimal::new(1, 3));
+    commands::fee_preview(&ctx, "BOB", "BTC", "USDT").await.unwrap();
+
+    commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", price, Decimal::new(1, 1), OrderType::Limit, "ask-3")
+        .await
+        .unwrap();
+    commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", price, Decimal::new(1, 1), OrderType::Limit, "bid-3")
+        .await
+        .unwrap();
+
+    // Maker pays nothing, taker 0.1%
+    let state = ctx.risk.state();
+    assert_eq!(state.get_balance(&AccountKey::trading_fee("USDT")), Decimal::from(1));
+    assert_eq!(state.get_balance(&AccountKey::trading_fee("BTC")), Decimal::new(5, 4));
+    assert_eq!(state.get_balance(&AccountKey::user_available("BOB", "USDT")), Decimal::new(14990, 1));
+
+    // No fee schedule: fills are free
+    let plain_dir = TempDir::new().unwrap();
+    let plain = AppContext::new(plain_dir.path()).await.unwrap();
+    assert!(plain.fees.is_empty());
+    assert_eq!(plain.fee_rate("ALICE", &pair).await.tier, None);
+}
+
+/// Test: Amounts respect each asset's scale; rounding residue is swept as dust
+#[tokio::test]
+async fn test_currency_precision_and_dust_sweep() {
+    use bibank_rpc::{commands, scheduler};
+
+    let temp_dir = TempDir::new().unwrap();
+    std::fs::write(temp_dir.path().join("currencies.json"), r#"{ "PRECTOKEN": 2 }"#).unwrap();
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    commands::init(&mut ctx, "genesis-1").await.unwrap();
+
+    // USDT has 6 decimals, the registered token 2
+    let err = commands::deposit(&mut ctx, "ALICE", Decimal::new(1_0000001, 7), "USDT", "dep-fine")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("6 decimal places"), "{}", err);
+    assert!(commands::deposit(&mut ctx, "ALICE", Decimal::new(1005, 3), "PRECTOKEN", "dep-tok")
+        .await
+        .is_err());
+    commands::deposit(&mut ctx, "ALICE", Decimal::new(101, 2), "PRECTOKEN", "dep-tok-2").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(10), "USDT", "dep-1").await.unwrap();
+    commands::deposit(&mut ctx, "BOB", Decimal::ONE, "BTC", "dep-2").await.unwrap();
+
+    // Prices finer than the quote's scale and quantities finer than the
+    // base's are rejected before anything is locked
+    let sequence = ctx.last_sequence();
+    let err = commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(1_2345678, 7), Decimal::new(3, 1), OrderType::Limit, "bid-fine")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("6 decimal places"), "{}", err);
+    assert!(commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::ONE, Decimal::new(1, 9), OrderType::Limit, "ask-fine")
+        .await
+        .is_err());
+    assert_eq!(ctx.last_sequence(), sequence);
+
+    // 0.3 BTC at 1.234567 locks 0.370370 (0.3703701 rounded down)
+    let price = Decimal::new(1_234567, 6);
+    commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", price, Decimal::new(3, 1), OrderType::Limit, "bid-1")
+        .await
+        .unwrap();
+    let locked = AccountKey::user_locked("ALICE", "USDT");
+    assert_eq!(ctx.risk.state().get_balance(&locked), Decimal::new(370370, 6));
+
+    // Three fills of 0.1 pay 0.123456 each, leaving 0.000002 locked
+    for i in 1..=3 {
+        commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", price, Decimal::new(1, 1), OrderType::Limit, &format!("ask-{}", i))
+            .await
+            .unwrap();
+    }
+    assert_eq!(ctx.risk.state().get_balance(&locked), Decimal::new(2, 6));
+
+    // 0.2 BTC locks 0.246913; two fills of 0.1 leave one unit, 0.000001
+    commands::deposit(&mut ctx, "CAROL", Decimal::from(10), "USDT", "dep-3").await.unwrap();
+    commands::place_order(&mut ctx, "CAROL", "buy", "BTC", "USDT", price, Decimal::new(2, 1), OrderType::Limit, "bid-3")
+        .await
+        .unwrap();
+    for i in 4..=5 {
+        commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", price, Decimal::new(1, 1), OrderType::Limit, &format!("ask-{}", i))
+            .await
+            .unwrap();
+    }
+    let carol_locked = AccountKey::user_locked("CAROL", "USDT");
+    assert_eq!(ctx.risk.state().get_balance(&carol_locked), Decimal::new(1, 6));
+
+    // A resting order's lock is not dust
+    commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", price, Decimal::new(1, 1), OrderType::Limit, "bid-2")
+        .await
+        .unwrap();
+    assert_eq!(ctx.risk.state().get_balance(&locked), Decimal::new(123458, 6));
+
+    let sequence = ctx.last_sequence();
+    let report = scheduler::run_dust_sweep(&mut ctx, true).await.unwrap();
+    assert_eq!(report.entries.len(), 2);
+    assert_eq!(ctx.last_sequence(), sequence);
+
+    let alice_available = ctx.risk.state().get_balance(&AccountKey::user_available("ALICE", "USDT"));
+    let report = scheduler::run_dust_sweep(&mut ctx, false).await.unwrap();
+    assert_eq!(report.entries.len(), 2);
+    assert!(report.failed.is_empty());
+
+    // More than one unit is not dust: it goes back to ALICE's AVAILABLE
+    assert_eq!(report.entries[0].user, "ALICE");
+    assert_eq!(report.entries[0].asset, "USDT");
+    assert_eq!(report.entries[0].amount, "0.000002");
+    assert!(report.entries[0].correlation_id.ends_with("-release"));
+
+    // CAROL's single unit is swept
+    assert_eq!(report.entries[1].user, "CAROL");
+    assert_eq!(report.entries[1].amount, "0.000001");
+
+    let state = ctx.risk.state();
+    assert_eq!(state.get_balance(&locked), Decimal::new(123456, 6));
+    assert_eq!(
+        state.get_balance(&AccountKey::user_available("ALICE", "USDT")),
+        alice_available + Decimal::new(2, 6)
+    );
+    assert_eq!(state.get_balance(&carol_locked), Decimal::ZERO);
+    assert_eq!(state.get_balance(&AccountKey::dust("USDT")), Decimal::new(1, 6));
+
+    let entry = ctx.committed_entry(&report.entries[0].correlation_id).unwrap().unwrap();
+    assert_eq!(entry.intent, TransactionIntent::OrderCancel);
+    assert_eq!(entry.metadata["job"], "dust_sweep");
+
+    let entry = ctx.committed_entry(&report.entries[1].correlation_id).unwrap().unwrap();
+    assert_eq!(entry.intent, TransactionIntent::Fee);
+    assert_eq!(entry.metadata["job"], "dust_sweep");
+
+    // Nothing left to sweep
+    let report = scheduler::run_dust_sweep(&mut ctx, false).await.unwrap();
+    assert!(report.entries.is_empty());
+}
+
+/// Test: Jobs can be triggered over JSON-RPC
+#[tokio::test]
+async fn test_server_job_methods() {
+    use bibank_rpc::RpcRequest;
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (handle, _writer) = start_server(temp_dir.path()).await;
+
+    let response = handle
+        .call(RpcRequest::new(
+            "borrow",
+            json!({ "user": "ALICE", "amount": "500", "asset": "USDT" }),
+        ))
+        .await;
+    assert!(response.error.is_none(), "{:?}", response.error);
+
+    let params = json!({ "date": "2026-01-25" });
+    let result = handle.call(RpcRequest::new("run_interest", params.clone())).await.result.unwrap();
+    assert_eq!(result["job"], "interest_accrual");
+    assert_eq!(result["entries"][0]["user"], "ALICE");
+    assert_eq!(result["entries"][0]["amount"], "0.25");
+
+    let result = handle.call(RpcRequest::new("run_interest", params)).await.result.unwrap();
+    assert_eq!(result["entries"], json!([]));
+    assert_eq!(result["skipped"].as_array().unwrap().len(), 1);
+
+    let result = handle
+        .call(RpcRequest::new("run_liquidation_sweep", json!({ "dry_run": true })))
+        .await
+        .result
+        .unwrap();
+    assert_eq!(result["job"], "liquidation_sweep");
+    assert_eq!(result["dry_run"], true);
+    assert_eq!(result["entries"], json!([]));
+}
+
+// ============================================================================
+// Approval Tests
+// ============================================================================
+
+/// Helper: Two registered approvers (2-of-2) with a 1000 USDT threshold
+fn approvers(ctx: &mut AppContext) -> (bibank_ledger::OperatorSigner, bibank_ledger::OperatorSigner) {
+    use bibank_approval::{ApprovalConfig, ApprovalStore, ApprovalWorkflow};
+    use bibank_ledger::{OperatorSigner, Signer};
+
+    let ops1 = OperatorSigner::generate("OPS1");
+    let ops2 = OperatorSigner::generate("OPS2");
+    let config = ApprovalConfig::default()
+        .with_approver("OPS1", ops1.public_key_hex())
+        .with_approver("OPS2", ops2.public_key_hex())
+        .with_threshold("USDT", Decimal::from(1000));
+    ctx.approvals = ApprovalWorkflow::new(ApprovalStore::in_memory().unwrap(), config);
+    (ops1, ops2)
+}
+
+/// Test: Adjustments are parked, then committed with every approver's signature
+#[tokio::test]
+async fn test_adjustment_committed_after_approval() {
+    use bibank_rpc::{commands, CommitError};
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    let (ops1, ops2) = approvers(&mut ctx);
+    commands::init(&mut ctx, "genesis-1").await.unwrap();
+    let sequence = ctx.last_sequence();
+
+    let err = commands::adjust(&mut ctx, "ALICE", Decimal::from(50), "USDT", "missed deposit", "adj-1")
+        .await
+        .unwrap_err();
+    let Some(CommitError::PendingApproval { approval_id, signatures_remaining }) = err.downcast_ref() else {
+        panic!("expected pending approval, got {}", err);
+    };
+    let approval_id = approval_id.clone();
+    assert_eq!(*signatures_remaining, 2);
+    assert_eq!(ctx.last_sequence(), sequence);
+
+    // Retrying the request points at the same approval
+    let err = commands::adjust(&mut ctx, "ALICE", Decimal::from(50), "USDT", "missed deposit", "adj-1")
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains(&approval_id));
+    assert_eq!(ctx.approvals.list_pending().unwrap().len(), 1);
+
+    let first = commands::approval_sign(&mut ctx, &approval_id, &ops1).await.unwrap();
+    assert!(first.is_none());
+    assert_eq!(ctx.last_sequence(), sequence);
+
+    let entry = commands::approval_sign(&mut ctx, &approval_id, &ops2).await.unwrap().unwrap();
+    assert_eq!(entry.sequence, sequence + 1);
+    assert_eq!(entry.intent, TransactionIntent::Adjustment);
+    assert_eq!(entry.approvers(), vec!["OPS1", "OPS2"]);
+    assert_eq!(
+        ctx.risk.state().get_available_balance("ALICE", "USDT"),
+        Decimal::from(50)
+    );
+
+    // Approver signatures verify against the journal copy
+    let reader = EventReader::from_directory(ctx.journal_path()).unwrap();
+    let journal = reader.read_all().unwrap();
+    assert!(journal.last().unwrap().verify_signatures().is_ok());
+    assert!(verify_chain(&journal).is_ok());
+
+    // Now committed: a retry returns the entry
+    let retry = commands::adjust(&mut ctx, "ALICE", Decimal::from(50), "USDT", "missed deposit", "adj-1")
+        .await
+        .unwrap();
+    assert_eq!(retry.sequence, entry.sequence);
+}
+
+/// Test: Reversals mirror the original once approved, and only once
+#[tokio::test]
+async fn test_reversal_after_approval() {
+    use bibank_risk::RiskError;
+    use bibank_rpc::{commands, CommitError};
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    let (ops1, ops2) = approvers(&mut ctx);
+    commands::init(&mut ctx, "genesis-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(100), "USDT", "dep-1").await.unwrap();
+    commands::deposit(&mut ctx, "BOB", Decimal::from(50), "USDT", "dep-2").await.unwrap();
+    commands::transfer(&mut ctx, "BOB", "CAROL", Decimal::from(50), "USDT", "tx-1").await.unwrap();
+
+    let err = commands::reverse(&mut ctx, 1, "oops").await.unwrap_err();
+    assert!(matches!(err.downcast_ref(), Some(CommitError::NotReversible { sequence: 1, .. })));
+
+    // BOB's deposit has already been spent
+    let err = commands::reverse(&mut ctx, 3, "duplicate").await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref(),
+        Some(CommitError::Risk(RiskError::InsufficientBalance { .. }))
+    ));
+    assert!(ctx.approvals.list_pending().unwrap().is_empty());
+
+    let err = commands::reverse(&mut ctx, 2, "duplicate").await.unwrap_err();
+    let Some(CommitError::PendingApproval { approval_id, .. }) = err.downcast_ref() else {
+        panic!("expected pending approval, got {}", err);
+    };
+    let approval_id = approval_id.clone();
+    commands::approval_sign(&mut ctx, &approval_id, &ops1).await.unwrap();
+    let reversal = commands::approval_sign(&mut ctx, &approval_id, &ops2).await.unwrap().unwrap();
+
+    assert_eq!(reversal.intent, TransactionIntent::Reversal);
+    assert_eq!(reversal.causality_id.as_deref(), Some("dep-1"));
+    assert_eq!(reversal.reversed_sequence(), Some(2));
+    assert_eq!(ctx.risk.state().get_available_balance("ALICE", "USDT"), Decimal::ZERO);
+
+    let err = commands::reverse(&mut ctx, 2, "again").await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref(),
+        Some(CommitError::AlreadyReversed { sequence: 2, reversal_sequence }) if *reversal_sequence == reversal.sequence
+    ));
+
+    let marked = ctx.projection.as_ref().unwrap().reversal.get(2).await.unwrap().unwrap();
+    assert_eq!(marked.reversal_sequence, reversal.sequence);
+    assert_eq!(marked.reason, "duplicate");
+}
+
+/// Test: Order-book fills can't be reversed, the book keeps them applied
+#[tokio::test]
+async fn test_order_fill_not_reversible() {
+    use bibank_rpc::{commands, CommitError};
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    approvers(&mut ctx);
+    commands::init(&mut ctx, "genesis-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(1000), "USDT", "dep-1").await.unwrap();
+    commands::deposit(&mut ctx, "BOB", Decimal::ONE, "BTC", "dep-2").await.unwrap();
+
+    commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::from(100), Decimal::ONE, OrderType::Limit, "ask-1")
+        .await
+        .unwrap();
+    commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::from(100), Decimal::ONE, OrderType::Limit, "bid-1")
+        .await
+        .unwrap();
+
+    let fill = ctx.committed_entry("bid-1-fill-1").unwrap().unwrap();
+    assert_eq!(fill.intent, TransactionIntent::Trade);
+    let err = commands::reverse(&mut ctx, fill.sequence, "bad fill").await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref(),
+        Some(CommitError::NotReversible { intent: TransactionIntent::Trade, .. })
+    ));
+    assert!(ctx.approvals.list_pending().unwrap().is_empty());
+}
+
+/// Test: Withdrawals above the threshold wait for approval and can be rejected
+#[tokio::test]
+async fn test_large_withdrawal_requires_approval() {
+    use bibank_approval::ApprovalStatus;
+    use bibank_ledger::OperatorSigner;
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    let (ops1, _) = approvers(&mut ctx);
+    commands::init(&mut ctx, "genesis-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(5000), "USDT", "dep-1").await.unwrap();
+    register(&mut ctx, "ALICE", 1).await;
+
+    // At the threshold: straight through
+    commands::withdraw(&mut ctx, "ALICE", Decimal::from(1000), "USDT", "wd-1").await.unwrap();
+
+    // Over the threshold (risk checks still run first)
+    let err = commands::withdraw(&mut ctx, "ALICE", Decimal::from(9000), "USDT", "wd-2").await.unwrap_err();
+    assert!(err.to_string().contains("Insufficient balance"));
+    let err = commands::withdraw(&mut ctx, "ALICE", Decimal::from(3000), "USDT", "wd-3").await.unwrap_err();
+    assert!(err.to_string().contains("parked for approval"));
+
+    let pending = ctx.approvals.list_pending().unwrap();
+    assert_eq!(pending.len(), 1);
+    let id = pending[0].id.clone();
+
+    // Only registered approvers may reject, with their own key
+    let mallory = OperatorSigner::generate("MALLORY");
+    assert!(commands::reject_approval(&ctx, &id, &mallory, None).is_err());
+    let impostor = OperatorSigner::generate("OPS2");
+    assert!(commands::reject_approval(&ctx, &id, &impostor, None).is_err());
+    assert_eq!(ctx.approvals.get_approval(&id).unwrap().status, ApprovalStatus::Pending);
+
+    let rejected = commands::reject_approval(&ctx, &id, &ops1, Some("unverified address")).unwrap();
+    assert_eq!(rejected.status, ApprovalStatus::Rejected);
+    assert_eq!(
+        rejected.rejection_reason.as_deref(),
+        Some("unverified address (rejected by OPS1)")
+    );
+
+    assert!(commands::approval_sign(&mut ctx, &id, &ops1).await.is_err());
+    assert_eq!(
+        ctx.risk.state().get_available_balance("ALICE", "USDT"),
+        Decimal::from(4000)
+    );
+}
+
+/// Test: Approvals over JSON-RPC with client-side signatures
+#[tokio::test]
+async fn test_server_approval_methods() {
+    use bibank_rpc::{RpcError, RpcRequest};
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    let (ops1, ops2) = approvers(&mut ctx);
+    let (handle, _writer) = bibank_rpc::server::spawn_writer(ctx);
+    handle.call(RpcRequest::new("init", json!(null))).await;
+
+    let error = handle
+        .call(RpcRequest::new(
+            "adjust",
+            json!({ "user": "BOB", "amount": "25", "asset": "USDT", "reason": "refund", "correlation_id": "adj-1" }),
+        ))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.code, RpcError::PENDING_APPROVAL);
+    assert_eq!(error.kind(), Some("pending_approval"));
+    let approval_id = error.data.unwrap()["approval_id"].as_str().unwrap().to_string();
+
+    let list = handle.call(RpcRequest::new("list_approvals", json!({}))).await.result.unwrap();
+    let approval = &list["approvals"][0];
+    assert_eq!(approval["approval_id"], approval_id.as_str());
+    assert_eq!(approval["unsigned_entry"]["intent"], "adjustment");
+
+    // Approvers sign the parked entry offline
+    let unsigned: bibank_ledger::UnsignedEntry =
+        serde_json::from_value(approval["unsigned_entry"].clone()).unwrap();
+
+    let forged = ops1.sign_approval(&unsigned);
+    let mut forged = serde_json::to_value(forged).unwrap();
+    forged["signer_id"] = json!("OPS2");
+    let error = handle
+        .call(RpcRequest::new(
+            "sign_approval",
+            json!({ "approval_id": approval_id, "signature": forged }),
+        ))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.kind(), Some("invalid_signature"));
+
+    for signer in [&ops1, &ops2] {
+        let response = handle
+            .call(RpcRequest::new(
+                "sign_approval",
+                json!({ "approval_id": approval_id, "signature": signer.sign_approval(&unsigned) }),
+            ))
+            .await;
+        assert!(response.error.is_none(), "{:?}", response.error);
+    }
+
+    let balance = handle.call(RpcRequest::new("balance", json!({ "user": "BOB" }))).await.result.unwrap();
+    assert_eq!(balance["balances"]["USDT"]["AVAILABLE"], json!("25"));
+
+    let list = handle.call(RpcRequest::new("list_approvals", json!({ "all": true }))).await.result.unwrap();
+    assert_eq!(list["approvals"][0]["status"], "approved");
+
+    // Rejections are signed too, over the approval id and reason
+    let error = handle
+        .call(RpcRequest::new(
+            "adjust",
+            json!({ "user": "BOB", "amount": "40", "asset": "USDT", "reason": "refund", "correlation_id": "adj-2" }),
+        ))
+        .await
+        .error
+        .unwrap();
+    let approval_id = error.data.unwrap()["approval_id"].as_str().unwrap().to_string();
+
+    let signature = ops2.sign_rejection(&approval_id, Some("duplicate"));
+    let error = handle
+        .call(RpcRequest::new(
+            "reject_approval",
+            json!({ "approval_id": approval_id, "reason": "typo", "signature": signature }),
+        ))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.kind(), Some("invalid_signature"));
+
+    let result = handle
+        .call(RpcRequest::new(
+            "reject_approval",
+            json!({ "approval_id": approval_id, "reason": "duplicate", "signature": signature }),
+        ))
+        .await
+        .result
+        .unwrap();
+    assert_eq!(result["status"], "rejected");
+}
+
+// ============================================================================
+// AML Screening Tests
+// ============================================================================
+
+/// Test: Large deposits are flagged and their funds moved into review
+#[tokio::test]
+async fn test_flagged_deposit_locked_for_review() {
+    use bibank_compliance::{ComplianceEvent, ComplianceLedger};
+    use bibank_rpc::{commands, screening, CommitError};
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    commands::init(&mut ctx, "init-1").await.unwrap();
+
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(100), "USDT", "dep-small").await.unwrap();
+    let flagged = commands::deposit(&mut ctx, "ALICE", Decimal::from(15000), "USDT", "dep-large")
+        .await
+        .unwrap();
+
+    // The deposit stands; the lock follows it
+    assert_eq!(flagged.sequence, 3);
+    assert_eq!(ctx.last_sequence(), 4);
+    let state = ctx.risk.state();
+    assert_eq!(state.get_balance(&AccountKey::user_available("ALICE", "USDT")), Decimal::from(100));
+    assert_eq!(state.get_balance(&AccountKey::user_review("ALICE", "USDT")), Decimal::from(15000));
+
+    let lock = ctx.committed_entry("review-lock-dep-large").unwrap().unwrap();
+    assert_eq!(lock.causality_id.as_deref(), Some("dep-large"));
+
+    // Every decision is in the Compliance Ledger
+    let events = ComplianceLedger::new(data_path.join(screening::LEDGER_FILE))
+        .unwrap()
+        .read_all()
+        .unwrap();
+    let decision = |cid: &str| {
+        events.iter().find_map(|e| match e {
+            ComplianceEvent::CheckPerformed { correlation_id, decision, rules_triggered, .. }
+                if correlation_id == cid => Some((decision.clone(), rules_triggered.clone())),
+            _ => None,
+        })
+    };
+    assert!(decision("dep-small").unwrap().0.is_approved());
+    let (large, rules) = decision("dep-large").unwrap();
+    assert!(large.is_flagged());
+    assert!(rules.contains(&"LARGE_TX_ALERT".to_string()));
+
+    let flag = events
+        .iter()
+        .find(|e| matches!(e, ComplianceEvent::TransactionFlagged { correlation_id, .. } if correlation_id == "dep-large"))
+        .unwrap();
+    assert_eq!(lock.metadata["flag_id"], serde_json::json!(flag.id()));
+
+    // Funds under review can't be withdrawn
+    let err = commands::withdraw(&mut ctx, "ALICE", Decimal::from(15000), "USDT", "wd-1")
+        .await
+        .unwrap_err();
+    assert!(matches!(err.downcast_ref::<CommitError>(), Some(CommitError::Risk(_))));
+}
+
+/// Test: Watchlisted users are blocked before anything reaches the journal
+#[tokio::test]
+async fn test_sanctioned_user_blocked() {
+    use bibank_compliance::{ComplianceEvent, ComplianceLedger};
+    use bibank_rpc::{commands, screening, CommitError, RpcError, RpcRequest};
+    use serde_json::json;
+
+    std::env::set_var("BIBANK_WATCHLIST", "SANCTIONED");
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    commands::init(&mut ctx, "init-1").await.unwrap();
+
+    let err = commands::deposit(&mut ctx, "sanctioned", Decimal::from(100), "USDT", "dep-blocked")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<CommitError>(),
+        Some(CommitError::Blocked { code, .. }) if code == "SANCTIONS_BLOCKED"
+    ));
+    assert_eq!(ctx.last_sequence(), 1);
+    assert!(ctx.correlations().get("dep-blocked").is_none());
+
+    let events = ComplianceLedger::new(data_path.join(screening::LEDGER_FILE))
+        .unwrap()
+        .read_all()
+        .unwrap();
+    assert!(events.iter().any(|e| matches!(
+        e,
+        ComplianceEvent::CheckPerformed { correlation_id, decision, .. }
+            if correlation_id == "dep-blocked" && decision.is_blocked()
+    )));
+
+    // Over RPC: structured error
+    let (handle, _writer) = bibank_rpc::server::spawn_writer(ctx);
+    let response = handle
+        .call(RpcRequest::new(
+            "deposit",
+            json!({ "user": "SANCTIONED", "amount": "100", "asset": "USDT" }),
+        ))
+        .await;
+    let error = response.error.unwrap();
+    assert_eq!(error.code, RpcError::COMPLIANCE_BLOCKED);
+    assert_eq!(error.data.unwrap()["kind"], json!("compliance_blocked"));
+}
+
+/// Test: KYC tier withdrawal limits and freezes, across restarts and over RPC
+#[tokio::test]
+async fn test_kyc_limits_and_frozen_users() {
+    use bibank_compliance::UserStatus;
+    use bibank_rpc::{commands, CommitError, RpcError, RpcRequest};
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    relax_screening(data_path);
+
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    commands::init(&mut ctx, "init-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(5000), "USDT", "dep-1").await.unwrap();
+    let onboarded = chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+    commands::user_register(&mut ctx, "alice", 0, onboarded, "ops").await.unwrap();
+
+    // Tier 0: 1000 a day
+    commands::withdraw(&mut ctx, "ALICE", Decimal::from(800), "USDT", "wd-1").await.unwrap();
+    let err = commands::withdraw(&mut ctx, "ALICE", Decimal::from(300), "USDT", "wd-2").await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<CommitError>(),
+        Some(CommitError::Blocked { code, .. }) if code == "KYC_DAILY_LIMIT"
+    ));
+
+    // Unregistered users get tier 0's limits; a higher tier lifts the limit
+    commands::deposit(&mut ctx, "BOB", Decimal::from(5000), "USDT", "dep-2").await.unwrap();
+    commands::withdraw(&mut ctx, "BOB", Decimal::from(1000), "USDT", "wd-3").await.unwrap();
+    let err = commands::withdraw(&mut ctx, "BOB", Decimal::from(1000), "USDT", "wd-4").await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<CommitError>(),
+        Some(CommitError::Blocked { code, .. }) if code == "KYC_DAILY_LIMIT"
+    ));
+    commands::user_set_tier(&mut ctx, "ALICE", 1, "documents verified", "ops").await.unwrap();
+    commands::withdraw(&mut ctx, "ALICE", Decimal::from(300), "USDT", "wd-2").await.unwrap();
+
+    // Frozen: no debits, credits still land
+    commands::user_set_status(&mut ctx, "ALICE", UserStatus::Frozen, "fraud report", "ops")
+        .await
+        .unwrap();
+    let err = commands::transfer(&mut ctx, "ALICE", "BOB", Decimal::from(10), "USDT", "tx-1")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<CommitError>(),
+        Some(CommitError::UserFrozen { user_id, status: UserStatus::Frozen }) if user_id == "ALICE"
+    ));
+    commands::transfer(&mut ctx, "BOB", "ALICE", Decimal::from(10), "USDT", "tx-2").await.unwrap();
+    let sequence = ctx.last_sequence();
+    drop(ctx);
+
+    // The registry comes back from the Compliance Ledger, the projection with it
+    let ctx = AppContext::new(data_path).await.unwrap();
+    let alice = ctx.users().get("ALICE").unwrap();
+    assert_eq!((alice.kyc_tier, alice.status), (1, UserStatus::Frozen));
+    let projected = ctx.projection.as_ref().unwrap().user().list().await.unwrap();
+    assert_eq!(projected, vec![alice.clone()]);
+
+    let (handle, writer) = bibank_rpc::server::spawn_writer(ctx);
+    let call = |method: &str, params: serde_json::Value| handle.call(RpcRequest::new(method, params));
+
+    let error = call("withdraw", json!({ "user": "ALICE", "amount": "10", "asset": "USDT" }))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.code, RpcError::COMPLIANCE_BLOCKED);
+    assert_eq!(error.data.unwrap()["kind"], json!("user_frozen"));
+
+    let user = call(
+        "set_user_status",
+        json!({ "user_id": "ALICE", "status": "active", "reason": "cleared" }),
+    )
+    .await
+    .result
+    .unwrap();
+    assert_eq!(user["status"], json!("active"));
+    let entry = call("withdraw", json!({ "user": "ALICE", "amount": "10", "asset": "USDT" }))
+        .await
+        .result
+        .unwrap();
+    assert_eq!(entry["sequence"], json!(sequence + 1));
+
+    let error = call("register_user", json!({ "user_id": "ALICE", "kyc_tier": 2 }))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.data.unwrap()["kind"], json!("user_exists"));
+    let error = call("register_user", json!({ "user_id": "CAROL", "kyc_tier": 9 }))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.data.unwrap()["kind"], json!("invalid_kyc_tier"));
+
+    call("register_user", json!({ "user_id": "BOB", "kyc_tier": 3 })).await.result.unwrap();
+    call("set_user_status", json!({ "user_id": "BOB", "status": "closed", "reason": "offboarded" }))
+        .await
+        .result
+        .unwrap();
+    let error = call("set_user_tier", json!({ "user_id": "BOB", "kyc_tier": 4, "reason": "upgrade" }))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.data.unwrap()["kind"], json!("user_closed"));
+
+    let users = call("list_users", json!(null)).await.result.unwrap();
+    assert_eq!(users["users"].as_array().unwrap().len(), 2);
+    let bob = call("get_user", json!({ "user_id": "bob" })).await.result.unwrap();
+    assert_eq!(bob["status"], json!("closed"));
+
+    handle.shutdown().await;
+    writer.await.unwrap();
+}
+
+/// Test: Freezing a maker cancels their resting orders, so a crossing taker doesn't hit them
+#[tokio::test]
+async fn test_frozen_maker_order_is_not_crossed() {
+    use bibank_compliance::UserStatus;
+    use bibank_matching::TradingPair;
+    use bibank_rpc::commands;
+
+    let temp_dir = TempDir::new().unwrap();
+    relax_screening(temp_dir.path());
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    setup_traders(&mut ctx).await;
+    let pair = TradingPair::btc_usdt();
+    let onboarded = chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+    commands::user_register(&mut ctx, "BOB", 3, onboarded, "ops").await.unwrap();
+
+    let ask = commands::place_order(&mut ctx, "BOB", "sell", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::ONE, OrderType::Limit, "ask-1")
+        .await
+        .unwrap();
+    commands::user_set_status(&mut ctx, "BOB", UserStatus::Frozen, "fraud report", "ops")
+        .await
+        .unwrap();
+
+    // BOB's lock went back to AVAILABLE before the freeze took effect
+    assert!(ctx.matching.get_order(&pair, &ask).is_none());
+    assert_eq!(ctx.risk.state().get_balance(&AccountKey::user_locked("BOB", "BTC")), Decimal::ZERO);
+    assert_eq!(ctx.risk.state().get_balance(&AccountKey::user_available("BOB", "BTC")), Decimal::new(10, 0));
+
+    // The crossing bid rests instead of settling against a frozen maker
+    let bid = commands::place_order(&mut ctx, "ALICE", "buy", "BTC", "USDT", Decimal::new(50_000, 0), Decimal::ONE, OrderType::Limit, "bid-1")
+        .await
+        .unwrap();
+    assert_eq!(ctx.matching.get_order(&pair, &bid).unwrap().remaining(), Decimal::ONE);
+    assert_eq!(ctx.risk.state().get_balance(&AccountKey::user_available("ALICE", "BTC")), Decimal::ZERO);
+    assert_eq!(ctx.matching.total_order_count(), 1);
+}
+
+const RULES: &str = r#"
+ruleset "AML_CUSTOM"
+version "1"
+approved_by "alice", "bob"
+
+rule WITHDRAWAL_LIMIT {
+    type block
+    priority 10
+    when all(custom("is_withdrawal"), amount >= 500)
+    then block("WITHDRAWAL_LIMIT", "Withdrawals of 500 or more are suspended")
+}
+
+rule MID_DEPOSIT {
+    type flag
+    when all(custom("is_deposit"), amount in 300..400)
+    then flag(high, L2, "Deposit in watched band")
+}
+"#;
+
+/// Test: rules from the rule file block and flag commits
+#[tokio::test]
+async fn test_rule_file_blocks_and_flags() {
+    use bibank_rpc::{commands, screening, CommitError};
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    relax_screening(data_path);
+    std::fs::write(data_path.join(screening::RULES_FILE), RULES).unwrap();
+
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    commands::init(&mut ctx, "init-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(1000), "USDT", "dep-1").await.unwrap();
+
+    let err = commands::withdraw(&mut ctx, "ALICE", Decimal::from(600), "USDT", "wd-large")
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<CommitError>(),
+        Some(CommitError::Blocked { code, .. }) if code == "WITHDRAWAL_LIMIT"
+    ));
+    commands::withdraw(&mut ctx, "ALICE", Decimal::from(100), "USDT", "wd-small").await.unwrap();
+
+    // A deposit in the watched band is locked for review
+    commands::deposit(&mut ctx, "BOB", Decimal::from(350), "USDT", "dep-band").await.unwrap();
+    let state = ctx.risk.state();
+    assert_eq!(state.get_balance(&AccountKey::user_review("BOB", "USDT")), Decimal::from(350));
+    let lock = ctx.committed_entry("review-lock-dep-band").unwrap().unwrap();
+    assert_eq!(lock.metadata["rules_triggered"], serde_json::json!(["MID_DEPOSIT"]));
+}
+
+/// Test: reloading the rule file records each activation in the Compliance Ledger
+#[tokio::test]
+async fn test_rule_file_reload_records_changes() {
+    use bibank_compliance::{ComplianceEvent, ComplianceLedger, RuleAction};
+    use bibank_dsl::parse_rule_set;
+    use bibank_rpc::{screening, RpcError, RpcRequest};
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    relax_screening(data_path);
+    let rules_path = data_path.join(screening::RULES_FILE);
+    std::fs::write(&rules_path, RULES).unwrap();
+
+    let changes = || -> Vec<(RuleAction, String, Vec<String>)> {
+        ComplianceLedger::new(data_path.join(screening::LEDGER_FILE))
+            .unwrap()
+            .read_all()
+            .unwrap()
+            .into_iter()
+            .filter_map(|e| match e {
+                ComplianceEvent::RuleSetChanged { action, rule_set_hash, approved_by, .. } => {
+                    Some((action, rule_set_hash, approved_by))
+                }
+                _ => None,
+            })
+            .collect()
+    };
+
+    // Activated at startup, once: restarting with the same file records nothing
+    let hash = parse_rule_set(RULES).unwrap().hash();
+    drop(AppContext::new(data_path).await.unwrap());
+    let ctx = AppContext::new(data_path).await.unwrap();
+    assert_eq!(
+        changes(),
+        vec![(RuleAction::Activated, hash.clone(), vec!["alice".to_string(), "bob".to_string()])]
+    );
+
+    let (handle, _writer) = bibank_rpc::server::spawn_writer(ctx);
+    let reload = |performed_by: &str| {
+        handle.call(RpcRequest::new("reload_rules", json!({ "performed_by": performed_by })))
+    };
+
+    let response = reload("ops").await;
+    assert_eq!(response.result.unwrap()["changed"], json!(false));
+
+    // An edited file is activated on reload
+    let edited = RULES.replace("amount >= 500", "amount >= 800").replace("version \"1\"", "version \"2\"");
+    std::fs::write(&rules_path, &edited).unwrap();
+    let result = reload("ops").await.result.unwrap();
+    let edited_hash = parse_rule_set(&edited).unwrap().hash();
+    assert_eq!(result["changed"], json!(true));
+    assert_eq!(result["version"], json!("2"));
+    assert_eq!(result["hash"], json!(edited_hash));
+
+    // A broken file is rejected with its position and the rules stay in force
+    std::fs::write(&rules_path, "ruleset \"BROKEN\"\nrule A {\n    type flag\n    when custom(\"nope\")\n}\n")
+        .unwrap();
+    let error = reload("ops").await.error.unwrap();
+    assert_eq!(error.code, RpcError::RULES_REJECTED);
+    let data = error.data.unwrap();
+    assert_eq!(data["kind"], json!("rule_parse_error"));
+    assert_eq!((data["line"].clone(), data["column"].clone()), (json!(4), json!(17)));
+
+    // Removing the file deactivates the rule set
+    std::fs::remove_file(&rules_path).unwrap();
+    let result = reload("ops").await.result.unwrap();
+    assert_eq!(result["changed"], json!(true));
+    assert_eq!(result["hash"], json!(null));
+
+    let actions: Vec<(RuleAction, String)> = changes().into_iter().map(|(a, h, _)| (a, h)).collect();
+    assert_eq!(
+        actions,
+        vec![
+            (RuleAction::Activated, hash),
+            (RuleAction::Activated, edited_hash.clone()),
+            (RuleAction::Deactivated, edited_hash),
+        ]
+    );
+}
+
+/// Test: approving a case releases the locked funds, rejecting reverses the deposit
+#[tokio::test]
+async fn test_case_approve_releases_and_reject_reverses() {
+    use bibank_compliance::{CaseStatus, ComplianceError, ReviewDecision};
+    use bibank_rpc::{commands, screening, CommitError};
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    commands::init(&mut ctx, "init-1").await.unwrap();
+    let vault = ctx.risk.state().get_balance(&AccountKey::system_vault("USDT"));
+
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(15000), "USDT", "dep-alice").await.unwrap();
+    commands::deposit(&mut ctx, "BOB", Decimal::from(20000), "USDT", "dep-bob").await.unwrap();
+
+    let cases = ctx.cases().await.unwrap();
+    let alice = cases.by_correlation_id("dep-alice").unwrap().clone();
+    let bob = cases.by_correlation_id("dep-bob").unwrap().clone();
+    assert_eq!(cases.open(None).len(), 2);
+
+    // Review locks are settled by their case, not reversed
+    let lock = ctx
+        .committed_entry(&format!("{}dep-alice", screening::REVIEW_LOCK_PREFIX))
+        .unwrap()
+        .unwrap();
+    let err = ctx.reverse(lock.sequence, "undo").await.unwrap_err();
+    assert!(matches!(err, CommitError::NotReversible { .. }));
+
+    // Assigned cases can only be decided by the assignee
+    ctx.assign_case(&alice.flag_id, "officer-1", "lead").await.unwrap();
+    let err = ctx
+        .resolve_case(&alice.flag_id, ReviewDecision::Approved, "officer-2", "")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CommitError::Compliance(ComplianceError::ReviewAssigned(..))));
+
+    let (case, entry) = ctx
+        .resolve_case(&alice.flag_id, ReviewDecision::Approved, "officer-1", "Salary")
+        .await
+        .unwrap();
+    assert_eq!(case.status, CaseStatus::Approved);
+    let release = entry.unwrap();
+    assert_eq!(release.correlation_id, format!("{}dep-alice", screening::REVIEW_RELEASE_PREFIX));
+    assert_eq!(release.causality_id.as_deref(), Some("dep-alice"));
+
+    let (case, entry) = ctx
+        .resolve_case(&bob.flag_id, ReviewDecision::Rejected, "officer-1", "Unknown source")
+        .await
+        .unwrap();
+    assert_eq!(case.status, CaseStatus::Rejected);
+    assert_eq!(entry.unwrap().intent, TransactionIntent::Withdrawal);
+
+    let state = ctx.risk.state();
+    assert_eq!(state.get_balance(&AccountKey::user_available("ALICE", "USDT")), Decimal::from(15000));
+    assert_eq!(state.get_balance(&AccountKey::user_review("ALICE", "USDT")), Decimal::ZERO);
+    assert_eq!(state.get_balance(&AccountKey::user_available("BOB", "USDT")), Decimal::ZERO);
+    assert_eq!(state.get_balance(&AccountKey::user_review("BOB", "USDT")), Decimal::ZERO);
+    assert_eq!(state.get_balance(&AccountKey::system_vault("USDT")), vault + Decimal::from(15000));
+
+    // The rejected deposit is already reversed
+    let deposit = ctx.committed_entry("dep-bob").unwrap().unwrap();
+    let err = ctx.reverse(deposit.sequence, "again").await.unwrap_err();
+    assert!(matches!(err, CommitError::AlreadyReversed { .. }));
+
+    // Closed cases stay closed
+    let err = ctx
+        .resolve_case(&bob.flag_id, ReviewDecision::Approved, "officer-1", "")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, CommitError::Compliance(ComplianceError::ReviewAlreadyResolved(_))));
+    assert!(ctx.cases().await.unwrap().open(None).is_empty());
+}
+
+/// Test: overdue cases escalate to the next approval level over RPC
+#[tokio::test]
+async fn test_case_escalation_over_rpc() {
+    use bibank_rpc::{commands, screening, RpcError, RpcRequest};
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    std::fs::write(data_path.join(screening::CONFIG_FILE), r#"{"review_expiry_hours": 0}"#).unwrap();
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    commands::init(&mut ctx, "init-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(15000), "USDT", "dep-1").await.unwrap();
+
+    let (handle, _writer) = bibank_rpc::server::spawn_writer(ctx);
+    let call = |method: &str, params: serde_json::Value| handle.call(RpcRequest::new(method, params));
+
+    let open = call("list_cases", json!({})).await.result.unwrap();
+    let flag_id = open[0]["flag_id"].as_str().unwrap().to_string();
+    let level = open[0]["required_approval"].clone();
+
+    call("assign_case", json!({ "flag_id": flag_id, "reviewer": "officer-1" })).await.result.unwrap();
+    let escalated = call("escalate_cases", json!(null)).await.result.unwrap();
+    let case = &escalated["escalated"][0];
+    assert_eq!(case["flag_id"], json!(flag_id));
+    assert_ne!(case["required_approval"], level);
+    assert_eq!(case["escalations"], json!(1));
+    assert_eq!(case["assigned_to"], json!(null));
+
+    // The old level's queue is empty, the new one has the case
+    assert_eq!(call("list_cases", json!({ "level": level })).await.result.unwrap(), json!([]));
+    let queue = call("list_cases", json!({ "level": case["required_approval"] })).await.result.unwrap();
+    assert_eq!(queue[0]["flag_id"], json!(flag_id));
+
+    let result = call("approve_case", json!({ "flag_id": flag_id, "reviewer": "officer-2" })).await.result.unwrap();
+    assert_eq!(result["case"]["status"], json!("approved"));
+    assert!(result["entry"]["sequence"].is_u64());
+
+    let error = call("reject_case", json!({ "flag_id": flag_id, "reviewer": "officer-2" })).await.error.unwrap();
+    assert_eq!(error.code, RpcError::CASE_REJECTED);
+    assert_eq!(error.data.unwrap()["kind"], json!("case_closed"));
+}
+
+/// Test: CTR and SAR reports over RPC, reproducible through their input hash
+#[tokio::test]
+async fn test_regulatory_reports_over_rpc() {
+    use bibank_rpc::{commands, RpcError, RpcRequest};
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    let mut ctx = AppContext::new(temp_dir.path()).await.unwrap();
+    commands::init(&mut ctx, "init-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(15000), "USDT", "dep-1").await.unwrap();
+    commands::deposit(&mut ctx, "BOB", Decimal::from(4000), "USDT", "dep-2").await.unwrap();
+    commands::deposit(&mut ctx, "BOB", Decimal::from(7000), "USDT", "dep-3").await.unwrap();
+    commands::deposit(&mut ctx, "CAROL", Decimal::from(500), "USDT", "dep-4").await.unwrap();
+
+    let (handle, _writer) = bibank_rpc::server::spawn_writer(ctx);
+    let call = |method: &str, params: serde_json::Value| handle.call(RpcRequest::new(method, params));
+    let today = chrono::Utc::now().date_naive().to_string();
+    let range = |kind: &str, format: &str| json!({ "kind": kind, "from": today, "to": today, "format": format });
+
+    // BOB's two deposits add up over the threshold; the review lock isn't counted
+    let ctr = call("report", range("ctr", "json")).await.result.unwrap();
+    assert_eq!(ctr["threshold"], json!("10000"));
+    let rows = ctr["rows"].as_array().unwrap();
+    let users: Vec<&str> = rows.iter().map(|r| r["user_id"].as_str().unwrap()).collect();
+    assert_eq!(users, vec!["ALICE", "BOB"]);
+    assert_eq!(rows[0]["cash_in"], json!("15000"));
+    assert_eq!(rows[1]["cash_in"], json!("11000"));
+    assert_eq!(rows[1]["transactions"].as_array().unwrap().len(), 2);
+
+    let csv = call("report", range("ctr", "csv")).await.result.unwrap();
+    assert_eq!(csv["input_hash"], ctr["input_hash"]);
+    assert!(csv["csv"].as_str().unwrap().contains(",BOB,USDT,11000,0,2,"));
+
+    // The flagged deposit is drafted with its lock entry
+    let sar = call("report", range("sar", "json")).await.result.unwrap();
+    let drafts = sar["drafts"].as_array().unwrap();
+    assert_eq!(drafts.len(), 1);
+    assert_eq!(drafts[0]["case"]["correlation_id"], json!("dep-1"));
+    assert!(!drafts[0]["rules_triggered"].as_array().unwrap().is_empty());
+    let linked: Vec<&str> = drafts[0]["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["correlation_id"].as_str().unwrap())
+        .collect();
+    assert_eq!(linked, vec!["dep-1", "review-lock-dep-1"]);
+
+    // Same inputs, same report
+    assert_eq!(call("report", range("sar", "json")).await.result.unwrap(), sar);
+    assert_ne!(sar["input_hash"], ctr["input_hash"]);
+
+    let error = call("report", json!({ "kind": "ctr", "from": today, "to": "2000-01-01" }))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.code, RpcError::INVALID_PARAMS);
+}
+
+/// Test: signed Merkle checkpoints, inclusion proofs over RPC and trusted audits
+#[tokio::test]
+async fn test_checkpoints_and_inclusion_proofs() {
+    use bibank_ledger::{Signer, SystemSigner};
+    use bibank_rpc::{commands, RpcError, RpcRequest};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    relax_screening(data_path);
+    let signer = Arc::new(SystemSigner::generate());
+    let public_key = signer.public_key_hex();
+
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    ctx.signer = Some(signer);
+    ctx.set_checkpoint_interval(3);
+    commands::init(&mut ctx, "init-1").await.unwrap();
+    for i in 1..=4 {
+        commands::deposit(&mut ctx, "ALICE", Decimal::from(100), "USDT", &format!("dep-{}", i)).await.unwrap();
+    }
+
+    let (handle, writer) = bibank_rpc::server::spawn_writer(ctx);
+    let call = |method: &str, params: serde_json::Value| handle.call(RpcRequest::new(method, params));
+
+    // Nothing is checkpointed until the writer's first quiescent point
+    let error = call("inclusion_proof", json!({ "sequence": 2 })).await.error.unwrap();
+    assert_eq!(error.code, RpcError::INVALID_PARAMS);
+    assert_eq!(error.kind(), Some("not_checkpointed"));
+
+    // The failed call above was followed by an automatic checkpoint of 1..5
+    let checkpoints = call("list_checkpoints", json!(null)).await.result.unwrap();
+    assert_eq!(checkpoints[0]["from_sequence"], json!(1));
+    assert_eq!(checkpoints[0]["to_sequence"], json!(5));
+
+    call("deposit", json!({ "user": "BOB", "amount": "5", "asset": "USDT", "correlation_id": "dep-5" }))
+        .await
+        .result
+        .unwrap();
+    let checkpoint = call("create_checkpoint", json!(null)).await.result.unwrap();
+    assert_eq!(checkpoint["from_sequence"], json!(6));
+    assert_eq!(checkpoint["to_sequence"], json!(6));
+    assert_eq!(call("create_checkpoint", json!(null)).await.result.unwrap(), json!(null));
+
+    let proven = call("inclusion_proof", json!({ "sequence": 2 })).await.result.unwrap();
+    assert_eq!(proven["checkpoint"], checkpoints[0]);
+    let verified = call("verify_proof", proven.clone()).await.result.unwrap();
+    assert_eq!(verified, json!({ "included": true, "signature_valid": true }));
+
+    let mut forged = proven.clone();
+    forged["proof"]["entry_hash"] = json!("0".repeat(64));
+    let verified = call("verify_proof", forged).await.result.unwrap();
+    assert_eq!(verified["included"], json!(false));
+
+    let mut forged = proven;
+    forged["checkpoint"]["root"] = json!("0".repeat(64));
+    let verified = call("verify_proof", forged).await.result.unwrap();
+    assert_eq!(verified, json!({ "included": false, "signature_valid": false }));
+
+    handle.shutdown().await;
+    let ctx = writer.await.unwrap();
+
+    // The latest trusted checkpoint anchors an audit of what follows it
+    let reader = EventReader::from_directory(ctx.journal_path()).unwrap();
+    let trusted = ctx.checkpoints.latest_trusted(&reader, Some(&public_key)).unwrap();
+    assert_eq!(trusted.to_sequence, 6);
+    assert!(ctx.checkpoints.latest_trusted(&reader, Some("other-key")).is_none());
+}
+
+/// Test: key rotation hands off by sequence; audits check each signature's key
+#[tokio::test]
+async fn test_key_rotation_and_registry() {
+    use bibank_ledger::{KeyEvent, LedgerError, Signer, SystemSigner};
+    use bibank_rpc::{commands, KeyStoreError};
+    use std::sync::Arc;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    relax_screening(data_path);
+    let old = Arc::new(SystemSigner::generate());
+    let old_key = old.public_key_hex();
+
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    ctx.signer = Some(old.clone());
+    ctx.activate_key("SYSTEM", &old_key, Some(1)).unwrap();
+    commands::init(&mut ctx, "init-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(100), "USDT", "dep-1").await.unwrap();
+    ctx.checkpoint().unwrap().unwrap();
+
+    // Rotation takes effect at the next entry, endorsed by the old key
+    let new = Arc::new(SystemSigner::generate());
+    let event = ctx.rotate_system_key(new.clone()).unwrap();
+    let KeyEvent::Activated { from_sequence, replaces, .. } = event else {
+        panic!("rotation is an activation");
+    };
+    assert_eq!(from_sequence, 3);
+    assert_eq!(replaces.as_deref(), Some(old_key.as_str()));
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(50), "USDT", "dep-2").await.unwrap();
+    commands::deposit(&mut ctx, "BOB", Decimal::from(50), "USDT", "dep-3").await.unwrap();
+
+    let registry = ctx.keys.registry().unwrap();
+    let journal = EventReader::from_directory(ctx.journal_path()).unwrap().read_all().unwrap();
+    for entry in &journal {
+        assert!(registry.verify_entry(entry).is_ok(), "seq {}", entry.sequence);
+    }
+    assert_eq!(journal[2].signatures[0].public_key, new.public_key_hex());
+
+    // An entry signed by the retired key after the hand-off is untrusted
+    let mut forged = journal[3].clone();
+    forged.signatures = vec![old.sign(&forged)];
+    assert!(forged.verify_signatures().is_ok());
+    assert!(matches!(registry.verify_entry(&forged), Err(LedgerError::UntrustedKey { sequence: 4, .. })));
+
+    // Checkpoints signed by the old key stay trusted through the registry
+    let reader = EventReader::from_directory(ctx.journal_path()).unwrap();
+    assert!(ctx.checkpoints.latest_trusted(&reader, Some(&new.public_key_hex())).is_none());
+    assert_eq!(ctx.checkpoints.latest_trusted_by(&reader, &registry).unwrap().to_sequence, 2);
+
+    // Rotating again needs the current key; a second SYSTEM key can't just be activated
+    let intruder = SystemSigner::generate();
+    assert!(matches!(
+        ctx.activate_key("SYSTEM", &intruder.public_key_hex(), None),
+        Err(KeyStoreError::Registry(LedgerError::KeyRegistry(_)))
+    ));
+    ctx.signer = Some(old);
+    assert!(ctx.rotate_system_key(Arc::new(intruder)).is_err());
+
+    // Revoking the new key back to sequence 4 invalidates what it signed since
+    ctx.revoke_key("SYSTEM", &new.public_key_hex(), Some(4), "compromised").unwrap();
+    let registry = ctx.keys.registry().unwrap();
+    assert!(registry.verify_entry(&journal[2]).is_ok());
+    assert!(registry.verify_entry(&journal[3]).is_err());
+    assert!(registry.active("SYSTEM").is_none());
+}
+
+/// Test: Proof of reserves per asset, at past sequences, with verifiable user proofs
+#[tokio::test]
+async fn test_proof_of_reserves() {
+    use bibank_risk::LiabilityProof;
+    use bibank_rpc::{commands, RpcError, RpcRequest};
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    relax_screening(data_path);
+
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    commands::init(&mut ctx, "init-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(100), "USDT", "dep-1").await.unwrap();
+    commands::deposit(&mut ctx, "BOB", Decimal::from(40), "USDT", "dep-2").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(1), "BTC", "dep-3").await.unwrap();
+    commands::withdraw(&mut ctx, "ALICE", Decimal::from(30), "USDT", "wd-1").await.unwrap();
+
+    let (handle, writer) = bibank_rpc::server::spawn_writer(ctx);
+    let call = |method: &str, params: serde_json::Value| handle.call(RpcRequest::new(method, params));
+
+    let summary = call("proof_of_reserves", json!(null)).await.result.unwrap();
+    assert_eq!(summary["sequence"], json!(5));
+    let usdt = summary["assets"].as_array().unwrap().iter().find(|a| a["asset"] == "USDT").unwrap().clone();
+    assert_eq!(usdt["total_liabilities"], json!("110"));
+    // Genesis capital sits in the vault alongside user deposits
+    assert_eq!(usdt["vault_balance"], json!("1000000110"));
+    assert_eq!(usdt["users"], json!(2));
+    assert_eq!(usdt["covered"], json!(true));
+
+    // Earlier sequences are rebuilt from the journal
+    let before = call("proof_of_reserves", json!({ "sequence": 3 })).await.result.unwrap();
+    assert_eq!(before["assets"][0]["total_liabilities"], json!("140"));
+    assert_ne!(before["assets"][0]["root"], usdt["root"]);
+
+    let proven = call("liability_proof", json!({ "user": "alice" })).await.result.unwrap();
+    assert_eq!(proven["user"], json!("ALICE"));
+    let proofs: Vec<LiabilityProof> = serde_json::from_value(proven["proofs"].clone()).unwrap();
+    assert_eq!(proofs.len(), 2);
+    let proof = proofs.iter().find(|p| p.asset == "USDT").unwrap();
+    assert!(proof.verify());
+    assert_eq!(proof.balance, Decimal::from(70));
+    assert_eq!(json!(proof.root), usdt["root"]);
+
+    let error = call("proof_of_reserves", json!({ "sequence": 99 })).await.error.unwrap();
+    assert_eq!(error.code, RpcError::INVALID_PARAMS);
+
+    handle.shutdown().await;
+    writer.await.unwrap();
+}
+
+/// Test: Trial balance, balance sheet and income statement over JSON-RPC
+#[tokio::test]
+async fn test_financial_statements() {
+    use bibank_rpc::{commands, RpcError, RpcRequest};
+    use serde_json::json;
+
+    let temp_dir = TempDir::new().unwrap();
+    let data_path = temp_dir.path();
+    relax_screening(data_path);
+
+    let mut ctx = AppContext::new(data_path).await.unwrap();
+    commands::init(&mut ctx, "init-1").await.unwrap();
+    commands::deposit(&mut ctx, "ALICE", Decimal::from(100), "USDT", "dep-1").await.unwrap();
+    commands::fee(&mut ctx, "ALICE", Decimal::from(2), "USDT", "trading", "fee-1").await.unwrap();
+
+    let (handle, writer) = bibank_rpc::server::spawn_writer(ctx);
+    let call = |method: &str, params: serde_json::Value| handle.call(RpcRequest::new(method, params));
+
+    let tb = call("trial_balance", json!(null)).await.result.unwrap();
+    assert_eq!(tb["sequence"], json!(3));
+    assert_eq!(tb["assets"][0]["total_debit"], tb["assets"][0]["total_credit"]);
+
+    // As of the deposit, before any revenue
+    let sheet = call("balance_sheet", json!({ "sequence": 2 })).await.result.unwrap();
+    assert_eq!(sheet["sheets"][0]["total_liabilities"], json!("100"));
+    assert_eq!(sheet["sheets"][0]["retained_earnings"], json!("0"));
+    let sheet = call("balance_sheet", json!({ "format": "csv" })).await.result.unwrap();
+    assert!(sheet["csv"].as_str().unwrap().contains("USDT,retained_earnings,,2\n"));
+
+    let today = chrono::Utc::now().date_naive();
+    let income = call("income_statement", json!({ "from": today, "to": today })).await.result.unwrap();
+    assert_eq!(income["statements"][0]["fee_revenue"], json!("2"));
+
+    let error = call("trial_balance", json!({ "sequence": 2, "at": chrono::Utc::now() }))
+        .await
+        .error
+        .unwrap();
+    assert_eq!(error.code, RpcError::INVALID_PARAMS);
+
+    handle.shutdown().await;
+    writer.await.unwrap();
+}